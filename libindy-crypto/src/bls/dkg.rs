@@ -0,0 +1,725 @@
+use errors::IndyCryptoError;
+use pair::{GroupOrderElement, PointG2};
+use bls::{Generator, VerKey};
+use bls::threshold::{SignKeyShare, VerKeyShare, index_to_element, eval_polynomial};
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Feldman commitment to the secret polynomial of a DKG participant.
+/// Must be broadcast to all participants.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DkgCommitment {
+    dealer: u32,
+    coefficients: Vec<PointG2>
+}
+
+impl DkgCommitment {
+    /// Returns index of the participant that created the commitment.
+    pub fn dealer(&self) -> u32 {
+        self.dealer
+    }
+}
+
+/// Secret share of the dealer polynomial.
+/// Must be sent to the receiver over private channel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DkgShare {
+    dealer: u32,
+    receiver: u32,
+    value: GroupOrderElement
+}
+
+impl DkgShare {
+    /// Returns index of the participant that created the share.
+    pub fn dealer(&self) -> u32 {
+        self.dealer
+    }
+
+    /// Returns index of the participant the share is intended for.
+    pub fn receiver(&self) -> u32 {
+        self.receiver
+    }
+}
+
+/// Complaint against the dealer that sent missing or invalid share.
+/// Must be broadcast to all participants.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DkgComplaint {
+    complainer: u32,
+    dealer: u32
+}
+
+impl DkgComplaint {
+    /// Returns index of the participant that raised the complaint.
+    pub fn complainer(&self) -> u32 {
+        self.complainer
+    }
+
+    /// Returns index of the accused dealer.
+    pub fn dealer(&self) -> u32 {
+        self.dealer
+    }
+}
+
+/// Answer of the dealer to the complaint: disputed share revealed publicly.
+/// Must be broadcast to all participants.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DkgJustification {
+    dealer: u32,
+    receiver: u32,
+    value: GroupOrderElement
+}
+
+impl DkgJustification {
+    /// Returns index of the participant that created the justification.
+    pub fn dealer(&self) -> u32 {
+        self.dealer
+    }
+
+    /// Returns index of the participant that raised the complaint.
+    pub fn receiver(&self) -> u32 {
+        self.receiver
+    }
+}
+
+/// Result of the DKG for single participant.
+#[derive(Debug)]
+pub struct DkgResult {
+    sign_key_share: SignKeyShare,
+    ver_key: VerKey,
+    ver_key_shares: Vec<VerKeyShare>,
+    qualified: Vec<u32>
+}
+
+impl DkgResult {
+    /// Returns sign key share of the participant.
+    pub fn sign_key_share(&self) -> &SignKeyShare {
+        &self.sign_key_share
+    }
+
+    /// Returns group verification key.
+    pub fn ver_key(&self) -> &VerKey {
+        &self.ver_key
+    }
+
+    /// Returns verification keys of the shares of all participants.
+    pub fn ver_key_shares(&self) -> &[VerKeyShare] {
+        self.ver_key_shares.as_slice()
+    }
+
+    /// Returns indexes of the dealers whose polynomials form the group key.
+    pub fn qualified(&self) -> &[u32] {
+        self.qualified.as_slice()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum DkgPhase {
+    Sharing,
+    Complaints,
+    Finished
+}
+
+/// Participant of Pedersen distributed key generation with Feldman verifiable secret sharing.
+///
+/// Protocol is transport-agnostic and runs in following rounds:
+///
+/// 1. Each participant broadcasts `commitment` and sends `share_for` to every other participant.
+/// 2. Each participant processes received messages with `receive_commitment` and `receive_share`,
+///    then calls `complaints` and broadcasts returned complaints.
+/// 3. Each participant processes all complaints with `receive_complaint`. Accused dealer
+///    broadcasts returned justifications that are processed with `receive_justification`.
+/// 4. Each participant calls `finalize`. Dealers that did not publish commitment, or did not
+///    answer a complaint with a valid justification, are disqualified.
+#[derive(Debug)]
+pub struct DkgParticipant {
+    index: u32,
+    threshold: usize,
+    total: u32,
+    gen: PointG2,
+    phase: DkgPhase,
+    polynomial: Vec<GroupOrderElement>,
+    commitments: BTreeMap<u32, Vec<PointG2>>,
+    shares: BTreeMap<u32, GroupOrderElement>,
+    complaints: BTreeMap<u32, BTreeSet<u32>>, /* dealer -> complainers */
+    disqualified: BTreeSet<u32>
+}
+
+impl DkgParticipant {
+    /// Creates and returns DKG participant with random secret polynomial.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the participant in 1..total
+    /// * `threshold` - Number of shares required to produce signature
+    /// * `total` - Total number of participants
+    /// * `gen` - Generator point
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::Generator;
+    /// use indy_crypto::bls::dkg::DkgParticipant;
+    /// let gen = Generator::new().unwrap();
+    /// DkgParticipant::new(1, 2, 3, &gen).unwrap();
+    /// ```
+    pub fn new(index: u32, threshold: usize, total: u32, gen: &Generator) -> Result<DkgParticipant, IndyCryptoError> {
+        if index == 0 || index > total {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Invalid participant index: expected value in 1..{}, actual {}", total, index)));
+        }
+
+        if threshold == 0 || threshold > total as usize {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Invalid threshold: expected value in 1..{}, actual {}", total, threshold)));
+        }
+
+        let mut polynomial = Vec::with_capacity(threshold);
+        for _ in 0..threshold {
+            polynomial.push(GroupOrderElement::new()?);
+        }
+
+        let mut participant = DkgParticipant {
+            index,
+            threshold,
+            total,
+            gen: gen.point,
+            phase: DkgPhase::Sharing,
+            polynomial,
+            commitments: BTreeMap::new(),
+            shares: BTreeMap::new(),
+            complaints: BTreeMap::new(),
+            disqualified: BTreeSet::new()
+        };
+
+        let commitment = participant.commitment()?;
+        let share = eval_polynomial(&participant.polynomial, index)?;
+        participant.commitments.insert(index, commitment.coefficients);
+        participant.shares.insert(index, share);
+
+        Ok(participant)
+    }
+
+    /// Returns index of the participant.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Returns commitment to the participant secret polynomial that must be broadcast.
+    pub fn commitment(&self) -> Result<DkgCommitment, IndyCryptoError> {
+        let coefficients = self.polynomial
+            .iter()
+            .map(|coefficient| self.gen.mul(coefficient))
+            .collect::<Result<Vec<PointG2>, IndyCryptoError>>()?;
+
+        Ok(DkgCommitment {
+            dealer: self.index,
+            coefficients
+        })
+    }
+
+    /// Returns share of the participant secret polynomial for the receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `receiver` - Index of the receiver
+    pub fn share_for(&self, receiver: u32) -> Result<DkgShare, IndyCryptoError> {
+        self._check_index(receiver)?;
+
+        Ok(DkgShare {
+            dealer: self.index,
+            receiver,
+            value: eval_polynomial(&self.polynomial, receiver)?
+        })
+    }
+
+    /// Processes commitment broadcast by other participant.
+    ///
+    /// Dealer whose commitment has wrong number of coefficients is disqualified.
+    ///
+    /// # Arguments
+    ///
+    /// * `commitment` - Commitment of other participant
+    pub fn receive_commitment(&mut self, commitment: &DkgCommitment) -> Result<(), IndyCryptoError> {
+        self._check_phase(DkgPhase::Sharing)?;
+        self._check_index(commitment.dealer)?;
+
+        if self.commitments.contains_key(&commitment.dealer) {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Commitment of dealer {} is already received", commitment.dealer)));
+        }
+
+        if commitment.coefficients.len() != self.threshold {
+            self.disqualified.insert(commitment.dealer);
+            return Ok(());
+        }
+
+        self.commitments.insert(commitment.dealer, commitment.coefficients.clone());
+
+        Ok(())
+    }
+
+    /// Processes share sent by other participant and returns complaint if the share
+    /// does not match the dealer commitment.
+    ///
+    /// Commitment of the dealer must be received first.
+    ///
+    /// # Arguments
+    ///
+    /// * `share` - Share sent by other participant
+    pub fn receive_share(&mut self, share: &DkgShare) -> Result<Option<DkgComplaint>, IndyCryptoError> {
+        self._check_phase(DkgPhase::Sharing)?;
+        self._check_index(share.dealer)?;
+
+        if share.receiver != self.index {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Share is intended for participant {}", share.receiver)));
+        }
+
+        if self.shares.contains_key(&share.dealer) {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Share of dealer {} is already received", share.dealer)));
+        }
+
+        if self.disqualified.contains(&share.dealer) {
+            return Ok(None);
+        }
+
+        let valid = {
+            let commitment = self.commitments.get(&share.dealer)
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Commitment of dealer {} is not received", share.dealer)))?;
+
+            self._verify_share(commitment, self.index, &share.value)?
+        };
+
+        if !valid {
+            return Ok(Some(self._complain(share.dealer)));
+        }
+
+        self.shares.insert(share.dealer, share.value);
+
+        Ok(None)
+    }
+
+    /// Finishes sharing round and returns complaints of the participant that must be broadcast.
+    ///
+    /// Complaint is raised against every dealer that published commitment but did not send
+    /// valid share. Dealers that did not publish commitment are disqualified.
+    pub fn complaints(&mut self) -> Result<Vec<DkgComplaint>, IndyCryptoError> {
+        self._check_phase(DkgPhase::Sharing)?;
+
+        for dealer in 1..(self.total + 1) {
+            if !self.commitments.contains_key(&dealer) {
+                self.disqualified.insert(dealer);
+            } else if !self.shares.contains_key(&dealer) {
+                self._complain(dealer);
+            }
+        }
+
+        self.phase = DkgPhase::Complaints;
+
+        let complaints = self.complaints
+            .iter()
+            .filter(|&(_, complainers)| complainers.contains(&self.index))
+            .map(|(&dealer, _)| DkgComplaint { complainer: self.index, dealer })
+            .collect();
+
+        Ok(complaints)
+    }
+
+    /// Processes complaint broadcast by other participant.
+    ///
+    /// Returns justification that must be broadcast if the complaint is raised against this participant.
+    ///
+    /// # Arguments
+    ///
+    /// * `complaint` - Complaint of other participant
+    pub fn receive_complaint(&mut self, complaint: &DkgComplaint) -> Result<Option<DkgJustification>, IndyCryptoError> {
+        self._check_phase(DkgPhase::Complaints)?;
+        self._check_index(complaint.complainer)?;
+        self._check_index(complaint.dealer)?;
+
+        if complaint.complainer == complaint.dealer {
+            return Err(IndyCryptoError::InvalidStructure(format!("Participant can't complain against itself")));
+        }
+
+        self.complaints
+            .entry(complaint.dealer)
+            .or_insert_with(BTreeSet::new)
+            .insert(complaint.complainer);
+
+        if complaint.dealer != self.index {
+            return Ok(None);
+        }
+
+        Ok(Some(DkgJustification {
+            dealer: self.index,
+            receiver: complaint.complainer,
+            value: eval_polynomial(&self.polynomial, complaint.complainer)?
+        }))
+    }
+
+    /// Processes justification broadcast by the accused dealer.
+    ///
+    /// Valid justification resolves the complaint, otherwise the dealer is disqualified.
+    ///
+    /// # Arguments
+    ///
+    /// * `justification` - Justification of the accused dealer
+    pub fn receive_justification(&mut self, justification: &DkgJustification) -> Result<(), IndyCryptoError> {
+        self._check_phase(DkgPhase::Complaints)?;
+        self._check_index(justification.dealer)?;
+        self._check_index(justification.receiver)?;
+
+        let complained = self.complaints
+            .get(&justification.dealer)
+            .map(|complainers| complainers.contains(&justification.receiver))
+            .unwrap_or(false);
+
+        if !complained || self.disqualified.contains(&justification.dealer) {
+            return Ok(());
+        }
+
+        let valid = match self.commitments.get(&justification.dealer) {
+            Some(commitment) => self._verify_share(commitment, justification.receiver, &justification.value)?,
+            None => false
+        };
+
+        if !valid {
+            self.disqualified.insert(justification.dealer);
+            return Ok(());
+        }
+
+        if let Some(complainers) = self.complaints.get_mut(&justification.dealer) {
+            complainers.remove(&justification.receiver);
+        }
+
+        if justification.receiver == self.index {
+            self.shares.insert(justification.dealer, justification.value);
+        }
+
+        Ok(())
+    }
+
+    /// Finishes DKG and returns sign key share of the participant and group verification key.
+    ///
+    /// Dealers with unresolved complaints are disqualified.
+    pub fn finalize(&mut self) -> Result<DkgResult, IndyCryptoError> {
+        self._check_phase(DkgPhase::Complaints)?;
+
+        for (&dealer, complainers) in self.complaints.iter() {
+            if !complainers.is_empty() {
+                self.disqualified.insert(dealer);
+            }
+        }
+
+        let qualified: Vec<u32> = self.commitments
+            .keys()
+            .filter(|dealer| !self.disqualified.contains(dealer))
+            .cloned()
+            .collect();
+
+        if qualified.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("All dealers are disqualified")));
+        }
+
+        let mut share = index_to_element(0)?;
+        let mut ver_key = PointG2::new_inf()?;
+
+        for dealer in qualified.iter() {
+            let value = self.shares.get(dealer)
+                .ok_or(IndyCryptoError::InvalidState(format!("Share of qualified dealer {} is missing", dealer)))?;
+            share = share.add_mod(value)?;
+            ver_key = ver_key.add(&self.commitments[dealer][0])?;
+        }
+
+        let mut ver_key_shares = Vec::with_capacity(self.total as usize);
+        for index in 1..(self.total + 1) {
+            let mut point = PointG2::new_inf()?;
+            for dealer in qualified.iter() {
+                point = point.add(&self._eval_commitment(&self.commitments[dealer], index)?)?;
+            }
+            ver_key_shares.push(VerKeyShare::from_point(index, point)?);
+        }
+
+        self.phase = DkgPhase::Finished;
+
+        Ok(DkgResult {
+            sign_key_share: SignKeyShare::from_group_order_element(self.index, share)?,
+            ver_key: VerKey {
+                point: ver_key,
+                bytes: ver_key.to_bytes()?
+            },
+            ver_key_shares,
+            qualified
+        })
+    }
+
+    fn _complain(&mut self, dealer: u32) -> DkgComplaint {
+        self.complaints
+            .entry(dealer)
+            .or_insert_with(BTreeSet::new)
+            .insert(self.index);
+
+        DkgComplaint {
+            complainer: self.index,
+            dealer
+        }
+    }
+
+    fn _verify_share(&self, commitment: &[PointG2], receiver: u32, value: &GroupOrderElement) -> Result<bool, IndyCryptoError> {
+        // g^f(i) == C_0 * C_1^i * ... * C_(t-1)^(i^(t-1))
+        let expected = self._eval_commitment(commitment, receiver)?;
+        Ok(self.gen.mul(value)?.to_bytes()? == expected.to_bytes()?)
+    }
+
+    fn _eval_commitment(&self, commitment: &[PointG2], x: u32) -> Result<PointG2, IndyCryptoError> {
+        let x = index_to_element(x)?;
+
+        let mut result = PointG2::new_inf()?;
+        for coefficient in commitment.iter().rev() {
+            result = result.mul(&x)?.add(coefficient)?;
+        }
+
+        Ok(result)
+    }
+
+    fn _check_index(&self, index: u32) -> Result<(), IndyCryptoError> {
+        if index == 0 || index > self.total {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Invalid participant index: expected value in 1..{}, actual {}", self.total, index)));
+        }
+        Ok(())
+    }
+
+    fn _check_phase(&self, phase: DkgPhase) -> Result<(), IndyCryptoError> {
+        if self.phase != phase {
+            return Err(IndyCryptoError::InvalidState(
+                format!("Invalid DKG phase: expected {:?}, actual {:?}", phase, self.phase)));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls::Bls;
+    use bls::threshold::{ThresholdBls, PartialSignature};
+    use serde_json;
+
+    fn _run_sharing(participants: &mut Vec<DkgParticipant>) -> Vec<DkgComplaint> {
+        let commitments: Vec<DkgCommitment> = participants.iter().map(|p| p.commitment().unwrap()).collect();
+
+        for participant in participants.iter_mut() {
+            for commitment in commitments.iter().filter(|c| c.dealer() != participant.index()) {
+                participant.receive_commitment(commitment).unwrap();
+            }
+        }
+
+        let mut shares = Vec::new();
+        for dealer in participants.iter() {
+            for receiver in participants.iter().filter(|r| r.index() != dealer.index()) {
+                shares.push(dealer.share_for(receiver.index()).unwrap());
+            }
+        }
+
+        for participant in participants.iter_mut() {
+            for share in shares.iter().filter(|s| s.receiver() == participant.index()) {
+                participant.receive_share(share).unwrap();
+            }
+        }
+
+        participants.iter_mut().flat_map(|p| p.complaints().unwrap()).collect()
+    }
+
+    fn _run_complaints(participants: &mut Vec<DkgParticipant>, complaints: &[DkgComplaint]) {
+        let mut justifications = Vec::new();
+        for participant in participants.iter_mut() {
+            for complaint in complaints.iter() {
+                if let Some(justification) = participant.receive_complaint(complaint).unwrap() {
+                    justifications.push(justification);
+                }
+            }
+        }
+
+        for participant in participants.iter_mut() {
+            for justification in justifications.iter() {
+                participant.receive_justification(justification).unwrap();
+            }
+        }
+    }
+
+    fn _check_threshold_signature(results: &[DkgResult], gen: &Generator) {
+        let message = vec![1, 2, 3, 4, 5];
+        let ver_key = results[0].ver_key();
+
+        for result in results.iter() {
+            assert_eq!(ver_key.as_bytes(), result.ver_key().as_bytes());
+        }
+
+        let partial_signatures: Vec<PartialSignature> = results.iter()
+            .map(|result| ThresholdBls::sign(&message, result.sign_key_share()).unwrap())
+            .collect();
+
+        for (partial_signature, ver_key_share) in partial_signatures.iter().zip(results[0].ver_key_shares()) {
+            assert!(ThresholdBls::verify_partial(partial_signature, &message, ver_key_share, gen).unwrap());
+        }
+
+        let partials: Vec<&PartialSignature> = partial_signatures.iter().rev().take(3).collect();
+        let signature = ThresholdBls::combine_signatures(&partials).unwrap();
+
+        assert!(Bls::verify(&signature, &message, ver_key, gen).unwrap());
+    }
+
+    #[test]
+    fn dkg_works() {
+        let gen = Generator::new().unwrap();
+        let mut participants: Vec<DkgParticipant> = (1..5)
+            .map(|index| DkgParticipant::new(index, 3, 4, &gen).unwrap())
+            .collect();
+
+        let complaints = _run_sharing(&mut participants);
+        assert!(complaints.is_empty());
+
+        _run_complaints(&mut participants, &complaints);
+
+        let results: Vec<DkgResult> = participants.iter_mut().map(|p| p.finalize().unwrap()).collect();
+
+        for result in results.iter() {
+            assert_eq!(vec![1, 2, 3, 4], result.qualified());
+        }
+
+        _check_threshold_signature(&results, &gen);
+    }
+
+    #[test]
+    fn dkg_works_for_misbehaving_participant() {
+        let gen = Generator::new().unwrap();
+        let mut participants: Vec<DkgParticipant> = (1..6)
+            .map(|index| DkgParticipant::new(index, 3, 5, &gen).unwrap())
+            .collect();
+
+        let commitments: Vec<DkgCommitment> = participants.iter().map(|p| p.commitment().unwrap()).collect();
+        for participant in participants.iter_mut() {
+            for commitment in commitments.iter().filter(|c| c.dealer() != participant.index()) {
+                participant.receive_commitment(commitment).unwrap();
+            }
+        }
+
+        // Participant 5 sends shares that do not match its commitment
+        let mut complaints = Vec::new();
+        for dealer_pos in 0..participants.len() {
+            for receiver_pos in 0..participants.len() {
+                if dealer_pos == receiver_pos {
+                    continue;
+                }
+
+                let mut share = participants[dealer_pos].share_for(participants[receiver_pos].index()).unwrap();
+                if share.dealer() == 5 {
+                    share.value = share.value.add_mod(&index_to_element(1).unwrap()).unwrap();
+                }
+
+                if let Some(complaint) = participants[receiver_pos].receive_share(&share).unwrap() {
+                    complaints.push(complaint);
+                }
+            }
+        }
+
+        assert_eq!(4, complaints.len());
+        assert!(complaints.iter().all(|complaint| complaint.dealer() == 5));
+
+        let complaints: Vec<DkgComplaint> = participants.iter_mut().flat_map(|p| p.complaints().unwrap()).collect();
+        assert_eq!(4, complaints.len());
+
+        // Participant 5 answers with invalid justifications
+        let mut justifications = Vec::new();
+        for participant in participants.iter_mut() {
+            for complaint in complaints.iter() {
+                if let Some(mut justification) = participant.receive_complaint(complaint).unwrap() {
+                    justification.value = justification.value.add_mod(&index_to_element(1).unwrap()).unwrap();
+                    justifications.push(justification);
+                }
+            }
+        }
+
+        for participant in participants.iter_mut() {
+            for justification in justifications.iter() {
+                participant.receive_justification(justification).unwrap();
+            }
+        }
+
+        let results: Vec<DkgResult> = participants.iter_mut().map(|p| p.finalize().unwrap()).collect();
+
+        for result in results.iter() {
+            assert_eq!(vec![1, 2, 3, 4], result.qualified());
+        }
+
+        _check_threshold_signature(&results, &gen);
+    }
+
+    #[test]
+    fn dkg_works_for_resolved_complaint() {
+        let gen = Generator::new().unwrap();
+        let mut participants: Vec<DkgParticipant> = (1..4)
+            .map(|index| DkgParticipant::new(index, 2, 3, &gen).unwrap())
+            .collect();
+
+        let commitments: Vec<DkgCommitment> = participants.iter().map(|p| p.commitment().unwrap()).collect();
+        for participant in participants.iter_mut() {
+            for commitment in commitments.iter().filter(|c| c.dealer() != participant.index()) {
+                participant.receive_commitment(commitment).unwrap();
+            }
+        }
+
+        // Share of participant 1 for participant 2 is lost
+        for dealer_pos in 0..participants.len() {
+            for receiver_pos in 0..participants.len() {
+                if dealer_pos == receiver_pos || (dealer_pos == 0 && receiver_pos == 1) {
+                    continue;
+                }
+                let share = participants[dealer_pos].share_for(participants[receiver_pos].index()).unwrap();
+                assert!(participants[receiver_pos].receive_share(&share).unwrap().is_none());
+            }
+        }
+
+        let complaints: Vec<DkgComplaint> = participants.iter_mut().flat_map(|p| p.complaints().unwrap()).collect();
+        assert_eq!(vec![DkgComplaint { complainer: 2, dealer: 1 }], complaints);
+
+        _run_complaints(&mut participants, &complaints);
+
+        let results: Vec<DkgResult> = participants.iter_mut().map(|p| p.finalize().unwrap()).collect();
+
+        for result in results.iter() {
+            assert_eq!(vec![1, 2, 3], result.qualified());
+        }
+
+        _check_threshold_signature(&results, &gen);
+    }
+
+    #[test]
+    fn dkg_works_for_invalid_phase() {
+        let gen = Generator::new().unwrap();
+        let mut participant = DkgParticipant::new(1, 2, 3, &gen).unwrap();
+
+        assert!(participant.finalize().is_err());
+        participant.complaints().unwrap();
+        assert!(participant.complaints().is_err());
+    }
+
+    #[test]
+    fn dkg_messages_serialization_works() {
+        let gen = Generator::new().unwrap();
+        let participant = DkgParticipant::new(1, 2, 3, &gen).unwrap();
+        let mut receiver = DkgParticipant::new(2, 2, 3, &gen).unwrap();
+
+        let commitment_json = serde_json::to_string(&participant.commitment().unwrap()).unwrap();
+        let share_json = serde_json::to_string(&participant.share_for(2).unwrap()).unwrap();
+
+        let commitment: DkgCommitment = serde_json::from_str(&commitment_json).unwrap();
+        let share: DkgShare = serde_json::from_str(&share_json).unwrap();
+
+        receiver.receive_commitment(&commitment).unwrap();
+        assert!(receiver.receive_share(&share).unwrap().is_none());
+    }
+}
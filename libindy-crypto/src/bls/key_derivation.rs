@@ -0,0 +1,256 @@
+//! Hierarchical deterministic derivation of BLS sign keys in the style of EIP-2333.
+//!
+//! Master key is derived from seed with HKDF and child keys are derived from parent key through
+//! compressed Lamport public key, so child keys can't be linked to the parent key.
+//! Group order of the selected pairing backend is used as `r`, so for BLS12-381 results
+//! match EIP-2333 test vectors.
+
+use errors::IndyCryptoError;
+use pair::GroupOrderElement;
+
+use sha2::{Sha256, Digest};
+
+const KEYGEN_SALT: &'static [u8] = b"BLS-SIG-KEYGEN-SALT-";
+const MIN_SEED_SIZE: usize = 32;
+const HASH_SIZE: usize = 32;
+const HMAC_BLOCK_SIZE: usize = 64;
+const LAMPORT_CHUNKS: usize = 255;
+/// ceil((3 * ceil(log2(r))) / 16) for both 254 and 255 bit group orders.
+const HKDF_MOD_R_OKM_SIZE: usize = 48;
+
+/// Derives sign key from seed along path of form `m/index/index/...`.
+pub fn derive(seed: &[u8], path: &str) -> Result<GroupOrderElement, IndyCryptoError> {
+    if seed.len() < MIN_SEED_SIZE {
+        return Err(IndyCryptoError::InvalidStructure(
+            format!("Invalid len of seed: expected at least {}, actual {}", MIN_SEED_SIZE, seed.len())));
+    }
+
+    let indices = parse_path(path)?;
+
+    let mut sign_key = derive_master(seed)?;
+    for index in indices {
+        sign_key = derive_child(&sign_key, index)?;
+    }
+
+    Ok(sign_key)
+}
+
+fn parse_path(path: &str) -> Result<Vec<u32>, IndyCryptoError> {
+    let mut nodes = path.split('/');
+
+    if nodes.next() != Some("m") {
+        return Err(IndyCryptoError::InvalidStructure(format!("Invalid derivation path: {}, must start with m", path)));
+    }
+
+    nodes
+        .map(|node| node.parse::<u32>()
+            .map_err(|_| IndyCryptoError::InvalidStructure(format!("Invalid derivation path: {}, invalid index {}", path, node))))
+        .collect()
+}
+
+fn derive_master(seed: &[u8]) -> Result<GroupOrderElement, IndyCryptoError> {
+    hkdf_mod_r(seed)
+}
+
+fn derive_child(parent: &GroupOrderElement, index: u32) -> Result<GroupOrderElement, IndyCryptoError> {
+    let lamport_pk = parent_to_lamport_pk(parent, index)?;
+    hkdf_mod_r(&lamport_pk)
+}
+
+fn hkdf_mod_r(ikm: &[u8]) -> Result<GroupOrderElement, IndyCryptoError> {
+    let mut salt = KEYGEN_SALT.to_vec();
+
+    loop {
+        salt = sha256(&[salt.as_slice()]);
+
+        let prk = hkdf_extract(&salt, &[ikm, &[0u8][..]]);
+        let okm = hkdf_expand(&prk, &[0u8, HKDF_MOD_R_OKM_SIZE as u8], HKDF_MOD_R_OKM_SIZE);
+        let sign_key = GroupOrderElement::from_bytes_mod_order(&okm)?;
+
+        if sign_key.to_bytes()?.iter().any(|&b| b != 0) {
+            return Ok(sign_key);
+        }
+    }
+}
+
+fn parent_to_lamport_pk(parent: &GroupOrderElement, index: u32) -> Result<Vec<u8>, IndyCryptoError> {
+    let salt = [(index >> 24) as u8, (index >> 16) as u8, (index >> 8) as u8, index as u8];
+
+    let parent_bytes = parent.to_bytes()?;
+    let ikm = &parent_bytes[parent_bytes.len() - HASH_SIZE..];
+    let not_ikm: Vec<u8> = ikm.iter().map(|b| !b).collect();
+
+    let mut lamport_pk = Sha256::default();
+    for ikm in [ikm, not_ikm.as_slice()].iter() {
+        let lamport_sk = hkdf_expand(&hkdf_extract(&salt, &[*ikm]), &[], HASH_SIZE * LAMPORT_CHUNKS);
+        for chunk in lamport_sk.chunks(HASH_SIZE) {
+            lamport_pk.input(&sha256(&[chunk]));
+        }
+    }
+
+    Ok(lamport_pk.result().to_vec())
+}
+
+fn hkdf_extract(salt: &[u8], ikm: &[&[u8]]) -> Vec<u8> {
+    hmac_sha256(salt, ikm)
+}
+
+fn hkdf_expand(prk: &[u8], info: &[u8], len: usize) -> Vec<u8> {
+    let mut okm = Vec::with_capacity(len + HASH_SIZE);
+    let mut t = Vec::new();
+    let mut counter = 1u8;
+
+    while okm.len() < len {
+        t = hmac_sha256(prk, &[t.as_slice(), info, &[counter][..]]);
+        okm.extend_from_slice(&t);
+        counter += 1;
+    }
+
+    okm.truncate(len);
+    okm
+}
+
+fn hmac_sha256(key: &[u8], data: &[&[u8]]) -> Vec<u8> {
+    let mut key_block = [0u8; HMAC_BLOCK_SIZE];
+    if key.len() > HMAC_BLOCK_SIZE {
+        key_block[..HASH_SIZE].copy_from_slice(&sha256(&[key]));
+    } else {
+        key_block[..key.len()].copy_from_slice(key);
+    }
+
+    let inner_key: Vec<u8> = key_block.iter().map(|b| b ^ 0x36).collect();
+    let outer_key: Vec<u8> = key_block.iter().map(|b| b ^ 0x5c).collect();
+
+    let mut inner_data: Vec<&[u8]> = vec![inner_key.as_slice()];
+    inner_data.extend_from_slice(data);
+    let inner_hash = sha256(&inner_data);
+
+    sha256(&[outer_key.as_slice(), inner_hash.as_slice()])
+}
+
+fn sha256(data: &[&[u8]]) -> Vec<u8> {
+    let mut hasher = Sha256::default();
+    for d in data {
+        hasher.input(d);
+    }
+    hasher.result().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    fn _from_hex(hex: &str) -> Vec<u8> {
+        hex.as_bytes()
+            .chunks(2)
+            .map(|c| u8::from_str_radix(::std::str::from_utf8(c).unwrap(), 16).unwrap())
+            .collect()
+    }
+
+    fn _check_vector(seed: &str, index: u32, master: &str, child: &str) {
+        let seed = _from_hex(seed);
+
+        let master_key = derive(&seed, "m").unwrap().to_bytes().unwrap();
+        assert_eq!(format!("{:0>1$}", master, master_key.len() * 2), _hex(&master_key));
+
+        let child_key = derive(&seed, &format!("m/{}", index)).unwrap().to_bytes().unwrap();
+        assert_eq!(format!("{:0>1$}", child, child_key.len() * 2), _hex(&child_key));
+    }
+
+    #[test]
+    fn hmac_sha256_works() {
+        // RFC 4231 test case 2
+        let mac = hmac_sha256(b"Jefe", &[&b"what do ya want "[..], &b"for nothing?"[..]]);
+        assert_eq!("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843", _hex(&mac));
+    }
+
+    #[test]
+    fn hkdf_works() {
+        // RFC 5869 test case 1
+        let ikm = [0x0bu8; 22];
+        let salt = _from_hex("000102030405060708090a0b0c");
+        let info = _from_hex("f0f1f2f3f4f5f6f7f8f9");
+
+        let prk = hkdf_extract(&salt, &[&ikm[..]]);
+        assert_eq!("077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5", _hex(&prk));
+
+        let okm = hkdf_expand(&prk, &info, 42);
+        assert_eq!("3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865", _hex(&okm));
+    }
+
+    // EIP-2333 test vectors
+    #[cfg(all(feature = "pair_bls12_381", not(feature = "pair_amcl")))]
+    #[test]
+    fn derive_works() {
+        _check_vector("c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+                      0,
+                      "0d7359d57963ab8fbbde1852dcf553fedbc31f464d80ee7d40ae683122b45070",
+                      "2d18bd6c14e6d15bf8b5085c9b74f3daae3b03cc2014770a599d8c1539e50f8e");
+        _check_vector("3141592653589793238462643383279502884197169399375105820974944592",
+                      3141592653,
+                      "41c9e07822b092a93fd6797396338c3ada4170cc81829fdfce6b5d34bd5e7ec7",
+                      "384843fad5f3d777ea39de3e47a8f999ae91f89e42bffa993d91d9782d152a0f");
+        _check_vector("0099ff991111002299dd7744ee3355bbdd8844115566cc55663355668888cc00",
+                      4294967295,
+                      "3cfa341ab3910a7d00d933d8f7c4fe87c91798a0397421d6b19fd5b815132e80",
+                      "40e86285582f35b28821340f6a53b448588efa575bc4d88c32ef8567b8d9479b");
+        _check_vector("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+                      42,
+                      "2a0e28ffa5fbbe2f8e7aad4ed94f745d6bf755c51182e119bb1694fe61d3afca",
+                      "455c0dc9fccb3395825d92a60d2672d69416be1c2578a87a7a3d3ced11ebb88d");
+    }
+
+    // EIP-2333 test vector seeds with BN254 group order used as r
+    #[cfg(feature = "pair_amcl")]
+    #[test]
+    fn derive_works() {
+        _check_vector("c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+                      0,
+                      "246bce5784051ab083fe354038f294e19f43b88aebc4ab3559f1f64c16b74d50",
+                      "07be54e24bd624b7b0047f47963a1f0dfb28533558b4cfe7a1585f0b01b954f9");
+        _check_vector("3141592653589793238462643383279502884197169399375105820974944592",
+                      3141592653,
+                      "1232a377b771708d36e32477e848f33aa8ff569cd332accf6cc85fbfac731e10",
+                      "092490b68ebe51f39205b25aff3769b7d6a2dba755476177cd61c41cbdc51de5");
+        _check_vector("0099ff991111002299dd7744ee3355bbdd8844115566cc55663355668888cc00",
+                      4294967295,
+                      "07581868c659c98c06585d0e84e883f845b3753a87a3d5b92bc2cccdf4d52987",
+                      "17084748a678055df02475b758ef0ef825936267bf7e11bacc58534f8a08d3d8");
+        _check_vector("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+                      42,
+                      "0ae1a11b77cd2a3349e3e311596f81b2ad6a285d555d1bb0235640edcb47718e",
+                      "12251be030f054f6d0f1dcfca115d855b33639210a896ab678ab5eff59584a8d");
+    }
+
+    #[test]
+    fn derive_works_for_hierarchical_path() {
+        let seed = [7u8; 32];
+
+        let direct = derive(&seed, "m/12381/3600/0/0").unwrap();
+        let step = derive_child(&derive_child(&derive_child(&derive_child(&derive_master(&seed).unwrap(), 12381).unwrap(), 3600).unwrap(), 0).unwrap(), 0).unwrap();
+        assert_eq!(direct.to_bytes().unwrap(), step.to_bytes().unwrap());
+
+        let other = derive(&seed, "m/12381/3600/1/0").unwrap();
+        assert_ne!(direct.to_bytes().unwrap(), other.to_bytes().unwrap());
+    }
+
+    #[test]
+    fn derive_works_for_invalid_path() {
+        let seed = [7u8; 32];
+
+        assert!(derive(&seed, "").is_err());
+        assert!(derive(&seed, "n/0").is_err());
+        assert!(derive(&seed, "m/").is_err());
+        assert!(derive(&seed, "m/-1").is_err());
+        assert!(derive(&seed, "m/4294967296").is_err());
+    }
+
+    #[test]
+    fn derive_works_for_short_seed() {
+        assert!(derive(&[7u8; 31], "m").is_err());
+    }
+}
@@ -0,0 +1,528 @@
+//! BLS signatures with minimal verification key size.
+//!
+//! Verification keys and generator are points of G1 and signatures are points of G2,
+//! so verification keys are small and signatures are large. Sign keys are shared with `bls` module.
+//! Verification keys and generator are encoded as compressed G1 points (x coordinate and parity of y).
+//!
+//! All byte representations start with tag byte, so they can never be confused with
+//! byte representations of `bls` module types or with each other.
+
+use errors::IndyCryptoError;
+use pair::{PointG1, PointG2, Pair};
+use bls::SignKey;
+
+use sha2::{Sha256, Digest};
+use sha3::Keccak256;
+
+const GENERATOR_TAG: u8 = 0xA1;
+const VER_KEY_TAG: u8 = 0xA2;
+const PROOF_OF_POSSESSION_TAG: u8 = 0xA3;
+const SIGNATURE_TAG: u8 = 0xA4;
+const MULTI_SIGNATURE_TAG: u8 = 0xA5;
+
+/// BLS generator point in G1.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Generator {
+    point: PointG1,
+    bytes: Vec<u8>
+}
+
+impl Generator {
+    /// Creates and returns random generator point that satisfies BLS algorithm requirements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::min_pk::Generator;
+    /// Generator::new().unwrap();
+    /// ```
+    pub fn new() -> Result<Generator, IndyCryptoError> {
+        let point = PointG1::new()?;
+        Ok(Generator {
+            point,
+            bytes: _to_tagged_bytes(GENERATOR_TAG, point.to_bytes_compressed()?)
+        })
+    }
+
+    /// Returns BLS generator point bytes representation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::min_pk::Generator;
+    /// let gen = Generator::new().unwrap();
+    /// let gen_bytes = gen.as_bytes();
+    /// assert!(gen_bytes.len() > 0);
+    /// ```
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Creates and returns generator point from bytes representation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::min_pk::Generator;
+    /// let gen = Generator::new().unwrap();
+    /// let gen_bytes = gen.as_bytes();
+    /// Generator::from_bytes(gen_bytes).unwrap();
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Generator, IndyCryptoError> {
+        Ok(Generator {
+            point: PointG1::from_bytes_compressed(_from_tagged_bytes(GENERATOR_TAG, bytes)?)?,
+            bytes: bytes.to_vec()
+        })
+    }
+}
+
+/// BLS verification key in G1.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerKey {
+    point: PointG1,
+    bytes: Vec<u8>
+}
+
+impl VerKey {
+    /// Creates and returns BLS ver key that corresponds to sign key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::SignKey;
+    /// use indy_crypto::bls::min_pk::{Generator, VerKey};
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// VerKey::new(&gen, &sign_key).unwrap();
+    /// ```
+    pub fn new(gen: &Generator, sign_key: &SignKey) -> Result<VerKey, IndyCryptoError> {
+        let point = gen.point.mul(&sign_key.group_order_element)?;
+
+        Ok(VerKey {
+            point,
+            bytes: _to_tagged_bytes(VER_KEY_TAG, point.to_bytes_compressed()?)
+        })
+    }
+
+    /// Returns BLS verification key bytes representation.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Creates and returns BLS verification key from bytes representation.
+    pub fn from_bytes(bytes: &[u8]) -> Result<VerKey, IndyCryptoError> {
+        Ok(VerKey {
+            point: PointG1::from_bytes_compressed(_from_tagged_bytes(VER_KEY_TAG, bytes)?)?,
+            bytes: bytes.to_vec()
+        })
+    }
+}
+
+/// Proof of possession for BLS verification key in G1.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofOfPossession {
+    point: PointG2,
+    bytes: Vec<u8>
+}
+
+impl ProofOfPossession {
+    /// Creates and returns BLS proof of possession that corresponds to ver key.
+    ///
+    /// # Arguments
+    ///
+    /// * `ver_key` - Ver key
+    /// * `sign_key` - Sign key
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::SignKey;
+    /// use indy_crypto::bls::min_pk::{Generator, VerKey, ProofOfPossession};
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+    /// ProofOfPossession::new(&ver_key, &sign_key).unwrap();
+    /// ```
+    pub fn new(ver_key: &VerKey, sign_key: &SignKey) -> Result<ProofOfPossession, IndyCryptoError> {
+        let point = Bls::_gen_signature(&ver_key.bytes, sign_key, Keccak256::default())?;
+
+        Ok(ProofOfPossession {
+            point,
+            bytes: _to_tagged_bytes(PROOF_OF_POSSESSION_TAG, point.to_bytes()?)
+        })
+    }
+
+    /// Returns BLS proof of possession bytes representation.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Creates and returns BLS proof of possession from bytes representation.
+    pub fn from_bytes(bytes: &[u8]) -> Result<ProofOfPossession, IndyCryptoError> {
+        Ok(ProofOfPossession {
+            point: PointG2::from_bytes(_from_tagged_bytes(PROOF_OF_POSSESSION_TAG, bytes)?)?,
+            bytes: bytes.to_vec()
+        })
+    }
+}
+
+/// BLS signature in G2.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Signature {
+    point: PointG2,
+    bytes: Vec<u8>
+}
+
+impl Signature {
+    /// Returns BLS signature bytes representation.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Creates and returns BLS signature from bytes representation.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Signature, IndyCryptoError> {
+        Ok(Signature {
+            point: PointG2::from_bytes(_from_tagged_bytes(SIGNATURE_TAG, bytes)?)?,
+            bytes: bytes.to_vec()
+        })
+    }
+}
+
+/// BLS multi signature in G2.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MultiSignature {
+    point: PointG2,
+    bytes: Vec<u8>
+}
+
+impl MultiSignature {
+    /// Creates and returns multi signature for provided list of signatures.
+    ///
+    /// # Arguments
+    ///
+    /// * `signatures` - List of signatures
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::SignKey;
+    /// use indy_crypto::bls::min_pk::{Bls, MultiSignature};
+    /// let sign_key1 = SignKey::new(None).unwrap();
+    /// let sign_key2 = SignKey::new(None).unwrap();
+    ///
+    /// let message = vec![1, 2, 3, 4, 5];
+    ///
+    /// let signature1 = Bls::sign(&message, &sign_key1).unwrap();
+    /// let signature2 = Bls::sign(&message, &sign_key2).unwrap();
+    ///
+    /// MultiSignature::new(&[&signature1, &signature2]).unwrap();
+    /// ```
+    pub fn new(signatures: &[&Signature]) -> Result<MultiSignature, IndyCryptoError> {
+        let mut point = PointG2::new_inf()?;
+
+        for signature in signatures {
+            point = point.add(&signature.point)?;
+        }
+
+        Ok(MultiSignature {
+            point,
+            bytes: _to_tagged_bytes(MULTI_SIGNATURE_TAG, point.to_bytes()?)
+        })
+    }
+
+    /// Returns BLS multi signature bytes representation.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Creates and returns BLS multi signature from bytes representation.
+    pub fn from_bytes(bytes: &[u8]) -> Result<MultiSignature, IndyCryptoError> {
+        Ok(MultiSignature {
+            point: PointG2::from_bytes(_from_tagged_bytes(MULTI_SIGNATURE_TAG, bytes)?)?,
+            bytes: bytes.to_vec()
+        })
+    }
+}
+
+pub struct Bls {}
+
+impl Bls {
+    /// Signs the message and returns signature.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - Message to sign
+    /// * `sign_key` - Sign key
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::SignKey;
+    /// use indy_crypto::bls::min_pk::Bls;
+    /// let message = vec![1, 2, 3, 4, 5];
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// Bls::sign(&message, &sign_key).unwrap();
+    /// ```
+    pub fn sign(message: &[u8], sign_key: &SignKey) -> Result<Signature, IndyCryptoError> {
+        let point = Bls::_gen_signature(message, sign_key, Sha256::default())?;
+
+        Ok(Signature {
+            point,
+            bytes: _to_tagged_bytes(SIGNATURE_TAG, point.to_bytes()?)
+        })
+    }
+
+    /// Verifies the message signature and returns true - if signature valid or false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `signature` - Signature to verify
+    /// * `message` - Message to verify
+    /// * `ver_key` - Verification key
+    /// * `gen` - Generator point
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::SignKey;
+    /// use indy_crypto::bls::min_pk::{Bls, Generator, VerKey};
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+    /// let message = vec![1, 2, 3, 4, 5];
+    /// let signature = Bls::sign(&message, &sign_key).unwrap();
+    ///
+    /// let valid = Bls::verify(&signature, &message, &ver_key, &gen).unwrap();
+    /// assert!(valid);
+    /// ```
+    pub fn verify(signature: &Signature, message: &[u8], ver_key: &VerKey, gen: &Generator) -> Result<bool, IndyCryptoError> {
+        Bls::_verify_signature(&signature.point, message, &ver_key.point, gen, Sha256::default())
+    }
+
+    /// Verifies the proof of possession and returns true - if valid or false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `pop` - Proof of possession
+    /// * `ver_key` - Verification key
+    /// * `gen` - Generator point
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::SignKey;
+    /// use indy_crypto::bls::min_pk::{Bls, Generator, VerKey, ProofOfPossession};
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+    /// let pop = ProofOfPossession::new(&ver_key, &sign_key).unwrap();
+    ///
+    /// let valid = Bls::verify_proof_of_posession(&pop, &ver_key, &gen).unwrap();
+    /// assert!(valid);
+    /// ```
+    pub fn verify_proof_of_posession(pop: &ProofOfPossession, ver_key: &VerKey, gen: &Generator) -> Result<bool, IndyCryptoError> {
+        Bls::_verify_signature(&pop.point, &ver_key.bytes, &ver_key.point, gen, Keccak256::default())
+    }
+
+    /// Verifies the message multi signature and returns true - if signature valid or false otherwise.
+    ///
+    /// Proof of possession must be checked for every verification key.
+    ///
+    /// # Arguments
+    ///
+    /// * `multi_sig` - Multi signature to verify
+    /// * `message` - Message to verify
+    /// * `ver_keys` - List of verification keys
+    /// * `gen` - Generator point
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::SignKey;
+    /// use indy_crypto::bls::min_pk::{Bls, Generator, VerKey, MultiSignature};
+    /// let gen = Generator::new().unwrap();
+    ///
+    /// let sign_key1 = SignKey::new(None).unwrap();
+    /// let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+    /// let sign_key2 = SignKey::new(None).unwrap();
+    /// let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+    ///
+    /// let message = vec![1, 2, 3, 4, 5];
+    ///
+    /// let signature1 = Bls::sign(&message, &sign_key1).unwrap();
+    /// let signature2 = Bls::sign(&message, &sign_key2).unwrap();
+    ///
+    /// let multi_sig = MultiSignature::new(&[&signature1, &signature2]).unwrap();
+    ///
+    /// let valid = Bls::verify_multi_sig(&multi_sig, &message, &[&ver_key1, &ver_key2], &gen).unwrap();
+    /// assert!(valid)
+    /// ```
+    pub fn verify_multi_sig(multi_sig: &MultiSignature, message: &[u8], ver_keys: &[&VerKey], gen: &Generator) -> Result<bool, IndyCryptoError> {
+        let mut aggregated_verkey = PointG1::new_inf()?;
+        for ver_key in ver_keys {
+            aggregated_verkey = aggregated_verkey.add(&ver_key.point)?;
+        }
+
+        Bls::_verify_signature(&multi_sig.point, message, &aggregated_verkey, gen, Sha256::default())
+    }
+
+    fn _gen_signature<T>(message: &[u8], sign_key: &SignKey, hasher: T) -> Result<PointG2, IndyCryptoError> where T: Digest {
+        Bls::_hash(message, hasher)?.mul(&sign_key.group_order_element)
+    }
+
+    fn _verify_signature<T>(signature: &PointG2, message: &[u8], ver_key: &PointG1, gen: &Generator, hasher: T) -> Result<bool, IndyCryptoError> where T: Digest {
+        let h = Bls::_hash(message, hasher)?;
+
+        // e(g, sigma) == e(v, h) <=> e(-g, sigma) * e(v, h) == 1
+        Pair::multi_pair(&[(&gen.point.neg()?, signature), (ver_key, &h)])?.is_unity()
+    }
+
+    fn _hash<T>(message: &[u8], mut hasher: T) -> Result<PointG2, IndyCryptoError> where T: Digest {
+        hasher.input(message);
+        Ok(PointG2::from_hash(hasher.result().as_slice())?)
+    }
+}
+
+fn _to_tagged_bytes(tag: u8, bytes: Vec<u8>) -> Vec<u8> {
+    let mut tagged_bytes = Vec::with_capacity(bytes.len() + 1);
+    tagged_bytes.push(tag);
+    tagged_bytes.extend(bytes);
+    tagged_bytes
+}
+
+fn _from_tagged_bytes(tag: u8, bytes: &[u8]) -> Result<&[u8], IndyCryptoError> {
+    match bytes.split_first() {
+        Some((&actual_tag, point_bytes)) if actual_tag == tag => Ok(point_bytes),
+        _ => Err(IndyCryptoError::InvalidStructure(format!("Invalid tag of bytes representation: expected {:#x}", tag)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls;
+
+    #[test]
+    fn verify_works() {
+        let message = vec![1, 2, 3, 4, 5];
+
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let signature = Bls::sign(&message, &sign_key).unwrap();
+
+        assert!(Bls::verify(&signature, &message, &ver_key, &gen).unwrap());
+    }
+
+    #[test]
+    fn verify_works_for_invalid_message() {
+        let message = vec![1, 2, 3, 4, 5];
+        let message_invalid = vec![1, 2, 3, 4, 5, 6];
+
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let signature = Bls::sign(&message, &sign_key).unwrap();
+
+        assert!(!Bls::verify(&signature, &message_invalid, &ver_key, &gen).unwrap());
+    }
+
+    #[test]
+    fn verify_works_for_other_ver_key() {
+        let message = vec![1, 2, 3, 4, 5];
+
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let other_ver_key = VerKey::new(&gen, &SignKey::new(None).unwrap()).unwrap();
+        let signature = Bls::sign(&message, &sign_key).unwrap();
+
+        assert!(!Bls::verify(&signature, &message, &other_ver_key, &gen).unwrap());
+    }
+
+    #[test]
+    fn verify_proof_of_posession_works() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let pop = ProofOfPossession::new(&ver_key, &sign_key).unwrap();
+
+        assert!(Bls::verify_proof_of_posession(&pop, &ver_key, &gen).unwrap());
+    }
+
+    #[test]
+    fn verify_multi_sig_works() {
+        let message = vec![1, 2, 3, 4, 5];
+
+        let gen = Generator::new().unwrap();
+        let sign_key1 = SignKey::new(None).unwrap();
+        let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+        let sign_key2 = SignKey::new(None).unwrap();
+        let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+
+        let signature1 = Bls::sign(&message, &sign_key1).unwrap();
+        let signature2 = Bls::sign(&message, &sign_key2).unwrap();
+
+        let multi_signature = MultiSignature::new(&[&signature1, &signature2]).unwrap();
+
+        assert!(Bls::verify_multi_sig(&multi_signature, &message, &[&ver_key1, &ver_key2], &gen).unwrap());
+        assert!(!Bls::verify_multi_sig(&multi_signature, &message, &[&ver_key1], &gen).unwrap());
+    }
+
+    #[test]
+    fn ver_key_is_smaller_than_signature() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let signature = Bls::sign(&[1, 2, 3], &sign_key).unwrap();
+
+        assert!(ver_key.as_bytes().len() < signature.as_bytes().len());
+        assert!(ver_key.as_bytes().len() < bls::VerKey::new(&bls::Generator::new().unwrap(), &sign_key).unwrap().as_bytes().len());
+    }
+
+    #[test]
+    fn from_bytes_works() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let signature = Bls::sign(&[1, 2, 3], &sign_key).unwrap();
+        let multi_signature = MultiSignature::new(&[&signature]).unwrap();
+
+        assert_eq!(gen.as_bytes(), Generator::from_bytes(gen.as_bytes()).unwrap().as_bytes());
+        assert_eq!(ver_key.as_bytes(), VerKey::from_bytes(ver_key.as_bytes()).unwrap().as_bytes());
+        assert_eq!(signature.as_bytes(), Signature::from_bytes(signature.as_bytes()).unwrap().as_bytes());
+        assert_eq!(multi_signature.as_bytes(), MultiSignature::from_bytes(multi_signature.as_bytes()).unwrap().as_bytes());
+
+        let restored_ver_key = VerKey::from_bytes(ver_key.as_bytes()).unwrap();
+        let restored_signature = Signature::from_bytes(signature.as_bytes()).unwrap();
+        assert!(Bls::verify(&restored_signature, &[1, 2, 3], &restored_ver_key, &gen).unwrap());
+    }
+
+    #[test]
+    fn from_bytes_works_for_other_variant() {
+        let sign_key = SignKey::new(None).unwrap();
+
+        let gen = Generator::new().unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let signature = Bls::sign(&[1, 2, 3], &sign_key).unwrap();
+        let multi_signature = MultiSignature::new(&[&signature]).unwrap();
+
+        let other_gen = bls::Generator::new().unwrap();
+        let other_ver_key = bls::VerKey::new(&other_gen, &sign_key).unwrap();
+        let other_signature = bls::Bls::sign(&[1, 2, 3], &sign_key).unwrap();
+
+        // G1 points of the other variant
+        assert!(VerKey::from_bytes(other_signature.as_bytes()).is_err());
+        assert!(Generator::from_bytes(other_signature.as_bytes()).is_err());
+        // G2 points of the other variant
+        assert!(Signature::from_bytes(other_ver_key.as_bytes()).is_err());
+        assert!(MultiSignature::from_bytes(other_gen.as_bytes()).is_err());
+
+        assert!(bls::Signature::from_bytes(ver_key.as_bytes()).is_err());
+        assert!(bls::VerKey::from_bytes(signature.as_bytes()).is_err());
+        assert!(bls::Generator::from_bytes(multi_signature.as_bytes()).is_err());
+
+        // Types of the same variant
+        assert!(VerKey::from_bytes(gen.as_bytes()).is_err());
+        assert!(Signature::from_bytes(multi_signature.as_bytes()).is_err());
+    }
+}
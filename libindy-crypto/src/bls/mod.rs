@@ -1,9 +1,31 @@
+pub mod threshold;
+pub mod dkg;
+pub mod min_pk;
+mod key_derivation;
+
 use errors::IndyCryptoError;
 use pair::{GroupOrderElement, PointG2, PointG1, Pair};
 
 use sha2::{Sha256, Digest};
 use sha3::Keccak256;
 
+use std::collections::HashSet;
+
+/// Domain separation tag of the hash that derives multi signature coefficients.
+const BDN_DST: &'static [u8] = b"INDY_CRYPTO_BLS_BDN_COEFFICIENT";
+/// Multi signature coefficients are 128 bit long.
+const BDN_COEFFICIENT_SIZE: usize = 16;
+
+/// BLS signature scheme version. Defines how messages are hashed to the curve.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SignatureScheme {
+    /// SHA-256 digest of the message is mapped to the curve by try-and-increment.
+    V1,
+    /// Message is mapped to the curve by IETF hash-to-curve with domain separation tag
+    /// `BLS_SIG_<hash-to-curve suite>NUL_`.
+    V2
+}
+
 /// BLS generator point.
 /// BLS algorithm requires choosing of generator point that must be known to all parties.
 /// The most of BLS methods require generator to be provided.
@@ -92,6 +114,34 @@ impl SignKey {
         })
     }
 
+    /// Derives and returns BLS sign key from master seed along hierarchical path (EIP-2333 style).
+    ///
+    /// Master key is derived from the seed with HKDF and every path index derives a child key,
+    /// so any number of independent keys can be regenerated from one backed-up seed.
+    ///
+    /// # Arguments
+    ///
+    /// * `master_seed` - Master seed, at least 32 bytes long
+    /// * `path` - Derivation path of form `m/index/index/...`, for example `m/12381/3600/0/0`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::SignKey;
+    /// let master_seed = [1u8; 32];
+    /// let sign_key1 = SignKey::derive(&master_seed, "m/12381/3600/0/0").unwrap();
+    /// let sign_key2 = SignKey::derive(&master_seed, "m/12381/3600/0/0").unwrap();
+    /// assert_eq!(sign_key1.as_bytes(), sign_key2.as_bytes());
+    /// ```
+    pub fn derive(master_seed: &[u8], path: &str) -> Result<SignKey, IndyCryptoError> {
+        let group_order_element = key_derivation::derive(master_seed, path)?;
+
+        Ok(SignKey {
+            group_order_element: group_order_element,
+            bytes: group_order_element.to_bytes()?
+        })
+    }
+
     /// Returns BLS sign key bytes representation.
     ///
     /// # Example
@@ -322,6 +372,53 @@ impl MultiSignature {
         })
     }
 
+    /// Creates and returns rogue-key safe multi signature for provided list of signatures.
+    ///
+    /// Each signature is weighted by a hash of its verification key and the whole set of verification keys
+    /// (Boneh-Drijvers-Neven scheme), so proofs of possession are not required.
+    /// Multi signature must be verified with `Bls::verify_multi_sig_bdn`.
+    ///
+    /// # Arguments
+    ///
+    /// * `signatures` - List of signatures
+    /// * `ver_keys` - List of verification keys. i-th key corresponds to i-th signature
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key1 = SignKey::new(None).unwrap();
+    /// let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+    /// let sign_key2 = SignKey::new(None).unwrap();
+    /// let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+    ///
+    /// let message = vec![1, 2, 3, 4, 5];
+    ///
+    /// let signature1 = Bls::sign(&message, &sign_key1).unwrap();
+    /// let signature2 = Bls::sign(&message, &sign_key2).unwrap();
+    ///
+    /// MultiSignature::new_bdn(&[&signature1, &signature2], &[&ver_key1, &ver_key2]).unwrap();
+    /// ```
+    pub fn new_bdn(signatures: &[&Signature], ver_keys: &[&VerKey]) -> Result<MultiSignature, IndyCryptoError> {
+        if signatures.len() != ver_keys.len() {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Number of signatures {} doesn't match number of verification keys {}", signatures.len(), ver_keys.len())));
+        }
+
+        let coefficients = Bls::_bdn_coefficients(ver_keys)?;
+
+        let mut point = PointG1::new_inf()?;
+        for (signature, coefficient) in signatures.iter().zip(coefficients.iter()) {
+            point = point.add(&signature.point.mul(coefficient)?)?;
+        }
+
+        Ok(MultiSignature {
+            point,
+            bytes: point.to_bytes()?
+        })
+    }
+
     /// Returns BLS multi signature bytes representation.
     ///
     /// # Example
@@ -370,7 +467,27 @@ impl Bls {
     /// Bls::sign(&message, &sign_key).unwrap();
     /// ```
     pub fn sign(message: &[u8], sign_key: &SignKey) -> Result<Signature, IndyCryptoError> {
-        let point = Bls::_gen_signature(message, sign_key, Sha256::default())?;
+        Bls::sign_with_scheme(message, sign_key, SignatureScheme::V1)
+    }
+
+    /// Signs the message with given signature scheme and returns signature.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - Message to sign
+    /// * `sign_key` - Sign key
+    /// * `scheme` - Signature scheme version
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let message = vec![1, 2, 3, 4, 5];
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// Bls::sign_with_scheme(&message, &sign_key, SignatureScheme::V2).unwrap();
+    /// ```
+    pub fn sign_with_scheme(message: &[u8], sign_key: &SignKey, scheme: SignatureScheme) -> Result<Signature, IndyCryptoError> {
+        let point = Bls::_hash_with_scheme(message, scheme)?.mul(&sign_key.group_order_element)?;
 
         Ok(Signature {
             point,
@@ -401,7 +518,35 @@ impl Bls {
     /// assert!(valid);
     /// ```
     pub fn verify(signature: &Signature, message: &[u8], ver_key: &VerKey, gen: &Generator) -> Result<bool, IndyCryptoError> {
-        Bls::_verify_signature(&signature.point, message, &ver_key.point, gen, Sha256::default())
+        Bls::verify_with_scheme(signature, message, ver_key, gen, SignatureScheme::V1)
+    }
+
+    /// Verifies the message signature created with given signature scheme and returns true - if signature valid or false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `signature` - Signature to verify
+    /// * `message` - Message to verify
+    /// * `ver_key` - Verification key
+    /// * `gen` - Generator point
+    /// * `scheme` - Signature scheme version the signature was created with
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+    /// let message = vec![1, 2, 3, 4, 5];
+    /// let signature = Bls::sign_with_scheme(&message, &sign_key, SignatureScheme::V2).unwrap();
+    ///
+    /// let valid = Bls::verify_with_scheme(&signature, &message, &ver_key, &gen, SignatureScheme::V2).unwrap();
+    /// assert!(valid);
+    /// ```
+    pub fn verify_with_scheme(signature: &Signature, message: &[u8], ver_key: &VerKey, gen: &Generator, scheme: SignatureScheme) -> Result<bool, IndyCryptoError> {
+        let h = Bls::_hash_with_scheme(message, scheme)?;
+        Bls::_verify_pairing(&signature.point, &h, &ver_key.point, gen)
     }
 
     /// Verifies the proof of possession and returns true - if valid or false otherwise.
@@ -482,13 +627,260 @@ impl Bls {
         Bls::_verify_signature(&multi_sig.point, message, &aggregated_verkey, gen, Sha256::default())
     }
 
+    /// Verifies the message rogue-key safe multi signature created with `MultiSignature::new_bdn`
+    /// and returns true - if signature valid or false otherwise.
+    ///
+    /// Verification keys don't require proofs of possession.
+    ///
+    /// # Arguments
+    ///
+    /// * `multi_sig` - Multi signature to verify
+    /// * `message` - Message to verify
+    /// * `ver_keys` - List of verification keys
+    /// * `gen` - Generator point
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let gen = Generator::new().unwrap();
+    ///
+    /// let sign_key1 = SignKey::new(None).unwrap();
+    /// let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+    /// let sign_key2 = SignKey::new(None).unwrap();
+    /// let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+    ///
+    /// let message = vec![1, 2, 3, 4, 5];
+    ///
+    /// let signature1 = Bls::sign(&message, &sign_key1).unwrap();
+    /// let signature2 = Bls::sign(&message, &sign_key2).unwrap();
+    ///
+    /// let ver_keys = vec![
+    ///   &ver_key1, &ver_key2
+    /// ];
+    ///
+    /// let multi_sig = MultiSignature::new_bdn(&[&signature1, &signature2], &ver_keys).unwrap();
+    ///
+    /// let valid = Bls::verify_multi_sig_bdn(&multi_sig, &message, &ver_keys, &gen).unwrap();
+    /// assert!(valid)
+    /// ```
+    pub fn verify_multi_sig_bdn(multi_sig: &MultiSignature, message: &[u8], ver_keys: &[&VerKey], gen: &Generator) -> Result<bool, IndyCryptoError> {
+        let coefficients = Bls::_bdn_coefficients(ver_keys)?;
+
+        let mut aggregated_verkey = PointG2::new_inf()?;
+        for (ver_key, coefficient) in ver_keys.iter().zip(coefficients.iter()) {
+            aggregated_verkey = aggregated_verkey.add(&ver_key.point.mul(coefficient)?)?;
+        }
+
+        Bls::_verify_signature(&multi_sig.point, message, &aggregated_verkey, gen, Sha256::default())
+    }
+
+    /// Verifies the aggregated signature over distinct messages and returns true - if signature valid or false otherwise.
+    ///
+    /// Each message must be signed by the paired verification key. Messages must be distinct,
+    /// otherwise error is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `multi_sig` - Aggregated signature to verify
+    /// * `messages` - List of messages with verification keys of their signers
+    /// * `gen` - Generator point
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let gen = Generator::new().unwrap();
+    ///
+    /// let sign_key1 = SignKey::new(None).unwrap();
+    /// let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+    /// let sign_key2 = SignKey::new(None).unwrap();
+    /// let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+    ///
+    /// let message1 = vec![1, 2, 3, 4, 5];
+    /// let message2 = vec![6, 7, 8, 9, 10];
+    ///
+    /// let signature1 = Bls::sign(&message1, &sign_key1).unwrap();
+    /// let signature2 = Bls::sign(&message2, &sign_key2).unwrap();
+    ///
+    /// let multi_sig = MultiSignature::new(&[&signature1, &signature2]).unwrap();
+    ///
+    /// let messages = vec![
+    ///   (message1.as_slice(), &ver_key1),
+    ///   (message2.as_slice(), &ver_key2)
+    /// ];
+    ///
+    /// let valid = Bls::verify_aggregate(&multi_sig, &messages, &gen).unwrap();
+    /// assert!(valid)
+    /// ```
+    pub fn verify_aggregate(multi_sig: &MultiSignature, messages: &[(&[u8], &VerKey)], gen: &Generator) -> Result<bool, IndyCryptoError> {
+        if messages.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("List of messages is empty")));
+        }
+
+        // Aggregation over equal messages is only safe with proofs of possession, so
+        // such aggregates must be verified with verify_multi_sig.
+        let mut unique_messages = HashSet::new();
+        for &(message, _) in messages {
+            if !unique_messages.insert(message) {
+                return Err(IndyCryptoError::InvalidStructure(format!("Aggregated messages must be distinct")));
+            }
+        }
+
+        // e(sigma, g) == e(H(m1), v1) * ... * e(H(mn), vn)
+        let mut points: Vec<(PointG1, PointG2)> = Vec::with_capacity(messages.len() + 1);
+        points.push((multi_sig.point.neg()?, gen.point));
+        for &(message, ver_key) in messages {
+            points.push((Bls::_hash(message, Sha256::default())?, ver_key.point));
+        }
+
+        let pairs: Vec<(&PointG1, &PointG2)> = points.iter().map(|&(ref p, ref q)| (p, q)).collect();
+
+        Pair::multi_pair(&pairs)?.is_unity()
+    }
+
+    /// Verifies a batch of independent signatures at once.
+    ///
+    /// Signatures are combined with random coefficients and checked by a single pairing product
+    /// equation. If the batch is invalid the first invalid signature is located by bisection.
+    /// Returns None - if all signatures are valid or index of the first invalid signature otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `signatures` - List of signatures with signed messages and verification keys
+    /// * `gen` - Generator point
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let gen = Generator::new().unwrap();
+    ///
+    /// let sign_key1 = SignKey::new(None).unwrap();
+    /// let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+    /// let sign_key2 = SignKey::new(None).unwrap();
+    /// let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+    ///
+    /// let message1 = vec![1, 2, 3, 4, 5];
+    /// let message2 = vec![6, 7, 8, 9, 10];
+    ///
+    /// let signature1 = Bls::sign(&message1, &sign_key1).unwrap();
+    /// let signature2 = Bls::sign(&message2, &sign_key2).unwrap();
+    ///
+    /// let signatures = vec![
+    ///   (&signature1, message1.as_slice(), &ver_key1),
+    ///   (&signature2, message2.as_slice(), &ver_key2)
+    /// ];
+    ///
+    /// let invalid_index = Bls::batch_verify(&signatures, &gen).unwrap();
+    /// assert!(invalid_index.is_none())
+    /// ```
+    pub fn batch_verify(signatures: &[(&Signature, &[u8], &VerKey)], gen: &Generator) -> Result<Option<usize>, IndyCryptoError> {
+        if signatures.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("List of signatures is empty")));
+        }
+
+        let hashes = signatures
+            .iter()
+            .map(|&(_, message, _)| Bls::_hash(message, Sha256::default()))
+            .collect::<Result<Vec<PointG1>, IndyCryptoError>>()?;
+
+        Bls::_batch_find_invalid(signatures, &hashes, 0, false, gen)
+    }
+
+    fn _batch_find_invalid(signatures: &[(&Signature, &[u8], &VerKey)],
+                           hashes: &[PointG1],
+                           offset: usize,
+                           known_invalid: bool,
+                           gen: &Generator) -> Result<Option<usize>, IndyCryptoError> {
+        if !known_invalid && Bls::_batch_verify(signatures, hashes, gen)? {
+            return Ok(None);
+        }
+
+        if signatures.len() == 1 {
+            return Ok(Some(offset));
+        }
+
+        let mid = signatures.len() / 2;
+
+        if let Some(index) = Bls::_batch_find_invalid(&signatures[..mid], &hashes[..mid], offset, false, gen)? {
+            return Ok(Some(index));
+        }
+
+        // The first half is valid, so the invalid signature must be in the second one
+        Bls::_batch_find_invalid(&signatures[mid..], &hashes[mid..], offset + mid, true, gen)
+    }
+
+    fn _batch_verify(signatures: &[(&Signature, &[u8], &VerKey)], hashes: &[PointG1], gen: &Generator) -> Result<bool, IndyCryptoError> {
+        // e(r1 * s1 + ... + rn * sn, g) == e(r1 * H(m1), v1) * ... * e(rn * H(mn), vn)
+        let mut aggregated_signature = PointG1::new_inf()?;
+        let mut points: Vec<(PointG1, PointG2)> = Vec::with_capacity(signatures.len() + 1);
+
+        for (&(signature, _, ver_key), h) in signatures.iter().zip(hashes.iter()) {
+            let r = GroupOrderElement::new()?;
+            aggregated_signature = aggregated_signature.add(&signature.point.mul(&r)?)?;
+            points.push((h.mul(&r)?, ver_key.point));
+        }
+
+        points.push((aggregated_signature.neg()?, gen.point));
+
+        let pairs: Vec<(&PointG1, &PointG2)> = points.iter().map(|&(ref p, ref q)| (p, q)).collect();
+
+        Pair::multi_pair(&pairs)?.is_unity()
+    }
+
     fn _gen_signature<T>(message: &[u8], sign_key: &SignKey, hasher: T) -> Result<PointG1, IndyCryptoError> where T: Digest {
         Bls::_hash(message, hasher)?.mul(&sign_key.group_order_element)
     }
 
     pub fn _verify_signature<T>(signature: &PointG1, message: &[u8], ver_key: &PointG2, gen: &Generator, hasher: T) -> Result<bool, IndyCryptoError> where T: Digest {
         let h = Bls::_hash(message, hasher)?;
-        Ok(Pair::pair(&signature, &gen.point)?.eq(&Pair::pair(&h, &ver_key)?))
+        Bls::_verify_pairing(signature, &h, ver_key, gen)
+    }
+
+    fn _verify_pairing(signature: &PointG1, h: &PointG1, ver_key: &PointG2, gen: &Generator) -> Result<bool, IndyCryptoError> {
+        // e(sigma, g) == e(h, v) <=> e(-sigma, g) * e(h, v) == 1
+        Pair::multi_pair(&[(&signature.neg()?, &gen.point), (h, ver_key)])?.is_unity()
+    }
+
+    fn _hash_with_scheme(message: &[u8], scheme: SignatureScheme) -> Result<PointG1, IndyCryptoError> {
+        match scheme {
+            SignatureScheme::V1 => Bls::_hash(message, Sha256::default()),
+            SignatureScheme::V2 => {
+                let dst = format!("BLS_SIG_{}NUL_", PointG1::HASH_TO_CURVE_SUITE);
+                PointG1::hash_to_curve(message, dst.as_bytes())
+            }
+        }
+    }
+
+    fn _bdn_coefficients(ver_keys: &[&VerKey]) -> Result<Vec<GroupOrderElement>, IndyCryptoError> {
+        if ver_keys.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("List of verification keys is empty")));
+        }
+
+        // t_i = H(BDN_DST || vk_i || H(vk_1 || ... || vk_n)), keys of the set are sorted
+        // to make coefficients independent of the keys order.
+        let mut key_set: Vec<&[u8]> = ver_keys.iter().map(|ver_key| ver_key.as_bytes()).collect();
+        key_set.sort();
+
+        let mut key_set_hasher = Sha256::default();
+        for ver_key in key_set {
+            key_set_hasher.input(ver_key);
+        }
+        let key_set_hash = key_set_hasher.result();
+
+        let mut coefficients = Vec::with_capacity(ver_keys.len());
+        for ver_key in ver_keys {
+            let mut hasher = Sha256::default();
+            hasher.input(BDN_DST);
+            hasher.input(ver_key.as_bytes());
+            hasher.input(key_set_hash.as_slice());
+            let hash = hasher.result();
+
+            coefficients.push(GroupOrderElement::from_bytes(&hash.as_slice()[..BDN_COEFFICIENT_SIZE])?);
+        }
+
+        Ok(coefficients)
     }
 
     fn _hash<T>(message: &[u8], mut hasher: T) -> Result<PointG1, IndyCryptoError> where T: Digest {
@@ -517,6 +909,30 @@ mod tests {
         SignKey::new(Some(&seed)).unwrap();
     }
 
+    #[test]
+    fn sign_key_derive_works() {
+        let seed = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 2, 3, 4, 5, 6, 7, 8, 9, 10, 21, 2, 3, 4, 5, 6, 7, 8, 9, 10, 31, 32];
+
+        let sign_key1 = SignKey::derive(&seed, "m/12381/3600/0/0").unwrap();
+        let sign_key2 = SignKey::derive(&seed, "m/12381/3600/0/0").unwrap();
+        let sign_key3 = SignKey::derive(&seed, "m/12381/3600/1/0").unwrap();
+
+        assert_eq!(sign_key1.as_bytes(), sign_key2.as_bytes());
+        assert_ne!(sign_key1.as_bytes(), sign_key3.as_bytes());
+
+        let gen = Generator::new().unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key1).unwrap();
+        let message = vec![1, 2, 3, 4, 5];
+        let signature = Bls::sign(&message, &sign_key2).unwrap();
+        assert!(Bls::verify(&signature, &message, &ver_key, &gen).unwrap());
+    }
+
+    #[test]
+    fn sign_key_derive_works_for_short_seed() {
+        let seed = vec![1, 2, 3, 4, 5];
+        assert!(SignKey::derive(&seed, "m/0").is_err());
+    }
+
     #[test]
     fn ver_key_new_works() {
         let gen = Generator::new().unwrap();
@@ -610,6 +1026,34 @@ mod tests {
         assert!(!valid)
     }
 
+    #[test]
+    fn verify_with_scheme_works() {
+        let message = vec![1, 2, 3, 4, 5];
+
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+
+        let signature = Bls::sign_with_scheme(&message, &sign_key, SignatureScheme::V2).unwrap();
+
+        let valid = Bls::verify_with_scheme(&signature, &message, &ver_key, &gen, SignatureScheme::V2).unwrap();
+        assert!(valid)
+    }
+
+    #[test]
+    fn verify_with_scheme_works_for_other_scheme() {
+        let message = vec![1, 2, 3, 4, 5];
+
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+
+        let signature = Bls::sign_with_scheme(&message, &sign_key, SignatureScheme::V2).unwrap();
+
+        let valid = Bls::verify(&signature, &message, &ver_key, &gen).unwrap();
+        assert!(!valid)
+    }
+
     #[test]
     fn verify_multi_sig_works() {
         let message = vec![1, 2, 3, 4, 5];
@@ -639,6 +1083,83 @@ mod tests {
         assert!(valid)
     }
 
+    #[test]
+    fn verify_multi_sig_bdn_works() {
+        let message = vec![1, 2, 3, 4, 5];
+
+        let gen = Generator::new().unwrap();
+        let sign_key1 = SignKey::new(None).unwrap();
+        let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+        let sign_key2 = SignKey::new(None).unwrap();
+        let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+        let sign_key3 = SignKey::new(None).unwrap();
+        let ver_key3 = VerKey::new(&gen, &sign_key3).unwrap();
+
+        let signature1 = Bls::sign(&message, &sign_key1).unwrap();
+        let signature2 = Bls::sign(&message, &sign_key2).unwrap();
+        let signature3 = Bls::sign(&message, &sign_key3).unwrap();
+
+        let multi_signature = MultiSignature::new_bdn(&[&signature1, &signature2, &signature3],
+                                                      &[&ver_key1, &ver_key2, &ver_key3]).unwrap();
+
+        assert!(Bls::verify_multi_sig_bdn(&multi_signature, &message, &[&ver_key1, &ver_key2, &ver_key3], &gen).unwrap());
+        assert!(Bls::verify_multi_sig_bdn(&multi_signature, &message, &[&ver_key3, &ver_key1, &ver_key2], &gen).unwrap());
+        assert!(!Bls::verify_multi_sig_bdn(&multi_signature, &message, &[&ver_key1, &ver_key2], &gen).unwrap());
+        assert!(!Bls::verify_multi_sig(&multi_signature, &message, &[&ver_key1, &ver_key2, &ver_key3], &gen).unwrap());
+    }
+
+    #[test]
+    fn verify_multi_sig_bdn_works_for_invalid_message() {
+        let message = vec![1, 2, 3, 4, 5];
+        let message_invalid = vec![1, 2, 3, 4, 5, 6];
+
+        let gen = Generator::new().unwrap();
+        let sign_key1 = SignKey::new(None).unwrap();
+        let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+        let sign_key2 = SignKey::new(None).unwrap();
+        let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+
+        let signature1 = Bls::sign(&message, &sign_key1).unwrap();
+        let signature2 = Bls::sign(&message, &sign_key2).unwrap();
+
+        let multi_signature = MultiSignature::new_bdn(&[&signature1, &signature2], &[&ver_key1, &ver_key2]).unwrap();
+
+        assert!(!Bls::verify_multi_sig_bdn(&multi_signature, &message_invalid, &[&ver_key1, &ver_key2], &gen).unwrap());
+    }
+
+    #[test]
+    fn verify_multi_sig_bdn_works_for_rogue_key() {
+        let message = vec![1, 2, 3, 4, 5];
+
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+
+        // Attacker publishes vk_r = g^x / vk without knowing its sign key
+        let attacker_sign_key = SignKey::new(None).unwrap();
+        let rogue_point = gen.point.mul(&attacker_sign_key.group_order_element).unwrap().sub(&ver_key.point).unwrap();
+        let rogue_ver_key = VerKey { point: rogue_point, bytes: rogue_point.to_bytes().unwrap() };
+
+        let forged_signature = Bls::sign(&message, &attacker_sign_key).unwrap();
+        let forged_multi_signature = MultiSignature::new(&[&forged_signature]).unwrap();
+
+        assert!(Bls::verify_multi_sig(&forged_multi_signature, &message, &[&ver_key, &rogue_ver_key], &gen).unwrap());
+        assert!(!Bls::verify_multi_sig_bdn(&forged_multi_signature, &message, &[&ver_key, &rogue_ver_key], &gen).unwrap());
+    }
+
+    #[test]
+    fn multi_signature_new_bdn_works_for_invalid_lists() {
+        let message = vec![1, 2, 3, 4, 5];
+
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let signature = Bls::sign(&message, &sign_key).unwrap();
+
+        assert!(MultiSignature::new_bdn(&[&signature], &[&ver_key, &ver_key]).is_err());
+        assert!(MultiSignature::new_bdn(&[], &[]).is_err());
+    }
+
     #[test]
     fn verify_multi_sig_works_for_invalid_message() {
         let message = vec![1, 2, 3, 4, 5];
@@ -698,4 +1219,163 @@ mod tests {
 
         assert!(!valid)
     }
+    #[test]
+    fn verify_aggregate_works() {
+        let message1 = vec![1, 2, 3, 4, 5];
+        let message2 = vec![6, 7, 8, 9, 10];
+
+        let gen = Generator::new().unwrap();
+        let sign_key1 = SignKey::new(None).unwrap();
+        let sign_key2 = SignKey::new(None).unwrap();
+        let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+        let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+
+        let signature1 = Bls::sign(&message1, &sign_key1).unwrap();
+        let signature2 = Bls::sign(&message2, &sign_key2).unwrap();
+
+        let multi_signature = MultiSignature::new(&[&signature1, &signature2]).unwrap();
+
+        let messages = vec![
+            (message1.as_slice(), &ver_key1),
+            (message2.as_slice(), &ver_key2)
+        ];
+
+        let valid = Bls::verify_aggregate(&multi_signature, &messages, &gen).unwrap();
+        assert!(valid)
+    }
+
+    #[test]
+    fn verify_aggregate_works_for_invalid_message() {
+        let message1 = vec![1, 2, 3, 4, 5];
+        let message2 = vec![6, 7, 8, 9, 10];
+        let message_invalid = vec![6, 7, 8, 9, 10, 11];
+
+        let gen = Generator::new().unwrap();
+        let sign_key1 = SignKey::new(None).unwrap();
+        let sign_key2 = SignKey::new(None).unwrap();
+        let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+        let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+
+        let signature1 = Bls::sign(&message1, &sign_key1).unwrap();
+        let signature2 = Bls::sign(&message2, &sign_key2).unwrap();
+
+        let multi_signature = MultiSignature::new(&[&signature1, &signature2]).unwrap();
+
+        let messages = vec![
+            (message1.as_slice(), &ver_key1),
+            (message_invalid.as_slice(), &ver_key2)
+        ];
+
+        let valid = Bls::verify_aggregate(&multi_signature, &messages, &gen).unwrap();
+        assert!(!valid)
+    }
+
+    #[test]
+    fn verify_aggregate_works_for_duplicate_messages() {
+        let message = vec![1, 2, 3, 4, 5];
+
+        let gen = Generator::new().unwrap();
+        let sign_key1 = SignKey::new(None).unwrap();
+        let sign_key2 = SignKey::new(None).unwrap();
+        let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+        let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+
+        let signature1 = Bls::sign(&message, &sign_key1).unwrap();
+        let signature2 = Bls::sign(&message, &sign_key2).unwrap();
+
+        let multi_signature = MultiSignature::new(&[&signature1, &signature2]).unwrap();
+
+        let messages = vec![
+            (message.as_slice(), &ver_key1),
+            (message.as_slice(), &ver_key2)
+        ];
+
+        let res = Bls::verify_aggregate(&multi_signature, &messages, &gen);
+        assert!(res.is_err())
+    }
+
+    #[test]
+    fn batch_verify_works() {
+        let gen = Generator::new().unwrap();
+
+        let messages: Vec<Vec<u8>> = (0..5u8).map(|i| vec![i, 2, 3, 4, 5]).collect();
+        let sign_keys: Vec<SignKey> = (0..5).map(|_| SignKey::new(None).unwrap()).collect();
+        let ver_keys: Vec<VerKey> = sign_keys.iter().map(|sign_key| VerKey::new(&gen, sign_key).unwrap()).collect();
+        let signatures: Vec<Signature> = messages.iter().zip(sign_keys.iter())
+            .map(|(message, sign_key)| Bls::sign(message, sign_key).unwrap())
+            .collect();
+
+        let batch: Vec<(&Signature, &[u8], &VerKey)> = (0..5)
+            .map(|i| (&signatures[i], messages[i].as_slice(), &ver_keys[i]))
+            .collect();
+
+        let invalid_index = Bls::batch_verify(&batch, &gen).unwrap();
+        assert_eq!(None, invalid_index);
+    }
+
+    #[test]
+    fn batch_verify_works_for_invalid_signatures() {
+        let gen = Generator::new().unwrap();
+
+        let messages: Vec<Vec<u8>> = (0..7u8).map(|i| vec![i, 2, 3, 4, 5]).collect();
+        let sign_keys: Vec<SignKey> = (0..7).map(|_| SignKey::new(None).unwrap()).collect();
+        let ver_keys: Vec<VerKey> = sign_keys.iter().map(|sign_key| VerKey::new(&gen, sign_key).unwrap()).collect();
+        let mut signatures: Vec<Signature> = messages.iter().zip(sign_keys.iter())
+            .map(|(message, sign_key)| Bls::sign(message, sign_key).unwrap())
+            .collect();
+
+        signatures[3] = Bls::sign(&messages[4], &sign_keys[3]).unwrap();
+        signatures[5] = Bls::sign(&messages[5], &sign_keys[6]).unwrap();
+
+        let batch: Vec<(&Signature, &[u8], &VerKey)> = (0..7)
+            .map(|i| (&signatures[i], messages[i].as_slice(), &ver_keys[i]))
+            .collect();
+
+        let invalid_index = Bls::batch_verify(&batch, &gen).unwrap();
+        assert_eq!(Some(3), invalid_index);
+    }
+
+    #[test]
+    fn batch_verify_works_for_empty_list() {
+        let gen = Generator::new().unwrap();
+        let res = Bls::batch_verify(&[], &gen);
+        assert!(res.is_err())
+    }
+
+    /*
+    Compares verification of N signatures one by one with batch verification.
+    Run with `cargo test --release batch_verify_benchmark -- --ignored --nocapture`
+    */
+    #[test]
+    #[ignore]
+    fn batch_verify_benchmark() {
+        extern crate time;
+
+        let gen = Generator::new().unwrap();
+
+        for &n in [10usize, 100, 500].iter() {
+            let messages: Vec<Vec<u8>> = (0..n).map(|i| format!("message {}", i).into_bytes()).collect();
+            let sign_keys: Vec<SignKey> = (0..n).map(|_| SignKey::new(None).unwrap()).collect();
+            let ver_keys: Vec<VerKey> = sign_keys.iter().map(|sign_key| VerKey::new(&gen, sign_key).unwrap()).collect();
+            let signatures: Vec<Signature> = messages.iter().zip(sign_keys.iter())
+                .map(|(message, sign_key)| Bls::sign(message, sign_key).unwrap())
+                .collect();
+
+            let start_time = time::get_time();
+            for i in 0..n {
+                assert!(Bls::verify(&signatures[i], &messages[i], &ver_keys[i], &gen).unwrap());
+            }
+            let end_time = time::get_time();
+            println!("Verify {} signatures one by one Time: {:?}", n, end_time - start_time);
+
+            let batch: Vec<(&Signature, &[u8], &VerKey)> = (0..n)
+                .map(|i| (&signatures[i], messages[i].as_slice(), &ver_keys[i]))
+                .collect();
+
+            let start_time = time::get_time();
+            assert_eq!(None, Bls::batch_verify(&batch, &gen).unwrap());
+            let end_time = time::get_time();
+            println!("Batch verify {} signatures Time: {:?}", n, end_time - start_time);
+        }
+    }
 }
\ No newline at end of file
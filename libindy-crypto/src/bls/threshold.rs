@@ -0,0 +1,464 @@
+use errors::IndyCryptoError;
+use pair::{GroupOrderElement, PointG1, PointG2};
+use bls::{Bls, Generator, SignKey, VerKey, Signature};
+
+use std::collections::HashSet;
+
+/// Share of BLS sign key created by Shamir secret sharing.
+/// Any `threshold` shares are enough to produce signature that verifies under the group ver key.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignKeyShare {
+    index: u32,
+    sign_key: SignKey
+}
+
+impl SignKeyShare {
+    pub(crate) fn from_group_order_element(index: u32, group_order_element: GroupOrderElement) -> Result<SignKeyShare, IndyCryptoError> {
+        Ok(SignKeyShare {
+            index,
+            sign_key: SignKey {
+                group_order_element,
+                bytes: group_order_element.to_bytes()?
+            }
+        })
+    }
+
+    /// Returns index of the share. Indexes start from 1.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Returns sign key of the share.
+    pub fn sign_key(&self) -> &SignKey {
+        &self.sign_key
+    }
+}
+
+/// Verification key that corresponds to BLS sign key share.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerKeyShare {
+    index: u32,
+    ver_key: VerKey
+}
+
+impl VerKeyShare {
+    /// Creates and returns verification key that corresponds to sign key share.
+    ///
+    /// # Arguments
+    ///
+    /// * `gen` - Generator point
+    /// * `sign_key_share` - Sign key share
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// use indy_crypto::bls::threshold::*;
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let shares = ThresholdBls::share_sign_key(&sign_key, 2, 3).unwrap();
+    /// VerKeyShare::new(&gen, &shares[0]).unwrap();
+    /// ```
+    pub fn new(gen: &Generator, sign_key_share: &SignKeyShare) -> Result<VerKeyShare, IndyCryptoError> {
+        Ok(VerKeyShare {
+            index: sign_key_share.index,
+            ver_key: VerKey::new(gen, &sign_key_share.sign_key)?
+        })
+    }
+
+    pub(crate) fn from_point(index: u32, point: PointG2) -> Result<VerKeyShare, IndyCryptoError> {
+        Ok(VerKeyShare {
+            index,
+            ver_key: VerKey {
+                point,
+                bytes: point.to_bytes()?
+            }
+        })
+    }
+
+    /// Returns index of the share.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Returns verification key of the share.
+    pub fn ver_key(&self) -> &VerKey {
+        &self.ver_key
+    }
+}
+
+/// BLS signature created with sign key share.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PartialSignature {
+    index: u32,
+    signature: Signature
+}
+
+impl PartialSignature {
+    /// Returns index of the sign key share the signature was created with.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Returns signature.
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+}
+
+pub struct ThresholdBls {}
+
+impl ThresholdBls {
+    /// Splits sign key into `total` shares so that any `threshold` of them can produce signature.
+    ///
+    /// # Arguments
+    ///
+    /// * `sign_key` - Sign key to split
+    /// * `threshold` - Number of shares required to produce signature
+    /// * `total` - Total number of shares
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// use indy_crypto::bls::threshold::*;
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let shares = ThresholdBls::share_sign_key(&sign_key, 2, 3).unwrap();
+    /// assert_eq!(3, shares.len());
+    /// ```
+    pub fn share_sign_key(sign_key: &SignKey, threshold: usize, total: usize) -> Result<Vec<SignKeyShare>, IndyCryptoError> {
+        if threshold == 0 || threshold > total {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Invalid threshold: expected value in 1..{}, actual {}", total, threshold)));
+        }
+
+        if total > u32::max_value() as usize {
+            return Err(IndyCryptoError::InvalidStructure(format!("Too many shares: {}", total)));
+        }
+
+        // f(x) = sk + a_1 * x + ... + a_(t-1) * x^(t-1)
+        let mut coefficients = Vec::with_capacity(threshold);
+        coefficients.push(sign_key.group_order_element);
+        for _ in 1..threshold {
+            coefficients.push(GroupOrderElement::new()?);
+        }
+
+        let mut shares = Vec::with_capacity(total);
+        for index in 1..(total as u32 + 1) {
+            shares.push(SignKeyShare::from_group_order_element(index, eval_polynomial(&coefficients, index)?)?);
+        }
+
+        Ok(shares)
+    }
+
+    /// Signs the message with sign key share and returns partial signature.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - Message to sign
+    /// * `sign_key_share` - Sign key share
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// use indy_crypto::bls::threshold::*;
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let shares = ThresholdBls::share_sign_key(&sign_key, 2, 3).unwrap();
+    /// let message = vec![1, 2, 3, 4, 5];
+    /// ThresholdBls::sign(&message, &shares[0]).unwrap();
+    /// ```
+    pub fn sign(message: &[u8], sign_key_share: &SignKeyShare) -> Result<PartialSignature, IndyCryptoError> {
+        Ok(PartialSignature {
+            index: sign_key_share.index,
+            signature: Bls::sign(message, &sign_key_share.sign_key)?
+        })
+    }
+
+    /// Verifies partial signature and returns true - if signature valid or false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `partial_signature` - Partial signature to verify
+    /// * `message` - Message to verify
+    /// * `ver_key_share` - Verification key of the share the signature was created with
+    /// * `gen` - Generator point
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// use indy_crypto::bls::threshold::*;
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let shares = ThresholdBls::share_sign_key(&sign_key, 2, 3).unwrap();
+    /// let ver_key_share = VerKeyShare::new(&gen, &shares[0]).unwrap();
+    /// let message = vec![1, 2, 3, 4, 5];
+    /// let partial_signature = ThresholdBls::sign(&message, &shares[0]).unwrap();
+    ///
+    /// let valid = ThresholdBls::verify_partial(&partial_signature, &message, &ver_key_share, &gen).unwrap();
+    /// assert!(valid);
+    /// ```
+    pub fn verify_partial(partial_signature: &PartialSignature, message: &[u8], ver_key_share: &VerKeyShare, gen: &Generator) -> Result<bool, IndyCryptoError> {
+        if partial_signature.index != ver_key_share.index {
+            return Ok(false);
+        }
+
+        Bls::verify(&partial_signature.signature, message, &ver_key_share.ver_key, gen)
+    }
+
+    /// Combines partial signatures into signature that verifies under the group ver key.
+    ///
+    /// At least `threshold` partial signatures created with distinct shares must be provided,
+    /// otherwise resulting signature will not verify.
+    ///
+    /// # Arguments
+    ///
+    /// * `partial_signatures` - List of partial signatures
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// use indy_crypto::bls::threshold::*;
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+    /// let shares = ThresholdBls::share_sign_key(&sign_key, 2, 3).unwrap();
+    /// let message = vec![1, 2, 3, 4, 5];
+    ///
+    /// let partial_signature1 = ThresholdBls::sign(&message, &shares[0]).unwrap();
+    /// let partial_signature3 = ThresholdBls::sign(&message, &shares[2]).unwrap();
+    ///
+    /// let signature = ThresholdBls::combine_signatures(&[&partial_signature1, &partial_signature3]).unwrap();
+    ///
+    /// let valid = Bls::verify(&signature, &message, &ver_key, &gen).unwrap();
+    /// assert!(valid);
+    /// ```
+    pub fn combine_signatures(partial_signatures: &[&PartialSignature]) -> Result<Signature, IndyCryptoError> {
+        let indices: Vec<u32> = partial_signatures.iter().map(|partial| partial.index).collect();
+        let coefficients = lagrange_coefficients(&indices)?;
+
+        let mut point = PointG1::new_inf()?;
+        for (partial, coefficient) in partial_signatures.iter().zip(coefficients.iter()) {
+            point = point.add(&partial.signature.point.mul(coefficient)?)?;
+        }
+
+        Ok(Signature {
+            point,
+            bytes: point.to_bytes()?
+        })
+    }
+
+    /// Combines verification keys of shares into the group verification key.
+    ///
+    /// At least `threshold` verification keys of distinct shares must be provided.
+    ///
+    /// # Arguments
+    ///
+    /// * `ver_key_shares` - List of verification keys of shares
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// use indy_crypto::bls::threshold::*;
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+    /// let shares = ThresholdBls::share_sign_key(&sign_key, 2, 3).unwrap();
+    ///
+    /// let ver_key_share1 = VerKeyShare::new(&gen, &shares[0]).unwrap();
+    /// let ver_key_share2 = VerKeyShare::new(&gen, &shares[1]).unwrap();
+    ///
+    /// let group_ver_key = ThresholdBls::combine_ver_keys(&[&ver_key_share1, &ver_key_share2]).unwrap();
+    /// assert_eq!(ver_key.as_bytes(), group_ver_key.as_bytes());
+    /// ```
+    pub fn combine_ver_keys(ver_key_shares: &[&VerKeyShare]) -> Result<VerKey, IndyCryptoError> {
+        let indices: Vec<u32> = ver_key_shares.iter().map(|share| share.index).collect();
+        let coefficients = lagrange_coefficients(&indices)?;
+
+        let mut point = PointG2::new_inf()?;
+        for (share, coefficient) in ver_key_shares.iter().zip(coefficients.iter()) {
+            point = point.add(&share.ver_key.point.mul(coefficient)?)?;
+        }
+
+        Ok(VerKey {
+            point,
+            bytes: point.to_bytes()?
+        })
+    }
+}
+
+/// Converts share index to group order element.
+pub(crate) fn index_to_element(index: u32) -> Result<GroupOrderElement, IndyCryptoError> {
+    GroupOrderElement::from_bytes(&[(index >> 24) as u8, (index >> 16) as u8, (index >> 8) as u8, index as u8])
+}
+
+/// Evaluates polynomial with given coefficients (lowest degree first) at point `x`.
+pub(crate) fn eval_polynomial(coefficients: &[GroupOrderElement], x: u32) -> Result<GroupOrderElement, IndyCryptoError> {
+    let x = index_to_element(x)?;
+
+    // Horner's rule
+    let mut result = index_to_element(0)?;
+    for coefficient in coefficients.iter().rev() {
+        result = result.mul_mod(&x)?.add_mod(coefficient)?;
+    }
+
+    Ok(result)
+}
+
+/// Lagrange coefficients for interpolation at zero: l_i = Π_(j != i) x_j / (x_j - x_i).
+pub(crate) fn lagrange_coefficients(indices: &[u32]) -> Result<Vec<GroupOrderElement>, IndyCryptoError> {
+    if indices.is_empty() {
+        return Err(IndyCryptoError::InvalidStructure(format!("List of shares is empty")));
+    }
+
+    let mut unique_indices = HashSet::new();
+    for &index in indices {
+        if index == 0 {
+            return Err(IndyCryptoError::InvalidStructure(format!("Share index must be greater than 0")));
+        }
+
+        if !unique_indices.insert(index) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Duplicate share index: {}", index)));
+        }
+    }
+
+    let elements = indices.iter()
+        .map(|&index| index_to_element(index))
+        .collect::<Result<Vec<GroupOrderElement>, IndyCryptoError>>()?;
+
+    let mut coefficients = Vec::with_capacity(elements.len());
+    for (i, x_i) in elements.iter().enumerate() {
+        let mut numerator = index_to_element(1)?;
+        let mut denominator = index_to_element(1)?;
+
+        for (j, x_j) in elements.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            numerator = numerator.mul_mod(x_j)?;
+            denominator = denominator.mul_mod(&x_j.sub_mod(x_i)?)?;
+        }
+
+        coefficients.push(numerator.mul_mod(&denominator.inverse()?)?);
+    }
+
+    Ok(coefficients)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn share_sign_key_works() {
+        let sign_key = SignKey::new(None).unwrap();
+        let shares = ThresholdBls::share_sign_key(&sign_key, 3, 5).unwrap();
+
+        assert_eq!(5, shares.len());
+        for (i, share) in shares.iter().enumerate() {
+            assert_eq!(i as u32 + 1, share.index());
+        }
+    }
+
+    #[test]
+    fn share_sign_key_works_for_invalid_threshold() {
+        let sign_key = SignKey::new(None).unwrap();
+
+        assert!(ThresholdBls::share_sign_key(&sign_key, 0, 5).is_err());
+        assert!(ThresholdBls::share_sign_key(&sign_key, 6, 5).is_err());
+    }
+
+    #[test]
+    fn share_sign_key_works_for_threshold_one() {
+        let sign_key = SignKey::new(None).unwrap();
+        let shares = ThresholdBls::share_sign_key(&sign_key, 1, 3).unwrap();
+
+        for share in shares.iter() {
+            assert_eq!(sign_key.as_bytes(), share.sign_key().as_bytes());
+        }
+    }
+
+    #[test]
+    fn verify_partial_works() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let shares = ThresholdBls::share_sign_key(&sign_key, 2, 3).unwrap();
+        let ver_key_share1 = VerKeyShare::new(&gen, &shares[0]).unwrap();
+        let ver_key_share2 = VerKeyShare::new(&gen, &shares[1]).unwrap();
+        let message = vec![1, 2, 3, 4, 5];
+
+        let partial_signature = ThresholdBls::sign(&message, &shares[0]).unwrap();
+
+        assert!(ThresholdBls::verify_partial(&partial_signature, &message, &ver_key_share1, &gen).unwrap());
+        assert!(!ThresholdBls::verify_partial(&partial_signature, &message, &ver_key_share2, &gen).unwrap());
+    }
+
+    #[test]
+    fn combine_signatures_works() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let shares = ThresholdBls::share_sign_key(&sign_key, 3, 5).unwrap();
+        let message = vec![1, 2, 3, 4, 5];
+
+        let partial_signatures: Vec<PartialSignature> = shares.iter()
+            .map(|share| ThresholdBls::sign(&message, share).unwrap())
+            .collect();
+
+        let subsets: [&[usize]; 4] = [&[0, 1, 2], &[4, 2, 0], &[1, 3, 4], &[0, 1, 2, 3, 4]];
+        for subset in subsets.iter() {
+            let partials: Vec<&PartialSignature> = subset.iter().map(|&i| &partial_signatures[i]).collect();
+            let signature = ThresholdBls::combine_signatures(&partials).unwrap();
+
+            assert!(Bls::verify(&signature, &message, &ver_key, &gen).unwrap());
+            assert_eq!(Bls::sign(&message, &sign_key).unwrap().as_bytes(), signature.as_bytes());
+        }
+    }
+
+    #[test]
+    fn combine_signatures_works_for_not_enough_signatures() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let shares = ThresholdBls::share_sign_key(&sign_key, 3, 5).unwrap();
+        let message = vec![1, 2, 3, 4, 5];
+
+        let partial_signature1 = ThresholdBls::sign(&message, &shares[0]).unwrap();
+        let partial_signature2 = ThresholdBls::sign(&message, &shares[1]).unwrap();
+
+        let signature = ThresholdBls::combine_signatures(&[&partial_signature1, &partial_signature2]).unwrap();
+
+        assert!(!Bls::verify(&signature, &message, &ver_key, &gen).unwrap());
+    }
+
+    #[test]
+    fn combine_signatures_works_for_duplicate_shares() {
+        let sign_key = SignKey::new(None).unwrap();
+        let shares = ThresholdBls::share_sign_key(&sign_key, 2, 3).unwrap();
+        let message = vec![1, 2, 3, 4, 5];
+
+        let partial_signature = ThresholdBls::sign(&message, &shares[0]).unwrap();
+
+        assert!(ThresholdBls::combine_signatures(&[&partial_signature, &partial_signature]).is_err());
+        assert!(ThresholdBls::combine_signatures(&[]).is_err());
+    }
+
+    #[test]
+    fn combine_ver_keys_works() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let shares = ThresholdBls::share_sign_key(&sign_key, 3, 5).unwrap();
+
+        let ver_key_shares: Vec<VerKeyShare> = shares.iter()
+            .map(|share| VerKeyShare::new(&gen, share).unwrap())
+            .collect();
+
+        let group_ver_key = ThresholdBls::combine_ver_keys(&[&ver_key_shares[4], &ver_key_shares[1], &ver_key_shares[3]]).unwrap();
+
+        assert_eq!(ver_key.as_bytes(), group_ver_key.as_bytes());
+    }
+}
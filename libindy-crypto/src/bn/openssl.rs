@@ -16,6 +16,7 @@ use std::error::Error;
 use std::fmt;
 use std::cmp::Ord;
 use std::cmp::Ordering;
+use std::hash::{Hash, Hasher as StdHasher};
 
 pub struct BigNumberContext {
     openssl_bn_context: BigNumContext
@@ -340,7 +341,7 @@ impl BigNumber {
     }
 
     pub fn increment(&self) -> Result<BigNumber, IndyCryptoError> {
-        let mut bn = BigNum::from_slice(&self.openssl_bn.to_vec())?;
+        let mut bn = self._to_signed_bignum()?;
         bn.add_word(1)?;
         Ok(BigNumber {
             openssl_bn: bn
@@ -348,7 +349,7 @@ impl BigNumber {
     }
 
     pub fn decrement(&self) -> Result<BigNumber, IndyCryptoError> {
-        let mut bn = BigNum::from_slice(&self.openssl_bn.to_vec())?;
+        let mut bn = self._to_signed_bignum()?;
         bn.sub_word(1)?;
         Ok(BigNumber {
             openssl_bn: bn
@@ -403,10 +404,16 @@ impl BigNumber {
 
     pub fn clone(&self) -> Result<BigNumber, IndyCryptoError> {
         Ok(BigNumber {
-            openssl_bn: BigNum::from_slice(&self.openssl_bn.to_vec()[..])?
+            openssl_bn: self._to_signed_bignum()?
         })
     }
 
+    fn _to_signed_bignum(&self) -> Result<BigNum, IndyCryptoError> {
+        let mut bn = BigNum::from_slice(&self.openssl_bn.to_vec())?;
+        bn.set_negative(self.openssl_bn.is_negative());
+        Ok(bn)
+    }
+
     pub fn hash_array(nums: &Vec<Vec<u8>>) -> Result<Vec<u8>, IndyCryptoError> {
         let mut sha256 = Hasher::new(MessageDigest::sha256())?;
 
@@ -418,6 +425,13 @@ impl BigNumber {
     }
 }
 
+impl Hash for BigNumber {
+    fn hash<H: StdHasher>(&self, state: &mut H) {
+        self.openssl_bn.is_negative().hash(state);
+        self.openssl_bn.to_vec().hash(state);
+    }
+}
+
 impl Ord for BigNumber {
     fn cmp(&self, other: &BigNumber) -> Ordering {
         self.openssl_bn.cmp(&other.openssl_bn)
@@ -576,6 +590,14 @@ mod tests {
         assert_eq!(num.increment().unwrap(), num.add(&BIGNUMBER_1).unwrap());
     }
 
+    #[test]
+    fn clone_works_for_negative() {
+        let num = BigNumber::from_dec("-1234567890123456789012345678901234567890").unwrap();
+        assert_eq!(num, num.clone().unwrap());
+        assert_eq!(BigNumber::from_dec("-1234567890123456789012345678901234567889").unwrap(), num.increment().unwrap());
+        assert_eq!(BigNumber::from_dec("-1234567890123456789012345678901234567891").unwrap(), num.decrement().unwrap());
+    }
+
     #[test]
     fn rshift1_works() {
         let num = BigNumber::from_u32(1000).unwrap();
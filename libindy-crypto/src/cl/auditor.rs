@@ -0,0 +1,141 @@
+use bn::{BigNumber, BIGNUMBER_1, BIGNUMBER_2};
+use cl::*;
+use cl::constants::LARGE_PRIME;
+use cl::helpers::*;
+use errors::IndyCryptoError;
+
+/// Trusted third party (escrow authority) that can recover verifiably encrypted attributes.
+pub struct Auditor {}
+
+impl Auditor {
+    /// Creates and returns auditor keys (public and private) for verifiable encryption.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::auditor::Auditor;
+    ///
+    /// let (_auditor_pub_key, _auditor_priv_key) = Auditor::new_auditor_keys().unwrap();
+    /// ```
+    pub fn new_auditor_keys() -> Result<(AuditorPublicKey, AuditorPrivateKey), IndyCryptoError> {
+        trace!("Auditor::new_auditor_keys: >>>");
+
+        let mut ctx = BigNumber::new_context()?;
+
+        let p = generate_safe_prime(LARGE_PRIME)?;
+        let mut q = generate_safe_prime(LARGE_PRIME)?;
+        while q == p {
+            q = generate_safe_prime(LARGE_PRIME)?;
+        }
+
+        let n = p.mul(&q, Some(&mut ctx))?;
+        let n_square = n.sqr(Some(&mut ctx))?;
+        let quarter_n_square = n_square.rshift(2)?;
+
+        let g = bn_rand_range(&n_square)?
+            .mod_exp(&n.lshift1()?, &n_square, Some(&mut ctx))?;
+
+        let x1 = bn_rand_range(&quarter_n_square)?;
+        let x2 = bn_rand_range(&quarter_n_square)?;
+        let x3 = bn_rand_range(&quarter_n_square)?;
+
+        let y1 = g.mod_exp(&x1, &n_square, Some(&mut ctx))?;
+        let y2 = g.mod_exp(&x2, &n_square, Some(&mut ctx))?;
+        let y3 = g.mod_exp(&x3, &n_square, Some(&mut ctx))?;
+
+        let auditor_pub_key = AuditorPublicKey { n, g, y1, y2, y3 };
+        let auditor_priv_key = AuditorPrivateKey { x1, x2, x3 };
+
+        trace!("Auditor::new_auditor_keys: <<< auditor_pub_key: {:?}, auditor_priv_key: {:?}", auditor_pub_key, secret!(&auditor_priv_key));
+
+        Ok((auditor_pub_key, auditor_priv_key))
+    }
+
+    /// Decrypts verifiably encrypted attribute value.
+    ///
+    /// Values are encrypted modulo `n` of auditor public key, so the result greater than `n / 2`
+    /// is returned as negative value (encoded integer attributes may be negative).
+    ///
+    /// # Arguments
+    /// * `auditor_pub_key` - Auditor public key.
+    /// * `auditor_priv_key` - Auditor private key.
+    /// * `ciphertext` - Ciphertext taken from verified proof.
+    pub fn decrypt(auditor_pub_key: &AuditorPublicKey,
+                   auditor_priv_key: &AuditorPrivateKey,
+                   ciphertext: &VerifiableCiphertext) -> Result<BigNumber, IndyCryptoError> {
+        trace!("Auditor::decrypt: >>> auditor_pub_key: {:?}, ciphertext: {:?}", auditor_pub_key, ciphertext);
+
+        let mut ctx = BigNumber::new_context()?;
+        let n = &auditor_pub_key.n;
+        let n_square = auditor_pub_key.n_square()?;
+
+        if auditor_pub_key.abs(&ciphertext.v)? != ciphertext.v {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid ciphertext")));
+        }
+
+        let v_exp = AuditorPublicKey::v_hash(&ciphertext.u, &ciphertext.e, &ciphertext.label)?
+            .mul(&auditor_priv_key.x3, Some(&mut ctx))?
+            .add(&auditor_priv_key.x2)?
+            .lshift1()?;
+
+        if ciphertext.v.mod_exp(&BIGNUMBER_2, &n_square, Some(&mut ctx))? != ciphertext.u.mod_exp(&v_exp, &n_square, Some(&mut ctx))? {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid ciphertext")));
+        }
+
+        // `(e / u^x1)^2t = h^m` for `t = 2^-1 mod n`
+        let t = BIGNUMBER_2.inverse(n, Some(&mut ctx))?;
+
+        let m_hat = ciphertext.e
+            .mod_mul(&ciphertext.u.mod_exp(&auditor_priv_key.x1.set_negative(true)?, &n_square, Some(&mut ctx))?, &n_square, Some(&mut ctx))?
+            .mod_exp(&t.lshift1()?, &n_square, Some(&mut ctx))?;
+
+        if m_hat.modulus(n, Some(&mut ctx))? != *BIGNUMBER_1 {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid ciphertext")));
+        }
+
+        let mut m = m_hat.decrement()?.div(n, Some(&mut ctx))?;
+
+        if m.lshift1()? > *n {
+            m = m.sub(n)?;
+        }
+
+        trace!("Auditor::decrypt: <<< m: {:?}", secret!(&m));
+
+        Ok(m)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Generation of safe primes is slow, so all tests share the same keys
+    lazy_static! {
+        static ref AUDITOR_KEYS: (AuditorPublicKey, AuditorPrivateKey) = Auditor::new_auditor_keys().unwrap();
+    }
+
+    #[test]
+    fn decrypt_works() {
+        let (ref auditor_pub_key, ref auditor_priv_key) = *AUDITOR_KEYS;
+
+        let m = BigNumber::from_dec("5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
+        let (ciphertext, _) = auditor_pub_key.encrypt(&m, "label").unwrap();
+
+        assert_eq!(m, Auditor::decrypt(auditor_pub_key, auditor_priv_key, &ciphertext).unwrap());
+
+        let m = BigNumber::from_dec("-250").unwrap();
+        let (ciphertext, _) = auditor_pub_key.encrypt(&m, "label").unwrap();
+
+        assert_eq!(m, Auditor::decrypt(auditor_pub_key, auditor_priv_key, &ciphertext).unwrap());
+    }
+
+    #[test]
+    fn decrypt_works_for_changed_label() {
+        let (ref auditor_pub_key, ref auditor_priv_key) = *AUDITOR_KEYS;
+
+        let m = BigNumber::from_dec("28").unwrap();
+        let (mut ciphertext, _) = auditor_pub_key.encrypt(&m, "label").unwrap();
+        ciphertext.label = "other label".to_owned();
+
+        assert!(Auditor::decrypt(auditor_pub_key, auditor_priv_key, &ciphertext).is_err());
+    }
+}
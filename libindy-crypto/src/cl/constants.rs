@@ -21,6 +21,37 @@ pub const ITERATION: usize = 4;
 */
 pub const LARGE_NONCE: usize = 80; // number of bits
 pub const LARGE_ALPHATILDE: usize = 2787;
+pub const LARGE_CHALLENGE: usize = 256; // size of proof challenge hash
+// LARGE_VPRIME + LARGE_CHALLENGE + 80 bits, so responses statistically hide `challenge * r`
+pub const LARGE_SET_MEMBERSHIP_RTILDE: usize = 2464;
+// LARGE_PRIME * 2 + LARGE_CHALLENGE + 80 bits, so responses statistically hide `challenge * r` for encryption randomness
+pub const LARGE_VERIFIABLE_ENCRYPTION_RTILDE: usize = 2384;
+// Encoded integers and payloads of tagged attribute values are below 2^ENCODING_PAYLOAD_BITS
+pub const ENCODING_PAYLOAD_BITS: usize = 256;
+// Domain separation tag for hashing pseudonym scope to PointG1
+pub const PSEUDONYM_DST: &[u8] = b"INDY-CRYPTO-CL-PSEUDONYM-V01_";
+// Domain separation tag for message bound to proof challenge
+pub const PROOF_MESSAGE_DST: &[u8] = b"INDY-CRYPTO-CL-PROOF-MESSAGE-V01_";
+// Version of proofs with challenge hashed from plain concatenation of values
+pub const LEGACY_PROOF_VERSION: u32 = 1;
+// Version of proofs with challenge derived from labeled transcript
+pub const CURRENT_PROOF_VERSION: u32 = 2;
+// Domain labels of Fiat-Shamir transcripts
+pub const PROOF_TRANSCRIPT_DOMAIN: &[u8] = b"INDY-CRYPTO-CL-PROOF-V02";
+pub const BLINDED_CREDENTIAL_SECRETS_TRANSCRIPT_DOMAIN: &[u8] = b"INDY-CRYPTO-CL-BLINDED-CREDENTIAL-SECRETS-V02";
+pub const VERIFIABLE_ENCRYPTION_TRANSCRIPT_DOMAIN: &[u8] = b"INDY-CRYPTO-CL-VERIFIABLE-ENCRYPTION-V01";
+// Labels of proof parts tau values belong to
+pub const NON_REVOCATION_PROOF_LABEL: &[u8] = b"non_revocation_proof";
+pub const PRIMARY_EQUAL_PROOF_LABEL: &[u8] = b"primary_equal_proof";
+pub const PRIMARY_PREDICATE_PROOF_LABEL: &[u8] = b"primary_predicate_proof";
+pub const SET_MEMBERSHIP_PROOF_LABEL: &[u8] = b"set_membership_proof";
+pub const PSEUDONYM_PROOF_LABEL: &[u8] = b"pseudonym_proof";
+pub const LINEAR_PREDICATE_PROOF_LABEL: &[u8] = b"linear_predicate_proof";
+pub const VERIFIABLE_ENCRYPTION_PROOF_LABEL: &[u8] = b"verifiable_encryption_proof";
+// Tails are generated in chunks of at least this size per thread
+pub const MIN_TAILS_CHUNK_SIZE: u32 = 1024;
+// Number of blocks of tails prefix sums file that are summed and checked when file is loaded
+pub const TAILS_PREFIX_SUMS_SPOT_CHECKS: usize = 8;
 
 // Constants that are used throughout the CL signatures code, so avoiding recomputation.
 lazy_static! {
@@ -32,4 +63,12 @@ lazy_static! {
                 None).unwrap().add(&LARGE_E_START_VALUE).unwrap();
     pub static ref LARGE_VPRIME_PRIME_VALUE: BigNumber = BIGNUMBER_2.exp(
         &BigNumber::from_u32(LARGE_VPRIME_PRIME - 1).unwrap(), None).unwrap();
+    pub static ref ENCODING_PAYLOAD_BOUND: BigNumber = BIGNUMBER_2.exp(
+        &BigNumber::from_u32(ENCODING_PAYLOAD_BITS).unwrap(), None).unwrap();
+    // Tagged values are `tag * ENCODING_TAG_BASE + payload`, so they never collide with integers
+    pub static ref ENCODING_TAG_BASE: BigNumber = BIGNUMBER_2.exp(
+        &BigNumber::from_u32(ENCODING_PAYLOAD_BITS + 1).unwrap(), None).unwrap();
+    // Days since epoch are shifted, so payloads of dates before 1970 are non-negative
+    pub static ref ENCODING_DATE_OFFSET: BigNumber = BIGNUMBER_2.exp(
+        &BigNumber::from_u32(ENCODING_PAYLOAD_BITS - 1).unwrap(), None).unwrap();
 }
@@ -0,0 +1,208 @@
+use bn::BigNumber;
+use cl::constants::{ENCODING_PAYLOAD_BOUND, ENCODING_TAG_BASE, ENCODING_DATE_OFFSET};
+use errors::IndyCryptoError;
+
+const STRING_TAG: usize = 1;
+const BOOL_TAG: usize = 2;
+const DATE_TAG: usize = 3;
+const BYTES_TAG: usize = 4;
+
+/// Raw (not encoded) value of credential attribute.
+///
+/// Integers are encoded as is, so they can be used in predicates, and must be in range `(-2^256, 2^256)`.
+/// Values of other types are encoded as `tag * 2^257 + payload` with payload below `2^256`,
+/// so encodings of values of different types never collide:
+/// * strings and bytes - SHA-256 hash of the value,
+/// * booleans - 0 or 1,
+/// * dates (`YYYY-MM-DD`) - `2^255 + days since 1970-01-01`, so dates can be compared by predicates
+///   with bounds encoded the same way.
+///
+/// Serialized as `{"type": "int", "value": "42"}`, `{"type": "string", "value": "Alex"}`, etc.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+pub enum AttributeValue {
+    Int(BigNumber),
+    String(String),
+    Bool(bool),
+    Date(String),
+    Bytes(Vec<u8>)
+}
+
+impl AttributeValue {
+    pub fn from_i64(value: i64) -> Result<AttributeValue, IndyCryptoError> {
+        Ok(AttributeValue::Int(BigNumber::from_dec(&value.to_string())?))
+    }
+
+    pub fn try_clone(&self) -> Result<AttributeValue, IndyCryptoError> {
+        Ok(match *self {
+            AttributeValue::Int(ref value) => AttributeValue::Int(value.clone()?),
+            AttributeValue::String(ref value) => AttributeValue::String(value.clone()),
+            AttributeValue::Bool(value) => AttributeValue::Bool(value),
+            AttributeValue::Date(ref value) => AttributeValue::Date(value.clone()),
+            AttributeValue::Bytes(ref value) => AttributeValue::Bytes(value.clone())
+        })
+    }
+
+    /// Returns encoded value that is signed by issuer and revealed in proofs.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::encoding::AttributeValue;
+    ///
+    /// let age = AttributeValue::from_i64(28).unwrap().encode().unwrap();
+    /// assert_eq!("28", age.to_dec().unwrap());
+    ///
+    /// let _name = AttributeValue::String("Alex".to_string()).encode().unwrap();
+    /// let _birth_date = AttributeValue::Date("1990-05-17".to_string()).encode().unwrap();
+    /// ```
+    pub fn encode(&self) -> Result<BigNumber, IndyCryptoError> {
+        trace!("AttributeValue::encode: >>> attribute_value: {:?}", self);
+
+        let encoded = match *self {
+            AttributeValue::Int(ref value) => {
+                if value.set_negative(false)? >= *ENCODING_PAYLOAD_BOUND {
+                    return Err(IndyCryptoError::InvalidStructure(format!("Integer attribute value {:?} is out of range", value)));
+                }
+                value.clone()?
+            }
+            AttributeValue::String(ref value) => _tagged(STRING_TAG, &BigNumber::from_bytes(&BigNumber::hash(value.as_bytes())?)?)?,
+            AttributeValue::Bool(value) => _tagged(BOOL_TAG, &BigNumber::from_u32(value as usize)?)?,
+            AttributeValue::Date(ref value) => {
+                let days = BigNumber::from_dec(&_days_since_epoch(value)?.to_string())?;
+                _tagged(DATE_TAG, &days.add(&ENCODING_DATE_OFFSET)?)?
+            }
+            AttributeValue::Bytes(ref value) => _tagged(BYTES_TAG, &BigNumber::from_bytes(&BigNumber::hash(value)?)?)?
+        };
+
+        trace!("AttributeValue::encode: <<< encoded: {:?}", encoded);
+
+        Ok(encoded)
+    }
+
+    /// Returns true if encoded values keep order of raw values, so they can be used in predicates.
+    pub fn is_ordered(&self) -> bool {
+        match *self {
+            AttributeValue::Int(_) | AttributeValue::Bool(_) | AttributeValue::Date(_) => true,
+            AttributeValue::String(_) | AttributeValue::Bytes(_) => false
+        }
+    }
+}
+
+fn _tagged(tag: usize, payload: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+    ENCODING_TAG_BASE
+        .mul(&BigNumber::from_u32(tag)?, None)?
+        .add(payload)
+}
+
+// Parses date in `YYYY-MM-DD` format and returns number of days since 1970-01-01
+fn _days_since_epoch(date: &str) -> Result<i64, IndyCryptoError> {
+    let invalid_date = || IndyCryptoError::InvalidStructure(format!("Invalid date {:?}, expected YYYY-MM-DD", date));
+
+    let parts = date.split('-').collect::<Vec<&str>>();
+
+    if parts.len() != 3 || parts[0].len() != 4 || parts[1].len() != 2 || parts[2].len() != 2
+        || !parts.iter().all(|part| part.bytes().all(|b| b.is_ascii_digit())) {
+        return Err(invalid_date());
+    }
+
+    let year = parts[0].parse::<i64>().map_err(|_| invalid_date())?;
+    let month = parts[1].parse::<i64>().map_err(|_| invalid_date())?;
+    let day = parts[2].parse::<i64>().map_err(|_| invalid_date())?;
+
+    let is_leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year => 29,
+        2 => 28,
+        _ => return Err(invalid_date())
+    };
+
+    if day < 1 || day > days_in_month {
+        return Err(invalid_date());
+    }
+
+    // Days from civil date algorithm with years starting on March 1 (http://howardhinnant.github.io/date_algorithms.html)
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    Ok(era * 146097 + day_of_era - 719468)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn encode_works_for_int() {
+        assert_eq!("28", AttributeValue::from_i64(28).unwrap().encode().unwrap().to_dec().unwrap());
+        assert_eq!("-250", AttributeValue::from_i64(-250).unwrap().encode().unwrap().to_dec().unwrap());
+    }
+
+    #[test]
+    fn encode_works_for_int_out_of_range() {
+        let value = ENCODING_PAYLOAD_BOUND.set_negative(true).unwrap();
+        assert!(AttributeValue::Int(value).encode().is_err());
+        assert!(AttributeValue::Int(ENCODING_PAYLOAD_BOUND.clone().unwrap()).encode().is_err());
+        assert!(AttributeValue::Int(ENCODING_PAYLOAD_BOUND.decrement().unwrap()).encode().is_ok());
+    }
+
+    #[test]
+    fn encode_works_for_bool() {
+        let false_value = ENCODING_TAG_BASE.mul(&BigNumber::from_u32(BOOL_TAG).unwrap(), None).unwrap();
+        assert_eq!(false_value, AttributeValue::Bool(false).encode().unwrap());
+        assert_eq!(false_value.increment().unwrap(), AttributeValue::Bool(true).encode().unwrap());
+    }
+
+    #[test]
+    fn encode_works_for_date() {
+        let epoch = ENCODING_TAG_BASE
+            .mul(&BigNumber::from_u32(DATE_TAG).unwrap(), None).unwrap()
+            .add(&ENCODING_DATE_OFFSET).unwrap();
+        assert_eq!(epoch, AttributeValue::Date("1970-01-01".to_string()).encode().unwrap());
+
+        let leap_day = AttributeValue::Date("2000-02-29".to_string()).encode().unwrap();
+        assert_eq!(epoch.add(&BigNumber::from_u32(11016).unwrap()).unwrap(), leap_day);
+
+        let before_epoch = AttributeValue::Date("1969-12-31".to_string()).encode().unwrap();
+        assert_eq!(epoch.decrement().unwrap(), before_epoch);
+    }
+
+    #[test]
+    fn encode_works_for_invalid_date() {
+        for date in ["2001-02-29", "2000-13-01", "2000-00-10", "2000-1-1", "20000101", "2000-01-+1", "today"].iter() {
+            assert!(AttributeValue::Date(date.to_string()).encode().is_err(), "{}", date);
+        }
+    }
+
+    #[test]
+    fn encode_works_for_different_types_of_same_value() {
+        let values = vec![
+            AttributeValue::from_i64(1).unwrap().encode().unwrap(),
+            AttributeValue::String("1".to_string()).encode().unwrap(),
+            AttributeValue::Bool(true).encode().unwrap(),
+            AttributeValue::Bytes(b"1".to_vec()).encode().unwrap()
+        ];
+
+        for i in 0..values.len() {
+            for j in i + 1..values.len() {
+                assert_ne!(values[i], values[j]);
+            }
+        }
+    }
+
+    #[test]
+    fn attribute_value_serialization_works() {
+        let value = AttributeValue::from_i64(-5).unwrap();
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(r#"{"type":"int","value":"-5"}"#, json);
+        assert_eq!(value, serde_json::from_str::<AttributeValue>(&json).unwrap());
+
+        let value: AttributeValue = serde_json::from_str(r#"{"type":"date","value":"2018-07-01"}"#).unwrap();
+        assert_eq!(AttributeValue::Date("2018-07-01".to_string()), value);
+    }
+}
@@ -1,4 +1,4 @@
-use bn::{BigNumber, BIGNUMBER_1};
+use bn::{BigNumber, BigNumberContext, BIGNUMBER_1, BIGNUMBER_2};
 use cl::*;
 use errors::IndyCryptoError;
 use pair::GroupOrderElement;
@@ -330,32 +330,19 @@ pub fn calc_tne(p_pub_key: &CredentialPrimaryPublicKey,
                 r: &HashMap<String, BigNumber>,
                 mj: &BigNumber,
                 alpha: &BigNumber,
+                upper_alpha: Option<&BigNumber>,
                 t: &HashMap<String, BigNumber>,
-                is_less: bool) -> Result<Vec<BigNumber>, IndyCryptoError> {
-    trace!("Helpers::calc_tge: >>> p_pub_key: {:?}, u: {:?}, r: {:?}, mj: {:?}, alpha: {:?}, t: {:?}", p_pub_key, u, r, mj, alpha, t);
+                predicate: &Predicate) -> Result<Vec<BigNumber>, IndyCryptoError> {
+    trace!("Helpers::calc_tne: >>> p_pub_key: {:?}, u: {:?}, r: {:?}, mj: {:?}, alpha: {:?}, upper_alpha: {:?}, t: {:?}, predicate: {:?}",
+           p_pub_key, u, r, mj, alpha, upper_alpha, t, predicate);
 
-    let mut tau_list: Vec<BigNumber> = Vec::new();
     let mut ctx = BigNumber::new_context()?;
 
-    for i in 0..ITERATION {
-        let cur_u = u.get(&i.to_string())
-            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in u", i)))?;
-        let cur_r = r.get(&i.to_string())
-            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in r", i)))?;
-
-        let t_tau = p_pub_key.z
-            .mod_exp(&cur_u, &p_pub_key.n, Some(&mut ctx))?
-            .mod_mul(
-                &p_pub_key.s.mod_exp(&cur_r, &p_pub_key.n, Some(&mut ctx))?,
-                &p_pub_key.n, Some(&mut ctx)
-            )?;
-
-        tau_list.push(t_tau);
-    }
+    let mut tau_list = _calc_tne_squares(p_pub_key, u, r, 0, &mut ctx)?;
 
     let delta = r.get("DELTA")
         .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in r", "DELTA")))?;
-    let delta_predicate = if is_less {
+    let delta_predicate = if predicate.is_less() {
         delta.set_negative(true)?
     } else {
         delta.clone()?
@@ -369,90 +356,257 @@ pub fn calc_tne(p_pub_key: &CredentialPrimaryPublicKey,
         )?;
 
     tau_list.push(t_tau);
+    tau_list.push(_calc_tne_q(p_pub_key, u, t, alpha, 0, &mut ctx)?);
+
+    match predicate.p_type {
+        PredicateType::NE => {
+            // Proves that T_SQUARE commits to the square of the value committed in T_DELTA
+            let t_delta = t.get("DELTA")
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in t", "DELTA")))?;
+            let r_square = r.get("SQUARE")
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in r", "SQUARE")))?;
+
+            let t_tau = t_delta
+                .mod_exp(&mj, &p_pub_key.n, Some(&mut ctx))?
+                .mod_mul(
+                    &p_pub_key.s.mod_exp(&r_square, &p_pub_key.n, Some(&mut ctx))?,
+                    &p_pub_key.n, Some(&mut ctx)
+                )?;
+
+            tau_list.push(t_tau);
+        }
+        PredicateType::BETWEEN => {
+            let upper_alpha = upper_alpha
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Upper alpha not found for predicate {:?}", predicate)))?;
 
+            tau_list.append(&mut _calc_tne_squares(p_pub_key, u, r, ITERATION, &mut ctx)?);
+            tau_list.push(_calc_tne_q(p_pub_key, u, t, upper_alpha, ITERATION, &mut ctx)?);
+        }
+        _ => {}
+    }
+
+    trace!("Helpers::calc_tne: <<< tau_list: {:?}", tau_list);
+
+    Ok(tau_list)
+}
+
+fn _calc_tne_squares(p_pub_key: &CredentialPrimaryPublicKey,
+                     u: &HashMap<String, BigNumber>,
+                     r: &HashMap<String, BigNumber>,
+                     offset: usize,
+                     ctx: &mut BigNumberContext) -> Result<Vec<BigNumber>, IndyCryptoError> {
+    let mut tau_list: Vec<BigNumber> = Vec::new();
+
+    for i in offset..offset + ITERATION {
+        let cur_u = u.get(&i.to_string())
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in u", i)))?;
+        let cur_r = r.get(&i.to_string())
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in r", i)))?;
+
+        let t_tau = p_pub_key.z
+            .mod_exp(&cur_u, &p_pub_key.n, Some(ctx))?
+            .mod_mul(
+                &p_pub_key.s.mod_exp(&cur_r, &p_pub_key.n, Some(ctx))?,
+                &p_pub_key.n, Some(ctx)
+            )?;
+
+        tau_list.push(t_tau);
+    }
+
+    Ok(tau_list)
+}
+
+fn _calc_tne_q(p_pub_key: &CredentialPrimaryPublicKey,
+               u: &HashMap<String, BigNumber>,
+               t: &HashMap<String, BigNumber>,
+               alpha: &BigNumber,
+               offset: usize,
+               ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
     let mut q: BigNumber = BIGNUMBER_1.clone()?;
 
-    for i in 0..ITERATION {
+    for i in offset..offset + ITERATION {
         let cur_t = t.get(&i.to_string())
             .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in t", i)))?;
         let cur_u = u.get(&i.to_string())
             .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in u", i)))?;
 
         q = cur_t
-            .mod_exp(&cur_u, &p_pub_key.n, Some(&mut ctx))?
-            .mul(&q, Some(&mut ctx))?;
+            .mod_exp(&cur_u, &p_pub_key.n, Some(ctx))?
+            .mul(&q, Some(ctx))?;
     }
 
     q = p_pub_key.s
-        .mod_exp(&alpha, &p_pub_key.n, Some(&mut ctx))?
-        .mod_mul(&q, &p_pub_key.n, Some(&mut ctx))?;
+        .mod_exp(&alpha, &p_pub_key.n, Some(ctx))?
+        .mod_mul(&q, &p_pub_key.n, Some(ctx))?;
 
-    tau_list.push(q);
+    Ok(q)
+}
 
-    trace!("Helpers::calc_tne: <<< tau_list: {:?}", tau_list);
+// Calculates commitment of set membership proof branch for `value`:
+// S^z * (T / Z^value)^(-c)
+pub fn calc_tset(p_pub_key: &CredentialPrimaryPublicKey,
+                 t: &BigNumber,
+                 value: &BigNumber,
+                 c: &BigNumber,
+                 z: &BigNumber,
+                 ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+    let t_value = p_pub_key.z
+        .mod_exp(&value, &p_pub_key.n, Some(ctx))?
+        .inverse(&p_pub_key.n, Some(ctx))?
+        .mod_mul(&t, &p_pub_key.n, Some(ctx))?;
 
-    Ok(tau_list)
-}
+    let tau = t_value
+        .mod_exp(&c, &p_pub_key.n, Some(ctx))?
+        .inverse(&p_pub_key.n, Some(ctx))?
+        .mod_mul(&p_pub_key.s.mod_exp(&z, &p_pub_key.n, Some(ctx))?, &p_pub_key.n, Some(ctx))?;
 
-fn largest_square_less_than(delta: usize) -> usize {
-    (delta as f64).sqrt().floor() as usize
+    Ok(tau)
 }
 
+// Values not longer than this are decomposed by exhaustive search, so results are deterministic,
+// larger values are decomposed with randomized Rabin-Shallit algorithm
+const FOUR_SQUARES_SEARCH_BITS: i32 = 32;
+
 //Express the natural number `delta` as a sum of four integer squares,
 // i.e `delta = a^2 + b^2 + c^2 + d^2` using Lagrange's four-square theorem
-pub fn four_squares(delta: i32) -> Result<HashMap<String, BigNumber>, IndyCryptoError> {
+pub fn four_squares(delta: &BigNumber) -> Result<HashMap<String, BigNumber>, IndyCryptoError> {
     trace!("Helpers::four_squares: >>> delta: {:?}", delta);
 
-    if delta < 0 {
-        return Err(IndyCryptoError::InvalidStructure(format!("Cannot express a negative number as sum of four squares {} ", delta)));
+    if delta.is_negative() {
+        return Err(IndyCryptoError::InvalidStructure(format!("Cannot express a negative number as sum of four squares {:?} ", delta)));
     }
 
-    let d = delta as usize;
-    let mut roots: [usize; 4] = [largest_square_less_than(d), 0, 0, 0];
+    let mut ctx = BigNumber::new_context()?;
+    let zero = BigNumber::new()?;
+
+    let mut roots: Vec<BigNumber> = Vec::new();
 
-    'outer: for i in (1..roots[0] + 1).rev() {
-        roots[0] = i;
-        if d == roots[0].pow(2) {
-            roots[1] = 0;
-            roots[2] = 0;
-            roots[3] = 0;
-            break 'outer;
+    if *delta == zero {
+        for _ in 0..4 {
+            roots.push(BigNumber::new()?);
         }
-        roots[1] = largest_square_less_than(d - roots[0].pow(2));
-        for j in (1..roots[1] + 1).rev() {
-            roots[1] = j;
-            if d == roots[0].pow(2) + roots[1].pow(2) {
-                roots[2] = 0;
-                roots[3] = 0;
-                break 'outer;
+    } else {
+        // delta = 4^v * n, roots of n are scaled by 2^v
+        let mut n = delta.clone()?;
+        let mut v = 0;
+        while !n.is_bit_set(0)? && !n.is_bit_set(1)? {
+            n = n.rshift(2)?;
+            v += 1;
+        }
+
+        let search = n.num_bits()? <= FOUR_SQUARES_SEARCH_BITS;
+
+        let x_max = bn_isqrt(&n, &mut ctx)?;
+        let mut x = x_max.clone()?;
+
+        while roots.is_empty() {
+            if !search {
+                x = _bn_rand_range(&x_max.increment()?)?;
             }
-            roots[2] = largest_square_less_than(d - roots[0].pow(2) - roots[1].pow(2));
-            for k in (1..roots[2] + 1).rev() {
-                roots[2] = k;
-                if d == roots[0].pow(2) + roots[1].pow(2) + roots[2].pow(2) {
-                    roots[3] = 0;
-                    break 'outer;
+
+            let rest = n.sub(&x.sqr(Some(&mut ctx))?)?;
+            let y_max = bn_isqrt(&rest, &mut ctx)?;
+            let mut y = y_max.clone()?;
+
+            loop {
+                if !search {
+                    y = _bn_rand_range(&y_max.increment()?)?;
+                }
+
+                if let Some((a, b)) = two_squares(&rest.sub(&y.sqr(Some(&mut ctx))?)?, &mut ctx)? {
+                    roots = vec![x.clone()?, y, a, b];
+                    break;
                 }
-                roots[3] = largest_square_less_than(d - roots[0].pow(2) - roots[1].pow(2) - roots[2].pow(2));
-                if d == roots[0].pow(2) + roots[1].pow(2) + roots[2].pow(2) + roots[3].pow(2) {
-                    break 'outer;
+
+                if !search || y == zero {
+                    break;
                 }
+                y = y.decrement()?;
             }
+
+            if search && roots.is_empty() {
+                x = x.decrement()?;
+
+                // Unreachable by Lagrange's four-square theorem, but the search must not run forever
+                if x.is_negative() {
+                    return Err(IndyCryptoError::InvalidState(format!("Cannot express {:?} as sum of four squares", delta)));
+                }
+            }
+        }
+
+        let mut scale = BigNumber::new()?;
+        scale.set_bit(v)?;
+
+        for root in roots.iter_mut() {
+            *root = root.mul(&scale, Some(&mut ctx))?;
         }
     }
 
-    let res = hashmap![
-        "0".to_string() => BigNumber::from_dec(&roots[0].to_string()[..])?,
-        "1".to_string() => BigNumber::from_dec(&roots[1].to_string()[..])?,
-        "2".to_string() => BigNumber::from_dec(&roots[2].to_string()[..])?,
-        "3".to_string() => BigNumber::from_dec(&roots[3].to_string()[..])?
-    ];
+    let mut res = HashMap::new();
+    for (i, root) in roots.into_iter().enumerate() {
+        res.insert(i.to_string(), root);
+    }
 
     trace!("Helpers::four_squares: <<< res: {:?}", res);
 
     Ok(res)
 }
 
+// Integer square root, i.e. largest `x` such that `x^2 <= n`
+fn bn_isqrt(n: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+    if n.num_bits()? == 0 {
+        return BigNumber::new();
+    }
+
+    let mut x = BigNumber::new()?;
+    x.set_bit((n.num_bits()? + 1) / 2)?;
+
+    loop {
+        let y = x.add(&n.div(&x, Some(ctx))?)?.rshift1()?;
+        if y >= x {
+            return Ok(x);
+        }
+        x = y;
+    }
+}
+
+// Express `p` as a sum of two squares if `p` is 0, 1, 2 or a prime `p = 1 mod 4`,
+// otherwise returns None
+fn two_squares(p: &BigNumber, ctx: &mut BigNumberContext) -> Result<Option<(BigNumber, BigNumber)>, IndyCryptoError> {
+    if p.num_bits()? <= 2 && *p <= *BIGNUMBER_2 {
+        let a = if p.num_bits()? == 0 { BigNumber::new()? } else { BIGNUMBER_1.clone()? };
+        let b = if *p == *BIGNUMBER_2 { BIGNUMBER_1.clone()? } else { BigNumber::new()? };
+        return Ok(Some((a, b)));
+    }
+
+    if !p.is_bit_set(0)? || p.is_bit_set(1)? || !p.is_prime(Some(ctx))? {
+        return Ok(None);
+    }
+
+    // s is a square root of -1 mod p, c^((p-1)/4) for quadratic non-residue c
+    let p_minus_one = p.decrement()?;
+    let exp = p.rshift(2)?;
+    let mut c = BIGNUMBER_2.clone()?;
+    let mut s = c.mod_exp(&exp, p, Some(ctx))?;
+    while s.mod_mul(&s, p, Some(ctx))? != p_minus_one {
+        c = c.increment()?;
+        s = c.mod_exp(&exp, p, Some(ctx))?;
+    }
+
+    // Euclidean algorithm on (p, s) stops at the first remainder below sqrt(p)
+    let mut a = p.clone()?;
+    let mut b = s;
+    while b.sqr(Some(ctx))? > *p {
+        let rem = a.modulus(&b, Some(ctx))?;
+        a = b;
+        b = rem;
+    }
+
+    let q = bn_isqrt(&p.sub(&b.sqr(Some(ctx))?)?, ctx)?;
+
+    Ok(Some((b, q)))
+}
+
 pub fn group_element_to_bignum(el: &GroupOrderElement) -> Result<BigNumber, IndyCryptoError> {
     Ok(BigNumber::from_bytes(&el.to_bytes()?)?)
 }
@@ -461,6 +615,17 @@ pub fn bignum_to_group_element(num: &BigNumber) -> Result<GroupOrderElement, Ind
     Ok(GroupOrderElement::from_bytes(&num.to_bytes()?)?)
 }
 
+// Reduces number of any size and sign modulo group order
+pub fn bignum_to_group_element_mod_order(num: &BigNumber) -> Result<GroupOrderElement, IndyCryptoError> {
+    let el = GroupOrderElement::from_bytes_mod_order(&num.to_bytes()?)?;
+
+    if num.is_negative() {
+        el.mod_neg()
+    } else {
+        Ok(el)
+    }
+}
+
 pub fn create_tau_list_expected_values(r_pub_key: &CredentialRevocationPublicKey,
                                        rev_reg: &RevocationRegistry,
                                        rev_acc_pub_key: &RevocationKeyPublic,
@@ -470,16 +635,24 @@ pub fn create_tau_list_expected_values(r_pub_key: &CredentialRevocationPublicKey
 
     let t1 = proof_c.e;
     let t2 = PointG1::new_inf()?;
-    let t3 = Pair::pair(&r_pub_key.h0.add(&proof_c.g)?, &r_pub_key.h_cap)?
-        .mul(&Pair::pair(&proof_c.a, &r_pub_key.y)?.inverse()?)?;
-    let t4 = Pair::pair(&proof_c.g, &rev_reg.accum)?
-        .mul(&Pair::pair(&r_pub_key.g, &proof_c.w)?.mul(&rev_acc_pub_key.z)?.inverse()?)?;
+    let t3 = Pair::multi_pair(&[
+        (&r_pub_key.h0.add(&proof_c.g)?, &r_pub_key.h_cap),
+        (&proof_c.a.neg()?, &r_pub_key.y)
+    ])?;
+    let t4 = Pair::multi_pair(&[
+        (&proof_c.g, &rev_reg.accum),
+        (&r_pub_key.g.neg()?, &proof_c.w)
+    ])?.mul(&rev_acc_pub_key.z.inverse()?)?;
     let t5 = proof_c.d;
     let t6 = PointG1::new_inf()?;
-    let t7 = Pair::pair(&r_pub_key.pk.add(&proof_c.g)?, &proof_c.s)?
-        .mul(&Pair::pair(&r_pub_key.g, &r_pub_key.g_dash)?.inverse()?)?;
-    let t8 = Pair::pair(&proof_c.g, &r_pub_key.u)?
-        .mul(&Pair::pair(&r_pub_key.g, &proof_c.u)?.inverse()?)?;
+    let t7 = Pair::multi_pair(&[
+        (&r_pub_key.pk.add(&proof_c.g)?, &proof_c.s),
+        (&r_pub_key.g.neg()?, &r_pub_key.g_dash)
+    ])?;
+    let t8 = Pair::multi_pair(&[
+        (&proof_c.g, &r_pub_key.u),
+        (&r_pub_key.g.neg()?, &proof_c.u)
+    ])?;
 
     let non_revoc_proof_tau_list = NonRevocProofTauList {
         t1,
@@ -511,15 +684,20 @@ pub fn create_tau_list_values(r_pub_key: &CredentialRevocationPublicKey,
     if t2.is_inf()? {
         t2 = PointG1::new_inf()?;
     }
-    let t3 = Pair::pair(&proof_c.a, &r_pub_key.h_cap)?.pow(&params.c)?
-        .mul(&Pair::pair(&r_pub_key.htilde, &r_pub_key.h_cap)?.pow(&params.r)?)?
-        .mul(&Pair::pair(&r_pub_key.htilde, &r_pub_key.y)?.pow(&params.rho)?
-            .mul(&Pair::pair(&r_pub_key.htilde, &r_pub_key.h_cap)?.pow(&params.m)?)?
-            .mul(&Pair::pair(&r_pub_key.h1, &r_pub_key.h_cap)?.pow(&params.m2)?)?
-            .mul(&Pair::pair(&r_pub_key.h2, &r_pub_key.h_cap)?.pow(&params.s)?)?.inverse()?)?;
-    let t4 = Pair::pair(&r_pub_key.htilde, &rev_reg.accum)?
-        .pow(&params.r)?
-        .mul(&Pair::pair(&r_pub_key.g.neg()?, &r_pub_key.h_cap)?.pow(&params.r_prime)?)?;
+    // Exponents are moved into G1 (e(P, Q)^x == e(P * x, Q)) and factors sharing the same
+    // G2 point are merged, so every value needs at most two Miller loops.
+    let t3 = Pair::multi_pair(&[
+        (&proof_c.a.mul(&params.c)?
+            .add(&r_pub_key.htilde.mul(&params.r.sub_mod(&params.m)?)?)?
+            .sub(&r_pub_key.h1.mul(&params.m2)?)?
+            .sub(&r_pub_key.h2.mul(&params.s)?)?,
+         &r_pub_key.h_cap),
+        (&r_pub_key.htilde.mul(&params.rho)?.neg()?, &r_pub_key.y)
+    ])?;
+    let t4 = Pair::multi_pair(&[
+        (&r_pub_key.htilde.mul(&params.r)?, &rev_reg.accum),
+        (&r_pub_key.g.neg()?.mul(&params.r_prime)?, &r_pub_key.h_cap)
+    ])?;
     let t5 = r_pub_key.g.mul(&params.r)?.add(&r_pub_key.htilde.mul(&params.o_prime)?)?;
     let mut t6 = proof_c.d.mul(&params.r_prime_prime)?
         .add(&r_pub_key.g.mul(&params.m_prime.mod_neg()?)?)?
@@ -527,11 +705,16 @@ pub fn create_tau_list_values(r_pub_key: &CredentialRevocationPublicKey,
     if t6.is_inf()? {
         t6 = PointG1::new_inf()?;
     }
-    let t7 = Pair::pair(&r_pub_key.pk.add(&proof_c.g)?, &r_pub_key.h_cap)?.pow(&params.r_prime_prime)?
-        .mul(&Pair::pair(&r_pub_key.htilde, &r_pub_key.h_cap)?.pow(&params.m_prime.mod_neg()?)?)?
-        .mul(&Pair::pair(&r_pub_key.htilde, &proof_c.s)?.pow(&params.r)?)?;
-    let t8 = Pair::pair(&r_pub_key.htilde, &r_pub_key.u)?.pow(&params.r)?
-        .mul(&Pair::pair(&r_pub_key.g.neg()?, &r_pub_key.h_cap)?.pow(&params.r_prime_prime_prime)?)?;
+    let t7 = Pair::multi_pair(&[
+        (&r_pub_key.pk.add(&proof_c.g)?.mul(&params.r_prime_prime)?
+            .add(&r_pub_key.htilde.mul(&params.m_prime.mod_neg()?)?)?,
+         &r_pub_key.h_cap),
+        (&r_pub_key.htilde.mul(&params.r)?, &proof_c.s)
+    ])?;
+    let t8 = Pair::multi_pair(&[
+        (&r_pub_key.htilde.mul(&params.r)?, &r_pub_key.u),
+        (&r_pub_key.g.neg()?.mul(&params.r_prime_prime_prime)?, &r_pub_key.h_cap)
+    ])?;
 
     let non_revoc_proof_tau_list = NonRevocProofTauList {
         t1,
@@ -577,9 +760,17 @@ mod tests {
         assert_eq!(result.unwrap(), bitwise_or_big_int(&a.unwrap(), &b.unwrap()).unwrap());
     }
 
+    fn _assert_four_squares(delta: &BigNumber, res: &HashMap<String, BigNumber>) {
+        let mut sum = BigNumber::new().unwrap();
+        for i in 0..4 {
+            sum = sum.add(&res.get(&i.to_string()).unwrap().sqr(None).unwrap()).unwrap();
+        }
+        assert_eq!(*delta, sum);
+    }
+
     #[test]
     fn four_squares_works() {
-        let res = four_squares(107 as i32);
+        let res = four_squares(&BigNumber::from_u32(107).unwrap());
         let res_data = res.unwrap();
 
         assert_eq!("9".to_string(), res_data.get("0").unwrap().to_dec().unwrap());
@@ -587,24 +778,13 @@ mod tests {
         assert_eq!("1".to_string(), res_data.get("2").unwrap().to_dec().unwrap());
         assert_eq!("0".to_string(), res_data.get("3").unwrap().to_dec().unwrap());
 
-        let res = four_squares(112 as i32);
-        let res_data = res.unwrap();
-
-        assert_eq!("10".to_string(), res_data.get("0").unwrap().to_dec().unwrap());
-        assert_eq!("2".to_string(), res_data.get("1").unwrap().to_dec().unwrap());
-        assert_eq!("2".to_string(), res_data.get("2").unwrap().to_dec().unwrap());
-        assert_eq!("2".to_string(), res_data.get("3").unwrap().to_dec().unwrap());
-
+        let delta = BigNumber::from_u32(112).unwrap();
+        _assert_four_squares(&delta, &four_squares(&delta).unwrap());
 
-        let res = four_squares(253 as i32);
-        let res_data = res.unwrap();
-
-        assert_eq!("14".to_string(), res_data.get("0").unwrap().to_dec().unwrap());
-        assert_eq!("7".to_string(), res_data.get("1").unwrap().to_dec().unwrap());
-        assert_eq!("2".to_string(), res_data.get("2").unwrap().to_dec().unwrap());
-        assert_eq!("2".to_string(), res_data.get("3").unwrap().to_dec().unwrap());
+        let delta = BigNumber::from_u32(253).unwrap();
+        _assert_four_squares(&delta, &four_squares(&delta).unwrap());
 
-        let res = four_squares(1506099439 as i32);
+        let res = four_squares(&BigNumber::from_u32(1506099439).unwrap());
         let res_data = res.unwrap();
 
         assert_eq!("38807".to_string(), res_data.get("0").unwrap().to_dec().unwrap());
@@ -613,6 +793,20 @@ mod tests {
         assert_eq!("11".to_string(), res_data.get("3").unwrap().to_dec().unwrap());
     }
 
+    #[test]
+    fn four_squares_works_for_big_values() {
+        for delta in ["0", "4294967296", "18446744073709551615", "340282366920938463463374607431768211456",
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935"].iter() {
+            let delta = BigNumber::from_dec(delta).unwrap();
+            _assert_four_squares(&delta, &four_squares(&delta).unwrap());
+        }
+    }
+
+    #[test]
+    fn four_squares_works_for_negative_value() {
+        assert!(four_squares(&BigNumber::from_dec("-1").unwrap()).is_err());
+    }
+
     #[test]
     fn transform_u32_to_array_of_u8_works() {
         let int = 0x74BA7445;
@@ -648,7 +842,7 @@ mod tests {
         let proof = prover::mocks::ne_proof();
         let pk = issuer::mocks::credential_primary_public_key();
 
-        let res = calc_tne(&pk, &proof.u, &proof.r, &proof.mj, &proof.alpha, &proof.t, proof.predicate.is_less());
+        let res = calc_tne(&pk, &proof.u, &proof.r, &proof.mj, &proof.alpha, proof.upper_alpha.as_ref(), &proof.t, &proof.predicate);
 
         assert!(res.is_ok());
 
@@ -7,7 +7,8 @@ use cl::helpers::*;
 use cl::commitment::get_pedersen_commitment;
 use cl::hash::get_hash_as_int;
 
-use std::collections::{HashMap, HashSet};
+use std::cmp::{max, min};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 /// Trust source that provides credentials to prover.
 pub struct Issuer {}
@@ -119,8 +120,26 @@ impl Issuer {
                                                                              RevocationKeyPrivate,
                                                                              RevocationRegistry,
                                                                              RevocationTailsGenerator), IndyCryptoError> {
-        trace!("Issuer::new_revocation_registry_def: >>> credential_pub_key: {:?}, max_cred_num: {:?}, issuance_by_default: {:?}",
-               credential_pub_key, max_cred_num, issuance_by_default);
+        Issuer::new_revocation_registry_def_parallel(credential_pub_key, max_cred_num, issuance_by_default, 1)
+    }
+
+    /// Creates and returns revocation registry definition entities (see `new_revocation_registry_def`)
+    /// calculating accumulator of issuance by default registry in `threads` threads.
+    ///
+    /// # Arguments
+    /// * `credential_pub_key` - Credential public key entity.
+    /// * `max_cred_num` - Max credential number in generated registry.
+    /// * `issuance_by_default` - Type of issuance.
+    /// * `threads` - Number of threads.
+    pub fn new_revocation_registry_def_parallel(credential_pub_key: &CredentialPublicKey,
+                                                max_cred_num: u32,
+                                                issuance_by_default: bool,
+                                                threads: usize) -> Result<(RevocationKeyPublic,
+                                                                           RevocationKeyPrivate,
+                                                                           RevocationRegistry,
+                                                                           RevocationTailsGenerator), IndyCryptoError> {
+        trace!("Issuer::new_revocation_registry_def_parallel: >>> credential_pub_key: {:?}, max_cred_num: {:?}, issuance_by_default: {:?}, threads: {:?}",
+               credential_pub_key, max_cred_num, issuance_by_default, threads);
 
         let cred_rev_pub_key: &CredentialRevocationPublicKey = credential_pub_key.r_key
             .as_ref()
@@ -131,14 +150,15 @@ impl Issuer {
         let rev_reg = Issuer::_new_revocation_registry(cred_rev_pub_key,
                                                        &rev_key_priv,
                                                        max_cred_num,
-                                                       issuance_by_default)?;
+                                                       issuance_by_default,
+                                                       threads)?;
 
         let rev_tails_generator = RevocationTailsGenerator::new(
             max_cred_num,
             rev_key_priv.gamma.clone(),
             cred_rev_pub_key.g_dash.clone());
 
-        trace!("Issuer::new_revocation_registry_def: <<< rev_key_pub: {:?}, rev_key_priv: {:?}, rev_reg: {:?}, rev_tails_generator: {:?}",
+        trace!("Issuer::new_revocation_registry_def_parallel: <<< rev_key_pub: {:?}, rev_key_priv: {:?}, rev_reg: {:?}, rev_tails_generator: {:?}",
                rev_key_pub, secret!(&rev_key_priv), rev_reg, rev_tails_generator);
 
         Ok((rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator))
@@ -572,6 +592,89 @@ impl Issuer {
         Ok(rev_reg_delta)
     }
 
+    /// Recovers and revokes credentials by rev_idx's in a given revocation registry
+    /// with single accumulator update and returns one delta for all changes.
+    /// Revocation registry isn't changed if any index is invalid.
+    ///
+    /// # Arguments
+    /// * `rev_reg` - Revocation registry.
+    /// * `max_cred_num` - Max credential number in revocation registry.
+    /// * `issued` - Indexes of credentials to recover.
+    /// * `revoked` - Indexes of credentials to revoke.
+    /// * `rev_tails_accessor` - Revocation registry tails accessor.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::SimpleTailsAccessor;
+    /// use indy_crypto::cl::issuer::Issuer;
+    /// use std::collections::BTreeSet;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("name").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+    /// non_credential_schema_builder.add_attr("master_secret").unwrap();
+    /// let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (cred_pub_key, _cred_priv_key, _cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, true).unwrap();
+    ///
+    /// let max_cred_num = 5;
+    /// let (_rev_key_pub, _rev_key_priv, mut rev_reg, mut rev_tails_generator) = Issuer::new_revocation_registry_def(&cred_pub_key, max_cred_num, true).unwrap();
+    ///
+    /// let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+    ///
+    /// let revoked: BTreeSet<u32> = vec![1, 2].into_iter().collect();
+    /// Issuer::update_revocation_registry(&mut rev_reg, max_cred_num, &BTreeSet::new(), &revoked, &simple_tail_accessor).unwrap();
+    /// ```
+    pub fn update_revocation_registry<RTA>(rev_reg: &mut RevocationRegistry,
+                                           max_cred_num: u32,
+                                           issued: &BTreeSet<u32>,
+                                           revoked: &BTreeSet<u32>,
+                                           rev_tails_accessor: &RTA) -> Result<RevocationRegistryDelta, IndyCryptoError> where RTA: RevocationTailsAccessor {
+        trace!("Issuer::update_revocation_registry: >>> rev_reg: {:?}, max_cred_num: {:?}, issued: {:?}, revoked: {:?}",
+               rev_reg, max_cred_num, secret!(issued), secret!(revoked));
+
+        for &rev_idx in issued.iter().chain(revoked.iter()) {
+            if rev_idx < 1 || rev_idx > max_cred_num {
+                return Err(IndyCryptoError::AnoncredsInvalidRevocationAccumulatorIndex(
+                    format!("Revocation index {} is out of range 1..{}", rev_idx, max_cred_num)));
+            }
+        }
+
+        if let Some(rev_idx) = issued.intersection(revoked).next() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Credential {} can't be issued and revoked at once", rev_idx)));
+        }
+
+        let prev_accum = rev_reg.accum.clone();
+        let mut accum = rev_reg.accum.clone();
+
+        for &rev_idx in issued.iter() {
+            rev_tails_accessor.access_tail(Issuer::_get_index(max_cred_num, rev_idx), &mut |tail| {
+                accum = accum.add(tail).unwrap();
+            })?;
+        }
+
+        for &rev_idx in revoked.iter() {
+            rev_tails_accessor.access_tail(Issuer::_get_index(max_cred_num, rev_idx), &mut |tail| {
+                accum = accum.sub(tail).unwrap();
+            })?;
+        }
+
+        rev_reg.accum = accum;
+
+        let rev_reg_delta = RevocationRegistryDelta {
+            prev_accum: Some(prev_accum),
+            accum: rev_reg.accum.clone(),
+            issued: issued.iter().cloned().collect(),
+            revoked: revoked.iter().cloned().collect()
+        };
+
+        trace!("Issuer::update_revocation_registry: <<< rev_reg_delta: {:?}", rev_reg_delta);
+
+        Ok(rev_reg_delta)
+    }
+
     fn _new_credential_primary_keys(credential_schema: &CredentialSchema,
                                     non_credential_schema: &NonCredentialSchema) ->
                                                                           Result<(CredentialPrimaryPublicKey,
@@ -712,16 +815,21 @@ impl Issuer {
     fn _new_revocation_registry(cred_rev_pub_key: &CredentialRevocationPublicKey,
                                 rev_key_priv: &RevocationKeyPrivate,
                                 max_cred_num: u32,
-                                issuance_by_default: bool) -> Result<RevocationRegistry, IndyCryptoError> {
-        trace!("Issuer::_new_revocation_registry: >>> cred_rev_pub_key: {:?}, rev_key_priv: {:?}, max_cred_num: {:?}, issuance_by_default: {:?}",
-               cred_rev_pub_key, secret!(rev_key_priv), max_cred_num, issuance_by_default);
+                                issuance_by_default: bool,
+                                threads: usize) -> Result<RevocationRegistry, IndyCryptoError> {
+        trace!("Issuer::_new_revocation_registry: >>> cred_rev_pub_key: {:?}, rev_key_priv: {:?}, max_cred_num: {:?}, issuance_by_default: {:?}, threads: {:?}",
+               cred_rev_pub_key, secret!(rev_key_priv), max_cred_num, issuance_by_default, threads);
 
         let mut accum = Accumulator::new_inf()?;
 
         if issuance_by_default {
-            for i in 1..max_cred_num + 1 {
-                let index = Issuer::_get_index(max_cred_num, i);
-                accum = accum.add(&Tail::new_tail(index, &cred_rev_pub_key.g_dash, &rev_key_priv.gamma)?)?;
+            // Indexes of tails for all credentials `_get_index(max_cred_num, 1..max_cred_num + 1)` are exactly `1..max_cred_num + 1`
+            // Chunks are never smaller than `MIN_TAILS_CHUNK_SIZE`, so small registries don't spawn useless threads
+            let threads = min(max(threads, 1), (max_cred_num / MIN_TAILS_CHUNK_SIZE + 1) as usize);
+            let chunks = TailsChunk::split(1, max_cred_num + 1, threads, &cred_rev_pub_key.g_dash, &rev_key_priv.gamma)?;
+
+            for sum in process_tails_chunks(chunks, TailsChunk::sum)? {
+                accum = accum.add(&sum)?;
             }
         };
 
@@ -761,7 +869,7 @@ impl Issuer {
         trace!("Issuer::_check_blinded_credential_secrets_correctness_proof: >>> blinded_cred_secrets: {:?}, blinded_cred_secrets_correctness_proof: {:?},\
          nonce: {:?}, cred_pr_pub_key: {:?}", blinded_cred_secrets, blinded_cred_secrets_correctness_proof, nonce, cred_pr_pub_key);
 
-        let mut values: Vec<u8> = Vec::new();
+        let mut committed_attributes: Vec<(&str, BigNumber, &BigNumber)> = Vec::new();
         let mut ctx = BigNumber::new_context()?;
 
         let u_cap = blinded_cred_secrets.hidden_attributes
@@ -792,16 +900,14 @@ impl Issuer {
                                                                       &cred_pr_pub_key.n, &mut ctx)?,
                                              &cred_pr_pub_key.n, Some(&mut ctx))?;
 
-            values.extend_from_slice(&comm_att_cap.to_bytes()?);
-            values.extend_from_slice(&value.to_bytes()?);
+            committed_attributes.push((key.as_str(), comm_att_cap, value));
         }
 
-
-        values.extend_from_slice(&blinded_cred_secrets.u.to_bytes()?);
-        values.extend_from_slice(&u_cap.to_bytes()?);
-        values.extend_from_slice(&nonce.to_bytes()?);
-
-        let c = get_hash_as_int(&vec![values])?;
+        let c = get_blinded_credential_secrets_challenge(blinded_cred_secrets_correctness_proof.version,
+                                                         &committed_attributes,
+                                                         &blinded_cred_secrets.u,
+                                                         &u_cap,
+                                                         nonce)?;
 
         let valid = blinded_cred_secrets_correctness_proof.c.eq(&c);
 
@@ -1123,6 +1229,68 @@ mod tests {
         Issuer::new_revocation_registry_def(&pub_key, 100, false).unwrap();
     }
 
+    #[test]
+    fn update_revocation_registry_works() {
+        let (pub_key, _, _) = Issuer::new_credential_def(&mocks::credential_schema(), &mocks::non_credential_schema(), true).unwrap();
+        let (_, _, rev_reg, mut rev_tails_generator) = Issuer::new_revocation_registry_def(&pub_key, 5, true).unwrap();
+        let simple_tails_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+
+        let mut expected_rev_reg = rev_reg.clone();
+        let mut rev_reg_delta = Issuer::revoke_credential(&mut expected_rev_reg, 5, 1, &simple_tails_accessor).unwrap();
+        for &rev_idx in [2, 3].iter() {
+            let other_rev_reg_delta = Issuer::revoke_credential(&mut expected_rev_reg, 5, rev_idx, &simple_tails_accessor).unwrap();
+            rev_reg_delta.merge(&other_rev_reg_delta).unwrap();
+        }
+        let other_rev_reg_delta = Issuer::recovery_credential(&mut expected_rev_reg, 5, 1, &simple_tails_accessor).unwrap();
+        rev_reg_delta.merge(&other_rev_reg_delta).unwrap();
+
+        let mut batch_rev_reg = rev_reg.clone();
+        let issued: BTreeSet<u32> = BTreeSet::new();
+        let revoked: BTreeSet<u32> = vec![2, 3].into_iter().collect();
+        let batch_rev_reg_delta = Issuer::update_revocation_registry(&mut batch_rev_reg, 5, &issued, &revoked, &simple_tails_accessor).unwrap();
+
+        assert_eq!(expected_rev_reg.accum, batch_rev_reg.accum);
+        assert_eq!(rev_reg_delta.accum, batch_rev_reg_delta.accum);
+        assert_eq!(rev_reg.accum, batch_rev_reg_delta.prev_accum.unwrap());
+        assert_eq!(rev_reg_delta.revoked, batch_rev_reg_delta.revoked);
+        assert!(batch_rev_reg_delta.issued.is_empty());
+    }
+
+    #[test]
+    fn update_revocation_registry_works_for_invalid_indexes() {
+        let (pub_key, _, _) = Issuer::new_credential_def(&mocks::credential_schema(), &mocks::non_credential_schema(), true).unwrap();
+        let (_, _, mut rev_reg, mut rev_tails_generator) = Issuer::new_revocation_registry_def(&pub_key, 5, true).unwrap();
+        let simple_tails_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+        let accum = rev_reg.accum.clone();
+
+        for &(issued, revoked) in [(&[][..], &[0][..]), (&[6][..], &[][..]), (&[1][..], &[1, 2][..])].iter() {
+            let issued: BTreeSet<u32> = issued.iter().cloned().collect();
+            let revoked: BTreeSet<u32> = revoked.iter().cloned().collect();
+            assert!(Issuer::update_revocation_registry(&mut rev_reg, 5, &issued, &revoked, &simple_tails_accessor).is_err());
+            assert_eq!(accum, rev_reg.accum);
+        }
+    }
+
+    #[cfg(feature = "pair_amcl")]
+    #[test]
+    fn new_revocation_registry_works_for_issuance_by_default() {
+        let cred_rev_pub_key = mocks::credential_revocation_public_key();
+        let rev_key_priv = mocks::revocation_key_private();
+        let max_cred_num = 2 * MIN_TAILS_CHUNK_SIZE + 1;
+
+        let mut expected_accum = Accumulator::new_inf().unwrap();
+        for i in 1..max_cred_num + 1 {
+            let index = Issuer::_get_index(max_cred_num, i);
+            expected_accum = expected_accum.add(&Tail::new_tail(index, &cred_rev_pub_key.g_dash, &rev_key_priv.gamma).unwrap()).unwrap();
+        }
+
+        for &threads in [1, 3].iter() {
+            let rev_reg = Issuer::_new_revocation_registry(&cred_rev_pub_key, &rev_key_priv, max_cred_num, true, threads).unwrap();
+            assert_eq!(expected_accum, rev_reg.accum);
+        }
+    }
+
+    #[cfg(feature = "pair_amcl")]
     #[test]
     fn sign_primary_credential_works() {
         MockHelper::inject();
@@ -1140,6 +1308,37 @@ mod tests {
         assert_eq!(expected_q, q);
     }
 
+    #[cfg(feature = "pair_amcl")]
+    #[test]
+    fn check_blinded_credential_secrets_correctness_proof_works_for_versions() {
+        MockHelper::inject();
+
+        let pub_key = mocks::credential_primary_public_key();
+        let nonce = mocks::credential_nonce();
+        let blinded_credential_secrets = prover::mocks::blinded_credential_secrets();
+        let mut blinded_credential_secrets_correctness_proof = prover::mocks::blinded_credential_secrets_correctness_proof();
+
+        // Mocked proof is legacy one, so it is checked against challenge without transcript
+        assert_eq!(LEGACY_PROOF_VERSION, blinded_credential_secrets_correctness_proof.version);
+        Issuer::_check_blinded_credential_secrets_correctness_proof(&blinded_credential_secrets,
+                                                                    &blinded_credential_secrets_correctness_proof,
+                                                                    &nonce,
+                                                                    &pub_key).unwrap();
+
+        blinded_credential_secrets_correctness_proof.version = CURRENT_PROOF_VERSION;
+        assert!(Issuer::_check_blinded_credential_secrets_correctness_proof(&blinded_credential_secrets,
+                                                                            &blinded_credential_secrets_correctness_proof,
+                                                                            &nonce,
+                                                                            &pub_key).is_err());
+
+        blinded_credential_secrets_correctness_proof.version = CURRENT_PROOF_VERSION + 1;
+        assert!(Issuer::_check_blinded_credential_secrets_correctness_proof(&blinded_credential_secrets,
+                                                                            &blinded_credential_secrets_correctness_proof,
+                                                                            &nonce,
+                                                                            &pub_key).is_err());
+    }
+
+    #[cfg(feature = "pair_amcl")]
     #[test]
     fn sign_credential_signature_works() {
         MockHelper::inject();
@@ -1277,6 +1476,7 @@ mod tests {
     }
 }
 
+// Group element mocks are BN254 points, so they and tests using them need `pair_amcl` backend.
 pub mod mocks {
     use super::*;
     use self::prover::mocks as prover_mocks;
@@ -1285,6 +1485,7 @@ pub mod mocks {
         BigNumber::from_dec("69500003785041890145270364348670634122591474903142468939711692725859480163330").unwrap()
     }
 
+    #[cfg(feature = "pair_amcl")]
     pub fn credential_public_key() -> CredentialPublicKey {
         CredentialPublicKey {
             p_key: credential_primary_public_key(),
@@ -1298,6 +1499,7 @@ pub mod mocks {
 
     pub fn credential_issuance_nonce() -> Nonce { BigNumber::from_dec("56533754654551822200471").unwrap() }
 
+    #[cfg(feature = "pair_amcl")]
     pub fn credential_private_key() -> CredentialPrivateKey {
         CredentialPrivateKey {
             p_key: credential_primary_private_key(),
@@ -1366,6 +1568,7 @@ pub mod mocks {
         }
     }
 
+    #[cfg(feature = "pair_amcl")]
     pub fn credential() -> CredentialSignature {
         CredentialSignature {
             p_credential: primary_credential(),
@@ -1389,6 +1592,7 @@ pub mod mocks {
         }
     }
 
+    #[cfg(feature = "pair_amcl")]
     pub fn revocation_credential() -> NonRevocationCredentialSignature {
         NonRevocationCredentialSignature {
             sigma: PointG1::from_string("false 1D18E69FA5AA97421F4AEBE933B40264261C5440090222C6AC61FEBE2CFEAA04 1461756FB88E41A2CB508A7057318CAFB551F4CD0C7051CBEC23DDFBC92248BC 095E45DDF417D05FB10933FFC63D474548B7FFFF7888802F07FFFFFF7D07A8A8").unwrap(),
@@ -1401,6 +1605,7 @@ pub mod mocks {
             }
     }
 
+    #[cfg(feature = "pair_amcl")]
     fn witness_signature() -> WitnessSignature {
         WitnessSignature {
             sigma_i: PointG2::from_string("false 02680D6A364915CE54A5E1DA89E7F1530B9394D2756312D6D97F776B0F39CC6F 15DE23D8864E2703884B81CB93EC5E8EE75D59BF2A8957F1C853C7407A3AF9AC 06B72EAC18E9FF42298D7B9B7F220E00A944FFC1864755EBB79A70E82C370335 116BF610CC4368D001D9F0BE121EE8DF2C7F0BEE2F1B3FE954EAF36C13DFD06F 095E45DDF417D05FB10933FFC63D474548B7FFFF7888802F07FFFFFF7D07A8A8 0000000000000000000000000000000000000000000000000000000000000000").unwrap(),
@@ -1409,12 +1614,14 @@ pub mod mocks {
         }
     }
 
+    #[cfg(feature = "pair_amcl")]
     pub fn witness() -> Witness {
         Witness {
             omega: PointG2::from_string("true 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0").unwrap()
         }
     }
 
+    #[cfg(feature = "pair_amcl")]
     pub fn credential_revocation_public_key() -> CredentialRevocationPublicKey {
         CredentialRevocationPublicKey {
             g: PointG1::from_string("false 03D433008A42E55FE3C6C4772D290EB3B0BF999F8281B4329E55033A32663625 0BDFD038889932B7C5CDD0BB846713710FBAB698201DFD4A380CDD1282E75060 095E45DDF417D05FB10933FFC63D474548B7FFFF7888802F07FFFFFF7D07A8A8").unwrap(),
@@ -1431,6 +1638,7 @@ pub mod mocks {
         }
     }
 
+    #[cfg(feature = "pair_amcl")]
     pub fn credential_revocation_private_key() -> CredentialRevocationPrivateKey {
         CredentialRevocationPrivateKey {
             x: GroupOrderElement::from_string("17F6C5FC0B644FF12D490ADF6A0A2D3CD6461E05982D2E9CA5F01DC9349F3FC3").unwrap(),
@@ -1438,22 +1646,26 @@ pub mod mocks {
         }
     }
 
+    #[cfg(feature = "pair_amcl")]
     pub fn revocation_key_public() -> RevocationKeyPublic {
         RevocationKeyPublic {
             z: Pair::from_string("BAF4F6C1044467 B355263E5FED41 8C0AF4C3EB94AF 3DE0C83ACA9928 2D6A7C6 FDF167021A1737 F7663EE5B2767B C5C4D3E69D387 34AA472296FCC7 B1660F7 C4741C69824558 CE22B92C952568 BB8179722E1BE7 1036505FEC026E 1C07F9FD DEAB5ECFD267CE 2E372388203E8D 973CB3DFAED87A EAB1BCFACB147E 12AC5746 BA65AD126B3FA5 1E1CF9FFC748E9 6017A982889E18 7AC0602B49C5E4 BAF574F 6CF7E2221ABC1 C4ABDFD08A7CD4 5CF4AB327CE15 3135590EE8EFC4 8192962 4FFCD9C89ABC45 3E0764B6CD0CF7 228E1021AA539B 8BA7447BCE3D7F F203473 DFB3E31073CDBD 7924EAC9D036C1 716066DCE76DC9 87B72FD4831A7 7296BA1 F417B8E0DAA939 9CA99939CB747E C79AC00D77664D D5C8F4836CDC28 1C615963 FD093CEBD6DED8 D16D939D4144E6 D209EEB27A2D40 E10AC83BFD60E4 4221B1A 535859DCF661A3 4A2F9EA4995F28 F9E4ECB0F4A21F CCB9D054387AF6 1B0A327E 20BF74410EF2D0 878F7EC03EA36B 76029AEF058F80 D988F4E307EC0E B9001C").unwrap()
         }
     }
 
+    #[cfg(feature = "pair_amcl")]
     pub fn revocation_key_private() -> RevocationKeyPrivate {
         RevocationKeyPrivate {
             gamma: GroupOrderElement::from_string("9A7934671787E7 B44902FD431283 E541AB2729B4F7 E4BDDF7F08FE77 19ADFD0").unwrap()
         }
     }
 
+    #[cfg(feature = "pair_amcl")]
     fn accumulator() -> Accumulator {
         PointG2::from_string("false DABF1B89B584A1 6528C2CA3BB434 797565BB1CCB90 E63C6A6DC3C91A 24471A93 31D1B4E5C6F7E8 A4C48C9D1E4D0F BF10C3FBF53B80 27C94984204EFC 17DBA383 32F293DFC739DF 7E3DD3E71A4918 E2D84BF08244AE 3D7178DB477364 22738A3 3F9BCA3702EBD8 F8039636941D3C 1CE9B219CC559 9408F318813CCD 16C4CE4 FFFFFF7D07A8A8 FFFF7888802F07 FFC63D474548B7 F417D05FB10933 95E45DD 0 0 0 0 0").unwrap()
     }
 
+    #[cfg(feature = "pair_amcl")]
     pub fn revocation_registry() -> RevocationRegistry {
         RevocationRegistry {
             accum: accumulator()
@@ -1464,6 +1676,7 @@ pub mod mocks {
         5
     }
 
+    #[cfg(feature = "pair_amcl")]
     pub fn revocation_registry_delta() -> RevocationRegistryDelta {
         RevocationRegistryDelta {
             prev_accum: Some(PointG2::from_string("true 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0").unwrap()),
@@ -1,22 +1,38 @@
 #[macro_use]
 pub mod logger;
+pub mod auditor;
 mod commitment;
 mod constants;
 #[macro_use]
 mod datastructures;
+pub mod encoding;
 #[macro_use]
 mod helpers;
 mod hash;
+mod transcript;
 pub mod issuer;
+pub mod tails;
 pub mod prover;
 pub mod verifier;
 
 use bn::BigNumber;
+use cl::constants::{PSEUDONYM_DST, PROOF_MESSAGE_DST, ITERATION,
+                    PROOF_TRANSCRIPT_DOMAIN, BLINDED_CREDENTIAL_SECRETS_TRANSCRIPT_DOMAIN, VERIFIABLE_ENCRYPTION_TRANSCRIPT_DOMAIN,
+                    PRIMARY_EQUAL_PROOF_LABEL, PRIMARY_PREDICATE_PROOF_LABEL, SET_MEMBERSHIP_PROOF_LABEL};
+pub use cl::constants::{LEGACY_PROOF_VERSION, CURRENT_PROOF_VERSION};
+use cl::encoding::AttributeValue;
+use cl::hash::get_hash_as_int;
+use cl::helpers::bn_rand_range;
+use cl::transcript::Transcript;
 use errors::IndyCryptoError;
 use pair::*;
 
+use rand::{thread_rng, Rng};
+
+use std::cmp::{min, max};
 use std::collections::{HashMap, HashSet, BTreeSet, BTreeMap};
 use std::hash::Hash;
+use std::thread;
 
 /// Creates random nonce
 ///
@@ -226,6 +242,16 @@ impl CredentialValuesBuilder {
         Ok(())
     }
 
+    /// Adds known attribute given as raw typed value, see `AttributeValue` for encoding rules.
+    pub fn add_typed_known(&mut self, attr: &str, value: &AttributeValue) -> Result<(), IndyCryptoError> {
+        self.add_value_known(attr, &value.encode()?)
+    }
+
+    /// Adds hidden attribute given as raw typed value, see `AttributeValue` for encoding rules.
+    pub fn add_typed_hidden(&mut self, attr: &str, value: &AttributeValue) -> Result<(), IndyCryptoError> {
+        self.add_value_hidden(attr, &value.encode()?)
+    }
+
     pub fn add_value_commitment(
         &mut self,
         attr: &str,
@@ -512,22 +538,250 @@ impl RevocationTailsGenerator {
 
         Ok(Some(tail))
     }
+
+    /// Generates up to `count` next tails splitting them between `threads` threads.
+    ///
+    /// # Arguments
+    /// * `count` - Max number of tails to generate.
+    /// * `threads` - Number of threads.
+    pub fn next_chunk(&mut self, count: u32, threads: usize) -> Result<Vec<Tail>, IndyCryptoError> {
+        let from = self.current_index;
+        let to = from + min(count, self.count());
+
+        let chunks = TailsChunk::split(from, to, threads, &self.g_dash, &self.gamma)?;
+        let tails = process_tails_chunks(chunks, TailsChunk::tails)?.concat();
+
+        self.current_index = to;
+
+        Ok(tails)
+    }
+}
+
+/// Range of tails generated by single thread.
+/// Power of `gamma` is computed directly only for the first tail of the chunk,
+/// every next power is obtained by multiplication by `gamma`.
+#[derive(Debug)]
+struct TailsChunk {
+    start: u32,
+    end: u32,
+    pow: GroupOrderElement,
+    g_dash: PointG2,
+    gamma: GroupOrderElement
+}
+
+impl TailsChunk {
+    /// Splits tails with indexes `from..to` into at most `threads` chunks of almost equal size.
+    fn split(from: u32, to: u32, threads: usize, g_dash: &PointG2, gamma: &GroupOrderElement) -> Result<Vec<TailsChunk>, IndyCryptoError> {
+        let len = to.saturating_sub(from) as u64;
+        let threads = min(max(threads as u64, 1), max(len, 1));
+        let chunk_size = (len + threads - 1) / threads;
+
+        let mut chunks: Vec<TailsChunk> = Vec::new();
+        let mut start = from;
+        while start < to {
+            let end = min(start as u64 + chunk_size, to as u64) as u32;
+            let pow = gamma.pow_mod(&GroupOrderElement::from_bytes(&helpers::transform_u32_to_array_of_u8(start))?)?;
+            chunks.push(TailsChunk { start, end, pow, g_dash: *g_dash, gamma: *gamma });
+            start = end;
+        }
+
+        Ok(chunks)
+    }
+
+    fn tails(self) -> Result<Vec<Tail>, IndyCryptoError> {
+        let mut tails: Vec<Tail> = Vec::with_capacity((self.end - self.start) as usize);
+        let mut pow = self.pow;
+        for _ in self.start..self.end {
+            tails.push(self.g_dash.mul(&pow)?);
+            pow = pow.mul_mod(&self.gamma)?;
+        }
+        Ok(tails)
+    }
+
+    fn sum(self) -> Result<PointG2, IndyCryptoError> {
+        let mut sum = PointG2::new_inf()?;
+        let mut pow = self.pow;
+        for _ in self.start..self.end {
+            sum = sum.add(&self.g_dash.mul(&pow)?)?;
+            pow = pow.mul_mod(&self.gamma)?;
+        }
+        Ok(sum)
+    }
+}
+
+/// Processes every tails chunk in separate thread and returns results in order of chunks.
+fn process_tails_chunks<T: Send + 'static>(chunks: Vec<TailsChunk>,
+                                           process: fn(TailsChunk) -> Result<T, IndyCryptoError>) -> Result<Vec<T>, IndyCryptoError> {
+    if chunks.len() <= 1 {
+        return chunks.into_iter().map(process).collect();
+    }
+
+    let handles: Vec<thread::JoinHandle<Result<T, IndyCryptoError>>> = chunks
+        .into_iter()
+        .map(|chunk| thread::spawn(move || process(chunk)))
+        .collect();
+
+    handles
+        .into_iter()
+        .map(|handle| handle.join().map_err(|_| IndyCryptoError::InvalidState(format!("Tails generation thread panicked")))?)
+        .collect()
 }
 
 pub trait RevocationTailsAccessor {
     fn access_tail(&self, tail_id: u32, accessor: &mut FnMut(&Tail)) -> Result<(), IndyCryptoError>;
+
+    /// Returns sum of tails with indexes `from..to` or `None` if accessor has no `TailsPrefixSums`
+    /// and tails must be summed one by one.
+    fn access_tails_sum(&self, _from: u32, _to: u32) -> Result<Option<Tail>, IndyCryptoError> {
+        Ok(None)
+    }
+}
+
+/// Block prefix sums of tails. Sum of any range of tails is computed
+/// from two block sums and at most `2 * (block_size - 1)` tails.
+#[derive(Debug, Clone)]
+pub struct TailsPrefixSums {
+    block_size: u32,
+    tails_count: u32,
+    sums: Vec<Tail>, /* sums[k] is sum of tails with indexes 0..k * block_size */
+    block_sum: Tail /* sum of added tails of the incomplete block */
+}
+
+impl TailsPrefixSums {
+    /// Creates empty prefix sums, tails must be added in order of indexes with `add_tail`.
+    ///
+    /// # Arguments
+    /// * `block_size` - Number of tails in block.
+    pub fn new(block_size: u32) -> Result<TailsPrefixSums, IndyCryptoError> {
+        if block_size == 0 {
+            return Err(IndyCryptoError::InvalidStructure(format!("Block size of tails prefix sums must be positive")));
+        }
+
+        Ok(TailsPrefixSums {
+            block_size,
+            tails_count: 0,
+            sums: vec![Tail::new_inf()?],
+            block_sum: Tail::new_inf()?
+        })
+    }
+
+    fn from_parts(block_size: u32, tails_count: u32, sums: Vec<Tail>) -> Result<TailsPrefixSums, IndyCryptoError> {
+        if block_size == 0 || sums.len() != (tails_count / block_size) as usize + 1 {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid tails prefix sums")));
+        }
+
+        let block_sum = Tail::new_inf()?;
+
+        Ok(TailsPrefixSums { block_size, tails_count, sums, block_sum })
+    }
+
+    pub fn add_tail(&mut self, tail: &Tail) -> Result<(), IndyCryptoError> {
+        self.block_sum = self.block_sum.add(tail)?;
+        self.tails_count += 1;
+
+        if self.tails_count % self.block_size == 0 {
+            let sum = self.sums[self.sums.len() - 1].add(&self.block_sum)?;
+            self.sums.push(sum);
+            self.block_sum = Tail::new_inf()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn block_size(&self) -> u32 {
+        self.block_size
+    }
+
+    pub fn tails_count(&self) -> u32 {
+        self.tails_count
+    }
+
+    /// Returns sum of tails with indexes `from..to`.
+    ///
+    /// # Arguments
+    /// * `from` - Index of the first tail.
+    /// * `to` - Index after the last tail.
+    /// * `rev_tails_accessor` - Accessor of tails the prefix sums are built for.
+    pub fn range_sum<RTA>(&self, from: u32, to: u32, rev_tails_accessor: &RTA) -> Result<Tail, IndyCryptoError>
+        where RTA: RevocationTailsAccessor + ?Sized {
+        if from > to || to > self.tails_count {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid range of tails {}..{}", from, to)));
+        }
+
+        self._prefix_sum(to, rev_tails_accessor)?.sub(&self._prefix_sum(from, rev_tails_accessor)?)
+    }
+
+    /// Checks prefix sums of `checks` random blocks (or of all blocks if there are fewer of them)
+    /// against sums of tails from the accessor.
+    ///
+    /// # Arguments
+    /// * `rev_tails_accessor` - Accessor of tails the prefix sums are built for.
+    /// * `checks` - Number of blocks to check.
+    pub fn spot_check<RTA>(&self, rev_tails_accessor: &RTA, checks: usize) -> Result<(), IndyCryptoError>
+        where RTA: RevocationTailsAccessor + ?Sized {
+        let blocks = self.sums.len() - 1;
+
+        let checked_blocks: Vec<usize> = if blocks <= checks {
+            (0..blocks).collect()
+        } else {
+            let mut rng = thread_rng();
+            (0..checks).map(|_| rng.gen_range(0, blocks)).collect()
+        };
+
+        if self.sums[0] != Tail::new_inf()? {
+            return Err(IndyCryptoError::InvalidStructure(format!("Tails prefix sums don't correspond to tails")));
+        }
+
+        for block in checked_blocks {
+            let mut sum = self.sums[block];
+            for tail_id in block as u32 * self.block_size..(block as u32 + 1) * self.block_size {
+                rev_tails_accessor.access_tail(tail_id, &mut |tail| {
+                    sum = sum.add(tail).unwrap();
+                })?;
+            }
+
+            if sum != self.sums[block + 1] {
+                return Err(IndyCryptoError::InvalidStructure(format!("Tails prefix sums don't correspond to tails")));
+            }
+        }
+
+        Ok(())
+    }
+
+    // Sum of tails with indexes 0..index
+    fn _prefix_sum<RTA>(&self, index: u32, rev_tails_accessor: &RTA) -> Result<Tail, IndyCryptoError>
+        where RTA: RevocationTailsAccessor + ?Sized {
+        let block = index / self.block_size;
+        let mut sum = self.sums[block as usize];
+
+        for tail_id in block * self.block_size..index {
+            rev_tails_accessor.access_tail(tail_id, &mut |tail| {
+                sum = sum.add(tail).unwrap();
+            })?;
+        }
+
+        Ok(sum)
+    }
 }
 
 /// Simple implementation of `RevocationTailsAccessor` that stores all tails as BTreeMap.
 #[derive(Debug, Clone)]
 pub struct SimpleTailsAccessor {
-    tails: Vec<Tail>
+    tails: Vec<Tail>,
+    prefix_sums: Option<TailsPrefixSums>
 }
 
 impl RevocationTailsAccessor for SimpleTailsAccessor {
     fn access_tail(&self, tail_id: u32, accessor: &mut FnMut(&Tail)) -> Result<(), IndyCryptoError> {
         Ok(accessor(&self.tails[tail_id as usize]))
     }
+
+    fn access_tails_sum(&self, from: u32, to: u32) -> Result<Option<Tail>, IndyCryptoError> {
+        match self.prefix_sums {
+            Some(ref prefix_sums) => Ok(Some(prefix_sums.range_sum(from, to, self)?)),
+            None => Ok(None)
+        }
+    }
 }
 
 impl SimpleTailsAccessor {
@@ -536,7 +790,23 @@ impl SimpleTailsAccessor {
         while let Some(tail) = rev_tails_generator.next()? {
             tails.push(tail);
         }
-        Ok(SimpleTailsAccessor { tails })
+        Ok(SimpleTailsAccessor { tails, prefix_sums: None })
+    }
+
+    /// Creates accessor that also keeps block prefix sums of tails to sum ranges of tails fast.
+    ///
+    /// # Arguments
+    /// * `rev_tails_generator` - Revocation tails generator.
+    /// * `block_size` - Number of tails in block of prefix sums.
+    pub fn new_with_prefix_sums(rev_tails_generator: &mut RevocationTailsGenerator,
+                                block_size: u32) -> Result<SimpleTailsAccessor, IndyCryptoError> {
+        let mut prefix_sums = TailsPrefixSums::new(block_size)?;
+        let mut tails: Vec<Tail> = Vec::new();
+        while let Some(tail) = rev_tails_generator.next()? {
+            prefix_sums.add_tail(&tail)?;
+            tails.push(tail);
+        }
+        Ok(SimpleTailsAccessor { tails, prefix_sums: Some(prefix_sums) })
     }
 }
 
@@ -597,6 +867,37 @@ impl Witness {
 
         let mut omega = PointG2::new_inf()?;
 
+        // Tails of all credentials j have indexes max_cred_num + 1 - j + rev_idx from rev_idx + 1 to max_cred_num + rev_idx
+        let tails_sum = if issuance_by_default {
+            rev_tails_accessor.access_tails_sum(rev_idx + 1, max_cred_num + rev_idx + 1)?
+        } else {
+            None
+        };
+
+        if let Some(tails_sum) = tails_sum {
+            omega = tails_sum;
+
+            // Tail of credential rev_idx itself
+            rev_tails_accessor.access_tail(max_cred_num + 1, &mut |tail| {
+                omega = omega.sub(tail).unwrap();
+            })?;
+
+            for j in rev_reg_delta.revoked.iter() {
+                if rev_idx.eq(j) || *j < 1 || *j > max_cred_num { continue; }
+
+                let index = max_cred_num + 1 - j + rev_idx;
+                rev_tails_accessor.access_tail(index, &mut |tail| {
+                    omega = omega.sub(tail).unwrap();
+                })?;
+            }
+
+            let witness = Witness { omega };
+
+            trace!("Witness::new: <<< witness: {:?}", witness);
+
+            return Ok(witness);
+        }
+
         let mut issued = if issuance_by_default {
             (1..max_cred_num + 1).collect::<HashSet<u32>>()
                 .difference(&rev_reg_delta.revoked).cloned().collect::<HashSet<u32>>()
@@ -716,6 +1017,8 @@ pub struct RevocationBlindedCredentialSecretsFactors {
 
 #[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct BlindedCredentialSecretsCorrectnessProof {
+    #[serde(default = "legacy_proof_version")]
+    version: u32, // Version of the proof format, proofs without version are legacy ones
     c: BigNumber, // Fiat-Shamir challenge hash
     v_dash_cap: BigNumber, // Value to prove knowledge of `u` construction in `BlindedCredentialSecrets`
     m_caps: BTreeMap<String, BigNumber>, // Values for proving knowledge of committed values
@@ -724,10 +1027,16 @@ pub struct BlindedCredentialSecretsCorrectnessProof {
 
 /// “Sub Proof Request” - input to create a Proof for a credential;
 /// Contains attributes to be revealed and predicates.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SubProofRequest {
     revealed_attrs: BTreeSet<String>,
     predicates: BTreeSet<Predicate>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    set_predicates: BTreeSet<SetMembershipPredicate>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pseudonym: Option<PseudonymRequest>,
 }
 
 /// Builder of “Sub Proof Request”.
@@ -741,7 +1050,9 @@ impl SubProofRequestBuilder {
         Ok(SubProofRequestBuilder {
             value: SubProofRequest {
                 revealed_attrs: BTreeSet::new(),
-                predicates: BTreeSet::new()
+                predicates: BTreeSet::new(),
+                set_predicates: BTreeSet::new(),
+                pseudonym: None
             }
         })
     }
@@ -752,62 +1063,304 @@ impl SubProofRequestBuilder {
     }
 
     pub fn add_predicate(&mut self, attr_name: &str, p_type: &str, value: i32) -> Result<(), IndyCryptoError> {
+        self.add_value_predicate(attr_name, p_type, &BigNumber::from_dec(&value.to_string())?)
+    }
+
+    /// Adds predicate with value given as decimal string, so it can be arbitrary large or negative.
+    ///
+    /// # Arguments
+    /// * `attr_name` - Related attribute
+    /// * `p_type` - Predicate type (`GE`, `LE`, `GT`, `LT` or `NE`)
+    /// * `dec_value` - Requested value as decimal string
+    pub fn add_dec_predicate(&mut self, attr_name: &str, p_type: &str, dec_value: &str) -> Result<(), IndyCryptoError> {
+        self.add_value_predicate(attr_name, p_type, &BigNumber::from_dec(dec_value)?)
+    }
+
+    /// Adds predicate with value given as raw typed value.
+    /// Only integers, booleans and dates can be used, as encodings of other types don't keep order.
+    pub fn add_typed_predicate(&mut self, attr_name: &str, p_type: &str, value: &AttributeValue) -> Result<(), IndyCryptoError> {
+        if !value.is_ordered() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Predicate value {:?} can't be compared", value)));
+        }
+        self.add_value_predicate(attr_name, p_type, &value.encode()?)
+    }
+
+    pub fn add_value_predicate(&mut self, attr_name: &str, p_type: &str, value: &BigNumber) -> Result<(), IndyCryptoError> {
         let p_type = match p_type {
             "GE" => PredicateType::GE,
             "LE" => PredicateType::LE,
             "GT" => PredicateType::GT,
             "LT" => PredicateType::LT,
+            "NE" => PredicateType::NE,
+            "BETWEEN" => return Err(IndyCryptoError::InvalidStructure(format!("Predicate type BETWEEN requires two bounds, use add_range_predicate"))),
             p_type => return Err(IndyCryptoError::InvalidStructure(format!("Invalid predicate type: {:?}", p_type)))
         };
 
         let predicate = Predicate {
             attr_name: attr_name.to_owned(),
             p_type,
-            value
+            value: value.to_dec()?,
+            upper_value: None
         };
 
         self.value.predicates.insert(predicate);
         Ok(())
     }
 
+    /// Adds predicate that requires attribute value to be in range `[from, to)`.
+    ///
+    /// Both bounds are proven by a single sub proof that shares one commitment to the attribute.
+    ///
+    /// # Arguments
+    /// * `attr_name` - Related attribute
+    /// * `from` - Lower bound (inclusive)
+    /// * `to` - Upper bound (exclusive)
+    pub fn add_range_predicate(&mut self, attr_name: &str, from: i32, to: i32) -> Result<(), IndyCryptoError> {
+        self.add_value_range_predicate(attr_name,
+                                       &BigNumber::from_dec(&from.to_string())?,
+                                       &BigNumber::from_dec(&to.to_string())?)
+    }
+
+    /// Adds range predicate with bounds given as decimal strings.
+    ///
+    /// # Arguments
+    /// * `attr_name` - Related attribute
+    /// * `dec_from` - Lower bound (inclusive) as decimal string
+    /// * `dec_to` - Upper bound (exclusive) as decimal string
+    pub fn add_dec_range_predicate(&mut self, attr_name: &str, dec_from: &str, dec_to: &str) -> Result<(), IndyCryptoError> {
+        self.add_value_range_predicate(attr_name, &BigNumber::from_dec(dec_from)?, &BigNumber::from_dec(dec_to)?)
+    }
+
+    pub fn add_value_range_predicate(&mut self, attr_name: &str, from: &BigNumber, to: &BigNumber) -> Result<(), IndyCryptoError> {
+        if from >= to {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid range predicate bounds: {:?} >= {:?}", from, to)));
+        }
+
+        let predicate = Predicate {
+            attr_name: attr_name.to_owned(),
+            p_type: PredicateType::BETWEEN,
+            value: from.to_dec()?,
+            upper_value: Some(to.to_dec()?)
+        };
+
+        self.value.predicates.insert(predicate);
+        Ok(())
+    }
+
+    /// Adds predicate that requires attribute value to be one of given values
+    /// without disclosing which one.
+    ///
+    /// # Arguments
+    /// * `attr_name` - Related attribute
+    /// * `dec_values` - Allowed encoded attribute values as decimal strings
+    pub fn add_set_membership_predicate(&mut self, attr_name: &str, dec_values: &[&str]) -> Result<(), IndyCryptoError> {
+        let mut values = Vec::new();
+        for dec_value in dec_values {
+            values.push(BigNumber::from_dec(dec_value)?);
+        }
+        self.add_value_set_membership_predicate(attr_name, &values)
+    }
+
+    pub fn add_value_set_membership_predicate(&mut self, attr_name: &str, values: &[BigNumber]) -> Result<(), IndyCryptoError> {
+        if values.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Set membership predicate for '{}' has no values", attr_name)));
+        }
+
+        let mut set = BTreeSet::new();
+        for value in values {
+            set.insert(value.to_dec()?);
+        }
+
+        let predicate = SetMembershipPredicate {
+            attr_name: attr_name.to_owned(),
+            values: set
+        };
+
+        self.value.set_predicates.insert(predicate);
+        Ok(())
+    }
+
+    /// Requests scope-exclusive pseudonym of hidden attribute (usually master secret).
+    ///
+    /// # Arguments
+    /// * `attr_name` - Hidden attribute pseudonym is derived from
+    /// * `scope` - Scope of pseudonym, e.g. identifier of relying party
+    pub fn add_pseudonym(&mut self, attr_name: &str, scope: &str) -> Result<(), IndyCryptoError> {
+        if self.value.pseudonym.is_some() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Pseudonym is already requested")));
+        }
+
+        self.value.pseudonym = Some(PseudonymRequest {
+            attr_name: attr_name.to_owned(),
+            scope: scope.to_owned()
+        });
+        Ok(())
+    }
+
     pub fn finalize(self) -> Result<SubProofRequest, IndyCryptoError> {
         Ok(self.value)
     }
 }
 
+/// Request of pseudonym of hidden attribute for the scope.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct PseudonymRequest {
+    attr_name: String,
+    scope: String,
+}
+
+/// Scope-exclusive pseudonym `nym = H(scope)^secret`.
+///
+/// Pseudonym is the same in all proofs of the same secret for the same scope, so relying party
+/// can use it as stable identifier of prover, and pseudonyms for different scopes are unlinkable.
+/// Proof shows that pseudonym is derived from the secret signed in the credential.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Pseudonym {
+    scope: String,
+    nym: PointG1,
+}
+
+impl Pseudonym {
+    pub fn scope(&self) -> &str {
+        &self.scope
+    }
+
+    pub fn nym(&self) -> Result<String, IndyCryptoError> {
+        self.nym.to_string()
+    }
+
+    // Returns `H(scope)` that is raised to secret
+    fn base(scope: &str) -> Result<PointG1, IndyCryptoError> {
+        PointG1::hash_to_curve(scope.as_bytes(), PSEUDONYM_DST)
+    }
+
+    // Appends scope, pseudonym and its `t` value to the tau list.
+    // Both are taken from the proof by verifier, so pseudonym can't be replaced in a valid proof.
+    fn append_tau_list<T: BytesView>(&self, tau_list: &mut TauList, t: &T) -> Result<(), IndyCryptoError> {
+        tau_list.push(PSEUDONYM_PROOF_LABEL, &self.scope.as_bytes().to_vec())?;
+        tau_list.push(PSEUDONYM_PROOF_LABEL, &self.nym)?;
+        tau_list.push(PSEUDONYM_PROOF_LABEL, t)
+    }
+}
+
 /// Some condition that must be satisfied.
+///
+/// Bounds are kept as decimal strings and converted to numbers when the proof is built.
+/// They are serialized as JSON numbers when they fit `i32` (the format used before arbitrary
+/// precision bounds were supported) and as decimal strings otherwise.
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
 pub struct Predicate {
     attr_name: String,
     p_type: PredicateType,
-    value: i32,
+    #[serde(with = "predicate_bound")]
+    value: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(with = "optional_predicate_bound")]
+    upper_value: Option<String>,
 }
 
 impl Predicate {
-    pub fn get_delta(&self, attr_value: i32) -> i32 {
+    /// Returns difference between attribute value and bound that must be non-negative
+    /// (for `NE` - non-zero, for `BETWEEN` - the lower bound).
+    pub fn get_delta(&self, attr_value: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+        let value = self._get_value()?;
         match self.p_type {
-            PredicateType::GE => attr_value - self.value,
-            PredicateType::GT => attr_value - self.value - 1,
-            PredicateType::LE => self.value - attr_value,
-            PredicateType::LT => self.value - attr_value - 1
+            PredicateType::GE | PredicateType::NE | PredicateType::BETWEEN => attr_value.sub(&value),
+            PredicateType::GT => attr_value.sub(&value)?.decrement(),
+            PredicateType::LE => value.sub(attr_value),
+            PredicateType::LT => value.sub(attr_value)?.decrement()
         }
     }
 
+    /// Returns difference between upper bound and attribute value of `BETWEEN` predicate
+    /// that must be non-negative.
+    pub fn get_upper_delta(&self, attr_value: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+        self._get_upper_value()?.decrement()?.sub(attr_value)
+    }
+
     pub fn get_delta_prime(&self) -> Result<BigNumber, IndyCryptoError> {
+        let value = self._get_value()?;
         match self.p_type {
-            PredicateType::GE => BigNumber::from_dec(&self.value.to_string()),
-            PredicateType::GT => BigNumber::from_dec(&(self.value + 1).to_string()),
-            PredicateType::LE => BigNumber::from_dec(&self.value.to_string()),
-            PredicateType::LT => BigNumber::from_dec(&(self.value - 1).to_string())
+            PredicateType::GE | PredicateType::NE | PredicateType::BETWEEN => Ok(value),
+            PredicateType::GT => value.increment(),
+            PredicateType::LE => Ok(value),
+            PredicateType::LT => value.decrement()
         }
     }
 
+    /// Returns width of `BETWEEN` predicate range: sum of lower and upper deltas.
+    pub fn get_range_width(&self) -> Result<BigNumber, IndyCryptoError> {
+        self._get_upper_value()?.decrement()?.sub(&self._get_value()?)
+    }
+
     pub fn is_less(&self) -> bool {
         match self.p_type {
-            PredicateType::GE | PredicateType::GT => false,
+            PredicateType::GE | PredicateType::GT | PredicateType::NE | PredicateType::BETWEEN => false,
             PredicateType::LE | PredicateType::LT => true
         }
     }
+
+    fn _get_value(&self) -> Result<BigNumber, IndyCryptoError> {
+        BigNumber::from_dec(&self.value)
+    }
+
+    fn _get_upper_value(&self) -> Result<BigNumber, IndyCryptoError> {
+        if let (&PredicateType::BETWEEN, &Some(ref upper_value)) = (&self.p_type, &self.upper_value) {
+            let upper_value = BigNumber::from_dec(upper_value)?;
+            if upper_value > self._get_value()? {
+                return Ok(upper_value);
+            }
+        }
+        Err(IndyCryptoError::InvalidStructure(format!("Predicate {:?} has no valid upper bound", self)))
+    }
+}
+
+mod predicate_bound {
+    use bn::BigNumber;
+    use serde::{Serializer, Deserialize, Deserializer};
+    use serde::ser::Error as SError;
+    use serde::de::Error as DError;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Bound {
+        Number(i64),
+        Dec(String)
+    }
+
+    pub fn serialize<S>(value: &String, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        match value.parse::<i32>() {
+            Ok(number) => serializer.serialize_i32(number),
+            Err(_) => serializer.serialize_str(value)
+        }
+    }
+
+    // Bound is normalized, so equal values are equal as strings
+    pub fn deserialize<'a, D>(deserializer: D) -> Result<String, D::Error> where D: Deserializer<'a> {
+        let dec = match Bound::deserialize(deserializer)? {
+            Bound::Number(number) => number.to_string(),
+            Bound::Dec(dec) => dec
+        };
+        BigNumber::from_dec(&dec)
+            .and_then(|value| value.to_dec())
+            .map_err(DError::custom)
+    }
+}
+
+mod optional_predicate_bound {
+    use serde::{Serializer, Deserializer};
+
+    pub fn serialize<S>(value: &Option<String>, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        match *value {
+            Some(ref value) => super::predicate_bound::serialize(value, serializer),
+            None => serializer.serialize_none()
+        }
+    }
+
+    pub fn deserialize<'a, D>(deserializer: D) -> Result<Option<String>, D::Error> where D: Deserializer<'a> {
+        super::predicate_bound::deserialize(deserializer).map(Some)
+    }
 }
 
 /// Condition type
@@ -816,7 +1369,363 @@ pub enum PredicateType {
     GE,
     LE,
     GT,
-    LT
+    LT,
+    /// Attribute is not equal to value
+    NE,
+    /// Attribute is in range `[value, upper_value)`
+    BETWEEN
+}
+
+/// Condition that attribute value is one of the listed values (kept as decimal strings).
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct SetMembershipPredicate {
+    attr_name: String,
+    values: BTreeSet<String>,
+}
+
+impl SetMembershipPredicate {
+    fn get_values(&self) -> Result<Vec<BigNumber>, IndyCryptoError> {
+        self.values.iter().map(|value| BigNumber::from_dec(value)).collect()
+    }
+}
+
+/// Reference to attribute of a sub proof by position of the sub proof in the proof.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct AttributeReference {
+    sub_proof_index: usize,
+    attr_name: String,
+}
+
+/// Requirement that hidden attributes of sub proofs (usually of different credentials) have equal values
+/// without revealing them.
+///
+/// Prover and verifier must both know the requirement: prover shares blinding of both attributes
+/// and verifier checks that their responses in equality proofs are the same.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct AttributeEquality {
+    first: AttributeReference,
+    second: AttributeReference,
+}
+
+impl AttributeEquality {
+    pub fn new(first_sub_proof_index: usize,
+               first_attr_name: &str,
+               second_sub_proof_index: usize,
+               second_attr_name: &str) -> Result<AttributeEquality, IndyCryptoError> {
+        if first_sub_proof_index == second_sub_proof_index && first_attr_name == second_attr_name {
+            return Err(IndyCryptoError::InvalidStructure(format!("Attribute equality must refer two different attributes")));
+        }
+
+        Ok(AttributeEquality {
+            first: AttributeReference { sub_proof_index: first_sub_proof_index, attr_name: first_attr_name.to_owned() },
+            second: AttributeReference { sub_proof_index: second_sub_proof_index, attr_name: second_attr_name.to_owned() }
+        })
+    }
+
+    // Returns pairs of (attribute of sub proof, attribute it must be equal to) for both directions
+    fn as_pairs(&self) -> [(&AttributeReference, &AttributeReference); 2] {
+        [(&self.first, &self.second), (&self.second, &self.first)]
+    }
+}
+
+/// Term `coefficient * attribute` of linear predicate.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct LinearTerm {
+    attr: AttributeReference,
+    #[serde(with = "predicate_bound")]
+    coefficient: String,
+}
+
+/// Condition `sum(coefficient * attribute) p_type value` over hidden attributes
+/// of (possibly) different sub proofs, e.g. `A.expiry - B.issue_date GT 0` or `income - 3 * rent GT 0`.
+///
+/// Prover commits to the combination in the group of the public key of the first term's credential
+/// and proves it satisfies the bound the same way as for `Predicate`; the commitment is linked
+/// to the attributes through their responses in equality proofs.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct LinearPredicate {
+    terms: Vec<LinearTerm>,
+    p_type: PredicateType,
+    #[serde(with = "predicate_bound")]
+    value: String,
+}
+
+impl LinearPredicate {
+    fn validate(&self) -> Result<(), IndyCryptoError> {
+        if self.terms.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Linear predicate has no terms")));
+        }
+
+        if self.terms.iter().any(|term| term.coefficient == "0") {
+            return Err(IndyCryptoError::InvalidStructure(format!("Linear predicate has term with zero coefficient")));
+        }
+
+        match self.p_type {
+            PredicateType::GE | PredicateType::LE | PredicateType::GT | PredicateType::LT => Ok(()),
+            ref p_type => Err(IndyCryptoError::InvalidStructure(format!("Predicate type {:?} is not supported by linear predicate", p_type)))
+        }
+    }
+
+    // Attribute which credential public key is used for commitments
+    fn key_attr(&self) -> &AttributeReference {
+        &self.terms[0].attr
+    }
+
+    // Predicate on the linear combination treated as a single attribute
+    fn as_predicate(&self) -> Result<Predicate, IndyCryptoError> {
+        Ok(Predicate {
+            attr_name: String::new(),
+            p_type: self.p_type.clone(),
+            value: self.value.clone(),
+            upper_value: None
+        })
+    }
+
+    // Returns sum of coefficients multiplied by values of referred attributes
+    fn combine<'a, F>(&self, mut attr_value: F) -> Result<BigNumber, IndyCryptoError>
+        where F: FnMut(&AttributeReference) -> Result<&'a BigNumber, IndyCryptoError> {
+        let mut ctx = BigNumber::new_context()?;
+        let mut sum = BigNumber::new()?;
+
+        for term in self.terms.iter() {
+            sum = BigNumber::from_dec(&term.coefficient)?
+                .mul(attr_value(&term.attr)?, Some(&mut ctx))?
+                .add(&sum)?;
+        }
+
+        Ok(sum)
+    }
+}
+
+/// Builder of linear predicate.
+#[derive(Debug)]
+pub struct LinearPredicateBuilder {
+    value: LinearPredicate
+}
+
+impl LinearPredicateBuilder {
+    pub fn new() -> Result<LinearPredicateBuilder, IndyCryptoError> {
+        Ok(LinearPredicateBuilder {
+            value: LinearPredicate {
+                terms: Vec::new(),
+                p_type: PredicateType::GE,
+                value: "0".to_owned()
+            }
+        })
+    }
+
+    /// Adds term `coefficient * attribute`.
+    ///
+    /// # Arguments
+    /// * `sub_proof_index` - Position of sub proof the attribute belongs to
+    /// * `attr_name` - Hidden attribute of the sub proof
+    /// * `coefficient` - Non-zero coefficient (negative for subtracted attributes)
+    pub fn add_term(&mut self, sub_proof_index: usize, attr_name: &str, coefficient: i32) -> Result<(), IndyCryptoError> {
+        self.add_value_term(sub_proof_index, attr_name, &BigNumber::from_dec(&coefficient.to_string())?)
+    }
+
+    pub fn add_value_term(&mut self, sub_proof_index: usize, attr_name: &str, coefficient: &BigNumber) -> Result<(), IndyCryptoError> {
+        self.value.terms.push(LinearTerm {
+            attr: AttributeReference { sub_proof_index, attr_name: attr_name.to_owned() },
+            coefficient: coefficient.to_dec()?
+        });
+        Ok(())
+    }
+
+    /// Sets condition the sum of terms must satisfy, `GE 0` by default.
+    ///
+    /// # Arguments
+    /// * `p_type` - Predicate type (`GE`, `LE`, `GT` or `LT`)
+    /// * `value` - Bound
+    pub fn set_predicate(&mut self, p_type: &str, value: i32) -> Result<(), IndyCryptoError> {
+        self.set_value_predicate(p_type, &BigNumber::from_dec(&value.to_string())?)
+    }
+
+    pub fn set_value_predicate(&mut self, p_type: &str, value: &BigNumber) -> Result<(), IndyCryptoError> {
+        self.value.p_type = match p_type {
+            "GE" => PredicateType::GE,
+            "LE" => PredicateType::LE,
+            "GT" => PredicateType::GT,
+            "LT" => PredicateType::LT,
+            p_type => return Err(IndyCryptoError::InvalidStructure(format!("Invalid linear predicate type: {:?}", p_type)))
+        };
+        self.value.value = value.to_dec()?;
+        Ok(())
+    }
+
+    pub fn finalize(self) -> Result<LinearPredicate, IndyCryptoError> {
+        self.value.validate()?;
+        Ok(self.value)
+    }
+}
+
+/// Public key of auditor (escrow authority) for Camenisch-Shoup verifiable encryption:
+/// `n` is RSA modulus of two safe primes, `g` generates subgroup of `2n`-th powers modulo `n^2`,
+/// `y1`, `y2`, `y3` are `g` raised to the private key components.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AuditorPublicKey {
+    n: BigNumber,
+    g: BigNumber,
+    y1: BigNumber,
+    y2: BigNumber,
+    y3: BigNumber,
+}
+
+impl AuditorPublicKey {
+    pub fn try_clone(&self) -> Result<AuditorPublicKey, IndyCryptoError> {
+        Ok(AuditorPublicKey {
+            n: self.n.clone()?,
+            g: self.g.clone()?,
+            y1: self.y1.clone()?,
+            y2: self.y2.clone()?,
+            y3: self.y3.clone()?
+        })
+    }
+
+    // Encrypts `m mod n` under the label and returns ciphertext with the randomness used for it
+    fn encrypt(&self, m: &BigNumber, label: &str) -> Result<(VerifiableCiphertext, BigNumber), IndyCryptoError> {
+        let mut ctx = BigNumber::new_context()?;
+        let n_square = self.n_square()?;
+
+        let r = bn_rand_range(&self.n.rshift(2)?)?;
+        let m = m.modulus(&self.n, Some(&mut ctx))?;
+
+        let u = self.g.mod_exp(&r, &n_square, Some(&mut ctx))?;
+        let e = self.y1.mod_exp(&r, &n_square, Some(&mut ctx))?
+            .mod_mul(&self.h()?.mod_exp(&m, &n_square, Some(&mut ctx))?, &n_square, Some(&mut ctx))?;
+        let v = self.abs(&self.v_base(&u, &e, label)?.mod_exp(&r, &n_square, Some(&mut ctx))?)?;
+
+        Ok((VerifiableCiphertext { u, e, v, label: label.to_owned() }, r))
+    }
+
+    // Returns `(g^2r, y1^2r * h^2m, v_base^2r)` multiplied by `(u, e, v)^-2c`:
+    // for prover (`c = 0`) it commits to blindings, verifier recomputes it from responses
+    fn calc_tau_list(&self,
+                     ciphertext: &VerifiableCiphertext,
+                     r: &BigNumber,
+                     m: &BigNumber,
+                     c: &BigNumber) -> Result<Vec<BigNumber>, IndyCryptoError> {
+        let mut ctx = BigNumber::new_context()?;
+        let n_square = self.n_square()?;
+
+        let two_r = r.lshift1()?;
+        let two_m = m.lshift1()?;
+        let minus_two_c = c.lshift1()?.set_negative(true)?;
+
+        let t_u = self.g.mod_exp(&two_r, &n_square, Some(&mut ctx))?
+            .mod_mul(&ciphertext.u.mod_exp(&minus_two_c, &n_square, Some(&mut ctx))?, &n_square, Some(&mut ctx))?;
+
+        let t_e = self.y1.mod_exp(&two_r, &n_square, Some(&mut ctx))?
+            .mod_mul(&self.h()?.mod_exp(&two_m, &n_square, Some(&mut ctx))?, &n_square, Some(&mut ctx))?
+            .mod_mul(&ciphertext.e.mod_exp(&minus_two_c, &n_square, Some(&mut ctx))?, &n_square, Some(&mut ctx))?;
+
+        let t_v = self.v_base(&ciphertext.u, &ciphertext.e, &ciphertext.label)?
+            .mod_exp(&two_r, &n_square, Some(&mut ctx))?
+            .mod_mul(&ciphertext.v.mod_exp(&minus_two_c, &n_square, Some(&mut ctx))?, &n_square, Some(&mut ctx))?;
+
+        Ok(vec![t_u, t_e, t_v])
+    }
+
+    fn n_square(&self) -> Result<BigNumber, IndyCryptoError> {
+        self.n.sqr(None)
+    }
+
+    // `h = n + 1` generates subgroup of order `n`, so `h^m` encodes `m mod n`
+    fn h(&self) -> Result<BigNumber, IndyCryptoError> {
+        self.n.increment()
+    }
+
+    // Returns `y2 * y3^H(u, e, label)` that is raised to randomness to get `v`
+    fn v_base(&self, u: &BigNumber, e: &BigNumber, label: &str) -> Result<BigNumber, IndyCryptoError> {
+        let mut ctx = BigNumber::new_context()?;
+        let n_square = self.n_square()?;
+
+        self.y3.mod_exp(&AuditorPublicKey::v_hash(u, e, label)?, &n_square, Some(&mut ctx))?
+            .mod_mul(&self.y2, &n_square, Some(&mut ctx))
+    }
+
+    // Returns `H(u, e, label)` with every value appended to transcript under its own label
+    fn v_hash(u: &BigNumber, e: &BigNumber, label: &str) -> Result<BigNumber, IndyCryptoError> {
+        let mut transcript = Transcript::new(VERIFIABLE_ENCRYPTION_TRANSCRIPT_DOMAIN);
+
+        transcript.append_message(b"u", &u.to_bytes()?);
+        transcript.append_message(b"e", &e.to_bytes()?);
+        transcript.append_message(b"label", label.as_bytes());
+
+        transcript.challenge(b"v_hash")
+    }
+
+    // Returns `v` or `n^2 - v`, whichever is not greater than `n^2 / 2`
+    fn abs(&self, v: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+        let n_square = self.n_square()?;
+
+        if v.lshift1()? > n_square {
+            n_square.sub(v)
+        } else {
+            v.clone()
+        }
+    }
+}
+
+/// Private key of auditor, allows to decrypt verifiably encrypted attributes.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AuditorPrivateKey {
+    x1: BigNumber,
+    x2: BigNumber,
+    x3: BigNumber,
+}
+
+/// Camenisch-Shoup ciphertext `(u, e, v)` of attribute value with the label bound to it.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct VerifiableCiphertext {
+    u: BigNumber,
+    e: BigNumber,
+    v: BigNumber,
+    label: String,
+}
+
+impl VerifiableCiphertext {
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn as_list(&self) -> Result<Vec<BigNumber>, IndyCryptoError> {
+        Ok(vec![self.u.clone()?, self.e.clone()?, self.v.clone()?])
+    }
+}
+
+/// Requirement that hidden attribute of sub proof is verifiably encrypted to auditor,
+/// so auditor can recover the attribute value later (e.g. in case of dispute).
+///
+/// Label is bound to the ciphertext and usually describes conditions of decryption.
+/// Proof shows that ciphertext contains the value signed in the credential;
+/// attribute response in equality proof links ciphertext to the signature.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct VerifiableEncryption {
+    attr: AttributeReference,
+    auditor_pub_key: AuditorPublicKey,
+    label: String,
+}
+
+impl VerifiableEncryption {
+    pub fn new(sub_proof_index: usize,
+               attr_name: &str,
+               auditor_pub_key: &AuditorPublicKey,
+               label: &str) -> Result<VerifiableEncryption, IndyCryptoError> {
+        Ok(VerifiableEncryption {
+            attr: AttributeReference { sub_proof_index, attr_name: attr_name.to_owned() },
+            auditor_pub_key: auditor_pub_key.try_clone()?,
+            label: label.to_owned()
+        })
+    }
+
+    pub fn try_clone(&self) -> Result<VerifiableEncryption, IndyCryptoError> {
+        Ok(VerifiableEncryption {
+            attr: self.attr.clone(),
+            auditor_pub_key: self.auditor_pub_key.try_clone()?,
+            label: self.label.clone()
+        })
+    }
 }
 
 /// Proof is complex crypto structure created by prover over multiple credentials that allows to prove that prover:
@@ -825,14 +1734,32 @@ pub enum PredicateType {
 /// 3) Credential contains attributes with valid predicates that verifier wants the prover to satisfy.
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Proof {
+    #[serde(default = "legacy_proof_version")]
+    version: u32,
     proofs: Vec<SubProof>,
     aggregated_proof: AggregatedProof,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    linear_proofs: Vec<PrimaryLinearPredicateProof>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    encryption_proofs: Vec<VerifiableEncryptionProof>,
+}
+
+impl Proof {
+    /// Returns ciphertexts of verifiably encrypted attributes in the order encryptions were requested in.
+    pub fn get_verifiable_ciphertexts(&self) -> Vec<&VerifiableCiphertext> {
+        self.encryption_proofs.iter().map(|encryption_proof| &encryption_proof.ciphertext).collect()
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SubProof {
     primary_proof: PrimaryProof,
-    non_revoc_proof: Option<NonRevocProof>
+    non_revoc_proof: Option<NonRevocProof>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pseudonym: Option<Pseudonym>
 }
 
 #[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
@@ -844,7 +1771,24 @@ pub struct AggregatedProof {
 #[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct PrimaryProof {
     eq_proof: PrimaryEqualProof,
-    ne_proofs: Vec<PrimaryPredicateInequalityProof>
+    ne_proofs: Vec<PrimaryPredicateInequalityProof>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    set_proofs: Vec<PrimarySetMembershipProof>
+}
+
+impl PrimaryProof {
+    // Appends c list values of the proof in the same order and with the same labels as `PrimaryInitProof::append_c_list`
+    fn append_c_list(&self, c_list: &mut TauList) -> Result<(), IndyCryptoError> {
+        c_list.push(PRIMARY_EQUAL_PROOF_LABEL, &self.eq_proof.a_prime)?;
+        for ne_proof in self.ne_proofs.iter() {
+            ne_proof.append_c_list(PRIMARY_PREDICATE_PROOF_LABEL, c_list)?;
+        }
+        for set_proof in self.set_proofs.iter() {
+            c_list.push(SET_MEMBERSHIP_PROOF_LABEL, &set_proof.t)?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Serialize)]
@@ -893,7 +1837,68 @@ pub struct PrimaryPredicateInequalityProof {
     mj: BigNumber,
     alpha: BigNumber,
     t: HashMap<String, BigNumber>,
-    predicate: Predicate
+    predicate: Predicate,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    upper_alpha: Option<BigNumber>
+}
+
+impl PrimaryPredicateInequalityProof {
+    // Appends commitments `t` in the order prover creates them: commitments to squares of delta, `DELTA`,
+    // commitments to squares of upper delta (BETWEEN predicate only) and `SQUARE` (NE predicate only)
+    fn append_c_list(&self, label: &'static [u8], c_list: &mut TauList) -> Result<(), IndyCryptoError> {
+        let squares = if self.predicate.p_type == PredicateType::BETWEEN { 2 } else { 1 };
+
+        let mut keys = Vec::new();
+        for idx in 0..squares {
+            for i in 0..ITERATION {
+                keys.push((idx * ITERATION + i).to_string());
+            }
+            if idx == 0 {
+                keys.push("DELTA".to_string());
+            }
+        }
+        if self.predicate.p_type == PredicateType::NE {
+            keys.push("SQUARE".to_string());
+        }
+
+        for key in keys.iter() {
+            let t = self.t.get(key)
+                .ok_or(IndyCryptoError::AnoncredsProofRejected(format!("Value by key '{}' not found in predicate proof", key)))?;
+            c_list.push(label, t)?;
+        }
+        Ok(())
+    }
+}
+
+/// Proof that committed attribute `t = Z^m S^r` is one of predicate values.
+///
+/// Contains OR-composition of proofs of knowledge of `r` such that `t / Z^value = S^r`,
+/// challenges `c` of all branches sum up to the proof challenge modulo `2^LARGE_CHALLENGE`.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PrimarySetMembershipProof {
+    predicate: SetMembershipPredicate,
+    t: BigNumber,
+    r: BigNumber,
+    c: Vec<BigNumber>,
+    z: Vec<BigNumber>
+}
+
+/// Proof of linear predicate: inequality proof for the committed linear combination of attributes,
+/// where `mj` is the combination of the attributes responses in equality proofs.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PrimaryLinearPredicateProof {
+    predicate: LinearPredicate,
+    ne_proof: PrimaryPredicateInequalityProof
+}
+
+/// Proof that ciphertext contains hidden attribute: `r_hat` is response for encryption randomness,
+/// response for the attribute is taken from equality proof of the sub proof.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct VerifiableEncryptionProof {
+    attr: AttributeReference,
+    ciphertext: VerifiableCiphertext,
+    r_hat: BigNumber
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -906,6 +1911,8 @@ pub struct NonRevocProof {
 pub struct InitProof {
     primary_init_proof: PrimaryInitProof,
     non_revoc_init_proof: Option<NonRevocInitProof>,
+    p_pub_key: CredentialPrimaryPublicKey,
+    pseudonym: Option<Pseudonym>,
     credential_values: CredentialValues,
     sub_proof_request: SubProofRequest,
     credential_schema: CredentialSchema,
@@ -916,24 +1923,31 @@ pub struct InitProof {
 #[derive(Debug, Eq, PartialEq)]
 pub struct PrimaryInitProof {
     eq_proof: PrimaryEqualInitProof,
-    ne_proofs: Vec<PrimaryPredicateInequalityInitProof>
+    ne_proofs: Vec<PrimaryPredicateInequalityInitProof>,
+    set_proofs: Vec<PrimarySetMembershipInitProof>
 }
 
 impl PrimaryInitProof {
-    pub fn as_c_list(&self) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
-        let mut c_list: Vec<Vec<u8>> = self.eq_proof.as_list()?;
+    fn append_c_list(&self, c_list: &mut TauList) -> Result<(), IndyCryptoError> {
+        c_list.append_vec(PRIMARY_EQUAL_PROOF_LABEL, &self.eq_proof.as_list()?)?;
         for ne_proof in self.ne_proofs.iter() {
-            c_list.append_vec(ne_proof.as_list()?)?;
+            c_list.append_vec(PRIMARY_PREDICATE_PROOF_LABEL, ne_proof.as_list()?)?;
         }
-        Ok(c_list)
+        for set_proof in self.set_proofs.iter() {
+            c_list.append_vec(SET_MEMBERSHIP_PROOF_LABEL, set_proof.as_list()?)?;
+        }
+        Ok(())
     }
 
-    pub fn as_tau_list(&self) -> Result<Vec<Vec<u8>>, IndyCryptoError> {
-        let mut tau_list: Vec<Vec<u8>> = self.eq_proof.as_tau_list()?;
+    fn append_tau_list(&self, tau_list: &mut TauList) -> Result<(), IndyCryptoError> {
+        tau_list.append_vec(PRIMARY_EQUAL_PROOF_LABEL, &self.eq_proof.as_tau_list()?)?;
         for ne_proof in self.ne_proofs.iter() {
-            tau_list.append_vec(ne_proof.as_tau_list()?)?;
+            tau_list.append_vec(PRIMARY_PREDICATE_PROOF_LABEL, ne_proof.as_tau_list()?)?;
+        }
+        for set_proof in self.set_proofs.iter() {
+            tau_list.append_vec(SET_MEMBERSHIP_PROOF_LABEL, set_proof.as_tau_list()?)?;
         }
-        Ok(tau_list)
+        Ok(())
     }
 }
 
@@ -991,6 +2005,7 @@ pub struct PrimaryPredicateInequalityInitProof {
     alpha_tilde: BigNumber,
     predicate: Predicate,
     t: HashMap<String, BigNumber>,
+    upper_alpha_tilde: Option<BigNumber>,
 }
 
 impl PrimaryPredicateInequalityInitProof {
@@ -1003,6 +2018,48 @@ impl PrimaryPredicateInequalityInitProof {
     }
 }
 
+#[derive(Debug, Eq, PartialEq)]
+pub struct PrimarySetMembershipInitProof {
+    c_list: Vec<BigNumber>,
+    tau_list: Vec<BigNumber>,
+    predicate: SetMembershipPredicate,
+    t: BigNumber,
+    index: usize,
+    r: BigNumber,
+    r_tilde: BigNumber,
+    rho_tilde: BigNumber,
+    c: Vec<BigNumber>,
+    z: Vec<BigNumber>
+}
+
+impl PrimarySetMembershipInitProof {
+    pub fn as_list(&self) -> Result<&Vec<BigNumber>, IndyCryptoError> {
+        Ok(&self.c_list)
+    }
+
+    pub fn as_tau_list(&self) -> Result<&Vec<BigNumber>, IndyCryptoError> {
+        Ok(&self.tau_list)
+    }
+}
+
+#[derive(Debug)]
+pub struct VerifiableEncryptionInitProof {
+    ciphertext: VerifiableCiphertext,
+    r: BigNumber,
+    r_tilde: BigNumber,
+    tau_list: Vec<BigNumber>,
+}
+
+impl VerifiableEncryptionInitProof {
+    pub fn as_list(&self) -> Result<Vec<BigNumber>, IndyCryptoError> {
+        self.ciphertext.as_list()
+    }
+
+    pub fn as_tau_list(&self) -> Result<&Vec<BigNumber>, IndyCryptoError> {
+        Ok(&self.tau_list)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct NonRevocProofXList {
     rho: GroupOrderElement,
@@ -1154,17 +2211,47 @@ impl BytesView for Pair {
     }
 }
 
-trait AppendByteArray {
-    fn append_vec<T: BytesView>(&mut self, other: &Vec<T>) -> Result<(), IndyCryptoError>;
+impl BytesView for Vec<u8> {
+    fn to_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        Ok(self.clone())
+    }
+}
+
+// Tau (or c list) values of the proof with labels of proof parts they belong to
+#[derive(Debug, Clone)]
+struct TauList {
+    values: Vec<(&'static [u8], Vec<u8>)>
 }
 
-impl AppendByteArray for Vec<Vec<u8>> {
-    fn append_vec<T: BytesView>(&mut self, other: &Vec<T>) -> Result<(), IndyCryptoError> {
+impl TauList {
+    fn new() -> TauList {
+        TauList { values: Vec::new() }
+    }
+
+    fn push<T: BytesView>(&mut self, label: &'static [u8], value: &T) -> Result<(), IndyCryptoError> {
+        self.values.push((label, value.to_bytes()?));
+        Ok(())
+    }
+
+    fn append_vec<T: BytesView>(&mut self, label: &'static [u8], other: &Vec<T>) -> Result<(), IndyCryptoError> {
         for el in other.iter() {
-            self.push(el.to_bytes()?);
+            self.push(label, el)?;
         }
         Ok(())
     }
+
+    fn append(&mut self, other: &mut TauList) {
+        self.values.append(&mut other.values);
+    }
+
+    // Returns values without labels
+    fn flatten(&self) -> Vec<Vec<u8>> {
+        self.values.iter().map(|&(_, ref value)| value.clone()).collect()
+    }
+}
+
+fn legacy_proof_version() -> u32 {
+    LEGACY_PROOF_VERSION
 }
 
 fn clone_bignum_map<K: Clone + Eq + Hash>(other: &HashMap<K, BigNumber>) -> Result<HashMap<K, BigNumber>, IndyCryptoError> {
@@ -1176,6 +2263,89 @@ fn clone_bignum_map<K: Clone + Eq + Hash>(other: &HashMap<K, BigNumber>) -> Resu
 }
 
 
+// Returns proof challenge of given proof version.
+//
+// Legacy proofs hash plain concatenation of tau list, c list, nonce and (optional) message
+// prefixed with domain separation tag. Current proofs append the same values with labels
+// to the transcript, so values of different proof parts can't be confused.
+fn get_proof_challenge(version: u32,
+                       tau_list: &TauList,
+                       c_list: &TauList,
+                       nonce: &Nonce,
+                       message: Option<&[u8]>) -> Result<BigNumber, IndyCryptoError> {
+    match version {
+        LEGACY_PROOF_VERSION => {
+            let mut values: Vec<Vec<u8>> = Vec::new();
+            values.extend(tau_list.flatten());
+            values.extend(c_list.flatten());
+            values.push(nonce.to_bytes()?);
+
+            if let Some(message) = message {
+                values.push(PROOF_MESSAGE_DST.to_vec());
+                values.push(BigNumber::hash(message)?);
+            }
+
+            get_hash_as_int(&values)
+        }
+        CURRENT_PROOF_VERSION => {
+            let mut transcript = Transcript::new(PROOF_TRANSCRIPT_DOMAIN);
+
+            transcript.append_list(b"tau_list", &tau_list.values);
+            transcript.append_list(b"c_list", &c_list.values);
+            transcript.append_message(b"nonce", &nonce.to_bytes()?);
+
+            if let Some(message) = message {
+                transcript.append_message(b"message", message);
+            }
+
+            transcript.challenge(b"challenge")
+        }
+        _ => Err(IndyCryptoError::InvalidStructure(format!("Unsupported proof version {}", version)))
+    }
+}
+
+// Returns challenge of blinded credential secrets correctness proof of given proof version.
+// `committed_attributes` contains name, tilde (or cap) value and commitment of every committed attribute.
+fn get_blinded_credential_secrets_challenge(version: u32,
+                                            committed_attributes: &[(&str, BigNumber, &BigNumber)],
+                                            u: &BigNumber,
+                                            u_tilde: &BigNumber,
+                                            nonce: &Nonce) -> Result<BigNumber, IndyCryptoError> {
+    match version {
+        LEGACY_PROOF_VERSION => {
+            let mut values: Vec<u8> = Vec::new();
+
+            for &(_, ref commitment_tilde, commitment) in committed_attributes.iter() {
+                values.extend_from_slice(&commitment_tilde.to_bytes()?);
+                values.extend_from_slice(&commitment.to_bytes()?);
+            }
+
+            values.extend_from_slice(&u.to_bytes()?);
+            values.extend_from_slice(&u_tilde.to_bytes()?);
+            values.extend_from_slice(&nonce.to_bytes()?);
+
+            get_hash_as_int(&vec![values])
+        }
+        CURRENT_PROOF_VERSION => {
+            let mut transcript = Transcript::new(BLINDED_CREDENTIAL_SECRETS_TRANSCRIPT_DOMAIN);
+
+            transcript.append_u64(b"committed_attributes", committed_attributes.len() as u64);
+            for &(attr, ref commitment_tilde, commitment) in committed_attributes.iter() {
+                transcript.append_message(b"committed_attribute_name", attr.as_bytes());
+                transcript.append_message(b"committed_attribute_tilde", &commitment_tilde.to_bytes()?);
+                transcript.append_message(b"committed_attribute", &commitment.to_bytes()?);
+            }
+
+            transcript.append_message(b"u", &u.to_bytes()?);
+            transcript.append_message(b"u_tilde", &u_tilde.to_bytes()?);
+            transcript.append_message(b"nonce", &nonce.to_bytes()?);
+
+            transcript.challenge(b"challenge")
+        }
+        _ => Err(IndyCryptoError::InvalidStructure(format!("Unsupported proof version {}", version)))
+    }
+}
+
 fn clone_credential_value_map<K: Clone + Eq + Ord>(other: &BTreeMap<K, CredentialValue>) -> Result<BTreeMap<K, CredentialValue>, IndyCryptoError> {
     let mut res = BTreeMap::new();
     for (k, v) in other {
@@ -1374,6 +2544,217 @@ mod test {
     }
 
 
+    #[test]
+    fn predicate_serialization_works() {
+        let predicate = Predicate { attr_name: "age".to_owned(), p_type: PredicateType::GE, value: "18".to_owned(), upper_value: None };
+        let json = serde_json::to_string(&predicate).unwrap();
+        assert_eq!(r#"{"attr_name":"age","p_type":"GE","value":18}"#, json);
+
+        let predicate = Predicate {
+            attr_name: "age".to_owned(),
+            p_type: PredicateType::BETWEEN,
+            value: "18".to_owned(),
+            upper_value: Some("65".to_owned())
+        };
+        let json = serde_json::to_string(&predicate).unwrap();
+        assert_eq!(r#"{"attr_name":"age","p_type":"BETWEEN","value":18,"upper_value":65}"#, json);
+        assert_eq!(predicate, serde_json::from_str::<Predicate>(&json).unwrap());
+    }
+
+    #[test]
+    fn predicate_serialization_works_for_big_and_negative_values() {
+        let predicate = Predicate {
+            attr_name: "timestamp".to_owned(),
+            p_type: PredicateType::BETWEEN,
+            value: "-5".to_owned(),
+            upper_value: Some("1700000000000".to_owned())
+        };
+        let json = serde_json::to_string(&predicate).unwrap();
+        assert_eq!(r#"{"attr_name":"timestamp","p_type":"BETWEEN","value":-5,"upper_value":"1700000000000"}"#, json);
+        assert_eq!(predicate, serde_json::from_str::<Predicate>(&json).unwrap());
+
+        let predicate = serde_json::from_str::<Predicate>(r#"{"attr_name":"timestamp","p_type":"GE","value":"-99999999999999999999"}"#).unwrap();
+        assert_eq!("-99999999999999999999", predicate.value);
+
+        let predicate = serde_json::from_str::<Predicate>(r#"{"attr_name":"timestamp","p_type":"GE","value":1700000000000}"#).unwrap();
+        assert_eq!("1700000000000", predicate.value);
+    }
+
+    #[test]
+    fn predicate_deltas_work() {
+        let predicate = Predicate {
+            attr_name: "age".to_owned(),
+            p_type: PredicateType::BETWEEN,
+            value: "18".to_owned(),
+            upper_value: Some("65".to_owned())
+        };
+        let age = BigNumber::from_u32(28).unwrap();
+        assert_eq!(BigNumber::from_u32(10).unwrap(), predicate.get_delta(&age).unwrap());
+        assert_eq!(BigNumber::from_u32(36).unwrap(), predicate.get_upper_delta(&age).unwrap());
+        assert_eq!(BigNumber::from_u32(46).unwrap(), predicate.get_range_width().unwrap());
+
+        let predicate = Predicate { attr_name: "age".to_owned(), p_type: PredicateType::NE, value: "30".to_owned(), upper_value: None };
+        assert_eq!(BigNumber::from_dec("-2").unwrap(), predicate.get_delta(&age).unwrap());
+        assert!(predicate.get_upper_delta(&age).is_err());
+
+        let predicate = Predicate { attr_name: "balance".to_owned(), p_type: PredicateType::LT, value: "-10".to_owned(), upper_value: None };
+        assert_eq!(BigNumber::from_u32(4).unwrap(), predicate.get_delta(&BigNumber::from_dec("-15").unwrap()).unwrap());
+        assert_eq!(BigNumber::from_dec("-11").unwrap(), predicate.get_delta_prime().unwrap());
+    }
+
+    #[test]
+    fn auditor_v_hash_works_for_ambiguous_concatenation() {
+        // Bytes of `u` and `e` are the same when concatenated: [1, 2, 3]
+        let hash = AuditorPublicKey::v_hash(&BigNumber::from_u32(1).unwrap(), &BigNumber::from_u32(515).unwrap(), "label").unwrap();
+        assert_ne!(hash, AuditorPublicKey::v_hash(&BigNumber::from_u32(258).unwrap(), &BigNumber::from_u32(3).unwrap(), "label").unwrap());
+        assert_ne!(hash, AuditorPublicKey::v_hash(&BigNumber::from_u32(1).unwrap(), &BigNumber::from_u32(2).unwrap(), "\u{3}label").unwrap());
+    }
+
+    #[test]
+    fn get_proof_challenge_works_for_message() {
+        let mut tau_list = TauList::new();
+        tau_list.push(PRIMARY_EQUAL_PROOF_LABEL, &vec![1u8, 2, 3]).unwrap();
+        let mut c_list = TauList::new();
+        c_list.push(PRIMARY_EQUAL_PROOF_LABEL, &vec![4u8, 5, 6]).unwrap();
+        let nonce = BigNumber::from_dec("1164046393264787986302355").unwrap();
+
+        for &version in [LEGACY_PROOF_VERSION, CURRENT_PROOF_VERSION].iter() {
+            let challenge = get_proof_challenge(version, &tau_list, &c_list, &nonce, None).unwrap();
+            let empty_message_challenge = get_proof_challenge(version, &tau_list, &c_list, &nonce, Some(b"")).unwrap();
+            let message_challenge = get_proof_challenge(version, &tau_list, &c_list, &nonce, Some(b"message")).unwrap();
+
+            assert_ne!(challenge, empty_message_challenge);
+            assert_ne!(challenge, message_challenge);
+            assert_ne!(empty_message_challenge, message_challenge);
+            assert_eq!(message_challenge, get_proof_challenge(version, &tau_list, &c_list, &nonce, Some(b"message")).unwrap());
+        }
+    }
+
+    #[test]
+    fn get_proof_challenge_works_for_versions() {
+        let nonce = BigNumber::from_dec("1164046393264787986302355").unwrap();
+        let empty_c_list = TauList::new();
+
+        let mut tau_list = TauList::new();
+        tau_list.push(PRIMARY_EQUAL_PROOF_LABEL, &vec![1u8, 2]).unwrap();
+        tau_list.push(PRIMARY_PREDICATE_PROOF_LABEL, &vec![3u8]).unwrap();
+
+        let mut other_tau_list = TauList::new();
+        other_tau_list.push(PRIMARY_EQUAL_PROOF_LABEL, &vec![1u8]).unwrap();
+        other_tau_list.push(PRIMARY_PREDICATE_PROOF_LABEL, &vec![2u8, 3]).unwrap();
+
+        // Legacy challenge doesn't separate values, so it is the same for both tau lists
+        assert_eq!(get_proof_challenge(LEGACY_PROOF_VERSION, &tau_list, &empty_c_list, &nonce, None).unwrap(),
+                   get_proof_challenge(LEGACY_PROOF_VERSION, &other_tau_list, &empty_c_list, &nonce, None).unwrap());
+        assert_ne!(get_proof_challenge(CURRENT_PROOF_VERSION, &tau_list, &empty_c_list, &nonce, None).unwrap(),
+                   get_proof_challenge(CURRENT_PROOF_VERSION, &other_tau_list, &empty_c_list, &nonce, None).unwrap());
+
+        let mut relabeled_tau_list = TauList::new();
+        relabeled_tau_list.push(PRIMARY_EQUAL_PROOF_LABEL, &vec![1u8, 2]).unwrap();
+        relabeled_tau_list.push(SET_MEMBERSHIP_PROOF_LABEL, &vec![3u8]).unwrap();
+
+        assert_ne!(get_proof_challenge(CURRENT_PROOF_VERSION, &tau_list, &empty_c_list, &nonce, None).unwrap(),
+                   get_proof_challenge(CURRENT_PROOF_VERSION, &relabeled_tau_list, &empty_c_list, &nonce, None).unwrap());
+
+        assert!(get_proof_challenge(CURRENT_PROOF_VERSION + 1, &tau_list, &empty_c_list, &nonce, None).is_err());
+
+        // C list values are labeled the same way
+        let mut c_list = TauList::new();
+        c_list.push(PRIMARY_EQUAL_PROOF_LABEL, &vec![1u8, 2]).unwrap();
+        c_list.push(PRIMARY_PREDICATE_PROOF_LABEL, &vec![3u8]).unwrap();
+
+        let mut relabeled_c_list = TauList::new();
+        relabeled_c_list.push(PRIMARY_EQUAL_PROOF_LABEL, &vec![1u8, 2]).unwrap();
+        relabeled_c_list.push(SET_MEMBERSHIP_PROOF_LABEL, &vec![3u8]).unwrap();
+
+        assert_eq!(get_proof_challenge(LEGACY_PROOF_VERSION, &TauList::new(), &c_list, &nonce, None).unwrap(),
+                   get_proof_challenge(LEGACY_PROOF_VERSION, &TauList::new(), &relabeled_c_list, &nonce, None).unwrap());
+        assert_ne!(get_proof_challenge(CURRENT_PROOF_VERSION, &TauList::new(), &c_list, &nonce, None).unwrap(),
+                   get_proof_challenge(CURRENT_PROOF_VERSION, &TauList::new(), &relabeled_c_list, &nonce, None).unwrap());
+    }
+
+    #[test]
+    fn revocation_tails_generator_next_chunk_works() {
+        let rev_tails_generator = RevocationTailsGenerator::new(5, GroupOrderElement::new().unwrap(), PointG2::new().unwrap());
+
+        let mut expected_tails: Vec<Tail> = Vec::new();
+        let mut serial_rev_tails_generator = rev_tails_generator.clone();
+        while let Some(tail) = serial_rev_tails_generator.next().unwrap() {
+            expected_tails.push(tail);
+        }
+
+        let mut parallel_rev_tails_generator = rev_tails_generator.clone();
+        let mut tails = parallel_rev_tails_generator.next_chunk(4, 3).unwrap();
+        assert_eq!(7, parallel_rev_tails_generator.count());
+        tails.extend(parallel_rev_tails_generator.next_chunk(100, 4).unwrap());
+        assert_eq!(0, parallel_rev_tails_generator.count());
+
+        assert_eq!(expected_tails, tails);
+        assert!(parallel_rev_tails_generator.next_chunk(1, 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn tails_chunk_sum_works() {
+        let g_dash = PointG2::new().unwrap();
+        let gamma = GroupOrderElement::new().unwrap();
+
+        let mut expected_sum = PointG2::new_inf().unwrap();
+        for index in 1..8 {
+            expected_sum = expected_sum.add(&Tail::new_tail(index, &g_dash, &gamma).unwrap()).unwrap();
+        }
+
+        let chunks = TailsChunk::split(1, 8, 3, &g_dash, &gamma).unwrap();
+        assert_eq!(3, chunks.len());
+
+        let mut sum = PointG2::new_inf().unwrap();
+        for chunk_sum in process_tails_chunks(chunks, TailsChunk::sum).unwrap() {
+            sum = sum.add(&chunk_sum).unwrap();
+        }
+
+        assert_eq!(expected_sum, sum);
+    }
+
+    #[test]
+    fn tails_prefix_sums_range_sum_works() {
+        let mut rev_tails_generator = RevocationTailsGenerator::new(5, GroupOrderElement::new().unwrap(), PointG2::new().unwrap());
+        let simple_tails_accessor = SimpleTailsAccessor::new_with_prefix_sums(&mut rev_tails_generator, 4).unwrap();
+        let prefix_sums = simple_tails_accessor.prefix_sums.as_ref().unwrap();
+        assert_eq!(11, prefix_sums.tails_count());
+
+        for from in 0..12 {
+            for to in from..12 {
+                let mut expected_sum = PointG2::new_inf().unwrap();
+                for tail in simple_tails_accessor.tails[from as usize..to as usize].iter() {
+                    expected_sum = expected_sum.add(tail).unwrap();
+                }
+                assert_eq!(expected_sum, prefix_sums.range_sum(from, to, &simple_tails_accessor).unwrap());
+            }
+        }
+
+        assert!(prefix_sums.range_sum(0, 12, &simple_tails_accessor).is_err());
+        assert!(prefix_sums.range_sum(3, 2, &simple_tails_accessor).is_err());
+    }
+
+    #[test]
+    fn witness_new_works_for_prefix_sums() {
+        let rev_tails_generator = RevocationTailsGenerator::new(5, GroupOrderElement::new().unwrap(), PointG2::new().unwrap());
+        let simple_tails_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator.clone()).unwrap();
+        let prefix_sums_tails_accessor = SimpleTailsAccessor::new_with_prefix_sums(&mut rev_tails_generator.clone(), 2).unwrap();
+
+        let rev_reg_delta = RevocationRegistryDelta {
+            prev_accum: None,
+            accum: PointG2::new_inf().unwrap(),
+            issued: HashSet::new(),
+            revoked: vec![2, 4].into_iter().collect()
+        };
+
+        for rev_idx in 1..6 {
+            let witness = Witness::new(rev_idx, 5, true, &rev_reg_delta, &simple_tails_accessor).unwrap();
+            let fast_witness = Witness::new(rev_idx, 5, true, &rev_reg_delta, &prefix_sums_tails_accessor).unwrap();
+            assert_eq!(witness.omega, fast_witness.omega);
+        }
+    }
+
     #[test]
     fn demo() {
         let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
@@ -1,4 +1,4 @@
-use bn::BigNumber;
+use bn::{BigNumber, BigNumberContext};
 use cl::*;
 use cl::constants::*;
 use errors::IndyCryptoError;
@@ -250,9 +250,12 @@ impl Prover {
     pub fn new_proof_builder() -> Result<ProofBuilder, IndyCryptoError> {
         Ok(ProofBuilder {
             common_attributes: HashMap::new(),
+            attr_equalities: BTreeSet::new(),
+            linear_predicates: Vec::new(),
+            verifiable_encryptions: Vec::new(),
             init_proofs: Vec::new(),
-            c_list: Vec::new(),
-            tau_list: Vec::new()
+            c_list: TauList::new(),
+            tau_list: TauList::new()
         })
     }
 
@@ -440,7 +443,7 @@ impl Prover {
         let mut m_tildes = BTreeMap::new();
         let mut r_tildes = BTreeMap::new();
 
-        let mut values: Vec<u8> = Vec::new();
+        let mut committed_attributes: Vec<(&str, BigNumber, &BigNumber)> = Vec::new();
         let mut u_tilde = p_pub_key.s.mod_exp(
             &v_dash_tilde,
             &p_pub_key.n,
@@ -481,11 +484,10 @@ impl Prover {
                     )?;
                     r_tildes.insert(attr.clone(), r_tilde);
 
-                    values.extend_from_slice(&commitment_tilde.to_bytes()?);
                     let ca_value = blinded_primary_credential_secrets.committed_attributes
                         .get(attr)
                         .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in primary_blinded_cred_secrets.committed_attributes", attr)))?;
-                    values.extend_from_slice(&ca_value.to_bytes()?);
+                    committed_attributes.push((attr.as_str(), commitment_tilde, ca_value));
                     ()
                 }
                 _ => (),
@@ -493,11 +495,11 @@ impl Prover {
             m_tildes.insert(attr.clone(), m_tilde);
         }
 
-        values.extend_from_slice(&blinded_primary_credential_secrets.u.to_bytes()?);
-        values.extend_from_slice(&u_tilde.to_bytes()?);
-        values.extend_from_slice(&nonce.to_bytes()?);
-
-        let c = get_hash_as_int(&vec![values])?;
+        let c = get_blinded_credential_secrets_challenge(CURRENT_PROOF_VERSION,
+                                                         &committed_attributes,
+                                                         &blinded_primary_credential_secrets.u,
+                                                         &u_tilde,
+                                                         nonce)?;
 
         let v_dash_cap = c.mul(&blinded_primary_credential_secrets.v_prime, Some(&mut ctx))?
             .add(&v_dash_tilde)?;
@@ -536,6 +538,7 @@ impl Prover {
 
         let blinded_credential_secrets_correctness_proof =
             BlindedCredentialSecretsCorrectnessProof {
+                version: CURRENT_PROOF_VERSION,
                 c,
                 v_dash_cap,
                 m_caps,
@@ -677,31 +680,39 @@ impl Prover {
         trace!("Prover::_test_witness_signature: >>> r_cred: {:?}, cred_rev_pub_key: {:?}, rev_key_pub: {:?}, rev_reg: {:?}, r_cnxt_m2: {:?}",
                r_cred, cred_rev_pub_key, rev_key_pub, rev_reg, r_cnxt_m2);
 
-        let z_calc = Pair::pair(&r_cred.witness_signature.g_i, &rev_reg.accum)?
-            .mul(&Pair::pair(&cred_rev_pub_key.g, &witness.omega)?.inverse()?)?;
+        let z_calc = Pair::multi_pair(&[
+            (&r_cred.witness_signature.g_i, &rev_reg.accum),
+            (&cred_rev_pub_key.g.neg()?, &witness.omega)
+        ])?;
 
         if z_calc != rev_key_pub.z {
             return Err(IndyCryptoError::InvalidStructure("Issuer is sending incorrect data".to_string()));
         }
-        let pair_gg_calc = Pair::pair(&cred_rev_pub_key.pk.add(&r_cred.g_i)?, &r_cred.witness_signature.sigma_i)?;
-        let pair_gg = Pair::pair(&cred_rev_pub_key.g, &cred_rev_pub_key.g_dash)?;
 
-        if pair_gg_calc != pair_gg {
+        // e(pk + g_i, sigma_i) == e(g, g_dash)
+        let pair_gg = Pair::multi_pair(&[
+            (&cred_rev_pub_key.pk.add(&r_cred.g_i)?, &r_cred.witness_signature.sigma_i),
+            (&cred_rev_pub_key.g.neg()?, &cred_rev_pub_key.g_dash)
+        ])?;
+
+        if !pair_gg.is_unity()? {
             return Err(IndyCryptoError::InvalidStructure("Issuer is sending incorrect data".to_string()));
         }
 
         let m2 = GroupOrderElement::from_bytes(&r_cnxt_m2.to_bytes()?)?;
 
-        let pair_h1 = Pair::pair(&r_cred.sigma, &cred_rev_pub_key.y.add(&cred_rev_pub_key.h_cap.mul(&r_cred.c)?)?)?;
-        let pair_h2 = Pair::pair(
-            &cred_rev_pub_key.h0
+        // e(sigma, y + h_cap * c) == e(h0 + h1 * m2 + h2 * vr_prime_prime + g_i, h_cap)
+        let pair_h = Pair::multi_pair(&[
+            (&r_cred.sigma, &cred_rev_pub_key.y.add(&cred_rev_pub_key.h_cap.mul(&r_cred.c)?)?),
+            (&cred_rev_pub_key.h0
                 .add(&cred_rev_pub_key.h1.mul(&m2)?)?
                 .add(&cred_rev_pub_key.h2.mul(&r_cred.vr_prime_prime)?)?
-                .add(&r_cred.g_i)?,
-            &cred_rev_pub_key.h_cap
-        )?;
+                .add(&r_cred.g_i)?
+                .neg()?,
+             &cred_rev_pub_key.h_cap)
+        ])?;
 
-        if pair_h1 != pair_h2 {
+        if !pair_h.is_unity()? {
             return Err(IndyCryptoError::InvalidStructure("Issuer is sending incorrect data".to_string()));
         }
 
@@ -714,9 +725,12 @@ impl Prover {
 #[derive(Debug)]
 pub struct ProofBuilder {
     common_attributes: HashMap<String, BigNumber>,
+    attr_equalities: BTreeSet<AttributeEquality>,
+    linear_predicates: Vec<LinearPredicate>,
+    verifiable_encryptions: Vec<VerifiableEncryption>,
     init_proofs: Vec<InitProof>,
-    c_list: Vec<Vec<u8>>,
-    tau_list: Vec<Vec<u8>>,
+    c_list: TauList,
+    tau_list: TauList,
 }
 
 impl ProofBuilder {
@@ -725,10 +739,51 @@ impl ProofBuilder {
         self.common_attributes.insert(attr_name.to_owned(), bn_rand(LARGE_MVECT)?);
         Ok(())
     }
+
+    /// Makes attributes referred by attribute equality share m_tilde, so verifier can check
+    /// that their values are equal.
+    /// Must be called before adding of sub proof requests the equality refers to.
+    ///
+    /// # Arguments
+    /// * `attr_equality` - Attribute equality requested by verifier.
+    pub fn add_attribute_equality(&mut self, attr_equality: &AttributeEquality) -> Result<(), IndyCryptoError> {
+        if attr_equality.first.sub_proof_index < self.init_proofs.len() || attr_equality.second.sub_proof_index < self.init_proofs.len() {
+            return Err(IndyCryptoError::InvalidState(format!("Attribute equality refers already added sub proof")));
+        }
+
+        self.attr_equalities.insert(attr_equality.clone());
+        Ok(())
+    }
+
+    /// Adds linear predicate over hidden attributes of sub proofs.
+    /// Linear predicates are proven on finalization, so can refer to sub proofs added later.
+    /// The order of linear predicates is important: both Prover and Verifier should use the same order.
+    ///
+    /// # Arguments
+    /// * `linear_predicate` - Linear predicate requested by verifier.
+    pub fn add_linear_predicate(&mut self, linear_predicate: &LinearPredicate) -> Result<(), IndyCryptoError> {
+        linear_predicate.validate()?;
+        self.linear_predicates.push(linear_predicate.clone());
+        Ok(())
+    }
+
+    /// Adds requirement to verifiably encrypt hidden attribute of sub proof to auditor.
+    /// Encryptions are proven on finalization, so can refer to sub proofs added later.
+    /// The order of encryptions is important: both Prover and Verifier should use the same order.
+    ///
+    /// # Arguments
+    /// * `verifiable_encryption` - Verifiable encryption requested by verifier.
+    pub fn add_verifiable_encryption(&mut self, verifiable_encryption: &VerifiableEncryption) -> Result<(), IndyCryptoError> {
+        self.verifiable_encryptions.push(verifiable_encryption.try_clone()?);
+        Ok(())
+    }
+
     /// Adds sub proof request to proof builder which will be used fo building of proof.
     /// Part of proof request related to a particular schema-key.
     /// The order of sub-proofs is important: both Prover and Verifier should use the same order.
     ///
+    /// Returns pseudonym if it is requested by sub proof request.
+    ///
     /// # Arguments
     /// * `proof_builder` - Proof builder.
     /// * `sub_proof_request` -Requested attributes and predicates.
@@ -809,7 +864,7 @@ impl ProofBuilder {
                                  credential_values: &CredentialValues,
                                  credential_pub_key: &CredentialPublicKey,
                                  rev_reg: Option<&RevocationRegistry>,
-                                 witness: Option<&Witness>) -> Result<(), IndyCryptoError> {
+                                 witness: Option<&Witness>) -> Result<Option<Pseudonym>, IndyCryptoError> {
         trace!("ProofBuilder::add_sub_proof_request: >>> sub_proof_request: {:?}, \
                                                          credential_schema: {:?}, \
                                                          non_credential_schema: {:?}, \
@@ -845,13 +900,15 @@ impl ProofBuilder {
                                                                  &r_pub_key,
                                                                  &witness)?;
 
-            self.c_list.extend_from_slice(&proof.as_c_list()?);
-            self.tau_list.extend_from_slice(&proof.as_tau_list()?);
+            self.c_list.append_vec(NON_REVOCATION_PROOF_LABEL, &proof.as_c_list()?)?;
+            self.tau_list.append_vec(NON_REVOCATION_PROOF_LABEL, &proof.as_tau_list()?)?;
             m2_tilde = Some(group_element_to_bignum(&proof.tau_list_params.m2)?);
             non_revoc_init_proof = Some(proof);
         }
 
-        let primary_init_proof = ProofBuilder::_init_primary_proof(&self.common_attributes,
+        let common_attributes = self._get_sub_proof_common_attributes()?;
+
+        let primary_init_proof = ProofBuilder::_init_primary_proof(&common_attributes,
                                                                    &credential_pub_key.p_key,
                                                                    &credential_signature.p_credential,
                                                                    credential_values,
@@ -860,12 +917,25 @@ impl ProofBuilder {
                                                                    sub_proof_request,
                                                                    m2_tilde)?;
 
-        self.c_list.extend_from_slice(&primary_init_proof.as_c_list()?);
-        self.tau_list.extend_from_slice(&primary_init_proof.as_tau_list()?);
+        primary_init_proof.append_c_list(&mut self.c_list)?;
+        primary_init_proof.append_tau_list(&mut self.tau_list)?;
+
+        let mut pseudonym = None;
+
+        if let Some(ref pseudonym_request) = sub_proof_request.pseudonym {
+            let (nym, t) = ProofBuilder::_init_pseudonym(pseudonym_request,
+                                                         credential_values,
+                                                         &primary_init_proof.eq_proof.m_tilde)?;
+
+            nym.append_tau_list(&mut self.tau_list, &t)?;
+            pseudonym = Some(nym);
+        }
 
         let init_proof = InitProof {
             primary_init_proof,
             non_revoc_init_proof,
+            p_pub_key: credential_pub_key.p_key.clone()?,
+            pseudonym: pseudonym.clone(),
             credential_values: credential_values.clone()?,
             sub_proof_request: sub_proof_request.clone(),
             credential_schema: credential_schema.clone(),
@@ -873,9 +943,9 @@ impl ProofBuilder {
         };
         self.init_proofs.push(init_proof);
 
-        trace!("ProofBuilder::add_sub_proof_request: <<<");
+        trace!("ProofBuilder::add_sub_proof_request: <<< pseudonym: {:?}", pseudonym);
 
-        Ok(())
+        Ok(pseudonym)
     }
 
     /// Finalize proof.
@@ -953,15 +1023,41 @@ impl ProofBuilder {
     /// let _proof = proof_builder.finalize(&proof_request_nonce).unwrap();
     /// ```
     pub fn finalize(&self, nonce: &Nonce) -> Result<Proof, IndyCryptoError> {
-        trace!("ProofBuilder::finalize: >>> nonce: {:?}", nonce);
+        self._finalize(CURRENT_PROOF_VERSION, nonce, None)
+    }
 
-        let mut values: Vec<Vec<u8>> = Vec::new();
-        values.extend_from_slice(&self.tau_list);
-        values.extend_from_slice(&self.c_list);
-        values.push(nonce.to_bytes()?);
+    /// Finalize proof bound to the message, so proof is signature of knowledge over the message:
+    /// e.g. holder of credential approves transaction or document hash.
+    /// Proof is valid only if verifier checks it against the same message.
+    ///
+    /// # Arguments
+    /// * `nonce` - Nonce.
+    /// * `message` - Message (or context) bound to the proof challenge.
+    pub fn finalize_with_message(&self, nonce: &Nonce, message: &[u8]) -> Result<Proof, IndyCryptoError> {
+        self._finalize(CURRENT_PROOF_VERSION, nonce, Some(message))
+    }
+
+    fn _finalize(&self, version: u32, nonce: &Nonce, message: Option<&[u8]>) -> Result<Proof, IndyCryptoError> {
+        trace!("ProofBuilder::_finalize: >>> version: {:?}, nonce: {:?}, message: {:?}", version, nonce, message);
+
+        let linear_init_proofs = self._init_linear_predicate_proofs()?;
+        let encryption_init_proofs = self._init_verifiable_encryption_proofs()?;
+
+        let mut c_list = self.c_list.clone();
+        let mut tau_list = self.tau_list.clone();
+
+        for &(_, ref linear_init_proof) in linear_init_proofs.iter() {
+            c_list.append_vec(LINEAR_PREDICATE_PROOF_LABEL, linear_init_proof.as_list()?)?;
+            tau_list.append_vec(LINEAR_PREDICATE_PROOF_LABEL, linear_init_proof.as_tau_list()?)?;
+        }
+
+        for &(_, ref encryption_init_proof) in encryption_init_proofs.iter() {
+            c_list.append_vec(VERIFIABLE_ENCRYPTION_PROOF_LABEL, &encryption_init_proof.as_list()?)?;
+            tau_list.append_vec(VERIFIABLE_ENCRYPTION_PROOF_LABEL, encryption_init_proof.as_tau_list()?)?;
+        }
 
         // In the anoncreds whitepaper, `challenge` is denoted by `c_h`
-        let challenge = get_hash_as_int(&values)?;
+        let challenge = get_proof_challenge(version, &tau_list, &c_list, nonce, message)?;
 
         let mut proofs: Vec<SubProof> = Vec::new();
 
@@ -980,19 +1076,144 @@ impl ProofBuilder {
                 &init_proof.sub_proof_request,
             )?;
 
-            let proof = SubProof { primary_proof, non_revoc_proof };
+            let proof = SubProof { primary_proof, non_revoc_proof, pseudonym: init_proof.pseudonym.clone() };
             proofs.push(proof);
         }
 
-        let aggregated_proof = AggregatedProof { c_hash: challenge, c_list: self.c_list.clone() };
+        let mut linear_proofs: Vec<PrimaryLinearPredicateProof> = Vec::new();
+
+        for &(predicate, ref linear_init_proof) in linear_init_proofs.iter() {
+            let mj = predicate.combine(|attr| Ok(&proofs[attr.sub_proof_index].primary_proof.eq_proof.m[&attr.attr_name]))?;
+
+            linear_proofs.push(PrimaryLinearPredicateProof {
+                predicate: predicate.clone(),
+                ne_proof: ProofBuilder::_finalize_inequality_proof(&challenge, linear_init_proof, &mj)?
+            });
+        }
+
+        let mut encryption_proofs: Vec<VerifiableEncryptionProof> = Vec::new();
 
-        let proof = Proof { proofs, aggregated_proof };
+        for (verifiable_encryption, encryption_init_proof) in encryption_init_proofs.into_iter() {
+            let r_hat = challenge
+                .mul(&encryption_init_proof.r, None)?
+                .add(&encryption_init_proof.r_tilde)?;
 
-        trace!("ProofBuilder::finalize: <<< proof: {:?}", proof);
+            encryption_proofs.push(VerifiableEncryptionProof {
+                attr: verifiable_encryption.attr.clone(),
+                ciphertext: encryption_init_proof.ciphertext,
+                r_hat
+            });
+        }
+
+        let aggregated_proof = AggregatedProof { c_hash: challenge, c_list: c_list.flatten() };
+
+        let proof = Proof { version, proofs, aggregated_proof, linear_proofs, encryption_proofs };
+
+        trace!("ProofBuilder::_finalize: <<< proof: {:?}", proof);
 
         Ok(proof)
     }
 
+    // Returns m_tildes shared by attributes of next sub proof with other attributes:
+    // common attributes and attributes referred by attribute equalities
+    fn _get_sub_proof_common_attributes(&self) -> Result<HashMap<String, BigNumber>, IndyCryptoError> {
+        let sub_proof_index = self.init_proofs.len();
+        let mut common_attributes = clone_bignum_map(&self.common_attributes)?;
+
+        for attr_equality in self.attr_equalities.iter() {
+            if attr_equality.first.sub_proof_index == sub_proof_index && attr_equality.second.sub_proof_index == sub_proof_index {
+                let m_tilde = match common_attributes.get(&attr_equality.first.attr_name)
+                    .or(common_attributes.get(&attr_equality.second.attr_name)) {
+                    Some(m_tilde) => m_tilde.clone()?,
+                    None => bn_rand(LARGE_MVECT)?
+                };
+                common_attributes.insert(attr_equality.first.attr_name.clone(), m_tilde.clone()?);
+                common_attributes.insert(attr_equality.second.attr_name.clone(), m_tilde);
+                continue;
+            }
+
+            for &(attr, other_attr) in attr_equality.as_pairs().iter() {
+                if attr.sub_proof_index != sub_proof_index || other_attr.sub_proof_index >= sub_proof_index {
+                    continue;
+                }
+
+                let m_tilde = self.init_proofs[other_attr.sub_proof_index].primary_init_proof.eq_proof.m_tilde
+                    .get(&other_attr.attr_name)
+                    .ok_or(IndyCryptoError::InvalidStructure(format!("Attribute '{}' of sub proof {} is not hidden",
+                                                                     other_attr.attr_name, other_attr.sub_proof_index)))?;
+
+                common_attributes.insert(attr.attr_name.clone(), m_tilde.clone()?);
+            }
+        }
+
+        Ok(common_attributes)
+    }
+
+    fn _init_linear_predicate_proofs(&self) -> Result<Vec<(&LinearPredicate, PrimaryPredicateInequalityInitProof)>, IndyCryptoError> {
+        let mut linear_init_proofs = Vec::new();
+
+        for linear_predicate in self.linear_predicates.iter() {
+            let attr_value = linear_predicate.combine(|attr| {
+                self._get_linear_term_init_proof(attr)?
+                    .credential_values.attrs_values.get(&attr.attr_name)
+                    .map(|value| value.value())
+                    .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in cred_values", attr.attr_name)))
+            })?;
+
+            let m_tilde = linear_predicate.combine(|attr| {
+                self._get_linear_term_init_proof(attr)?
+                    .primary_init_proof.eq_proof.m_tilde.get(&attr.attr_name)
+                    .ok_or(IndyCryptoError::InvalidStructure(format!("Attribute '{}' of sub proof {} is not hidden",
+                                                                     attr.attr_name, attr.sub_proof_index)))
+            })?;
+
+            let p_pub_key = &self._get_linear_term_init_proof(linear_predicate.key_attr())?.p_pub_key;
+
+            let linear_init_proof = ProofBuilder::_init_inequality_proof(p_pub_key,
+                                                                         &attr_value,
+                                                                         &m_tilde,
+                                                                         &linear_predicate.as_predicate()?)?;
+
+            linear_init_proofs.push((linear_predicate, linear_init_proof));
+        }
+
+        Ok(linear_init_proofs)
+    }
+
+    fn _init_verifiable_encryption_proofs(&self) -> Result<Vec<(&VerifiableEncryption, VerifiableEncryptionInitProof)>, IndyCryptoError> {
+        let mut encryption_init_proofs = Vec::new();
+
+        for verifiable_encryption in self.verifiable_encryptions.iter() {
+            let attr = &verifiable_encryption.attr;
+
+            let init_proof = self.init_proofs.get(attr.sub_proof_index)
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Verifiable encryption refers to absent sub proof {}", attr.sub_proof_index)))?;
+
+            let attr_value = init_proof.credential_values.attrs_values.get(&attr.attr_name)
+                .map(|value| value.value())
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in cred_values", attr.attr_name)))?;
+
+            let m_tilde = init_proof.primary_init_proof.eq_proof.m_tilde.get(&attr.attr_name)
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Attribute '{}' of sub proof {} is not hidden",
+                                                                 attr.attr_name, attr.sub_proof_index)))?;
+
+            let auditor_pub_key = &verifiable_encryption.auditor_pub_key;
+
+            let (ciphertext, r) = auditor_pub_key.encrypt(attr_value, &verifiable_encryption.label)?;
+            let r_tilde = bn_rand(LARGE_VERIFIABLE_ENCRYPTION_RTILDE)?;
+            let tau_list = auditor_pub_key.calc_tau_list(&ciphertext, &r_tilde, m_tilde, &BigNumber::new()?)?;
+
+            encryption_init_proofs.push((verifiable_encryption, VerifiableEncryptionInitProof { ciphertext, r, r_tilde, tau_list }));
+        }
+
+        Ok(encryption_init_proofs)
+    }
+
+    fn _get_linear_term_init_proof(&self, attr: &AttributeReference) -> Result<&InitProof, IndyCryptoError> {
+        self.init_proofs.get(attr.sub_proof_index)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Linear predicate refers to absent sub proof {}", attr.sub_proof_index)))
+    }
+
     fn _check_add_sub_proof_request_params_consistency(
         cred_values: &CredentialValues,
         sub_proof_request: &SubProofRequest,
@@ -1032,17 +1253,50 @@ impl ProofBuilder {
             .predicates
             .iter()
             .map(|predicate| predicate.attr_name.clone())
+            .chain(sub_proof_request.set_predicates.iter().map(|predicate| predicate.attr_name.clone()))
             .collect::<BTreeSet<String>>();
 
         if predicates_attrs.difference(&cred_attrs).count() != 0 {
             return Err(IndyCryptoError::InvalidStructure(format!("Credential doesn't contain attribute requested in predicate")));
         }
 
+        if let Some(ref pseudonym_request) = sub_proof_request.pseudonym {
+            if !cred_attrs.contains(&pseudonym_request.attr_name) || sub_proof_request.revealed_attrs.contains(&pseudonym_request.attr_name) {
+                return Err(IndyCryptoError::InvalidStructure(format!("Credential doesn't contain hidden attribute requested for pseudonym")));
+            }
+        }
+
         trace!("ProofBuilder::_check_add_sub_proof_request_params_consistency: <<<");
 
         Ok(())
     }
 
+    // Returns pseudonym `H(scope)^m` and `t = H(scope)^m_tilde` of attribute `m`
+    fn _init_pseudonym(pseudonym_request: &PseudonymRequest,
+                       cred_values: &CredentialValues,
+                       m_tilde: &HashMap<String, BigNumber>) -> Result<(Pseudonym, PointG1), IndyCryptoError> {
+        trace!("ProofBuilder::_init_pseudonym: >>> pseudonym_request: {:?}, cred_values: {:?}, m_tilde: {:?}",
+               pseudonym_request, cred_values, m_tilde);
+
+        let attr_value = cred_values.attrs_values.get(&pseudonym_request.attr_name)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in cred_values", pseudonym_request.attr_name)))?;
+
+        let attr_m_tilde = m_tilde.get(&pseudonym_request.attr_name)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in m_tilde", pseudonym_request.attr_name)))?;
+
+        let base = Pseudonym::base(&pseudonym_request.scope)?;
+
+        let pseudonym = Pseudonym {
+            scope: pseudonym_request.scope.clone(),
+            nym: base.mul(&bignum_to_group_element_mod_order(attr_value.value())?)?
+        };
+        let t = base.mul(&bignum_to_group_element_mod_order(attr_m_tilde)?)?;
+
+        trace!("ProofBuilder::_init_pseudonym: <<< pseudonym: {:?}, t: {:?}", pseudonym, t);
+
+        Ok((pseudonym, t))
+    }
+
     fn _init_primary_proof(common_attributes: &HashMap<String, BigNumber>,
                            issuer_pub_key: &CredentialPrimaryPublicKey,
                            c1: &PrimaryCredentialSignature,
@@ -1082,7 +1336,18 @@ impl ProofBuilder {
             ne_proofs.push(ne_proof);
         }
 
-        let primary_init_proof = PrimaryInitProof { eq_proof, ne_proofs };
+        let mut set_proofs: Vec<PrimarySetMembershipInitProof> = Vec::new();
+        for predicate in sub_proof_request.set_predicates.iter() {
+            let set_proof = ProofBuilder::_init_set_membership_proof(
+                &issuer_pub_key,
+                &eq_proof.m_tilde,
+                cred_values,
+                predicate,
+            )?;
+            set_proofs.push(set_proof);
+        }
+
+        let primary_init_proof = PrimaryInitProof { eq_proof, ne_proofs, set_proofs };
 
         trace!("ProofBuilder::_init_primary_proof: <<< primary_init_proof: {:?}", primary_init_proof);
 
@@ -1184,53 +1449,96 @@ impl ProofBuilder {
         trace!("ProofBuilder::_init_ne_proof: >>> p_pub_key: {:?}, m_tilde: {:?}, cred_values: {:?}, predicate: {:?}",
                p_pub_key, m_tilde, cred_values, predicate);
 
-        let mut ctx = BigNumber::new_context()?;
-
         let attr_value = cred_values.attrs_values.get(&predicate.attr_name)
             .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in cred_values", predicate.attr_name)))?
-            .value()
-            .to_dec()?
-            .parse::<i32>()
-            .map_err(|_| IndyCryptoError::InvalidStructure(format!("Value by key '{}' has invalid format", predicate.attr_name)))?;
+            .value();
 
-        let delta = predicate.get_delta(attr_value);
+        let mj = m_tilde.get(&predicate.attr_name)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in eq_proof.mtilde", predicate.attr_name)))?;
 
-        if delta < 0 {
-            return Err(IndyCryptoError::InvalidStructure("Predicate is not satisfied".to_string()));
-        }
+        let primary_predicate_ne_init_proof = ProofBuilder::_init_inequality_proof(p_pub_key, attr_value, mj, predicate)?;
 
-        let u = four_squares(delta)?;
+        trace!("ProofBuilder::_init_ne_proof: <<< primary_predicate_ne_init_proof: {:?}", primary_predicate_ne_init_proof);
+
+        Ok(primary_predicate_ne_init_proof)
+    }
 
+    // Proves predicate for value committed by `mj` that is m_tilde of attribute or their linear combination
+    fn _init_inequality_proof(p_pub_key: &CredentialPrimaryPublicKey,
+                              attr_value: &BigNumber,
+                              mj: &BigNumber,
+                              predicate: &Predicate) -> Result<PrimaryPredicateInequalityInitProof, IndyCryptoError> {
+        trace!("ProofBuilder::_init_inequality_proof: >>> p_pub_key: {:?}, attr_value: {:?}, mj: {:?}, predicate: {:?}",
+               p_pub_key, attr_value, mj, predicate);
+
+        let mut ctx = BigNumber::new_context()?;
+
+        let delta = predicate.get_delta(attr_value)?;
+
+        // Values that must be proven to be sums of four squares:
+        // delta itself for GE/GT/LE/LT, delta^2 - 1 for NE and both deltas for BETWEEN
+        let mut squares: Vec<HashMap<String, BigNumber>> = Vec::new();
+
+        match predicate.p_type {
+            PredicateType::NE => {
+                if delta == BigNumber::from_u32(0)? {
+                    return Err(IndyCryptoError::InvalidStructure("Predicate is not satisfied".to_string()));
+                }
+                squares.push(four_squares(&delta.sqr(Some(&mut ctx))?.decrement()?)?);
+            }
+            PredicateType::BETWEEN => {
+                let upper_delta = predicate.get_upper_delta(attr_value)?;
+                if delta.is_negative() || upper_delta.is_negative() {
+                    return Err(IndyCryptoError::InvalidStructure("Predicate is not satisfied".to_string()));
+                }
+                squares.push(four_squares(&delta)?);
+                squares.push(four_squares(&upper_delta)?);
+            }
+            _ => {
+                if delta.is_negative() {
+                    return Err(IndyCryptoError::InvalidStructure("Predicate is not satisfied".to_string()));
+                }
+                squares.push(four_squares(&delta)?);
+            }
+        }
+
+        let mut u = HashMap::new();
         let mut r = HashMap::new();
         let mut t = HashMap::new();
         let mut c_list: Vec<BigNumber> = Vec::new();
 
-        for i in 0..ITERATION {
-            let cur_u = u.get(&i.to_string())
-                .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in u1", i)))?;
+        for (idx, cur_squares) in squares.iter().enumerate() {
+            for i in 0..ITERATION {
+                let cur_u = cur_squares.get(&i.to_string())
+                    .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in u1", i)))?;
 
-            let cur_r = bn_rand(LARGE_VPRIME)?;
-            let cut_t = get_pedersen_commitment(&p_pub_key.z, &cur_u, &p_pub_key.s,
-                                                &cur_r, &p_pub_key.n, &mut ctx)?;
+                let cur_r = bn_rand(LARGE_VPRIME)?;
+                let cut_t = get_pedersen_commitment(&p_pub_key.z, &cur_u, &p_pub_key.s,
+                                                    &cur_r, &p_pub_key.n, &mut ctx)?;
 
-            r.insert(i.to_string(), cur_r);
-            t.insert(i.to_string(), cut_t.clone()?);
-            c_list.push(cut_t)
-        }
+                let key = (idx * ITERATION + i).to_string();
+                u.insert(key.clone(), cur_u.clone()?);
+                r.insert(key.clone(), cur_r);
+                t.insert(key, cut_t.clone()?);
+                c_list.push(cut_t)
+            }
 
-        let r_delta = bn_rand(LARGE_VPRIME)?;
+            if idx == 0 {
+                let r_delta = bn_rand(LARGE_VPRIME)?;
 
-        let t_delta = get_pedersen_commitment(&p_pub_key.z, &BigNumber::from_dec(&delta.to_string())?,
-                                              &p_pub_key.s, &r_delta, &p_pub_key.n, &mut ctx)?;
+                let t_delta = get_pedersen_commitment(&p_pub_key.z, &delta,
+                                                      &p_pub_key.s, &r_delta, &p_pub_key.n, &mut ctx)?;
 
-        r.insert("DELTA".to_string(), r_delta);
-        t.insert("DELTA".to_string(), t_delta.clone()?);
-        c_list.push(t_delta);
+                r.insert("DELTA".to_string(), r_delta);
+                t.insert("DELTA".to_string(), t_delta.clone()?);
+                c_list.push(t_delta);
+            }
+        }
 
         let mut u_tilde = HashMap::new();
         let mut r_tilde = HashMap::new();
 
-        for i in 0..ITERATION {
+        for i in 0..squares.len() * ITERATION {
             u_tilde.insert(i.to_string(), bn_rand(LARGE_UTILDE)?);
             r_tilde.insert(i.to_string(), bn_rand(LARGE_RTILDE)?);
         }
@@ -1238,10 +1546,25 @@ impl ProofBuilder {
         r_tilde.insert("DELTA".to_string(), bn_rand(LARGE_RTILDE)?);
         let alpha_tilde = bn_rand(LARGE_ALPHATILDE)?;
 
-        let mj = m_tilde.get(&predicate.attr_name)
-            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in eq_proof.mtilde", predicate.attr_name)))?;
+        let upper_alpha_tilde = match predicate.p_type {
+            PredicateType::BETWEEN => Some(bn_rand(LARGE_ALPHATILDE)?),
+            _ => None
+        };
+
+        if predicate.p_type == PredicateType::NE {
+            // T_SQUARE = T_DELTA^delta * S^r_square = Z^(delta^2) * S^(delta * r_delta + r_square)
+            let r_square = bn_rand(LARGE_VPRIME)?;
+            let t_square = get_pedersen_commitment(&t["DELTA"], &delta, &p_pub_key.s,
+                                                   &r_square, &p_pub_key.n, &mut ctx)?;
+
+            u.insert("DELTA".to_string(), delta.clone()?);
+            r.insert("SQUARE".to_string(), r_square);
+            r_tilde.insert("SQUARE".to_string(), bn_rand(LARGE_RTILDE)?);
+            t.insert("SQUARE".to_string(), t_square.clone()?);
+            c_list.push(t_square);
+        }
 
-        let tau_list = calc_tne(&p_pub_key, &u_tilde, &r_tilde, &mj, &alpha_tilde, &t, predicate.is_less())?;
+        let tau_list = calc_tne(&p_pub_key, &u_tilde, &r_tilde, &mj, &alpha_tilde, upper_alpha_tilde.as_ref(), &t, predicate)?;
 
         let primary_predicate_ne_init_proof = PrimaryPredicateInequalityInitProof {
             c_list,
@@ -1252,14 +1575,81 @@ impl ProofBuilder {
             r_tilde,
             alpha_tilde,
             predicate: predicate.clone(),
-            t
+            t,
+            upper_alpha_tilde
         };
 
-        trace!("ProofBuilder::_init_ne_proof: <<< primary_predicate_ne_init_proof: {:?}", primary_predicate_ne_init_proof);
+        trace!("ProofBuilder::_init_inequality_proof: <<< primary_predicate_ne_init_proof: {:?}", primary_predicate_ne_init_proof);
 
         Ok(primary_predicate_ne_init_proof)
     }
 
+    fn _init_set_membership_proof(p_pub_key: &CredentialPrimaryPublicKey,
+                                  m_tilde: &HashMap<String, BigNumber>,
+                                  cred_values: &CredentialValues,
+                                  predicate: &SetMembershipPredicate) -> Result<PrimarySetMembershipInitProof, IndyCryptoError> {
+        trace!("ProofBuilder::_init_set_membership_proof: >>> p_pub_key: {:?}, m_tilde: {:?}, cred_values: {:?}, predicate: {:?}",
+               p_pub_key, m_tilde, cred_values, predicate);
+
+        let mut ctx = BigNumber::new_context()?;
+
+        let attr_value = cred_values.attrs_values.get(&predicate.attr_name)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in cred_values", predicate.attr_name)))?
+            .value();
+
+        let values = predicate.get_values()?;
+
+        let index = values.iter()
+            .position(|value| value == attr_value)
+            .ok_or(IndyCryptoError::InvalidStructure("Predicate is not satisfied".to_string()))?;
+
+        let mj_tilde = m_tilde.get(&predicate.attr_name)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in eq_proof.mtilde", predicate.attr_name)))?;
+
+        let r = bn_rand(LARGE_VPRIME)?;
+        let t = get_pedersen_commitment(&p_pub_key.z, attr_value, &p_pub_key.s, &r, &p_pub_key.n, &mut ctx)?;
+
+        // Binds committed value to the attribute of equality proof
+        let r_tilde = bn_rand(LARGE_SET_MEMBERSHIP_RTILDE)?;
+        let mut tau_list = vec![get_pedersen_commitment(&p_pub_key.z, mj_tilde, &p_pub_key.s, &r_tilde, &p_pub_key.n, &mut ctx)?];
+
+        let rho_tilde = bn_rand(LARGE_SET_MEMBERSHIP_RTILDE)?;
+        let mut c = Vec::new();
+        let mut z = Vec::new();
+
+        for (i, value) in values.iter().enumerate() {
+            if i == index {
+                tau_list.push(p_pub_key.s.mod_exp(&rho_tilde, &p_pub_key.n, Some(&mut ctx))?);
+                c.push(BigNumber::new()?);
+                z.push(BigNumber::new()?);
+            } else {
+                // Branches for other values are simulated with random challenges and responses
+                let c_i = bn_rand(LARGE_CHALLENGE)?;
+                let z_i = bn_rand(LARGE_SET_MEMBERSHIP_RTILDE)?;
+                tau_list.push(calc_tset(&p_pub_key, &t, value, &c_i, &z_i, &mut ctx)?);
+                c.push(c_i);
+                z.push(z_i);
+            }
+        }
+
+        let primary_set_membership_init_proof = PrimarySetMembershipInitProof {
+            c_list: vec![t.clone()?],
+            tau_list,
+            predicate: predicate.clone(),
+            t,
+            index,
+            r,
+            r_tilde,
+            rho_tilde,
+            c,
+            z
+        };
+
+        trace!("ProofBuilder::_init_set_membership_proof: <<< primary_set_membership_init_proof: {:?}", primary_set_membership_init_proof);
+
+        Ok(primary_set_membership_init_proof)
+    }
+
     fn _finalize_eq_proof(init_proof: &PrimaryEqualInitProof,
                           challenge: &BigNumber,
                           cred_schema: &CredentialSchema,
@@ -1347,59 +1737,146 @@ impl ProofBuilder {
                           eq_proof: &PrimaryEqualProof) -> Result<PrimaryPredicateInequalityProof, IndyCryptoError> {
         trace!("ProofBuilder::_finalize_ne_proof: >>> c_h: {:?}, init_proof: {:?}, eq_proof: {:?}", c_h, init_proof, eq_proof);
 
+        let mj = eq_proof.m.get(&init_proof.predicate.attr_name)
+            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in eq_proof.m", init_proof.predicate.attr_name)))?;
+
+        let primary_predicate_ne_proof = ProofBuilder::_finalize_inequality_proof(c_h, init_proof, mj)?;
+
+        trace!("ProofBuilder::_finalize_ne_proof: <<< primary_predicate_ne_proof: {:?}", primary_predicate_ne_proof);
+
+        Ok(primary_predicate_ne_proof)
+    }
+
+    fn _finalize_inequality_proof(c_h: &BigNumber,
+                                  init_proof: &PrimaryPredicateInequalityInitProof,
+                                  mj: &BigNumber) -> Result<PrimaryPredicateInequalityProof, IndyCryptoError> {
+        trace!("ProofBuilder::_finalize_inequality_proof: >>> c_h: {:?}, init_proof: {:?}, mj: {:?}", c_h, init_proof, mj);
+
         let mut ctx = BigNumber::new_context()?;
         let mut u = HashMap::new();
         let mut r = HashMap::new();
-        let mut urproduct = BigNumber::new()?;
-
-        for i in 0..ITERATION {
-            let cur_utilde = &init_proof.u_tilde[&i.to_string()];
-            let cur_u = &init_proof.u[&i.to_string()];
-            let cur_rtilde = &init_proof.r_tilde[&i.to_string()];
-            let cur_r = &init_proof.r[&i.to_string()];
 
+        for (key, cur_utilde) in init_proof.u_tilde.iter() {
             let new_u: BigNumber = c_h
-                .mul(&cur_u, Some(&mut ctx))?
+                .mul(&init_proof.u[key], Some(&mut ctx))?
                 .add(&cur_utilde)?;
-            let new_r: BigNumber = c_h
-                .mul(&cur_r, Some(&mut ctx))?
-                .add(&cur_rtilde)?;
 
-            u.insert(i.to_string(), new_u);
-            r.insert(i.to_string(), new_r);
+            u.insert(key.clone(), new_u);
+        }
 
-            urproduct = cur_u
-                .mul(&cur_r, Some(&mut ctx))?
-                .add(&urproduct)?;
+        for (key, cur_rtilde) in init_proof.r_tilde.iter() {
+            let new_r: BigNumber = c_h
+                .mul(&init_proof.r[key], Some(&mut ctx))?
+                .add(&cur_rtilde)?;
 
-            let cur_rtilde_delta = &init_proof.r_tilde["DELTA"];
+            r.insert(key.clone(), new_r);
+        }
 
-            let new_delta = c_h
+        // Randomness of the commitment that must be equal to the product of four squares commitments
+        let r_target = match init_proof.predicate.p_type {
+            PredicateType::NE => init_proof.u["DELTA"]
                 .mul(&init_proof.r["DELTA"], Some(&mut ctx))?
-                .add(&cur_rtilde_delta)?;
-
-            r.insert("DELTA".to_string(), new_delta);
-        }
+                .add(&init_proof.r["SQUARE"])?,
+            _ => init_proof.r["DELTA"].clone()?
+        };
 
-        let alpha = init_proof.r["DELTA"]
-            .sub(&urproduct)?
+        let alpha = r_target
+            .sub(&ProofBuilder::_ur_product(init_proof, 0, &mut ctx)?)?
             .mul(&c_h, Some(&mut ctx))?
             .add(&init_proof.alpha_tilde)?;
 
+        let upper_alpha = match init_proof.upper_alpha_tilde {
+            Some(ref upper_alpha_tilde) => Some(
+                BigNumber::new()?
+                    .sub(&init_proof.r["DELTA"])?
+                    .sub(&ProofBuilder::_ur_product(init_proof, ITERATION, &mut ctx)?)?
+                    .mul(&c_h, Some(&mut ctx))?
+                    .add(upper_alpha_tilde)?
+            ),
+            None => None
+        };
+
         let primary_predicate_ne_proof = PrimaryPredicateInequalityProof {
             u,
             r,
-            mj: eq_proof.m[&init_proof.predicate.attr_name].clone()?,
+            mj: mj.clone()?,
             alpha,
             t: clone_bignum_map(&init_proof.t)?,
-            predicate: init_proof.predicate.clone()
+            predicate: init_proof.predicate.clone(),
+            upper_alpha
         };
 
-        trace!("ProofBuilder::_finalize_ne_proof: <<< primary_predicate_ne_proof: {:?}", primary_predicate_ne_proof);
+        trace!("ProofBuilder::_finalize_inequality_proof: <<< primary_predicate_ne_proof: {:?}", primary_predicate_ne_proof);
 
         Ok(primary_predicate_ne_proof)
     }
 
+    fn _ur_product(init_proof: &PrimaryPredicateInequalityInitProof,
+                   offset: usize,
+                   ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+        let mut urproduct = BigNumber::new()?;
+
+        for i in offset..offset + ITERATION {
+            urproduct = init_proof.u[&i.to_string()]
+                .mul(&init_proof.r[&i.to_string()], Some(ctx))?
+                .add(&urproduct)?;
+        }
+
+        Ok(urproduct)
+    }
+
+    fn _finalize_set_membership_proof(c_h: &BigNumber,
+                                      init_proof: &PrimarySetMembershipInitProof) -> Result<PrimarySetMembershipProof, IndyCryptoError> {
+        trace!("ProofBuilder::_finalize_set_membership_proof: >>> c_h: {:?}, init_proof: {:?}", c_h, init_proof);
+
+        let mut ctx = BigNumber::new_context()?;
+
+        let mut challenge_modulus = BigNumber::new()?;
+        challenge_modulus.set_bit(LARGE_CHALLENGE as i32)?;
+
+        // Challenge of the real branch is what remains from proof challenge after simulated ones
+        let mut c_real = c_h.clone()?;
+        for (i, c_i) in init_proof.c.iter().enumerate() {
+            if i != init_proof.index {
+                c_real = c_real.sub(c_i)?;
+            }
+        }
+        let c_real = c_real.modulus(&challenge_modulus, Some(&mut ctx))?;
+
+        let z_real = c_real
+            .mul(&init_proof.r, Some(&mut ctx))?
+            .add(&init_proof.rho_tilde)?;
+
+        let mut c = Vec::new();
+        let mut z = Vec::new();
+
+        for i in 0..init_proof.c.len() {
+            if i == init_proof.index {
+                c.push(c_real.clone()?);
+                z.push(z_real.clone()?);
+            } else {
+                c.push(init_proof.c[i].clone()?);
+                z.push(init_proof.z[i].clone()?);
+            }
+        }
+
+        let r = c_h
+            .mul(&init_proof.r, Some(&mut ctx))?
+            .add(&init_proof.r_tilde)?;
+
+        let primary_set_membership_proof = PrimarySetMembershipProof {
+            predicate: init_proof.predicate.clone(),
+            t: init_proof.t.clone()?,
+            r,
+            c,
+            z
+        };
+
+        trace!("ProofBuilder::_finalize_set_membership_proof: <<< primary_set_membership_proof: {:?}", primary_set_membership_proof);
+
+        Ok(primary_set_membership_proof)
+    }
+
     fn _finalize_primary_proof(init_proof: &PrimaryInitProof,
                                challenge: &BigNumber,
                                cred_schema: &CredentialSchema,
@@ -1431,7 +1908,14 @@ impl ProofBuilder {
             ne_proofs.push(ne_proof);
         }
 
-        let primary_proof = PrimaryProof { eq_proof, ne_proofs };
+        let mut set_proofs: Vec<PrimarySetMembershipProof> = Vec::new();
+
+        for init_set_proof in init_proof.set_proofs.iter() {
+            let set_proof = ProofBuilder::_finalize_set_membership_proof(challenge, init_set_proof)?;
+            set_proofs.push(set_proof);
+        }
+
+        let primary_proof = PrimaryProof { eq_proof, ne_proofs, set_proofs };
 
         trace!("ProofBuilder::_finalize_primary_proof: <<< primary_proof: {:?}", primary_proof);
 
@@ -1586,6 +2070,8 @@ impl ProofBuilder {
 mod tests {
     use super::*;
     use cl::issuer;
+    use cl::verifier::Verifier;
+    use errors::{ErrorCode, ToErrorCode};
     use serde_json;
 
     #[test]
@@ -1653,6 +2139,7 @@ mod tests {
         assert_eq!(_blinded_primary_credential_secrets.v_prime, expected_v_prime);
     }
 
+    #[cfg(feature = "pair_amcl")]
     #[test]
     fn generate_blinded_revocation_credential_secrets_works() {
         MockHelper::inject();
@@ -1661,6 +2148,7 @@ mod tests {
         Prover::_generate_blinded_revocation_credential_secrets(&r_pk).unwrap();
     }
 
+    #[cfg(feature = "pair_amcl")]
     #[test]
     fn generate_blinded_credential_secrets_works() {
         MockHelper::inject();
@@ -1679,10 +2167,11 @@ mod tests {
         assert!(credential_secrets_blinding_factors.vr_prime.is_some());
 
         let expected_blinded_credential_secrets_correctness_proof = BlindedCredentialSecretsCorrectnessProof {
-            c: BigNumber::from_dec("62987495574713125276927020393421215004000405197826691815490873602430880071520").unwrap(),
-            v_dash_cap: BigNumber::from_dec("2483151605786321488759217858501299625266963483281448836084574210022576435206971822449121240893054977812189270973953627508037664673471639762741607301607126279750737315145287752582361238341982527770578389690599482404077240465548717461486060588059213780492836836099219386099202724363461616817064821942869122776850201866032510147820895329800625363912874314625087444433725870407798073299656701830491704373376211865053561350143025726764395631372922114017947241869273369417350214158750221246048552576266052085095574621924764537536843949047976577393704529093690922681660593080666896873908897832916208130004347705000071204141451034807634347513854683282384195146310448772446387795954589966062700678176289767810096596561215075778").unwrap(),
+            version: CURRENT_PROOF_VERSION,
+            c: BigNumber::from_dec("51240107196558638985864964336007190908391661003988912873554625789517875152153").unwrap(),
+            v_dash_cap: BigNumber::from_dec("2070446752643119669973750370542578014168916715638037372964960959091569557734854111623426161837166212389948145654636662468665016143138973452507346054296223963683454193028216742073619857862585842004185682484563561475017058019874403983226206834434936777741899918294610837604498677395208950882742405126399849856074906327247291059092258334714253654745853773602141679019354030168750516111188510507685905587347543344330558595103411647597044709523816786606986968951802935712835354862615006512518365688893709951028047147405955162045757072853676313405685715021844574683862937820887562169732300831107315552778867622017343391379410610629783277282587870281019980726631774544706126111170207944355831705497696571884856815864378573348").unwrap(),
             m_caps: btreemap![
-                "master_secret".to_string() => BigNumber::from_dec("10838856720335086997514321276808275847406618787892605766896852714686897722667846274831751967934281244850533820384194801107183060846242551328524580159640640402707269360579673792415").unwrap()
+                "master_secret".to_string() => BigNumber::from_dec("10838856720335086997514321023322785806396320064860910461542535012943670243175417613019657850502387604097897662972697359119837549100910832614373215911788762704471105112003619426300").unwrap()
             ],
             r_caps: BTreeMap::new()
         };
@@ -1702,6 +2191,7 @@ mod tests {
         assert_eq!(mocks::primary_credential(), credential);
     }
 
+    #[cfg(feature = "pair_amcl")]
     #[ignore]
     #[test]
     fn process_credential_works() {
@@ -1727,6 +2217,7 @@ mod tests {
         assert_eq!(mocks::primary_credential(), credential_signature.p_credential);
     }
 
+    #[cfg(feature = "pair_amcl")]
     #[test]
     fn init_eq_proof_works() {
         MockHelper::inject();
@@ -1767,6 +2258,7 @@ mod tests {
         assert_eq!(mocks::primary_ne_init_proof(), init_ne_proof);
     }
 
+    #[cfg(feature = "pair_amcl")]
     #[test]
     fn init_primary_proof_works() {
         MockHelper::inject();
@@ -1791,6 +2283,25 @@ mod tests {
         assert_eq!(mocks::primary_init_proof(), init_proof);
     }
 
+    #[test]
+    fn init_pseudonym_works() {
+        let credential_values = issuer::mocks::credential_values();
+        let m_tilde = hashmap!["master_secret".to_string() => mocks::m1_t()];
+        let pseudonym_request = PseudonymRequest { attr_name: "master_secret".to_owned(), scope: "example.com".to_owned() };
+
+        let (pseudonym, t) = ProofBuilder::_init_pseudonym(&pseudonym_request, &credential_values, &m_tilde).unwrap();
+        let (same_scope_pseudonym, _) = ProofBuilder::_init_pseudonym(&pseudonym_request, &credential_values, &m_tilde).unwrap();
+
+        assert_eq!("example.com", pseudonym.scope());
+        assert_eq!(pseudonym, same_scope_pseudonym);
+        assert!(pseudonym.nym != t);
+
+        let pseudonym_request = PseudonymRequest { attr_name: "master_secret".to_owned(), scope: "example.org".to_owned() };
+        let (other_scope_pseudonym, _) = ProofBuilder::_init_pseudonym(&pseudonym_request, &credential_values, &m_tilde).unwrap();
+
+        assert!(pseudonym.nym != other_scope_pseudonym.nym);
+    }
+
     #[test]
     fn finalize_eq_proof_works() {
         MockHelper::inject();
@@ -1847,6 +2358,76 @@ mod tests {
         assert_eq!(mocks::primary_proof(), proof);
     }
 
+    #[test]
+    fn finalize_works_for_legacy_proof_version() {
+        let credential_schema = issuer::mocks::credential_schema();
+        let non_credential_schema = issuer::mocks::non_credential_schema();
+        let credential_values = issuer::mocks::credential_values();
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) =
+            issuer::Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
+
+        let credential_nonce = new_nonce().unwrap();
+        let (blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof) =
+            Prover::blind_credential_secrets(&credential_pub_key,
+                                             &credential_key_correctness_proof,
+                                             &credential_values,
+                                             &credential_nonce).unwrap();
+
+        let credential_issuance_nonce = new_nonce().unwrap();
+        let (mut credential_signature, signature_correctness_proof) =
+            issuer::Issuer::sign_credential(mocks::PROVER_DID,
+                                            &blinded_credential_secrets,
+                                            &blinded_credential_secrets_correctness_proof,
+                                            &credential_nonce,
+                                            &credential_issuance_nonce,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            &credential_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut credential_signature,
+                                             &credential_values,
+                                             &signature_correctness_proof,
+                                             &credential_secrets_blinding_factors,
+                                             &credential_pub_key,
+                                             &credential_issuance_nonce,
+                                             None, None, None).unwrap();
+
+        let sub_proof_request = mocks::sub_proof_request();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+
+        let nonce = new_nonce().unwrap();
+        let proof = proof_builder._finalize(LEGACY_PROOF_VERSION, &nonce, None).unwrap();
+
+        // Proofs created before proof versioning have no version field
+        let mut proof_json = serde_json::to_value(&proof).unwrap();
+        proof_json.as_object_mut().unwrap().remove("version");
+        let proof: Proof = serde_json::from_value(proof_json).unwrap();
+        assert_eq!(LEGACY_PROOF_VERSION, proof.version);
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        // Verifier can refuse legacy proofs
+        proof_verifier.set_min_proof_version(CURRENT_PROOF_VERSION).unwrap();
+        let res = proof_verifier.verify(&proof, &nonce);
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+    }
+
     extern crate time;
 
     /*
@@ -1968,6 +2549,7 @@ mod tests {
         println!("Update Proof test -> end");
     }
 
+    #[cfg(feature = "pair_amcl")]
     #[test]
     #[ignore]
     fn generate_proof_mocks() {
@@ -2023,6 +2605,7 @@ pub mod mocks {
         hashmap!["master_secret".to_string() => BigNumber::from_dec("67940925789970108743024738273926421512152745397724199848594503731042154269417576665420030681245389493783225644817826683796657351721363490290016166310023506339911751676800452438014771736117676826911321621579680668201191205819012441197794443970687648330757835198888257781967404396196813475280544039772512800509").unwrap()]
     }
 
+    #[cfg(feature = "pair_amcl")]
     pub fn blinded_credential_secrets() -> BlindedCredentialSecrets {
         BlindedCredentialSecrets {
             u: primary_blinded_credential_secrets_factors().u,
@@ -2032,6 +2615,7 @@ pub mod mocks {
         }
     }
 
+    #[cfg(feature = "pair_amcl")]
     pub fn credential_secrets_blinding_factors() -> CredentialSecretsBlindingFactors {
         CredentialSecretsBlindingFactors {
             v_prime: primary_blinded_credential_secrets_factors().v_prime,
@@ -2048,6 +2632,7 @@ pub mod mocks {
         }
     }
 
+    #[cfg(feature = "pair_amcl")]
     pub fn revocation_blinded_credential_secrets_factors() -> RevocationBlindedCredentialSecretsFactors {
         RevocationBlindedCredentialSecretsFactors {
             ur: PointG1::from_string("false 19C7E3A5BC00073DFDF072C87818E94E5036FABABCECED727CA52B35CD13623E 14338266060779CA8E7881A8C6F01D76493C6E2E0799699B48B9B0C37EFB7F10 095E45DDF417D05FB10933FFC63D474548B7FFFF7888802F07FFFFFF7D07A8A8").unwrap(),
@@ -2057,6 +2642,7 @@ pub mod mocks {
 
     pub fn blinded_credential_secrets_correctness_proof() -> BlindedCredentialSecretsCorrectnessProof {
         BlindedCredentialSecretsCorrectnessProof {
+            version: LEGACY_PROOF_VERSION,
             c: BigNumber::from_dec("22221897091810097116104550881114461643082148268292262107370452543809392119980").unwrap(),
             v_dash_cap: BigNumber::from_dec("138550075139853703898921089249742109370933383442122536758241462797136898723372217745073733484760321944313196224005959283313214691823866099200206619511094340510410379756132564018900336272408451952758744911909745270186687469351805926400048940751546663384456932526357210721105286298911954309847673030848924669039030892926351271528361352647229815570200886413331044936523522169734991711074388744670236246090794460955452530165454411261777397994727107643177636412180478391610457843032096905372535244501319937136828286952881920600234066686774078964666681460452086101831609169073744423947479573031477418206287304633516634999897586640587369694314600219843979420979423192697295668825747083934480262776849450155189470507444304681").unwrap(),
             m_caps: btreemap![
@@ -2066,6 +2652,7 @@ pub mod mocks {
         }
     }
 
+    #[cfg(feature = "pair_amcl")]
     pub fn credential() -> CredentialSignature {
         CredentialSignature {
             p_credential: primary_credential(),
@@ -2086,6 +2673,7 @@ pub mod mocks {
         }
     }
 
+    #[cfg(feature = "pair_amcl")]
     pub fn revocation_credential() -> NonRevocationCredentialSignature {
         NonRevocationCredentialSignature {
             sigma: PointG1::from_string("false 61FEBE2CFEAA04 5440090222C6AC E933B40264261C A5AA97421F4AEB 1D18E69F 23DDFBC92248BC F4CD0C7051CBEC 7057318CAFB551 B88E41A2CB508A 1461756F FFFFFF7D07A8A8 FFFF7888802F07 FFC63D474548B7 F417D05FB10933 95E45DD").unwrap(),
@@ -2104,24 +2692,31 @@ pub mod mocks {
 
     pub fn proof_request_nonce() -> Nonce { BigNumber::from_dec("1164046393264787986302355").unwrap() }
 
+    #[cfg(feature = "pair_amcl")]
     pub fn proof() -> Proof {
         Proof {
+            version: LEGACY_PROOF_VERSION,
             proofs: vec![subproof()],
-            aggregated_proof: aggregated_proof()
+            aggregated_proof: aggregated_proof(),
+            linear_proofs: Vec::new(),
+            encryption_proofs: Vec::new()
         }
     }
 
+    #[cfg(feature = "pair_amcl")]
     pub fn subproof() -> SubProof {
         SubProof {
             primary_proof: primary_proof(),
-            non_revoc_proof: Some(non_revoc_proof())
+            non_revoc_proof: Some(non_revoc_proof()),
+            pseudonym: None
         }
     }
 
     pub fn primary_init_proof() -> PrimaryInitProof {
         PrimaryInitProof {
             eq_proof: primary_equal_init_proof(),
-            ne_proofs: vec![primary_ne_init_proof()]
+            ne_proofs: vec![primary_ne_init_proof()],
+            set_proofs: Vec::new()
         }
     }
 
@@ -2193,7 +2788,8 @@ pub mod mocks {
                 "2".to_string() => BigNumber::from_dec("36722226848982314680567811997771062638383828354047012538919806599939999127160456447237226368950393496439962666992459033698311124733744083963711166393470803955290971381911274507193981709387505523191368117187074091384646924346700638973173807722733727281592410397831676026466279786567075569837905995849670457506509424137093869661050737596446262008457839619766874798049461600065862281592856187622939978475437479264484697284570903713919546205855317475701520320262681749419906746018812343025594374083863097715974951329849978864273409720176255874977432080252739943546406857149724432737271924184396597489413743665435203185036").unwrap(),
                 "3".to_string() => BigNumber::from_dec("36722226848982314680567811997771062638383828354047012538919806599939999127160456447237226368950393496439962666992459033698311124733744083963711166393470803955290971381911274507193981709387505523191368117187074091384646924346700638973173807722733727281592410397831676026466279786567075569837905995849670457506509424137093869661050737596446262008457839619766874798049461600065862281592856187622939978475437479264484697284570903713919546205855317475701520320262681749419906746018812343025594374083863097715974951329849978864273409720176255874977432080252739943546406857149724432737271924184396597489413743665435203185036").unwrap(),
                 "DELTA".to_string() => BigNumber::from_dec("15200925076882677157789591684702017059623383056989770565868903056027181948730543992958006723308726004921912800892308236693106779956052024828189927624378588628187084092193792048585904847438401997035239363347036370831220022455446480767807526930979439902956066177870277956875422590851200730884317152112566873283886794804628965955076151434506744414935581441315505752347360465283012954289570640444309747412339681120486660356348167053880912640976118012919486038730936152926928255294036631715239230898556511907889484813751124436548299317858768444665139178324370349441645851840646275463995503285251979214896561204281531077329").unwrap()
-            ]
+            ],
+            upper_alpha_tilde: None
         }
     }
 
@@ -2290,17 +2886,20 @@ pub mod mocks {
                 "3".to_string() => BigNumber::from_dec("36722226848982314680567811997771062638383828354047012538919806599939999127160456447237226368950393496439962666992459033698311124733744083963711166393470803955290971381911274507193981709387505523191368117187074091384646924346700638973173807722733727281592410397831676026466279786567075569837905995849670457506509424137093869661050737596446262008457839619766874798049461600065862281592856187622939978475437479264484697284570903713919546205855317475701520320262681749419906746018812343025594374083863097715974951329849978864273409720176255874977432080252739943546406857149724432737271924184396597489413743665435203185036").unwrap(),
                 "DELTA".to_string() => BigNumber::from_dec("15200925076882677157789591684702017059623383056989770565868903056027181948730543992958006723308726004921912800892308236693106779956052024828189927624378588628187084092193792048585904847438401997035239363347036370831220022455446480767807526930979439902956066177870277956875422590851200730884317152112566873283886794804628965955076151434506744414935581441315505752347360465283012954289570640444309747412339681120486660356348167053880912640976118012919486038730936152926928255294036631715239230898556511907889484813751124436548299317858768444665139178324370349441645851840646275463995503285251979214896561204281531077329").unwrap()
             ],
-            predicate: predicate()
+            predicate: predicate(),
+            upper_alpha: None
         }
     }
 
     pub fn primary_proof() -> PrimaryProof {
         PrimaryProof {
             eq_proof: eq_proof(),
-            ne_proofs: vec![ne_proof()]
+            ne_proofs: vec![ne_proof()],
+            set_proofs: Vec::new()
         }
     }
 
+    #[cfg(feature = "pair_amcl")]
     pub fn init_non_revocation_proof() -> NonRevocInitProof {
         NonRevocInitProof {
             c_list_params: NonRevocProofXList {
@@ -2357,6 +2956,7 @@ pub mod mocks {
         }
     }
 
+    #[cfg(feature = "pair_amcl")]
     pub fn non_revoc_proof() -> NonRevocProof {
         NonRevocProof {
             x_list: NonRevocProofXList {
@@ -2412,7 +3012,8 @@ pub mod mocks {
         Predicate {
             attr_name: "age".to_owned(),
             p_type: PredicateType::GE,
-            value: 18
+            value: "18".to_owned(),
+            upper_value: None
         }
     }
 }
@@ -0,0 +1,879 @@
+use cl::*;
+use cl::constants::{MIN_TAILS_CHUNK_SIZE, TAILS_PREFIX_SUMS_SPOT_CHECKS};
+use cl::helpers::transform_u32_to_array_of_u8;
+use errors::IndyCryptoError;
+use pair::{Pair, PointG2};
+
+use sha2::{Sha256, Digest};
+use serde_json;
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Binary tails file format (all integers are big-endian):
+/// * magic `INDYTAIL` - 8 bytes,
+/// * format version - 2 bytes,
+/// * size of encoded tail - 2 bytes,
+/// * number of tails - 4 bytes,
+/// * tails with indexes from 0 encoded as `PointG2::to_bytes`,
+/// * SHA-256 hash of all preceding bytes - 32 bytes.
+///
+/// Hex encoded trailing hash is the canonical tails hash that is published with the registry definition.
+pub const TAILS_FILE_MAGIC: &[u8] = b"INDYTAIL";
+pub const TAILS_FILE_VERSION: u16 = 1;
+pub const TAILS_FILE_HEADER_SIZE: usize = 16;
+pub const TAILS_FILE_HASH_SIZE: usize = 32;
+
+/// Optional block prefix sums file stored next to the tails file (see `tails_prefix_sums_path`):
+/// * magic `INDYTIDX` - 8 bytes,
+/// * format version - 2 bytes,
+/// * size of encoded tail - 2 bytes,
+/// * block size - 4 bytes,
+/// * number of tails - 4 bytes,
+/// * hash of the tails file - 32 bytes,
+/// * prefix sums of tails at blocks boundaries encoded as `PointG2::to_bytes`,
+/// * SHA-256 hash of all preceding bytes - 32 bytes.
+pub const TAILS_PREFIX_SUMS_FILE_MAGIC: &[u8] = b"INDYTIDX";
+pub const TAILS_PREFIX_SUMS_FILE_VERSION: u16 = 1;
+pub const TAILS_PREFIX_SUMS_FILE_HEADER_SIZE: usize = 52;
+
+/// Writer of tails file.
+pub struct TailsFileWriter {}
+
+impl TailsFileWriter {
+    /// Writes all tails of unused generator to the tails file and returns canonical tails hash.
+    ///
+    /// # Arguments
+    /// * `rev_tails_generator` - Revocation tails generator returned by `Issuer::new_revocation_registry_def`.
+    /// * `path` - Path of the tails file to create.
+    pub fn write<P: AsRef<Path>>(rev_tails_generator: &mut RevocationTailsGenerator, path: P) -> Result<String, IndyCryptoError> {
+        TailsFileWriter::write_parallel(rev_tails_generator, path, 1)
+    }
+
+    /// Writes all tails of unused generator to the tails file generating them in `threads` threads
+    /// and returns canonical tails hash.
+    ///
+    /// # Arguments
+    /// * `rev_tails_generator` - Revocation tails generator returned by `Issuer::new_revocation_registry_def`.
+    /// * `path` - Path of the tails file to create.
+    /// * `threads` - Number of threads.
+    pub fn write_parallel<P: AsRef<Path>>(rev_tails_generator: &mut RevocationTailsGenerator,
+                                          path: P,
+                                          threads: usize) -> Result<String, IndyCryptoError> {
+        trace!("TailsFileWriter::write_parallel: >>> rev_tails_generator: {:?}, path: {:?}, threads: {:?}", rev_tails_generator, path.as_ref(), threads);
+
+        let tails_hash = _to_hex(&TailsFileWriter::_write(rev_tails_generator, path.as_ref(), threads, None, None)?);
+
+        trace!("TailsFileWriter::write_parallel: <<< tails_hash: {:?}", tails_hash);
+
+        Ok(tails_hash)
+    }
+
+    /// Writes tails to the tails file generating them in `threads` threads, saves generator
+    /// checkpoint after each written chunk and returns canonical tails hash.
+    ///
+    /// If generator is restored from the checkpoint by `RevocationTailsGenerator::load_checkpoint`
+    /// writing is resumed: tails written before the checkpoint are checked against the header
+    /// and re-hashed and the rest of the file is overwritten. Checkpoint is removed when file is written.
+    ///
+    /// # Arguments
+    /// * `rev_tails_generator` - Revocation tails generator returned by `Issuer::new_revocation_registry_def`
+    ///   or restored from the checkpoint.
+    /// * `path` - Path of the tails file to create or resume.
+    /// * `threads` - Number of threads.
+    /// * `checkpoint_path` - Path of the generator checkpoint file.
+    pub fn write_with_checkpoints<P: AsRef<Path>, C: AsRef<Path>>(rev_tails_generator: &mut RevocationTailsGenerator,
+                                                                  path: P,
+                                                                  threads: usize,
+                                                                  checkpoint_path: C) -> Result<String, IndyCryptoError> {
+        trace!("TailsFileWriter::write_with_checkpoints: >>> rev_tails_generator: {:?}, path: {:?}, threads: {:?}, checkpoint_path: {:?}",
+               rev_tails_generator, path.as_ref(), threads, checkpoint_path.as_ref());
+
+        let hash = TailsFileWriter::_write(rev_tails_generator, path.as_ref(), threads, None, Some(checkpoint_path.as_ref()))?;
+        fs::remove_file(checkpoint_path.as_ref())?;
+
+        let tails_hash = _to_hex(&hash);
+
+        trace!("TailsFileWriter::write_with_checkpoints: <<< tails_hash: {:?}", tails_hash);
+
+        Ok(tails_hash)
+    }
+
+    /// Writes all tails of unused generator to the tails file and block prefix sums of tails
+    /// to the file next to it and returns canonical tails hash.
+    /// Prefix sums allow provers to create witnesses of issuance by default registries fast.
+    ///
+    /// # Arguments
+    /// * `rev_tails_generator` - Revocation tails generator returned by `Issuer::new_revocation_registry_def`.
+    /// * `path` - Path of the tails file to create.
+    /// * `threads` - Number of threads.
+    /// * `block_size` - Number of tails in block of prefix sums.
+    pub fn write_with_prefix_sums<P: AsRef<Path>>(rev_tails_generator: &mut RevocationTailsGenerator,
+                                                  path: P,
+                                                  threads: usize,
+                                                  block_size: u32) -> Result<String, IndyCryptoError> {
+        trace!("TailsFileWriter::write_with_prefix_sums: >>> rev_tails_generator: {:?}, path: {:?}, threads: {:?}, block_size: {:?}",
+               rev_tails_generator, path.as_ref(), threads, block_size);
+
+        let mut prefix_sums = TailsPrefixSums::new(block_size)?;
+        let hash = TailsFileWriter::_write(rev_tails_generator, path.as_ref(), threads, Some(&mut prefix_sums), None)?;
+        _write_prefix_sums_file(&tails_prefix_sums_path(&path), &prefix_sums, &hash)?;
+
+        let tails_hash = _to_hex(&hash);
+
+        trace!("TailsFileWriter::write_with_prefix_sums: <<< tails_hash: {:?}", tails_hash);
+
+        Ok(tails_hash)
+    }
+
+    // Writes tails file and returns its trailing hash.
+    // Used generator is accepted only with checkpoint, in this case writing is resumed.
+    fn _write(rev_tails_generator: &mut RevocationTailsGenerator,
+              path: &Path,
+              threads: usize,
+              mut prefix_sums: Option<&mut TailsPrefixSums>,
+              checkpoint_path: Option<&Path>) -> Result<Vec<u8>, IndyCryptoError> {
+        let mut hasher = Sha256::default();
+
+        let header = _tails_file_header(rev_tails_generator.size);
+        hasher.input(&header);
+
+        let file = if rev_tails_generator.current_index == 0 {
+            let mut file = File::create(path)?;
+            file.write_all(&header)?;
+            file
+        } else if checkpoint_path.is_some() {
+            TailsFileWriter::_rehash_written_tails(rev_tails_generator, path, &header, &mut hasher, &mut prefix_sums)?
+        } else {
+            return Err(IndyCryptoError::InvalidState(format!("Revocation tails generator is already used")));
+        };
+
+        let mut writer = BufWriter::new(file);
+
+        let chunk_size = MIN_TAILS_CHUNK_SIZE.saturating_mul(::std::cmp::max(threads, 1) as u32);
+        while rev_tails_generator.count() > 0 {
+            for tail in rev_tails_generator.next_chunk(chunk_size, threads)? {
+                if let Some(ref mut prefix_sums) = prefix_sums {
+                    prefix_sums.add_tail(&tail)?;
+                }
+
+                let tail = tail.to_bytes()?;
+                hasher.input(&tail);
+                writer.write_all(&tail)?;
+            }
+
+            if let Some(checkpoint_path) = checkpoint_path {
+                // Tails must be on disk before checkpoint that refers to them
+                writer.flush()?;
+                writer.get_ref().sync_all()?;
+                rev_tails_generator.save_checkpoint(checkpoint_path)?;
+            }
+        }
+
+        let hash = hasher.result();
+        writer.write_all(&hash)?;
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+
+        Ok(hash.as_slice().to_vec())
+    }
+
+    // Checks tails written before the checkpoint, feeds them to the hasher and prefix sums
+    // and returns the file truncated after them.
+    fn _rehash_written_tails(rev_tails_generator: &RevocationTailsGenerator,
+                             path: &Path,
+                             header: &[u8],
+                             hasher: &mut Sha256,
+                             prefix_sums: &mut Option<&mut TailsPrefixSums>) -> Result<File, IndyCryptoError> {
+        let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+
+        let written_size = (TAILS_FILE_HEADER_SIZE + rev_tails_generator.current_index as usize * PointG2::BYTES_REPR_SIZE) as u64;
+        if file.metadata()?.len() < written_size {
+            return Err(IndyCryptoError::InvalidStructure(format!("Tails file is shorter than revocation tails generator checkpoint")));
+        }
+
+        file.set_len(written_size)?;
+
+        let mut last_tail = None;
+        {
+            let mut reader = BufReader::new(&file);
+
+            let mut file_header = [0u8; TAILS_FILE_HEADER_SIZE];
+            reader.read_exact(&mut file_header)?;
+
+            if &file_header[..] != header {
+                return Err(IndyCryptoError::InvalidStructure(format!("Tails file doesn't correspond to revocation tails generator checkpoint")));
+            }
+
+            let mut tail = vec![0u8; PointG2::BYTES_REPR_SIZE];
+            for _ in 0..rev_tails_generator.current_index {
+                reader.read_exact(&mut tail)?;
+                hasher.input(&tail);
+
+                if let Some(ref mut prefix_sums) = *prefix_sums {
+                    prefix_sums.add_tail(&Tail::from_bytes(&tail)?)?;
+                }
+
+                last_tail = Some(tail.clone());
+            }
+        }
+
+        // The last written tail is regenerated to make sure file was written with the same generator
+        if let Some(last_tail) = last_tail {
+            let expected_tail = Tail::new_tail(rev_tails_generator.current_index - 1,
+                                               &rev_tails_generator.g_dash,
+                                               &rev_tails_generator.gamma)?;
+            if Tail::from_bytes(&last_tail)? != expected_tail {
+                return Err(IndyCryptoError::InvalidStructure(format!("Tails file doesn't correspond to revocation tails generator checkpoint")));
+            }
+        }
+
+        file.seek(SeekFrom::End(0))?;
+
+        Ok(file)
+    }
+}
+
+/// Returns path of the block prefix sums file stored next to the tails file.
+pub fn tails_prefix_sums_path<P: AsRef<Path>>(path: P) -> PathBuf {
+    let mut prefix_sums_path = path.as_ref().as_os_str().to_owned();
+    prefix_sums_path.push(".idx");
+    PathBuf::from(prefix_sums_path)
+}
+
+impl RevocationTailsGenerator {
+    /// Saves generator state to the checkpoint file, so generation can be resumed after restart
+    /// (see `TailsFileWriter::write_with_checkpoints`).
+    /// Checkpoint is written to the temporary file first and then renamed, so it is never partially written.
+    ///
+    /// Note that checkpoint contains `gamma` and must be protected as `RevocationKeyPrivate`.
+    ///
+    /// # Arguments
+    /// * `path` - Path of the checkpoint file.
+    pub fn save_checkpoint<P: AsRef<Path>>(&self, path: P) -> Result<(), IndyCryptoError> {
+        trace!("RevocationTailsGenerator::save_checkpoint: >>> path: {:?}", path.as_ref());
+
+        let mut tmp_path = path.as_ref().as_os_str().to_owned();
+        tmp_path.push(".tmp");
+
+        {
+            let mut writer = BufWriter::new(File::create(&tmp_path)?);
+            serde_json::to_writer(&mut writer, self)?;
+            writer.flush()?;
+            writer.get_ref().sync_all()?;
+        }
+
+        fs::rename(&tmp_path, path)?;
+
+        trace!("RevocationTailsGenerator::save_checkpoint: <<<");
+
+        Ok(())
+    }
+
+    /// Restores generator state saved by `save_checkpoint`.
+    ///
+    /// # Arguments
+    /// * `path` - Path of the checkpoint file.
+    pub fn load_checkpoint<P: AsRef<Path>>(path: P) -> Result<RevocationTailsGenerator, IndyCryptoError> {
+        trace!("RevocationTailsGenerator::load_checkpoint: >>> path: {:?}", path.as_ref());
+
+        let rev_tails_generator: RevocationTailsGenerator = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+
+        if rev_tails_generator.current_index > rev_tails_generator.size {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid revocation tails generator checkpoint")));
+        }
+
+        trace!("RevocationTailsGenerator::load_checkpoint: <<< rev_tails_generator: {:?}", rev_tails_generator);
+
+        Ok(rev_tails_generator)
+    }
+}
+
+/// Implementation of `RevocationTailsAccessor` that reads tails from the tails file on demand,
+/// so tails of large registries are never loaded to memory at once.
+#[derive(Debug)]
+pub struct FileTailsAccessor {
+    file: File,
+    tails_count: u32,
+    tails_hash: String,
+    prefix_sums: Option<TailsPrefixSums>
+}
+
+impl FileTailsAccessor {
+    // Opens tails file written by `TailsFileWriter` and checks its header and trailing hash.
+    // Block prefix sums are loaded from the file next to the tails file if it exists.
+    //
+    // Prefix sums file isn't covered by the published tails hash, so several random blocks of it
+    // are checked against the tails. Prefix sums file that is broken or fails the check is ignored
+    // and tails are summed one by one. Note that undetected wrong prefix sums can only produce
+    // a witness that fails proof verification.
+    fn _open<P: AsRef<Path>>(path: P) -> Result<FileTailsAccessor, IndyCryptoError> {
+        trace!("FileTailsAccessor::_open: >>> path: {:?}", path.as_ref());
+
+        let file = File::open(path.as_ref())?;
+        let file_size = file.metadata()?.len();
+
+        let mut reader = BufReader::new(&file);
+        let mut hasher = Sha256::default();
+
+        let mut header = [0u8; TAILS_FILE_HEADER_SIZE];
+        reader.read_exact(&mut header)?;
+        hasher.input(&header);
+
+        let tails_count = _parse_tails_file_header(&header)?;
+
+        if file_size != (TAILS_FILE_HEADER_SIZE + tails_count as usize * PointG2::BYTES_REPR_SIZE + TAILS_FILE_HASH_SIZE) as u64 {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid tails file size {}", file_size)));
+        }
+
+        let mut tail = vec![0u8; PointG2::BYTES_REPR_SIZE];
+        for _ in 0..tails_count {
+            reader.read_exact(&mut tail)?;
+            hasher.input(&tail);
+        }
+
+        let mut hash = [0u8; TAILS_FILE_HASH_SIZE];
+        reader.read_exact(&mut hash)?;
+
+        if hasher.result().as_slice() != &hash[..] {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid tails file hash")));
+        }
+
+        let tails_hash = _to_hex(&hash);
+        let mut file_tails_accessor = FileTailsAccessor { file, tails_count, tails_hash, prefix_sums: None };
+
+        let prefix_sums_path = tails_prefix_sums_path(&path);
+        if prefix_sums_path.exists() {
+            let prefix_sums = _read_prefix_sums_file(&prefix_sums_path, &hash, tails_count)
+                .and_then(|prefix_sums| {
+                    prefix_sums.spot_check(&file_tails_accessor, TAILS_PREFIX_SUMS_SPOT_CHECKS)?;
+                    Ok(prefix_sums)
+                });
+
+            match prefix_sums {
+                Ok(prefix_sums) => file_tails_accessor.prefix_sums = Some(prefix_sums),
+                Err(err) => warn!("FileTailsAccessor::_open: tails prefix sums file {:?} is ignored: {:?}", prefix_sums_path, err)
+            }
+        }
+
+        trace!("FileTailsAccessor::_open: <<< file_tails_accessor: {:?}", file_tails_accessor);
+
+        Ok(file_tails_accessor)
+    }
+
+    /// Opens tails file written by `TailsFileWriter`, checks it against the published tails hash
+    /// and checks only the endpoints of the file against the revocation registry: the first tail
+    /// against `g'` and the tail `L+1` against `z` of the registry public key. Other tails are trusted
+    /// to be the powers of `gamma` as far as the published tails hash is trusted.
+    /// This is the only way to open tails file, so tails from the file that fails any check are never served.
+    ///
+    /// Block prefix sums are loaded from the file next to the tails file if it exists.
+    /// Prefix sums file isn't covered by the published tails hash, so several random blocks of it
+    /// are checked against the tails and the file that is broken or fails the check is ignored.
+    ///
+    /// # Arguments
+    /// * `path` - Path of the tails file.
+    /// * `tails_hash` - Canonical tails hash published with the registry definition.
+    /// * `credential_pub_key` - Credential public key with revocation part.
+    /// * `rev_key_pub` - Revocation registry public key.
+    /// * `max_cred_num` - Max credential number in the revocation registry.
+    pub fn open_with_endpoint_checks<P: AsRef<Path>>(path: P,
+                                                     tails_hash: &str,
+                                                     credential_pub_key: &CredentialPublicKey,
+                                                     rev_key_pub: &RevocationKeyPublic,
+                                                     max_cred_num: u32) -> Result<FileTailsAccessor, IndyCryptoError> {
+        trace!("FileTailsAccessor::open_with_endpoint_checks: >>> path: {:?}, tails_hash: {:?}, credential_pub_key: {:?}, rev_key_pub: {:?}, max_cred_num: {:?}",
+               path.as_ref(), tails_hash, credential_pub_key, rev_key_pub, max_cred_num);
+
+        let file_tails_accessor = FileTailsAccessor::_open(path)?;
+        file_tails_accessor._check_endpoints(tails_hash, credential_pub_key, rev_key_pub, max_cred_num)?;
+
+        trace!("FileTailsAccessor::open_with_endpoint_checks: <<< file_tails_accessor: {:?}", file_tails_accessor);
+
+        Ok(file_tails_accessor)
+    }
+
+    // Checks that tails file has the published tails hash and that its endpoints belong to the revocation registry.
+    //
+    // Only tails `g'^{gamma^0}` and `g'^{gamma^{L+1}}` are checked against `g'`
+    // and `z = e(g, g')^{gamma^{L+1}}` with pairing. Other tails can't be checked
+    // without `g^gamma` that isn't published, so they are bound only by the tails hash.
+    fn _check_endpoints(&self,
+                        tails_hash: &str,
+                        credential_pub_key: &CredentialPublicKey,
+                        rev_key_pub: &RevocationKeyPublic,
+                        max_cred_num: u32) -> Result<(), IndyCryptoError> {
+        trace!("FileTailsAccessor::_check_endpoints: >>> tails_hash: {:?}, credential_pub_key: {:?}, rev_key_pub: {:?}, max_cred_num: {:?}",
+               tails_hash, credential_pub_key, rev_key_pub, max_cred_num);
+
+        let cred_rev_pub_key: &CredentialRevocationPublicKey = credential_pub_key.r_key
+            .as_ref()
+            .ok_or(IndyCryptoError::InvalidStructure(format!("There are not revocation keys in the credential public key.")))?;
+
+        if self.tails_hash != tails_hash.to_lowercase() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Tails file hash doesn't match published tails hash")));
+        }
+
+        if self.tails_count as u64 != 2 * max_cred_num as u64 + 1 {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid number of tails {} for max credential number {}",
+                                                                 self.tails_count, max_cred_num)));
+        }
+
+        if self._read_tail(0)? != cred_rev_pub_key.g_dash {
+            return Err(IndyCryptoError::InvalidStructure(format!("Tails file doesn't correspond to the credential public key")));
+        }
+
+        let tail = self._read_tail(max_cred_num + 1)?;
+        if Pair::pair(&cred_rev_pub_key.g, &tail)? != rev_key_pub.z {
+            return Err(IndyCryptoError::InvalidStructure(format!("Tails file doesn't correspond to the revocation registry public key")));
+        }
+
+        trace!("FileTailsAccessor::_check_endpoints: <<<");
+
+        Ok(())
+    }
+
+    pub fn tails_count(&self) -> u32 {
+        self.tails_count
+    }
+
+    /// Returns block prefix sums of tails loaded from the file next to the tails file.
+    pub fn prefix_sums(&self) -> Option<&TailsPrefixSums> {
+        self.prefix_sums.as_ref()
+    }
+
+    /// Returns canonical tails hash of the file.
+    pub fn tails_hash(&self) -> &str {
+        &self.tails_hash
+    }
+
+    fn _read_tail(&self, tail_id: u32) -> Result<Tail, IndyCryptoError> {
+        if tail_id >= self.tails_count {
+            return Err(IndyCryptoError::InvalidStructure(format!("Tail {} is out of tails file range", tail_id)));
+        }
+
+        let offset = (TAILS_FILE_HEADER_SIZE + tail_id as usize * PointG2::BYTES_REPR_SIZE) as u64;
+        let mut tail = vec![0u8; PointG2::BYTES_REPR_SIZE];
+        _read_exact_at(&self.file, &mut tail, offset)?;
+
+        Tail::from_bytes(&tail)
+    }
+}
+
+impl RevocationTailsAccessor for FileTailsAccessor {
+    fn access_tail(&self, tail_id: u32, accessor: &mut FnMut(&Tail)) -> Result<(), IndyCryptoError> {
+        let tail = self._read_tail(tail_id)?;
+        Ok(accessor(&tail))
+    }
+
+    fn access_tails_sum(&self, from: u32, to: u32) -> Result<Option<Tail>, IndyCryptoError> {
+        match self.prefix_sums {
+            Some(ref prefix_sums) => Ok(Some(prefix_sums.range_sum(from, to, self)?)),
+            None => Ok(None)
+        }
+    }
+}
+
+fn _write_prefix_sums_file(path: &Path, prefix_sums: &TailsPrefixSums, tails_file_hash: &[u8]) -> Result<(), IndyCryptoError> {
+    let mut content: Vec<u8> = Vec::new();
+    content.extend_from_slice(TAILS_PREFIX_SUMS_FILE_MAGIC);
+    content.extend_from_slice(&_u16_to_bytes(TAILS_PREFIX_SUMS_FILE_VERSION));
+    content.extend_from_slice(&_u16_to_bytes(PointG2::BYTES_REPR_SIZE as u16));
+    content.extend_from_slice(&transform_u32_to_array_of_u8(prefix_sums.block_size));
+    content.extend_from_slice(&transform_u32_to_array_of_u8(prefix_sums.tails_count));
+    content.extend_from_slice(tails_file_hash);
+
+    for sum in prefix_sums.sums.iter() {
+        content.extend_from_slice(&sum.to_bytes()?);
+    }
+
+    let mut hasher = Sha256::default();
+    hasher.input(&content);
+    content.extend_from_slice(hasher.result().as_slice());
+
+    let mut file = File::create(path)?;
+    file.write_all(&content)?;
+    file.flush()?;
+
+    Ok(())
+}
+
+// Reads prefix sums and checks that they belong to the tails file with given hash
+fn _read_prefix_sums_file(path: &Path, tails_file_hash: &[u8], tails_count: u32) -> Result<TailsPrefixSums, IndyCryptoError> {
+    let mut content: Vec<u8> = Vec::new();
+    File::open(path)?.read_to_end(&mut content)?;
+
+    if content.len() < TAILS_PREFIX_SUMS_FILE_HEADER_SIZE + TAILS_FILE_HASH_SIZE {
+        return Err(IndyCryptoError::InvalidStructure(format!("Invalid tails prefix sums file size {}", content.len())));
+    }
+
+    let (content, hash) = content.split_at(content.len() - TAILS_FILE_HASH_SIZE);
+
+    let mut hasher = Sha256::default();
+    hasher.input(content);
+    if hasher.result().as_slice() != hash {
+        return Err(IndyCryptoError::InvalidStructure(format!("Invalid tails prefix sums file hash")));
+    }
+
+    let (header, sums) = content.split_at(TAILS_PREFIX_SUMS_FILE_HEADER_SIZE);
+
+    if &header[0..8] != TAILS_PREFIX_SUMS_FILE_MAGIC {
+        return Err(IndyCryptoError::InvalidStructure(format!("Invalid tails prefix sums file magic")));
+    }
+
+    let version = _u16_from_bytes(&header[8..10]);
+    if version != TAILS_PREFIX_SUMS_FILE_VERSION {
+        return Err(IndyCryptoError::InvalidStructure(format!("Unsupported tails prefix sums file version {}", version)));
+    }
+
+    let tail_size = _u16_from_bytes(&header[10..12]);
+    if tail_size as usize != PointG2::BYTES_REPR_SIZE || sums.len() % PointG2::BYTES_REPR_SIZE != 0 {
+        return Err(IndyCryptoError::InvalidStructure(format!("Invalid tail size {}", tail_size)));
+    }
+
+    if _u32_from_bytes(&header[16..20]) != tails_count || &header[20..52] != tails_file_hash {
+        return Err(IndyCryptoError::InvalidStructure(format!("Tails prefix sums file doesn't belong to the tails file")));
+    }
+
+    let sums = sums
+        .chunks(PointG2::BYTES_REPR_SIZE)
+        .map(|sum| Tail::from_bytes(sum))
+        .collect::<Result<Vec<Tail>, IndyCryptoError>>()?;
+
+    TailsPrefixSums::from_parts(_u32_from_bytes(&header[12..16]), tails_count, sums)
+}
+
+fn _tails_file_header(tails_count: u32) -> Vec<u8> {
+    let mut header: Vec<u8> = Vec::with_capacity(TAILS_FILE_HEADER_SIZE);
+    header.extend_from_slice(TAILS_FILE_MAGIC);
+    header.extend_from_slice(&_u16_to_bytes(TAILS_FILE_VERSION));
+    header.extend_from_slice(&_u16_to_bytes(PointG2::BYTES_REPR_SIZE as u16));
+    header.extend_from_slice(&transform_u32_to_array_of_u8(tails_count));
+    header
+}
+
+// Checks tails file header and returns number of tails
+fn _parse_tails_file_header(header: &[u8]) -> Result<u32, IndyCryptoError> {
+    if &header[0..8] != TAILS_FILE_MAGIC {
+        return Err(IndyCryptoError::InvalidStructure(format!("Invalid tails file magic")));
+    }
+
+    let version = _u16_from_bytes(&header[8..10]);
+    if version != TAILS_FILE_VERSION {
+        return Err(IndyCryptoError::InvalidStructure(format!("Unsupported tails file version {}", version)));
+    }
+
+    let tail_size = _u16_from_bytes(&header[10..12]);
+    if tail_size as usize != PointG2::BYTES_REPR_SIZE {
+        return Err(IndyCryptoError::InvalidStructure(format!("Invalid tail size {}", tail_size)));
+    }
+
+    Ok(_u32_from_bytes(&header[12..16]))
+}
+
+fn _to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn _u16_to_bytes(value: u16) -> [u8; 2] {
+    [(value >> 8) as u8, value as u8]
+}
+
+fn _u16_from_bytes(bytes: &[u8]) -> u16 {
+    (bytes[0] as u16) << 8 | bytes[1] as u16
+}
+
+fn _u32_from_bytes(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0u32, |acc, &b| (acc << 8) | b as u32)
+}
+
+#[cfg(unix)]
+fn _read_exact_at(file: &File, mut buf: &mut [u8], mut offset: u64) -> Result<(), IndyCryptoError> {
+    use std::os::unix::fs::FileExt;
+
+    while !buf.is_empty() {
+        let read = file.read_at(buf, offset)?;
+        if read == 0 {
+            return Err(IndyCryptoError::InvalidStructure(format!("Unexpected end of tails file")));
+        }
+        let tmp = buf;
+        buf = &mut tmp[read..];
+        offset += read as u64;
+    }
+
+    Ok(())
+}
+
+// Positional reads are not available, so the shared cursor of the file is used
+#[cfg(not(unix))]
+fn _read_exact_at(file: &File, buf: &mut [u8], offset: u64) -> Result<(), IndyCryptoError> {
+    use std::io::{Seek, SeekFrom};
+
+    let mut file = file;
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(buf)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cl::issuer::{Issuer, mocks as issuer_mocks};
+    use pair::GroupOrderElement;
+
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    fn _tails_file_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("indy_crypto_{}.tails", name))
+    }
+
+    fn _rev_tails_generator() -> RevocationTailsGenerator {
+        RevocationTailsGenerator::new(5, GroupOrderElement::new().unwrap(), PointG2::new().unwrap())
+    }
+
+    #[test]
+    fn file_tails_accessor_works() {
+        let path = _tails_file_path("file_tails_accessor_works");
+
+        let mut rev_tails_generator = _rev_tails_generator();
+        let simple_tails_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator.clone()).unwrap();
+        let tails_hash = TailsFileWriter::write(&mut rev_tails_generator, &path).unwrap();
+        assert_eq!(0, rev_tails_generator.count());
+
+        let file_tails_accessor = FileTailsAccessor::_open(&path).unwrap();
+        assert_eq!(11, file_tails_accessor.tails_count());
+        assert_eq!(tails_hash, file_tails_accessor.tails_hash());
+        assert_eq!(TAILS_FILE_HASH_SIZE * 2, tails_hash.len());
+
+        for tail_id in 0..file_tails_accessor.tails_count() {
+            let mut tail = None;
+            file_tails_accessor.access_tail(tail_id, &mut |t| tail = Some(*t)).unwrap();
+            simple_tails_accessor.access_tail(tail_id, &mut |t| assert_eq!(tail.unwrap(), *t)).unwrap();
+        }
+
+        assert!(file_tails_accessor.access_tail(11, &mut |_| ()).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn tails_file_writer_works_for_used_generator() {
+        let path = _tails_file_path("tails_file_writer_works_for_used_generator");
+
+        let mut rev_tails_generator = _rev_tails_generator();
+        rev_tails_generator.next().unwrap();
+
+        assert!(TailsFileWriter::write(&mut rev_tails_generator, &path).is_err());
+    }
+
+    #[test]
+    fn tails_file_writer_write_parallel_works() {
+        let path = _tails_file_path("tails_file_writer_write_parallel_works");
+        let parallel_path = _tails_file_path("tails_file_writer_write_parallel_works_parallel");
+
+        let mut rev_tails_generator = _rev_tails_generator();
+        let tails_hash = TailsFileWriter::write(&mut rev_tails_generator.clone(), &path).unwrap();
+        let parallel_tails_hash = TailsFileWriter::write_parallel(&mut rev_tails_generator, &parallel_path, 3).unwrap();
+
+        assert_eq!(tails_hash, parallel_tails_hash);
+        assert_eq!(fs::read(&path).unwrap(), fs::read(&parallel_path).unwrap());
+
+        fs::remove_file(&path).unwrap();
+        fs::remove_file(&parallel_path).unwrap();
+    }
+
+    #[test]
+    fn revocation_tails_generator_checkpoint_works() {
+        let path = env::temp_dir().join("indy_crypto_revocation_tails_generator_checkpoint_works.json");
+
+        let mut rev_tails_generator = _rev_tails_generator();
+        let simple_tails_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator.clone()).unwrap();
+
+        let mut tails = rev_tails_generator.next_chunk(4, 2).unwrap();
+        rev_tails_generator.save_checkpoint(&path).unwrap();
+
+        let mut restored_rev_tails_generator = RevocationTailsGenerator::load_checkpoint(&path).unwrap();
+        assert_eq!(7, restored_rev_tails_generator.count());
+        tails.extend(restored_rev_tails_generator.next_chunk(7, 2).unwrap());
+
+        assert_eq!(11, tails.len());
+        for (tail_id, tail) in tails.iter().enumerate() {
+            simple_tails_accessor.access_tail(tail_id as u32, &mut |t| assert_eq!(tail, t)).unwrap();
+        }
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn tails_file_writer_write_with_checkpoints_works_for_resumed_write() {
+        let path = _tails_file_path("tails_file_writer_write_with_checkpoints_works_for_resumed_write");
+        let resumed_path = _tails_file_path("tails_file_writer_write_with_checkpoints_works_for_resumed_write_resumed");
+        let checkpoint_path = env::temp_dir().join("indy_crypto_tails_file_writer_write_with_checkpoints_works_for_resumed_write.json");
+
+        let mut rev_tails_generator = _rev_tails_generator();
+        let tails_hash = TailsFileWriter::write(&mut rev_tails_generator.clone(), &path).unwrap();
+
+        // Simulates write interrupted after checkpoint of 4 tails with partially written 5th tail
+        {
+            let mut file = File::create(&resumed_path).unwrap();
+            file.write_all(&_tails_file_header(rev_tails_generator.count())).unwrap();
+            for tail in rev_tails_generator.next_chunk(4, 2).unwrap() {
+                file.write_all(&tail.to_bytes().unwrap()).unwrap();
+            }
+            file.write_all(&[1, 2, 3]).unwrap();
+        }
+        rev_tails_generator.save_checkpoint(&checkpoint_path).unwrap();
+
+        let mut restored_rev_tails_generator = RevocationTailsGenerator::load_checkpoint(&checkpoint_path).unwrap();
+        let resumed_tails_hash = TailsFileWriter::write_with_checkpoints(&mut restored_rev_tails_generator,
+                                                                         &resumed_path,
+                                                                         2,
+                                                                         &checkpoint_path).unwrap();
+
+        assert_eq!(tails_hash, resumed_tails_hash);
+        assert_eq!(fs::read(&path).unwrap(), fs::read(&resumed_path).unwrap());
+        assert!(!checkpoint_path.exists());
+
+        fs::remove_file(&path).unwrap();
+        fs::remove_file(&resumed_path).unwrap();
+    }
+
+    #[test]
+    fn tails_file_writer_write_with_checkpoints_works_for_other_generator() {
+        let path = _tails_file_path("tails_file_writer_write_with_checkpoints_works_for_other_generator");
+        let checkpoint_path = env::temp_dir().join("indy_crypto_tails_file_writer_write_with_checkpoints_works_for_other_generator.json");
+
+        let mut rev_tails_generator = _rev_tails_generator();
+        {
+            let mut file = File::create(&path).unwrap();
+            file.write_all(&_tails_file_header(rev_tails_generator.count())).unwrap();
+            for tail in _rev_tails_generator().next_chunk(4, 1).unwrap() {
+                file.write_all(&tail.to_bytes().unwrap()).unwrap();
+            }
+        }
+        rev_tails_generator.next_chunk(4, 1).unwrap();
+        rev_tails_generator.save_checkpoint(&checkpoint_path).unwrap();
+
+        assert!(TailsFileWriter::write_with_checkpoints(&mut rev_tails_generator, &path, 1, &checkpoint_path).is_err());
+
+        fs::remove_file(&path).unwrap();
+        fs::remove_file(&checkpoint_path).unwrap();
+    }
+
+    #[test]
+    fn file_tails_accessor_works_for_prefix_sums() {
+        let path = _tails_file_path("file_tails_accessor_works_for_prefix_sums");
+        let prefix_sums_path = tails_prefix_sums_path(&path);
+
+        let mut rev_tails_generator = _rev_tails_generator();
+        let simple_tails_accessor = SimpleTailsAccessor::new_with_prefix_sums(&mut rev_tails_generator.clone(), 3).unwrap();
+        let tails_hash = TailsFileWriter::write_with_prefix_sums(&mut rev_tails_generator, &path, 2, 3).unwrap();
+        assert_eq!(tails_hash, FileTailsAccessor::_open(&path).unwrap().tails_hash());
+
+        let file_tails_accessor = FileTailsAccessor::_open(&path).unwrap();
+        assert_eq!(simple_tails_accessor.prefix_sums.as_ref().unwrap().sums, file_tails_accessor.prefix_sums().unwrap().sums);
+        assert_eq!(simple_tails_accessor.access_tails_sum(2, 9).unwrap(), file_tails_accessor.access_tails_sum(2, 9).unwrap());
+
+        let mut content = fs::read(&prefix_sums_path).unwrap();
+        content[TAILS_PREFIX_SUMS_FILE_HEADER_SIZE] ^= 1;
+        fs::write(&prefix_sums_path, &content).unwrap();
+        let file_tails_accessor = FileTailsAccessor::_open(&path).unwrap();
+        assert!(file_tails_accessor.prefix_sums().is_none());
+        assert!(file_tails_accessor.access_tails_sum(2, 9).unwrap().is_none());
+
+        fs::remove_file(&prefix_sums_path).unwrap();
+        let file_tails_accessor = FileTailsAccessor::_open(&path).unwrap();
+        assert!(file_tails_accessor.prefix_sums().is_none());
+        assert!(file_tails_accessor.access_tails_sum(2, 9).unwrap().is_none());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn file_tails_accessor_open_works_for_forged_prefix_sums() {
+        let path = _tails_file_path("file_tails_accessor_open_works_for_forged_prefix_sums");
+        let prefix_sums_path = tails_prefix_sums_path(&path);
+
+        let mut rev_tails_generator = _rev_tails_generator();
+        let mut simple_tails_accessor = SimpleTailsAccessor::new_with_prefix_sums(&mut rev_tails_generator.clone(), 3).unwrap();
+        TailsFileWriter::write_with_prefix_sums(&mut rev_tails_generator, &path, 1, 3).unwrap();
+
+        // Prefix sums file with valid own hash, but with wrong sum of the last block
+        let mut hash = [0u8; TAILS_FILE_HASH_SIZE];
+        hash.copy_from_slice(&fs::read(&path).unwrap()[11 * PointG2::BYTES_REPR_SIZE + TAILS_FILE_HEADER_SIZE..]);
+        {
+            let prefix_sums = simple_tails_accessor.prefix_sums.as_mut().unwrap();
+            prefix_sums.sums[3] = prefix_sums.sums[3].add(&PointG2::new().unwrap()).unwrap();
+            _write_prefix_sums_file(&prefix_sums_path, prefix_sums, &hash).unwrap();
+        }
+
+        let file_tails_accessor = FileTailsAccessor::_open(&path).unwrap();
+        assert!(file_tails_accessor.prefix_sums().is_none());
+
+        fs::remove_file(&prefix_sums_path).unwrap();
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn file_tails_accessor_open_works_for_corrupted_file() {
+        let path = _tails_file_path("file_tails_accessor_open_works_for_corrupted_file");
+        TailsFileWriter::write(&mut _rev_tails_generator(), &path).unwrap();
+
+        let mut content = fs::read(&path).unwrap();
+        content[TAILS_FILE_HEADER_SIZE] ^= 1;
+        fs::write(&path, &content).unwrap();
+        assert!(FileTailsAccessor::_open(&path).is_err());
+
+        content[TAILS_FILE_HEADER_SIZE] ^= 1;
+        content.pop();
+        fs::write(&path, &content).unwrap();
+        assert!(FileTailsAccessor::_open(&path).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn file_tails_accessor_open_with_endpoint_checks_works() {
+        let path = _tails_file_path("file_tails_accessor_open_with_endpoint_checks_works");
+
+        let (credential_pub_key, _, _) = Issuer::new_credential_def(&issuer_mocks::credential_schema(),
+                                                                    &issuer_mocks::non_credential_schema(),
+                                                                    true).unwrap();
+        let (rev_key_pub, _, _, mut rev_tails_generator) = Issuer::new_revocation_registry_def(&credential_pub_key, 5, false).unwrap();
+        let tails_hash = TailsFileWriter::write(&mut rev_tails_generator, &path).unwrap();
+
+        let file_tails_accessor = FileTailsAccessor::open_with_endpoint_checks(&path, &tails_hash, &credential_pub_key, &rev_key_pub, 5).unwrap();
+        assert_eq!(11, file_tails_accessor.tails_count());
+
+        assert!(FileTailsAccessor::open_with_endpoint_checks(&path, &tails_hash.to_uppercase(), &credential_pub_key, &rev_key_pub, 5).is_ok());
+        assert!(FileTailsAccessor::open_with_endpoint_checks(&path, &tails_hash, &credential_pub_key, &rev_key_pub, 4).is_err());
+
+        let (other_rev_key_pub, _, _, _) = Issuer::new_revocation_registry_def(&credential_pub_key, 5, false).unwrap();
+        assert!(FileTailsAccessor::open_with_endpoint_checks(&path, &tails_hash, &credential_pub_key, &other_rev_key_pub, 5).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn file_tails_accessor_open_with_endpoint_checks_works_for_other_tails_hash() {
+        let path = _tails_file_path("file_tails_accessor_open_with_endpoint_checks_works_for_other_tails_hash");
+
+        let (credential_pub_key, _, _) = Issuer::new_credential_def(&issuer_mocks::credential_schema(),
+                                                                    &issuer_mocks::non_credential_schema(),
+                                                                    true).unwrap();
+        let (rev_key_pub, _, _, mut rev_tails_generator) = Issuer::new_revocation_registry_def(&credential_pub_key, 5, false).unwrap();
+        TailsFileWriter::write(&mut rev_tails_generator, &path).unwrap();
+
+        let other_tails_hash = TailsFileWriter::write(&mut _rev_tails_generator(), &_tails_file_path("other_tails_hash")).unwrap();
+        fs::remove_file(&_tails_file_path("other_tails_hash")).unwrap();
+
+        assert!(FileTailsAccessor::open_with_endpoint_checks(&path, &other_tails_hash, &credential_pub_key, &rev_key_pub, 5).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,111 @@
+use bn::BigNumber;
+use cl::hash::get_hash_as_int;
+use errors::IndyCryptoError;
+
+/// Fiat-Shamir transcript (in the spirit of Merlin transcripts).
+///
+/// Every message is appended as `label length || label || message length || message`,
+/// so different sequences of labeled messages never produce the same hash input.
+#[derive(Debug)]
+pub struct Transcript {
+    frames: Vec<Vec<u8>>
+}
+
+impl Transcript {
+    /// Creates transcript separated from transcripts of other protocols by domain label.
+    pub fn new(domain: &[u8]) -> Transcript {
+        let mut transcript = Transcript { frames: Vec::new() };
+        transcript.append_message(b"dom-sep", domain);
+        transcript
+    }
+
+    pub fn append_message(&mut self, label: &[u8], message: &[u8]) {
+        let mut frame: Vec<u8> = Vec::with_capacity(16 + label.len() + message.len());
+        frame.extend_from_slice(&_encode_u64(label.len() as u64));
+        frame.extend_from_slice(label);
+        frame.extend_from_slice(&_encode_u64(message.len() as u64));
+        frame.extend_from_slice(message);
+        self.frames.push(frame);
+    }
+
+    pub fn append_u64(&mut self, label: &[u8], value: u64) {
+        self.append_message(label, &_encode_u64(value));
+    }
+
+    /// Appends number of messages under the list label followed by messages under their own labels.
+    pub fn append_list(&mut self, label: &[u8], messages: &[(&[u8], Vec<u8>)]) {
+        self.append_u64(label, messages.len() as u64);
+        for &(message_label, ref message) in messages.iter() {
+            self.append_message(message_label, message);
+        }
+    }
+
+    /// Returns challenge: hash of all messages appended to the transcript followed by challenge label.
+    pub fn challenge(mut self, label: &[u8]) -> Result<BigNumber, IndyCryptoError> {
+        self.append_message(label, &[]);
+        get_hash_as_int(&self.frames)
+    }
+}
+
+fn _encode_u64(value: u64) -> [u8; 8] {
+    let mut bytes = [0u8; 8];
+    for i in 0..8 {
+        bytes[i] = (value >> (56 - 8 * i)) as u8;
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _challenge(messages: &[(&[u8], &[u8])]) -> BigNumber {
+        let mut transcript = Transcript::new(b"domain");
+        for &(label, message) in messages.iter() {
+            transcript.append_message(label, message);
+        }
+        transcript.challenge(b"challenge").unwrap()
+    }
+
+    #[test]
+    fn challenge_works() {
+        let challenge = _challenge(&[(b"a", b"bc")]);
+        assert_eq!(challenge, _challenge(&[(b"a", b"bc")]));
+        assert!(challenge.num_bits().unwrap() <= 256);
+    }
+
+    #[test]
+    fn challenge_works_for_ambiguous_concatenation() {
+        let challenge = _challenge(&[(b"a", b"bc")]);
+        assert_ne!(challenge, _challenge(&[(b"ab", b"c")]));
+        assert_ne!(challenge, _challenge(&[(b"a", b"b"), (b"a", b"c")]));
+        assert_ne!(_challenge(&[(b"a", b"")]), _challenge(&[]));
+    }
+
+    #[test]
+    fn challenge_works_for_different_domains() {
+        let challenge = Transcript::new(b"domain").challenge(b"challenge").unwrap();
+        assert_ne!(challenge, Transcript::new(b"other domain").challenge(b"challenge").unwrap());
+        assert_ne!(challenge, Transcript::new(b"domain").challenge(b"other challenge").unwrap());
+    }
+
+    #[test]
+    fn append_list_works() {
+        let mut transcript = Transcript::new(b"domain");
+        transcript.append_list(b"list", &[(&b"first"[..], vec![1, 2]), (&b"second"[..], vec![3])]);
+        let challenge = transcript.challenge(b"challenge").unwrap();
+
+        let mut other_transcript = Transcript::new(b"domain");
+        other_transcript.append_list(b"list", &[(&b"first"[..], vec![1]), (&b"second"[..], vec![2, 3])]);
+        assert_ne!(challenge, other_transcript.challenge(b"challenge").unwrap());
+
+        let mut relabeled_transcript = Transcript::new(b"domain");
+        relabeled_transcript.append_list(b"list", &[(&b"first"[..], vec![1, 2]), (&b"first"[..], vec![3])]);
+        assert_ne!(challenge, relabeled_transcript.challenge(b"challenge").unwrap());
+    }
+
+    #[test]
+    fn encode_u64_works() {
+        assert_eq!([0, 0, 0, 0, 0, 0, 1, 2], _encode_u64(258));
+    }
+}
@@ -1,11 +1,14 @@
 use bn::BigNumber;
 use cl::*;
-use cl::constants::{LARGE_E_START_VALUE, ITERATION};
+use cl::constants::{LARGE_E_START_VALUE, LARGE_CHALLENGE, ITERATION, NON_REVOCATION_PROOF_LABEL, PRIMARY_EQUAL_PROOF_LABEL,
+                    PRIMARY_PREDICATE_PROOF_LABEL, SET_MEMBERSHIP_PROOF_LABEL,
+                    LINEAR_PREDICATE_PROOF_LABEL, VERIFIABLE_ENCRYPTION_PROOF_LABEL};
+use cl::commitment::get_pedersen_commitment;
+use cl::encoding::AttributeValue;
 use cl::helpers::*;
-use cl::hash::get_hash_as_int;
 use errors::IndyCryptoError;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::iter::FromIterator;
 
 /// Party that wants to check that prover has some credentials provided by issuer.
@@ -45,14 +48,45 @@ impl Verifier {
     pub fn new_proof_verifier() -> Result<ProofVerifier, IndyCryptoError> {
         Ok(ProofVerifier {
             credentials: Vec::new(),
+            attr_equalities: BTreeSet::new(),
+            linear_predicates: Vec::new(),
+            verifiable_encryptions: Vec::new(),
+            raw_revealed_values: BTreeMap::new(),
+            min_proof_version: LEGACY_PROOF_VERSION,
         })
     }
+
+    /// Creates and returns linear predicate entity builder.
+    ///
+    /// The purpose of linear predicate builder is building of predicate over linear combination
+    /// of hidden attributes of (possibly) different sub proofs.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::verifier::Verifier;
+    ///
+    /// // income > 3 * rent
+    /// let mut linear_predicate_builder = Verifier::new_linear_predicate_builder().unwrap();
+    /// linear_predicate_builder.add_term(0, "income", 1).unwrap();
+    /// linear_predicate_builder.add_term(1, "rent", -3).unwrap();
+    /// linear_predicate_builder.set_predicate("GT", 0).unwrap();
+    /// let _linear_predicate = linear_predicate_builder.finalize().unwrap();
+    /// ```
+    pub fn new_linear_predicate_builder() -> Result<LinearPredicateBuilder, IndyCryptoError> {
+        let res = LinearPredicateBuilder::new()?;
+        Ok(res)
+    }
 }
 
 
 #[derive(Debug)]
 pub struct ProofVerifier {
     credentials: Vec<VerifiableCredential>,
+    attr_equalities: BTreeSet<AttributeEquality>,
+    linear_predicates: Vec<LinearPredicate>,
+    verifiable_encryptions: Vec<VerifiableEncryption>,
+    raw_revealed_values: BTreeMap<AttributeReference, AttributeValue>,
+    min_proof_version: u32,
 }
 
 impl ProofVerifier {
@@ -116,6 +150,102 @@ impl ProofVerifier {
         Ok(())
     }
 
+    /// Add requirement that two hidden attributes of sub proofs are equal.
+    /// Sub proofs are referred by the order they were added in.
+    ///
+    /// # Arguments
+    /// * `attr_equality` - Attribute equality.
+    ///
+    /// #Example
+    /// ```
+    /// use indy_crypto::cl::AttributeEquality;
+    /// use indy_crypto::cl::verifier::Verifier;
+    ///
+    /// let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+    /// proof_verifier.add_attribute_equality(&AttributeEquality::new(0, "name", 1, "name").unwrap()).unwrap();
+    /// ```
+    pub fn add_attribute_equality(&mut self, attr_equality: &AttributeEquality) -> Result<(), IndyCryptoError> {
+        self.attr_equalities.insert(attr_equality.clone());
+        Ok(())
+    }
+
+    /// Add linear predicate over hidden attributes of sub proofs.
+    /// The order of linear predicates is important: both Prover and Verifier should use the same order.
+    ///
+    /// # Arguments
+    /// * `linear_predicate` - Linear predicate.
+    pub fn add_linear_predicate(&mut self, linear_predicate: &LinearPredicate) -> Result<(), IndyCryptoError> {
+        linear_predicate.validate()?;
+        self.linear_predicates.push(linear_predicate.clone());
+        Ok(())
+    }
+
+    /// Add requirement that hidden attribute of sub proof is verifiably encrypted to auditor.
+    /// The order of encryptions is important: both Prover and Verifier should use the same order.
+    ///
+    /// Ciphertexts of verified proof can be taken with `Proof::get_verifiable_ciphertexts`.
+    ///
+    /// # Arguments
+    /// * `verifiable_encryption` - Verifiable encryption.
+    pub fn add_verifiable_encryption(&mut self, verifiable_encryption: &VerifiableEncryption) -> Result<(), IndyCryptoError> {
+        self.verifiable_encryptions.push(verifiable_encryption.try_clone()?);
+        Ok(())
+    }
+
+    /// Add raw value that revealed attribute of sub proof must be encoded from.
+    /// Verification fails if the attribute is not revealed or revealed value differs from encoded raw value.
+    ///
+    /// # Arguments
+    /// * `sub_proof_index` - Index of sub proof in the order sub proof requests were added in.
+    /// * `attr_name` - Revealed attribute name.
+    /// * `raw_value` - Raw attribute value.
+    ///
+    /// #Example
+    /// ```
+    /// use indy_crypto::cl::encoding::AttributeValue;
+    /// use indy_crypto::cl::verifier::Verifier;
+    ///
+    /// let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+    /// proof_verifier.add_raw_revealed_value(0, "name", &AttributeValue::String("Alex".to_string())).unwrap();
+    /// ```
+    pub fn add_raw_revealed_value(&mut self,
+                                  sub_proof_index: usize,
+                                  attr_name: &str,
+                                  raw_value: &AttributeValue) -> Result<(), IndyCryptoError> {
+        raw_value.encode()?;
+
+        self.raw_revealed_values.insert(
+            AttributeReference { sub_proof_index, attr_name: attr_name.to_owned() },
+            raw_value.try_clone()?
+        );
+        Ok(())
+    }
+
+    /// Sets minimal version of proofs accepted by verifier, proofs of lower versions are rejected.
+    ///
+    /// Legacy proofs (`LEGACY_PROOF_VERSION`) are accepted by default for compatibility with
+    /// older provers; set `CURRENT_PROOF_VERSION` to reject them.
+    ///
+    /// # Arguments
+    /// * `min_proof_version` - Minimal accepted proof version.
+    ///
+    /// #Example
+    /// ```
+    /// use indy_crypto::cl::CURRENT_PROOF_VERSION;
+    /// use indy_crypto::cl::verifier::Verifier;
+    ///
+    /// let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+    /// proof_verifier.set_min_proof_version(CURRENT_PROOF_VERSION).unwrap();
+    /// ```
+    pub fn set_min_proof_version(&mut self, min_proof_version: u32) -> Result<(), IndyCryptoError> {
+        if min_proof_version < LEGACY_PROOF_VERSION || min_proof_version > CURRENT_PROOF_VERSION {
+            return Err(IndyCryptoError::InvalidStructure(format!("Unsupported proof version {}", min_proof_version)));
+        }
+
+        self.min_proof_version = min_proof_version;
+        Ok(())
+    }
+
     /// Verifies proof.
     ///
     /// # Arguments
@@ -203,11 +333,38 @@ impl ProofVerifier {
     pub fn verify(&self,
                   proof: &Proof,
                   nonce: &Nonce) -> Result<bool, IndyCryptoError> {
-        trace!("ProofVerifier::verify: >>> proof: {:?}, nonce: {:?}", proof, nonce);
+        self._verify(proof, nonce, None)
+    }
+
+    /// Verifies proof bound to the message (see `ProofBuilder::finalize_with_message`).
+    ///
+    /// # Arguments
+    /// * `proof` - Proof generated by Prover.
+    /// * `nonce` - Nonce.
+    /// * `message` - Message (or context) the proof must be bound to.
+    pub fn verify_with_message(&self,
+                               proof: &Proof,
+                               nonce: &Nonce,
+                               message: &[u8]) -> Result<bool, IndyCryptoError> {
+        self._verify(proof, nonce, Some(message))
+    }
+
+    fn _verify(&self,
+               proof: &Proof,
+               nonce: &Nonce,
+               message: Option<&[u8]>) -> Result<bool, IndyCryptoError> {
+        trace!("ProofVerifier::_verify: >>> proof: {:?}, nonce: {:?}, message: {:?}", proof, nonce, message);
+
+        if proof.version < self.min_proof_version {
+            return Err(IndyCryptoError::AnoncredsProofRejected(format!("Proof version {} is lower than minimal accepted version {}",
+                                                                       proof.version, self.min_proof_version)));
+        }
 
         ProofVerifier::_check_verify_params_consistency(&self.credentials, proof)?;
+        ProofVerifier::_check_raw_revealed_values(&self.raw_revealed_values, proof)?;
 
-        let mut tau_list: Vec<Vec<u8>> = Vec::new();
+        let mut tau_list = TauList::new();
+        let mut c_list = TauList::new();
 
         assert_eq!(proof.proofs.len(), self.credentials.len()); //FIXME return error
         for idx in 0..proof.proofs.len() {
@@ -217,41 +374,94 @@ impl ProofVerifier {
                                                                                                              credential.pub_key.r_key.as_ref(),
                                                                                                              credential.rev_reg.as_ref(),
                                                                                                              credential.rev_key_pub.as_ref()) {
-                tau_list.extend_from_slice(
+                c_list.append_vec(NON_REVOCATION_PROOF_LABEL, &non_revocation_proof.c_list.as_list()?)?;
+                tau_list.append_vec(
+                    NON_REVOCATION_PROOF_LABEL,
                     &ProofVerifier::_verify_non_revocation_proof(&cred_rev_pub_key,
                                                                  &rev_reg,
                                                                  &rev_key_pub,
                                                                  &proof.aggregated_proof.c_hash,
                                                                  &non_revocation_proof)?.as_slice()?
-                );
+                )?;
             };
 
-            tau_list.append_vec(
-                &ProofVerifier::_verify_primary_proof(&credential.pub_key.p_key,
-                                                      &proof.aggregated_proof.c_hash,
-                                                      &proof_item.primary_proof,
-                                                      &credential.credential_schema,
-                                                      &credential.non_credential_schema,
-                                                      &credential.sub_proof_request)?
-            )?;
+            let equal_attrs = ProofVerifier::_get_equal_attrs(&self.attr_equalities, proof, idx)?;
+
+            proof_item.primary_proof.append_c_list(&mut c_list)?;
+
+            tau_list.append(
+                &mut ProofVerifier::_verify_primary_proof(&credential.pub_key.p_key,
+                                                          &proof.aggregated_proof.c_hash,
+                                                          &proof_item.primary_proof,
+                                                          &credential.credential_schema,
+                                                          &credential.non_credential_schema,
+                                                          &credential.sub_proof_request,
+                                                          &equal_attrs)?
+            );
+
+            if let (Some(ref pseudonym_request), Some(ref pseudonym)) = (credential.sub_proof_request.pseudonym.as_ref(),
+                                                                         proof_item.pseudonym.as_ref()) {
+                pseudonym.append_tau_list(
+                    &mut tau_list,
+                    &ProofVerifier::_verify_pseudonym(pseudonym_request,
+                                                      pseudonym,
+                                                      &proof_item.primary_proof.eq_proof,
+                                                      &proof.aggregated_proof.c_hash)?
+                )?;
+            }
+        }
+
+        tau_list.append_vec(LINEAR_PREDICATE_PROOF_LABEL, &self._verify_linear_predicates(proof)?)?;
+        tau_list.append_vec(VERIFIABLE_ENCRYPTION_PROOF_LABEL, &self._verify_verifiable_encryptions(proof)?)?;
+
+        for linear_proof in proof.linear_proofs.iter() {
+            linear_proof.ne_proof.append_c_list(LINEAR_PREDICATE_PROOF_LABEL, &mut c_list)?;
+        }
+        for encryption_proof in proof.encryption_proofs.iter() {
+            c_list.append_vec(VERIFIABLE_ENCRYPTION_PROOF_LABEL, &encryption_proof.ciphertext.as_list()?)?;
         }
 
-        let mut values: Vec<Vec<u8>> = Vec::new();
-        values.extend_from_slice(&tau_list);
-        values.extend_from_slice(&proof.aggregated_proof.c_list);
-        values.push(nonce.to_bytes()?);
+        // C list is labeled by verifier from the proof parts, so prover's list must contain the same values
+        if c_list.flatten() != proof.aggregated_proof.c_list {
+            trace!("ProofVerifier::_verify: <<< valid: false, c list doesn't correspond to the proof");
+            return Ok(false);
+        }
 
-        let c_hver = get_hash_as_int(&values)?;
+        let c_hver = get_proof_challenge(proof.version, &tau_list, &c_list, nonce, message)?;
 
         info!(target: "anoncreds_service", "Verifier verify proof -> done");
 
         let valid = c_hver == proof.aggregated_proof.c_hash;
 
-        trace!("ProofVerifier::verify: <<< valid: {:?}", valid);
+        trace!("ProofVerifier::_verify: <<< valid: {:?}", valid);
 
         Ok(valid)
     }
 
+    /// Verifies proof and returns pseudonyms of sub proofs that requested them (in the order of sub proofs)
+    /// if proof is valid.
+    ///
+    /// # Arguments
+    /// * `proof` - Proof generated by Prover.
+    /// * `nonce` - Nonce.
+    /// * `message` - (Optional) Message the proof must be bound to (see `verify_with_message`).
+    pub fn verify_with_pseudonyms(&self,
+                                  proof: &Proof,
+                                  nonce: &Nonce,
+                                  message: Option<&[u8]>) -> Result<Option<Vec<Pseudonym>>, IndyCryptoError> {
+        trace!("ProofVerifier::verify_with_pseudonyms: >>> proof: {:?}, nonce: {:?}, message: {:?}", proof, nonce, message);
+
+        let pseudonyms = if self._verify(proof, nonce, message)? {
+            Some(proof.proofs.iter().filter_map(|sub_proof| sub_proof.pseudonym.clone()).collect())
+        } else {
+            None
+        };
+
+        trace!("ProofVerifier::verify_with_pseudonyms: <<< pseudonyms: {:?}", pseudonyms);
+
+        Ok(pseudonyms)
+    }
+
     fn _check_add_sub_proof_request_params_consistency(sub_proof_request: &SubProofRequest,
                                                        cred_schema: &CredentialSchema) -> Result<(), IndyCryptoError> {
         trace!("ProofVerifier::_check_add_sub_proof_request_params_consistency: >>> sub_proof_request: {:?}, cred_schema: {:?}", sub_proof_request, cred_schema);
@@ -263,6 +473,7 @@ impl ProofVerifier {
         let predicates_attrs =
             sub_proof_request.predicates.iter()
                 .map(|predicate| predicate.attr_name.clone())
+                .chain(sub_proof_request.set_predicates.iter().map(|predicate| predicate.attr_name.clone()))
                 .collect::<BTreeSet<String>>();
 
         if predicates_attrs.difference(&cred_schema.attrs).count() != 0 {
@@ -292,11 +503,27 @@ impl ProofVerifier {
             let proof_predicates =
                 proof_for_credential.primary_proof.ne_proofs.iter()
                     .map(|ne_proof| ne_proof.predicate.clone())
-                    .collect::<BTreeSet<Predicate>>();
+                    .collect::<Result<BTreeSet<Predicate>, IndyCryptoError>>()?;
 
             if proof_predicates != credential.sub_proof_request.predicates {
                 return Err(IndyCryptoError::AnoncredsProofRejected(format!("Proof predicates not correspond to requested predicates")));
             }
+
+            let proof_set_predicates =
+                proof_for_credential.primary_proof.set_proofs.iter()
+                    .map(|set_proof| set_proof.predicate.clone())
+                    .collect::<Result<BTreeSet<SetMembershipPredicate>, IndyCryptoError>>()?;
+
+            if proof_set_predicates != credential.sub_proof_request.set_predicates {
+                return Err(IndyCryptoError::AnoncredsProofRejected(format!("Proof set membership predicates not correspond to requested predicates")));
+            }
+
+            let proof_pseudonym_scope = proof_for_credential.pseudonym.as_ref().map(|pseudonym| &pseudonym.scope);
+            let requested_pseudonym_scope = credential.sub_proof_request.pseudonym.as_ref().map(|pseudonym| &pseudonym.scope);
+
+            if proof_pseudonym_scope != requested_pseudonym_scope {
+                return Err(IndyCryptoError::AnoncredsProofRejected(format!("Proof pseudonym not correspond to requested pseudonym")));
+            }
         }
 
         trace!("ProofVerifier::_check_verify_params_consistency: <<<");
@@ -304,24 +531,101 @@ impl ProofVerifier {
         Ok(())
     }
 
+    fn _check_raw_revealed_values(raw_revealed_values: &BTreeMap<AttributeReference, AttributeValue>,
+                                  proof: &Proof) -> Result<(), IndyCryptoError> {
+        trace!("ProofVerifier::_check_raw_revealed_values: >>> raw_revealed_values: {:?}, proof: {:?}", raw_revealed_values, proof);
+
+        for (attr, raw_value) in raw_revealed_values {
+            let sub_proof = proof.proofs.get(attr.sub_proof_index)
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Sub proof {} not found", attr.sub_proof_index)))?;
+
+            let revealed_value = sub_proof.primary_proof.eq_proof.revealed_attrs.get(&attr.attr_name)
+                .ok_or(IndyCryptoError::AnoncredsProofRejected(format!("Attribute {:?} of sub proof {} is not revealed", attr.attr_name, attr.sub_proof_index)))?;
+
+            if *revealed_value != raw_value.encode()? {
+                return Err(IndyCryptoError::AnoncredsProofRejected(format!("Revealed value of attribute {:?} of sub proof {} not correspond to raw value", attr.attr_name, attr.sub_proof_index)));
+            }
+        }
+
+        trace!("ProofVerifier::_check_raw_revealed_values: <<<");
+
+        Ok(())
+    }
+
+    // Returns `t = H(scope)^m_hat * nym^(-c_hash)` that equals to `H(scope)^m_tilde` of prover for valid pseudonym
+    fn _verify_pseudonym(pseudonym_request: &PseudonymRequest,
+                         pseudonym: &Pseudonym,
+                         eq_proof: &PrimaryEqualProof,
+                         c_hash: &BigNumber) -> Result<Vec<u8>, IndyCryptoError> {
+        trace!("ProofVerifier::_verify_pseudonym: >>> pseudonym_request: {:?}, pseudonym: {:?}, eq_proof: {:?}, c_hash: {:?}",
+               pseudonym_request, pseudonym, eq_proof, c_hash);
+
+        let m_hat = eq_proof.m.get(&pseudonym_request.attr_name)
+            .ok_or(IndyCryptoError::AnoncredsProofRejected(format!("Attribute '{}' of pseudonym is not hidden in proof", pseudonym_request.attr_name)))?;
+
+        let t = Pseudonym::base(&pseudonym.scope)?
+            .mul(&bignum_to_group_element_mod_order(m_hat)?)?
+            .sub(&pseudonym.nym.mul(&bignum_to_group_element_mod_order(c_hash)?)?)?;
+
+        trace!("ProofVerifier::_verify_pseudonym: <<< t: {:?}", t);
+
+        t.to_bytes()
+    }
+
+    // Returns responses that hidden attributes of sub proof must be equal to according to attribute equalities
+    fn _get_equal_attrs<'a>(attr_equalities: &BTreeSet<AttributeEquality>,
+                            proof: &'a Proof,
+                            sub_proof_index: usize) -> Result<Vec<(String, &'a BigNumber)>, IndyCryptoError> {
+        let mut equal_attrs = Vec::new();
+
+        for attr_equality in attr_equalities.iter() {
+            for &(attr, other_attr) in attr_equality.as_pairs().iter() {
+                if attr.sub_proof_index != sub_proof_index {
+                    continue;
+                }
+
+                let other_m = proof.proofs.get(other_attr.sub_proof_index)
+                    .ok_or(IndyCryptoError::InvalidStructure(format!("Attribute equality refers to absent sub proof {}", other_attr.sub_proof_index)))?
+                    .primary_proof.eq_proof.m
+                    .get(&other_attr.attr_name)
+                    .ok_or(IndyCryptoError::AnoncredsProofRejected(format!("Attribute '{}' of sub proof {} is not hidden",
+                                                                           other_attr.attr_name, other_attr.sub_proof_index)))?;
+
+                equal_attrs.push((attr.attr_name.clone(), other_m));
+            }
+        }
+
+        Ok(equal_attrs)
+    }
+
     fn _verify_primary_proof(p_pub_key: &CredentialPrimaryPublicKey,
                              c_hash: &BigNumber,
                              primary_proof: &PrimaryProof,
                              cred_schema: &CredentialSchema,
                              non_cred_schema: &NonCredentialSchema,
-                             sub_proof_request: &SubProofRequest) -> Result<Vec<BigNumber>, IndyCryptoError> {
+                             sub_proof_request: &SubProofRequest,
+                             equal_attrs: &[(String, &BigNumber)]) -> Result<TauList, IndyCryptoError> {
         trace!("ProofVerifier::_verify_primary_proof: >>> p_pub_key: {:?}, c_hash: {:?}, primary_proof: {:?}, cred_schema: {:?}, sub_proof_request: {:?}",
                p_pub_key, c_hash, primary_proof, cred_schema, sub_proof_request);
 
-        let mut t_hat: Vec<BigNumber> = ProofVerifier::_verify_equality(p_pub_key,
-                                                                        &primary_proof.eq_proof,
-                                                                        c_hash,
-                                                                        cred_schema,
-                                                                        non_cred_schema,
-                                                                        sub_proof_request)?;
+        let mut t_hat = TauList::new();
+
+        t_hat.append_vec(PRIMARY_EQUAL_PROOF_LABEL,
+                         &ProofVerifier::_verify_equality(p_pub_key,
+                                                          &primary_proof.eq_proof,
+                                                          c_hash,
+                                                          cred_schema,
+                                                          non_cred_schema,
+                                                          sub_proof_request,
+                                                          equal_attrs)?)?;
 
         for ne_proof in primary_proof.ne_proofs.iter() {
-            t_hat.append(&mut ProofVerifier::_verify_ne_predicate(p_pub_key, ne_proof, c_hash)?)
+            t_hat.append_vec(PRIMARY_PREDICATE_PROOF_LABEL, &ProofVerifier::_verify_ne_predicate(p_pub_key, ne_proof, c_hash)?)?;
+        }
+
+        for set_proof in primary_proof.set_proofs.iter() {
+            t_hat.append_vec(SET_MEMBERSHIP_PROOF_LABEL,
+                             &ProofVerifier::_verify_set_membership_predicate(p_pub_key, set_proof, &primary_proof.eq_proof, c_hash)?)?;
         }
 
         trace!("ProofVerifier::_verify_primary_proof: <<< t_hat: {:?}", t_hat);
@@ -334,9 +638,20 @@ impl ProofVerifier {
                         c_hash: &BigNumber,
                         cred_schema: &CredentialSchema,
                         non_cred_schema: &NonCredentialSchema,
-                        sub_proof_request: &SubProofRequest) -> Result<Vec<BigNumber>, IndyCryptoError> {
-        trace!("ProofVerifier::_verify_equality: >>> p_pub_key: {:?}, proof: {:?}, c_hash: {:?}, cred_schema: {:?}, sub_proof_request: {:?}",
-               p_pub_key, proof, c_hash, cred_schema, sub_proof_request);
+                        sub_proof_request: &SubProofRequest,
+                        equal_attrs: &[(String, &BigNumber)]) -> Result<Vec<BigNumber>, IndyCryptoError> {
+        trace!("ProofVerifier::_verify_equality: >>> p_pub_key: {:?}, proof: {:?}, c_hash: {:?}, cred_schema: {:?}, sub_proof_request: {:?}, equal_attrs: {:?}",
+               p_pub_key, proof, c_hash, cred_schema, sub_proof_request, equal_attrs);
+
+        // Equal attributes blinded with the same m_tilde have the same responses
+        for &(ref attr, other_m) in equal_attrs.iter() {
+            let m = proof.m.get(attr)
+                .ok_or(IndyCryptoError::AnoncredsProofRejected(format!("Attribute '{}' requested in attribute equality is not hidden", attr)))?;
+
+            if m != other_m {
+                return Err(IndyCryptoError::AnoncredsProofRejected(format!("Attribute '{}' doesn't satisfy attribute equality", attr)));
+            }
+        }
 
 
         let unrevealed_attrs = cred_schema
@@ -375,6 +690,87 @@ impl ProofVerifier {
         Ok(vec![t])
     }
 
+    fn _verify_linear_predicates(&self, proof: &Proof) -> Result<Vec<BigNumber>, IndyCryptoError> {
+        trace!("ProofVerifier::_verify_linear_predicates: >>> linear_predicates: {:?}, proof: {:?}", self.linear_predicates, proof);
+
+        if proof.linear_proofs.len() != self.linear_predicates.len() {
+            return Err(IndyCryptoError::AnoncredsProofRejected(format!("Proof linear predicates not correspond to requested linear predicates")));
+        }
+
+        let mut tau_list: Vec<BigNumber> = Vec::new();
+
+        for (linear_predicate, linear_proof) in self.linear_predicates.iter().zip(proof.linear_proofs.iter()) {
+            if linear_proof.predicate != *linear_predicate || linear_proof.ne_proof.predicate != linear_predicate.as_predicate()? {
+                return Err(IndyCryptoError::AnoncredsProofRejected(format!("Proof linear predicates not correspond to requested linear predicates")));
+            }
+
+            let mj = linear_predicate.combine(|attr| {
+                proof.proofs.get(attr.sub_proof_index)
+                    .ok_or(IndyCryptoError::InvalidStructure(format!("Linear predicate refers to absent sub proof {}", attr.sub_proof_index)))?
+                    .primary_proof.eq_proof.m
+                    .get(&attr.attr_name)
+                    .ok_or(IndyCryptoError::AnoncredsProofRejected(format!("Attribute '{}' of sub proof {} is not hidden",
+                                                                           attr.attr_name, attr.sub_proof_index)))
+            })?;
+
+            if linear_proof.ne_proof.mj != mj {
+                return Err(IndyCryptoError::AnoncredsProofRejected(format!("Linear predicate is not linked to attributes of sub proofs")));
+            }
+
+            let p_pub_key = &self.credentials.get(linear_predicate.key_attr().sub_proof_index)
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Linear predicate refers to absent sub proof {}",
+                                                                 linear_predicate.key_attr().sub_proof_index)))?
+                .pub_key.p_key;
+
+            tau_list.append(&mut ProofVerifier::_verify_ne_predicate(p_pub_key, &linear_proof.ne_proof, &proof.aggregated_proof.c_hash)?);
+        }
+
+        trace!("ProofVerifier::_verify_linear_predicates: <<< tau_list: {:?}", tau_list);
+
+        Ok(tau_list)
+    }
+
+    fn _verify_verifiable_encryptions(&self, proof: &Proof) -> Result<Vec<BigNumber>, IndyCryptoError> {
+        trace!("ProofVerifier::_verify_verifiable_encryptions: >>> verifiable_encryptions: {:?}, proof: {:?}", self.verifiable_encryptions, proof);
+
+        if proof.encryption_proofs.len() != self.verifiable_encryptions.len() {
+            return Err(IndyCryptoError::AnoncredsProofRejected(format!("Proof encryptions not correspond to requested encryptions")));
+        }
+
+        let mut tau_list: Vec<BigNumber> = Vec::new();
+
+        for (verifiable_encryption, encryption_proof) in self.verifiable_encryptions.iter().zip(proof.encryption_proofs.iter()) {
+            let auditor_pub_key = &verifiable_encryption.auditor_pub_key;
+            let ciphertext = &encryption_proof.ciphertext;
+
+            if encryption_proof.attr != verifiable_encryption.attr || ciphertext.label != verifiable_encryption.label {
+                return Err(IndyCryptoError::AnoncredsProofRejected(format!("Proof encryptions not correspond to requested encryptions")));
+            }
+
+            if auditor_pub_key.abs(&ciphertext.v)? != ciphertext.v {
+                return Err(IndyCryptoError::AnoncredsProofRejected(format!("Invalid ciphertext of verifiable encryption")));
+            }
+
+            let attr = &verifiable_encryption.attr;
+
+            let m_hat = proof.proofs.get(attr.sub_proof_index)
+                .ok_or(IndyCryptoError::InvalidStructure(format!("Verifiable encryption refers to absent sub proof {}", attr.sub_proof_index)))?
+                .primary_proof.eq_proof.m
+                .get(&attr.attr_name)
+                .ok_or(IndyCryptoError::AnoncredsProofRejected(format!("Attribute '{}' of sub proof {} is not hidden",
+                                                                       attr.attr_name, attr.sub_proof_index)))?;
+
+            tau_list.append(&mut auditor_pub_key.calc_tau_list(ciphertext,
+                                                               &encryption_proof.r_hat,
+                                                               m_hat,
+                                                               &proof.aggregated_proof.c_hash)?);
+        }
+
+        trace!("ProofVerifier::_verify_verifiable_encryptions: <<< tau_list: {:?}", tau_list);
+
+        Ok(tau_list)
+    }
+
     fn _verify_ne_predicate(p_pub_key: &CredentialPrimaryPublicKey,
                             proof: &PrimaryPredicateInequalityProof,
                             c_hash: &BigNumber) -> Result<Vec<BigNumber>, IndyCryptoError> {
@@ -382,7 +778,7 @@ impl ProofVerifier {
 
         let mut ctx = BigNumber::new_context()?;
         let mut tau_list = calc_tne(&p_pub_key, &proof.u, &proof.r, &proof.mj,
-                                    &proof.alpha, &proof.t, proof.predicate.is_less())?;
+                                    &proof.alpha, proof.upper_alpha.as_ref(), &proof.t, &proof.predicate)?;
 
         for i in 0..ITERATION {
             let cur_t = proof.t.get(&i.to_string())
@@ -411,16 +807,115 @@ impl ProofVerifier {
             .inverse(&p_pub_key.n, Some(&mut ctx))?
             .mod_mul(&tau_list[ITERATION], &p_pub_key.n, Some(&mut ctx))?;
 
-        tau_list[ITERATION + 1] = delta
-            .mod_exp(&c_hash, &p_pub_key.n, Some(&mut ctx))?
-            .inverse(&p_pub_key.n, Some(&mut ctx))?
-            .mod_mul(&tau_list[ITERATION + 1], &p_pub_key.n, Some(&mut ctx))?;
+        match proof.predicate.p_type {
+            PredicateType::NE => {
+                // Four squares are committed to delta^2 - 1 = T_SQUARE / Z
+                let square = proof.t.get("SQUARE")
+                    .ok_or(IndyCryptoError::AnoncredsProofRejected(format!("Value by key '{}' not found in proof.t", "SQUARE")))?;
+
+                let square_minus_one = p_pub_key.z
+                    .inverse(&p_pub_key.n, Some(&mut ctx))?
+                    .mod_mul(&square, &p_pub_key.n, Some(&mut ctx))?;
+
+                tau_list[ITERATION + 1] = square_minus_one
+                    .mod_exp(&c_hash, &p_pub_key.n, Some(&mut ctx))?
+                    .inverse(&p_pub_key.n, Some(&mut ctx))?
+                    .mod_mul(&tau_list[ITERATION + 1], &p_pub_key.n, Some(&mut ctx))?;
+
+                tau_list[ITERATION + 2] = delta
+                    .mod_exp(&proof.predicate.get_delta_prime()?, &p_pub_key.n, Some(&mut ctx))?
+                    .mod_mul(&square, &p_pub_key.n, Some(&mut ctx))?
+                    .mod_exp(&c_hash, &p_pub_key.n, Some(&mut ctx))?
+                    .inverse(&p_pub_key.n, Some(&mut ctx))?
+                    .mod_mul(&tau_list[ITERATION + 2], &p_pub_key.n, Some(&mut ctx))?;
+            }
+            PredicateType::BETWEEN => {
+                tau_list[ITERATION + 1] = delta
+                    .mod_exp(&c_hash, &p_pub_key.n, Some(&mut ctx))?
+                    .inverse(&p_pub_key.n, Some(&mut ctx))?
+                    .mod_mul(&tau_list[ITERATION + 1], &p_pub_key.n, Some(&mut ctx))?;
+
+                for i in ITERATION..2 * ITERATION {
+                    let cur_t = proof.t.get(&i.to_string())
+                        .ok_or(IndyCryptoError::AnoncredsProofRejected(format!("Value by key '{}' not found in proof.t", i)))?;
+
+                    tau_list[i + 2] = cur_t
+                        .mod_exp(&c_hash, &p_pub_key.n, Some(&mut ctx))?
+                        .inverse(&p_pub_key.n, Some(&mut ctx))?
+                        .mod_mul(&tau_list[i + 2], &p_pub_key.n, Some(&mut ctx))?;
+                }
+
+                // Upper delta is committed in Z^(range width) / T_DELTA
+                let upper_delta = p_pub_key.z
+                    .mod_exp(&proof.predicate.get_range_width()?, &p_pub_key.n, Some(&mut ctx))?
+                    .mod_mul(&delta.inverse(&p_pub_key.n, Some(&mut ctx))?, &p_pub_key.n, Some(&mut ctx))?;
+
+                tau_list[2 * ITERATION + 2] = upper_delta
+                    .mod_exp(&c_hash, &p_pub_key.n, Some(&mut ctx))?
+                    .inverse(&p_pub_key.n, Some(&mut ctx))?
+                    .mod_mul(&tau_list[2 * ITERATION + 2], &p_pub_key.n, Some(&mut ctx))?;
+            }
+            _ => {
+                tau_list[ITERATION + 1] = delta
+                    .mod_exp(&c_hash, &p_pub_key.n, Some(&mut ctx))?
+                    .inverse(&p_pub_key.n, Some(&mut ctx))?
+                    .mod_mul(&tau_list[ITERATION + 1], &p_pub_key.n, Some(&mut ctx))?;
+            }
+        }
 
         trace!("ProofVerifier::_verify_ne_predicate: <<< tau_list: {:?},", tau_list);
 
         Ok(tau_list)
     }
 
+    fn _verify_set_membership_predicate(p_pub_key: &CredentialPrimaryPublicKey,
+                                        proof: &PrimarySetMembershipProof,
+                                        eq_proof: &PrimaryEqualProof,
+                                        c_hash: &BigNumber) -> Result<Vec<BigNumber>, IndyCryptoError> {
+        trace!("ProofVerifier::_verify_set_membership_predicate: >>> p_pub_key: {:?}, proof: {:?}, eq_proof: {:?}, c_hash: {:?}",
+               p_pub_key, proof, eq_proof, c_hash);
+
+        let mut ctx = BigNumber::new_context()?;
+
+        if proof.c.len() != proof.predicate.values.len() || proof.z.len() != proof.predicate.values.len() {
+            return Err(IndyCryptoError::AnoncredsProofRejected(format!("Set membership proof doesn't correspond to predicate values")));
+        }
+
+        let mut challenge_modulus = BigNumber::new()?;
+        challenge_modulus.set_bit(LARGE_CHALLENGE as i32)?;
+
+        let mut c_sum = BigNumber::new()?;
+        for c_i in proof.c.iter() {
+            if c_i.is_negative() || *c_i >= challenge_modulus {
+                return Err(IndyCryptoError::AnoncredsProofRejected(format!("Set membership proof contains invalid challenge")));
+            }
+            c_sum = c_sum.add(c_i)?;
+        }
+
+        if c_sum.modulus(&challenge_modulus, Some(&mut ctx))? != c_hash.modulus(&challenge_modulus, Some(&mut ctx))? {
+            return Err(IndyCryptoError::AnoncredsProofRejected(format!("Set membership proof challenges don't match proof challenge")));
+        }
+
+        let mj = eq_proof.m.get(&proof.predicate.attr_name)
+            .ok_or(IndyCryptoError::AnoncredsProofRejected(format!("Value by key '{}' not found in eq_proof.m", proof.predicate.attr_name)))?;
+
+        let mut tau_list = vec![
+            proof.t
+                .mod_exp(&c_hash, &p_pub_key.n, Some(&mut ctx))?
+                .inverse(&p_pub_key.n, Some(&mut ctx))?
+                .mod_mul(&get_pedersen_commitment(&p_pub_key.z, mj, &p_pub_key.s, &proof.r, &p_pub_key.n, &mut ctx)?,
+                         &p_pub_key.n, Some(&mut ctx))?
+        ];
+
+        for (i, value) in proof.predicate.get_values()?.iter().enumerate() {
+            tau_list.push(calc_tset(&p_pub_key, &proof.t, value, &proof.c[i], &proof.z[i], &mut ctx)?);
+        }
+
+        trace!("ProofVerifier::_verify_set_membership_predicate: <<< tau_list: {:?},", tau_list);
+
+        Ok(tau_list)
+    }
+
     fn _verify_non_revocation_proof(r_pub_key: &CredentialRevocationPublicKey,
                                     rev_reg: &RevocationRegistry,
                                     rev_key_pub: &RevocationKeyPublic,
@@ -458,6 +953,7 @@ mod tests {
     use cl::issuer;
     use cl::helpers::MockHelper;
     use cl::prover::mocks::*;
+    use errors::{ErrorCode, ToErrorCode};
 
     #[test]
     fn sub_proof_request_builder_works() {
@@ -470,6 +966,176 @@ mod tests {
         assert!(sub_proof_request.predicates.contains(&predicate()));
     }
 
+    #[test]
+    fn sub_proof_request_builder_works_for_ne_and_range_predicates() {
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_predicate("age", "NE", 18).unwrap();
+        sub_proof_request_builder.add_range_predicate("height", 150, 200).unwrap();
+        assert!(sub_proof_request_builder.add_predicate("age", "BETWEEN", 18).is_err());
+        assert!(sub_proof_request_builder.add_range_predicate("age", 18, 18).is_err());
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        assert!(sub_proof_request.predicates.contains(&Predicate {
+            attr_name: "age".to_owned(),
+            p_type: PredicateType::NE,
+            value: "18".to_owned(),
+            upper_value: None
+        }));
+        assert!(sub_proof_request.predicates.contains(&Predicate {
+            attr_name: "height".to_owned(),
+            p_type: PredicateType::BETWEEN,
+            value: "150".to_owned(),
+            upper_value: Some("200".to_owned())
+        }));
+        assert_eq!(2, sub_proof_request.predicates.len());
+    }
+
+    #[test]
+    fn sub_proof_request_builder_works_for_set_membership_predicate() {
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_set_membership_predicate("age", &["28", "18", "28"]).unwrap();
+        assert!(sub_proof_request_builder.add_set_membership_predicate("height", &[]).is_err());
+        assert!(sub_proof_request_builder.add_set_membership_predicate("height", &["tall"]).is_err());
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        assert!(sub_proof_request.set_predicates.contains(&SetMembershipPredicate {
+            attr_name: "age".to_owned(),
+            values: btreeset!["18".to_owned(), "28".to_owned()]
+        }));
+        assert_eq!(1, sub_proof_request.set_predicates.len());
+    }
+
+    #[test]
+    fn sub_proof_request_builder_works_for_typed_predicate() {
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_typed_predicate("age", "GE", &AttributeValue::from_i64(18).unwrap()).unwrap();
+        assert!(sub_proof_request_builder.add_typed_predicate("name", "GE", &AttributeValue::String("Alex".to_owned())).is_err());
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        assert!(sub_proof_request.predicates.contains(&predicate()));
+        assert_eq!(1, sub_proof_request.predicates.len());
+    }
+
+    #[test]
+    fn sub_proof_request_builder_works_for_pseudonym() {
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_pseudonym("master_secret", "example.com").unwrap();
+        assert!(sub_proof_request_builder.add_pseudonym("master_secret", "example.org").is_err());
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        assert_eq!(Some(PseudonymRequest { attr_name: "master_secret".to_owned(), scope: "example.com".to_owned() }),
+                   sub_proof_request.pseudonym);
+    }
+
+    #[test]
+    fn linear_predicate_builder_works() {
+        let mut linear_predicate_builder = Verifier::new_linear_predicate_builder().unwrap();
+        linear_predicate_builder.add_term(0, "income", 1).unwrap();
+        linear_predicate_builder.add_term(1, "rent", -3).unwrap();
+        assert!(linear_predicate_builder.set_predicate("NE", 0).is_err());
+        linear_predicate_builder.set_predicate("GT", 0).unwrap();
+        let linear_predicate = linear_predicate_builder.finalize().unwrap();
+
+        assert_eq!(PredicateType::GT, linear_predicate.p_type);
+        assert_eq!("0", linear_predicate.value);
+        assert_eq!(vec![
+            LinearTerm {
+                attr: AttributeReference { sub_proof_index: 0, attr_name: "income".to_owned() },
+                coefficient: "1".to_owned()
+            },
+            LinearTerm {
+                attr: AttributeReference { sub_proof_index: 1, attr_name: "rent".to_owned() },
+                coefficient: "-3".to_owned()
+            }
+        ], linear_predicate.terms);
+    }
+
+    #[test]
+    fn linear_predicate_builder_works_for_invalid_terms() {
+        let linear_predicate_builder = Verifier::new_linear_predicate_builder().unwrap();
+        assert!(linear_predicate_builder.finalize().is_err());
+
+        let mut linear_predicate_builder = Verifier::new_linear_predicate_builder().unwrap();
+        linear_predicate_builder.add_term(0, "income", 0).unwrap();
+        assert!(linear_predicate_builder.finalize().is_err());
+    }
+
+    #[test]
+    fn _verify_pseudonym_works() {
+        let master_secret = prover::mocks::master_secret().value().unwrap();
+        let m_tilde = prover::mocks::m1_t();
+        let pseudonym_request = PseudonymRequest { attr_name: "master_secret".to_owned(), scope: "example.com".to_owned() };
+
+        let base = Pseudonym::base("example.com").unwrap();
+        let pseudonym = Pseudonym {
+            scope: "example.com".to_owned(),
+            nym: base.mul(&bignum_to_group_element_mod_order(&master_secret).unwrap()).unwrap()
+        };
+        let t = base.mul(&bignum_to_group_element_mod_order(&m_tilde).unwrap()).unwrap();
+
+        let c_hash = prover::mocks::aggregated_proof().c_hash;
+        let m_hat = c_hash.mul(&master_secret, None).unwrap().add(&m_tilde).unwrap();
+
+        let mut eq_proof = prover::mocks::eq_proof();
+        eq_proof.m.insert("master_secret".to_owned(), m_hat);
+
+        let res = ProofVerifier::_verify_pseudonym(&pseudonym_request, &pseudonym, &eq_proof, &c_hash).unwrap();
+        assert_eq!(t.to_bytes().unwrap(), res);
+
+        let other_pseudonym = Pseudonym { scope: "example.org".to_owned(), nym: pseudonym.nym };
+        let res = ProofVerifier::_verify_pseudonym(&pseudonym_request, &other_pseudonym, &eq_proof, &c_hash).unwrap();
+        assert!(t.to_bytes().unwrap() != res);
+    }
+
+    #[cfg(feature = "pair_amcl")]
+    #[test]
+    fn _check_raw_revealed_values_works() {
+        let proof = prover::mocks::proof();
+        let name = AttributeValue::Int(BigNumber::from_dec("66682250590915135919393234675423675079281389286836524491448775067034910960723").unwrap());
+
+        let raw_revealed_values = btreemap![
+            AttributeReference { sub_proof_index: 0, attr_name: "name".to_owned() } => name
+        ];
+        ProofVerifier::_check_raw_revealed_values(&raw_revealed_values, &proof).unwrap();
+    }
+
+    #[cfg(feature = "pair_amcl")]
+    #[test]
+    fn _check_raw_revealed_values_works_for_mismatched_or_not_revealed_value() {
+        let proof = prover::mocks::proof();
+
+        let raw_revealed_values = btreemap![
+            AttributeReference { sub_proof_index: 0, attr_name: "name".to_owned() } => AttributeValue::String("Alex".to_owned())
+        ];
+        let res = ProofVerifier::_check_raw_revealed_values(&raw_revealed_values, &proof);
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+
+        let raw_revealed_values = btreemap![
+            AttributeReference { sub_proof_index: 0, attr_name: "age".to_owned() } => AttributeValue::from_i64(28).unwrap()
+        ];
+        let res = ProofVerifier::_check_raw_revealed_values(&raw_revealed_values, &proof);
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+    }
+
+    #[cfg(feature = "pair_amcl")]
+    #[test]
+    fn _verify_verifiable_encryptions_works_for_missed_encryption() {
+        let value = BigNumber::from_u32(4).unwrap();
+        let auditor_pub_key = AuditorPublicKey {
+            n: BigNumber::from_u32(15).unwrap(),
+            g: value.clone().unwrap(),
+            y1: value.clone().unwrap(),
+            y2: value.clone().unwrap(),
+            y3: value
+        };
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_verifiable_encryption(&VerifiableEncryption::new(0, "age", &auditor_pub_key, "label").unwrap()).unwrap();
+
+        let res = proof_verifier._verify_verifiable_encryptions(&prover::mocks::proof());
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+    }
+
     #[test]
     fn verify_equality_works() {
         MockHelper::inject();
@@ -489,7 +1155,8 @@ mod tests {
                                                                   &c_h,
                                                                   &credential_schema,
                                                                   &non_credential_schema,
-                                                                  &sub_proof_request).unwrap();
+                                                                  &sub_proof_request,
+                                                                  &[]).unwrap();
 
         assert_eq!("10403187904873314760355557832761590691431383521745031865309573910963034393207684\
         41047372720051528347747837647360259125725910627967862485202935551931564829193622679374932738\
@@ -500,6 +1167,34 @@ mod tests {
         02507347769428679283112853202405399796966635008669186194259851326316679551259", res[0].to_dec().unwrap());
     }
 
+    #[test]
+    fn verify_equality_works_for_attribute_equality() {
+        MockHelper::inject();
+
+        let proof = prover::mocks::eq_proof();
+        let pk = issuer::mocks::credential_primary_public_key();
+        let c_h = prover::mocks::aggregated_proof().c_hash;
+        let credential_schema = issuer::mocks::credential_schema();
+        let non_credential_schema = issuer::mocks::non_credential_schema();
+
+        let mut sub_proof_request_builder = SubProofRequestBuilder::new().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let m_age = proof.m["age"].clone().unwrap();
+        assert!(ProofVerifier::_verify_equality(&pk, &proof, &c_h, &credential_schema, &non_credential_schema,
+                                                &sub_proof_request, &[("age".to_string(), &m_age)]).is_ok());
+
+        let m_other = m_age.increment().unwrap();
+        let res = ProofVerifier::_verify_equality(&pk, &proof, &c_h, &credential_schema, &non_credential_schema,
+                                                  &sub_proof_request, &[("age".to_string(), &m_other)]);
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+
+        let res = ProofVerifier::_verify_equality(&pk, &proof, &c_h, &credential_schema, &non_credential_schema,
+                                                  &sub_proof_request, &[("name".to_string(), &m_age)]);
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+    }
+
     #[test]
     fn _verify_ne_predicate_works() {
         MockHelper::inject();
@@ -191,6 +191,12 @@ impl From<serde_json::Error> for IndyCryptoError {
     }
 }
 
+impl From<io::Error> for IndyCryptoError {
+    fn from(err: io::Error) -> IndyCryptoError {
+        IndyCryptoError::IOError(err)
+    }
+}
+
 impl From<log::SetLoggerError> for IndyCryptoError {
     fn from(err: log::SetLoggerError) -> IndyCryptoError{
         IndyCryptoError::InvalidState(err.description().to_owned())
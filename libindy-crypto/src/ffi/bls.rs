@@ -2,6 +2,8 @@ use bls::*;
 
 use errors::ErrorCode;
 use errors::ToErrorCode;
+use ffi::ctypes::CTypesUtils;
+use libc::c_char;
 use std::os::raw::c_void;
 use std::slice;
 
@@ -153,6 +155,46 @@ pub extern fn indy_crypto_bls_sign_key_new(seed: *const u8,
     res
 }
 
+/// Derives and returns BLS sign key from master seed along hierarchical path (EIP-2333 style).
+///
+/// Note: Sign Key instance deallocation must be performed by calling indy_crypto_bls_sign_key_free.
+///
+/// # Arguments
+/// * `master_seed` - Master seed buffer pointer. Seed must be at least 32 bytes long.
+/// * `master_seed_len` - Master seed buffer len.
+/// * `path` - Derivation path of form `m/index/index/...`, for example `m/12381/3600/0/0`
+/// * `sign_key_p` - Reference that will contain sign key instance pointer
+#[no_mangle]
+pub extern fn indy_crypto_bls_sign_key_derive(master_seed: *const u8,
+                                              master_seed_len: usize,
+                                              path: *const c_char,
+                                              sign_key_p: *mut *const c_void) -> ErrorCode {
+    trace!("indy_crypto_bls_sign_key_derive: >>> master_seed: {:?}, master_seed_len: {:?}, path: {:?}, sign_key_p: {:?}",
+           master_seed, master_seed_len, path, sign_key_p);
+
+    check_useful_c_byte_array!(master_seed, master_seed_len,
+                               ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+    check_useful_c_str!(path, ErrorCode::CommonInvalidParam3);
+    check_useful_c_ptr!(sign_key_p, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_crypto_bls_sign_key_derive: master_seed: {:?}, path: {:?}", secret!(&master_seed), path);
+
+    let res = match SignKey::derive(master_seed, &path) {
+        Ok(sign_key) => {
+            trace!("indy_crypto_bls_sign_key_derive: sign_key: {:?}", secret!(&sign_key));
+            unsafe {
+                *sign_key_p = Box::into_raw(Box::new(sign_key)) as *const c_void;
+                trace!("indy_crypto_bls_sign_key_derive: *sign_key_p: {:?}", *sign_key_p);
+            }
+            ErrorCode::Success
+        }
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_bls_sign_key_derive: <<< res: {:?}", res);
+    res
+}
+
 /// Creates and returns sign key from bytes representation.
 ///
 /// Note: Sign key instance deallocation must be performed by calling indy_crypto_bls_sign_key_free
@@ -586,6 +628,51 @@ pub extern fn indy_crypto_bls_multi_signature_new(signatures: *const *const c_vo
     res
 }
 
+/// Creates and returns rogue-key safe multi signature for provided list of signatures.
+///
+/// Each signature is weighted by a hash of its verification key and the whole set of verification keys,
+/// so verification keys don't require proofs of possession.
+/// Multi signature must be verified with indy_crypto_bls_verify_multi_sig_bdn.
+///
+/// Note: Multi signature instance deallocation must be performed by calling indy_crypto_bls_multi_signature_free.
+///
+/// # Arguments
+/// * `signatures` - Signature instance pointers array
+/// * `signatures_len` - Signature instance pointers array len
+/// * `ver_keys` - Verification key instance pointers array. i-th key corresponds to i-th signature
+/// * `ver_keys_len` - Verification key instance pointers array len
+/// * `multi_sig_p` - Reference that will contain multi signature instance pointer
+#[no_mangle]
+pub extern fn indy_crypto_bls_multi_signature_new_bdn(signatures: *const *const c_void,
+                                                      signatures_len: usize,
+                                                      ver_keys: *const *const c_void,
+                                                      ver_keys_len: usize,
+                                                      multi_sig_p: *mut *const c_void) -> ErrorCode {
+    trace!("indy_crypto_bls_multi_signature_new_bdn: >>> signatures: {:?}, signatures_len: {:?}, ver_keys: {:?}, ver_keys_len: {:?}, multi_sig_p: {:?}",
+           signatures, signatures_len, ver_keys, ver_keys_len, multi_sig_p);
+
+    check_useful_c_reference_array!(signatures, signatures_len, Signature, ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+    check_useful_c_reference_array!(ver_keys, ver_keys_len, VerKey, ErrorCode::CommonInvalidParam3, ErrorCode::CommonInvalidParam4);
+    check_useful_c_ptr!(multi_sig_p, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_crypto_bls_multi_signature_new_bdn: signatures: {:?}, ver_keys: {:?}", signatures, ver_keys);
+
+    let res = match MultiSignature::new_bdn(&signatures, &ver_keys) {
+        Ok(multi_sig) => {
+            trace!("indy_crypto_bls_multi_signature_new_bdn: multi_sig: {:?}", multi_sig);
+            unsafe {
+                *multi_sig_p = Box::into_raw(Box::new(multi_sig)) as *const c_void;
+                trace!("indy_crypto_bls_multi_signature_new_bdn: *multi_sig_p: {:?}", *multi_sig_p);
+            }
+            ErrorCode::Success
+        }
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_bls_multi_signature_new_bdn: <<< res: {:?}", res);
+    res
+}
+
 /// Creates and returns multi signature from bytes representation.
 ///
 /// Note: Multi signature instance deallocation must be performed by calling indy_crypto_bls_multi_signature_free
@@ -793,6 +880,106 @@ pub extern fn indy_crypto_bls_verify_multi_sig(multi_sig: *const c_void,
     res
 }
 
+/// Verifies the message rogue-key safe multi signature and returns true - if signature valid or false otherwise.
+///
+/// # Arguments
+///
+/// * `multi_sig` - Multi signature instance pointer created with indy_crypto_bls_multi_signature_new_bdn
+/// * `message` - Message to verify buffer pointer
+/// * `message_len` - Message to verify buffer len
+/// * `ver_keys` - Verification key instance pointers array
+/// * `ver_keys_len` - Verification keys instance pointers array len
+/// * `gen` - Generator point instance
+/// * `valid_p` - Reference that will be filled with true - if signature valid or false otherwise.
+#[no_mangle]
+pub extern fn indy_crypto_bls_verify_multi_sig_bdn(multi_sig: *const c_void,
+                                                   message: *const u8,
+                                                   message_len: usize,
+                                                   ver_keys: *const *const c_void,
+                                                   ver_keys_len: usize,
+                                                   gen: *const c_void,
+                                                   valid_p: *mut bool) -> ErrorCode {
+    trace!("indy_crypto_bls_verify_multi_sig_bdn: >>> multi_sig: {:?}, message: {:?}, message_len: {:?}, ver_keys: {:?}, ver_keys_len: {:?}, gen: {:?}, valid_p: {:?}", multi_sig, message, message_len, ver_keys, ver_keys_len, gen, valid_p);
+
+    check_useful_c_reference!(multi_sig, MultiSignature, ErrorCode::CommonInvalidParam1);
+    check_useful_c_byte_array!(message, message_len, ErrorCode::CommonInvalidParam2, ErrorCode::CommonInvalidParam3);
+    check_useful_c_reference_array!(ver_keys, ver_keys_len, VerKey, ErrorCode::CommonInvalidParam4, ErrorCode::CommonInvalidParam5);
+    check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam6);
+    check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_crypto_bls_verify_multi_sig_bdn: multi_sig: {:?}, message: {:?}, ver_keys: {:?}, gen: {:?}", multi_sig, message, ver_keys, gen);
+
+    let res = match Bls::verify_multi_sig_bdn(multi_sig, message, &ver_keys, gen) {
+        Ok(valid) => {
+            trace!("indy_crypto_bls_verify_multi_sig_bdn: valid: {:?}", valid);
+            unsafe { *valid_p = valid; }
+            ErrorCode::Success
+        }
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_bls_verify_multi_sig_bdn: <<< res: {:?}", res);
+    res
+}
+
+/// Verifies the aggregated signature over distinct messages and returns true - if signature valid or false otherwise.
+///
+/// # Arguments
+///
+/// * `multi_sig` - Aggregated signature instance pointer
+/// * `messages` - Message buffer pointers array
+/// * `messages_lens` - Message buffer lens array
+/// * `ver_keys` - Verification key instance pointers array. i-th key corresponds to i-th message
+/// * `ver_keys_len` - Verification keys instance pointers array len. Also len of messages and messages_lens arrays
+/// * `gen` - Generator point instance
+/// * `valid_p` - Reference that will be filled with true - if signature valid or false otherwise.
+#[no_mangle]
+pub extern fn indy_crypto_bls_verify_aggregate(multi_sig: *const c_void,
+                                               messages: *const *const u8,
+                                               messages_lens: *const usize,
+                                               ver_keys: *const *const c_void,
+                                               ver_keys_len: usize,
+                                               gen: *const c_void,
+                                               valid_p: *mut bool) -> ErrorCode {
+    trace!("indy_crypto_bls_verify_aggregate: >>> multi_sig: {:?}, messages: {:?}, messages_lens: {:?}, ver_keys: {:?}, ver_keys_len: {:?}, gen: {:?}, valid_p: {:?}",
+           multi_sig, messages, messages_lens, ver_keys, ver_keys_len, gen, valid_p);
+
+    check_useful_c_reference!(multi_sig, MultiSignature, ErrorCode::CommonInvalidParam1);
+    check_useful_c_ptr!(messages, ErrorCode::CommonInvalidParam2);
+    check_useful_c_ptr!(messages_lens, ErrorCode::CommonInvalidParam3);
+    check_useful_c_reference_array!(ver_keys, ver_keys_len, VerKey, ErrorCode::CommonInvalidParam4, ErrorCode::CommonInvalidParam5);
+    check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam6);
+    check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam7);
+
+    let messages = unsafe { slice::from_raw_parts(messages, ver_keys_len) };
+    let messages_lens = unsafe { slice::from_raw_parts(messages_lens, ver_keys_len) };
+
+    if messages.iter().any(|message| message.is_null()) {
+        return ErrorCode::CommonInvalidParam2;
+    }
+
+    let messages: Vec<(&[u8], &VerKey)> = messages
+        .iter()
+        .zip(messages_lens.iter())
+        .map(|(message, message_len)| unsafe { slice::from_raw_parts(*message, *message_len) })
+        .zip(ver_keys.into_iter())
+        .collect();
+
+    trace!("indy_crypto_bls_verify_aggregate: multi_sig: {:?}, messages: {:?}, gen: {:?}", multi_sig, messages, gen);
+
+    let res = match Bls::verify_aggregate(multi_sig, &messages, gen) {
+        Ok(valid) => {
+            trace!("indy_crypto_bls_verify_aggregate: valid: {:?}", valid);
+            unsafe { *valid_p = valid; }
+            ErrorCode::Success
+        }
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_bls_verify_aggregate: <<< res: {:?}", res);
+    res
+}
+
 /// Verifies the proof of possession and returns true - if signature valid or false otherwise.
 ///
 /// # Arguments
@@ -832,6 +1019,7 @@ pub extern fn indy_crypto_bsl_verify_pop(pop: *const c_void,
 mod tests {
     use super::*;
     use std::ptr;
+    use std::ffi::CString;
 
     #[test]
     fn indy_crypto_bls_generator_new_works() {
@@ -928,6 +1116,38 @@ mod tests {
         assert_eq!(err_code, ErrorCode::Success);
     }
 
+    #[test]
+    fn indy_crypto_bls_sign_key_derive_works() {
+        let mut sign_key: *const c_void = ptr::null();
+
+        let seed_v = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10,
+                          11, 2, 3, 4, 5, 6, 7, 8, 9, 10,
+                          21, 2, 3, 4, 5, 6, 7, 8, 9, 10, 31, 32];
+        let seed = seed_v.as_ptr();
+        let seed_len = seed_v.len();
+        let path = CString::new("m/12381/3600/0/0").unwrap();
+
+        let err_code = indy_crypto_bls_sign_key_derive(seed, seed_len, path.as_ptr(), &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!sign_key.is_null());
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_sign_key_derive_works_for_invalid_path() {
+        let mut sign_key: *const c_void = ptr::null();
+
+        let seed_v = vec![7; 32];
+        let seed = seed_v.as_ptr();
+        let seed_len = seed_v.len();
+        let path = CString::new("12381/3600/0/0").unwrap();
+
+        let err_code = indy_crypto_bls_sign_key_derive(seed, seed_len, path.as_ptr(), &mut sign_key);
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+    }
+
     #[test]
     fn indy_crypto_bls_sign_key_as_bytes_works() {
         let mut sign_key: *const c_void = ptr::null();
@@ -1794,6 +2014,167 @@ mod tests {
         assert_eq!(err_code, ErrorCode::Success);
     }
 
+    #[test]
+    fn indy_crypto_bls_verify_multi_sig_bdn_works() {
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sign_key1: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sign_key2: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let message_v = vec![1, 2, 3, 4, 5];
+        let message = message_v.as_ptr();
+        let message_len = message_v.len();
+
+        let mut signature1: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key1, &mut signature1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut signature2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key2, &mut signature2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut ver_key1: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key1, &mut ver_key1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut ver_key2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key2, &mut ver_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let signatures = [signature1, signature2];
+        let ver_keys = [ver_key1, ver_key2];
+
+        let mut multi_sig: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_new_bdn(signatures.as_ptr(), signatures.len(),
+                                                               ver_keys.as_ptr(), ver_keys.len(),
+                                                               &mut multi_sig);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut valid = false;
+
+        let err_code = indy_crypto_bls_verify_multi_sig_bdn(multi_sig,
+                                                            message, message_len,
+                                                            ver_keys.as_ptr(), ver_keys.len(),
+                                                            gen,
+                                                            &mut valid);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(valid);
+
+        let err_code = indy_crypto_bls_generator_free(gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_ver_key_free(ver_key1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_ver_key_free(ver_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_signature_free(signature1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_signature_free(signature2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_multi_signature_free(multi_sig);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_verify_aggregate_works() {
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sign_key1: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sign_key2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let message1_v = vec![1, 2, 3, 4, 5];
+        let message2_v = vec![6, 7, 8, 9, 10];
+
+        let mut signature1: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message1_v.as_ptr(), message1_v.len(), sign_key1, &mut signature1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut signature2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message2_v.as_ptr(), message2_v.len(), sign_key2, &mut signature2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let signatures = [signature1, signature2];
+
+        let mut multi_sig: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut ver_key1: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key1, &mut ver_key1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut ver_key2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key2, &mut ver_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let messages = [message1_v.as_ptr(), message2_v.as_ptr()];
+        let messages_lens = [message1_v.len(), message2_v.len()];
+        let ver_keys = [ver_key1, ver_key2];
+        let mut valid = false;
+
+        let err_code = indy_crypto_bls_verify_aggregate(multi_sig,
+                                                        messages.as_ptr(), messages_lens.as_ptr(),
+                                                        ver_keys.as_ptr(), ver_keys.len(),
+                                                        gen,
+                                                        &mut valid);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(valid);
+
+        let err_code = indy_crypto_bls_generator_free(gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_ver_key_free(ver_key1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_ver_key_free(ver_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_signature_free(signature1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_signature_free(signature2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_multi_signature_free(multi_sig);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
     #[test]
     fn indy_crypto_bls_verify_multi_sig_works_for_invalid() {
         let mut gen: *const c_void = ptr::null();
@@ -0,0 +1,167 @@
+use cl::auditor::*;
+use cl::*;
+use errors::ToErrorCode;
+use errors::ErrorCode;
+use ffi::ctypes::CTypesUtils;
+
+use serde_json;
+use std::os::raw::c_void;
+use libc::c_char;
+
+/// Creates and returns auditor keys (public and private) for verifiable encryption of attributes.
+///
+/// # Arguments
+/// * `auditor_pub_key_json_p` - Reference that will contain auditor public key json.
+/// * `auditor_priv_key_json_p` - Reference that will contain auditor private key json.
+#[no_mangle]
+pub extern fn indy_crypto_cl_auditor_new_auditor_keys(auditor_pub_key_json_p: *mut *const c_char,
+                                                      auditor_priv_key_json_p: *mut *const c_char) -> ErrorCode {
+    trace!("indy_crypto_cl_auditor_new_auditor_keys: >>> auditor_pub_key_json_p: {:?}, auditor_priv_key_json_p: {:?}",
+           auditor_pub_key_json_p, auditor_priv_key_json_p);
+
+    check_useful_c_ptr!(auditor_pub_key_json_p, ErrorCode::CommonInvalidParam1);
+    check_useful_c_ptr!(auditor_priv_key_json_p, ErrorCode::CommonInvalidParam2);
+
+    let res = match Auditor::new_auditor_keys() {
+        Ok((auditor_pub_key, auditor_priv_key)) => {
+            trace!("indy_crypto_cl_auditor_new_auditor_keys: auditor_pub_key: {:?}, auditor_priv_key: {:?}", auditor_pub_key, secret!(&auditor_priv_key));
+            match (serde_json::to_string(&auditor_pub_key), serde_json::to_string(&auditor_priv_key)) {
+                (Ok(auditor_pub_key_json), Ok(auditor_priv_key_json)) => {
+                    unsafe {
+                        *auditor_pub_key_json_p = CTypesUtils::string_to_cstring(auditor_pub_key_json).into_raw();
+                        *auditor_priv_key_json_p = CTypesUtils::string_to_cstring(auditor_priv_key_json).into_raw();
+                        trace!("indy_crypto_cl_auditor_new_auditor_keys: *auditor_pub_key_json_p: {:?}, *auditor_priv_key_json_p: {:?}",
+                               *auditor_pub_key_json_p, *auditor_priv_key_json_p);
+                    }
+                    ErrorCode::Success
+                }
+                _ => ErrorCode::CommonInvalidState
+            }
+        }
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_auditor_new_auditor_keys: <<< res: {:?}", res);
+    res
+}
+
+/// Returns json array of ciphertexts of verifiably encrypted attributes of the proof
+/// in the order encryptions were requested in.
+///
+/// # Arguments
+/// * `proof` - Reference that contains proof instance pointer.
+/// * `ciphertexts_json_p` - Reference that will contain ciphertexts json.
+#[no_mangle]
+pub extern fn indy_crypto_cl_proof_get_verifiable_ciphertexts(proof: *const c_void,
+                                                              ciphertexts_json_p: *mut *const c_char) -> ErrorCode {
+    trace!("indy_crypto_cl_proof_get_verifiable_ciphertexts: >>> proof: {:?}, ciphertexts_json_p: {:?}", proof, ciphertexts_json_p);
+
+    check_useful_c_reference!(proof, Proof, ErrorCode::CommonInvalidParam1);
+    check_useful_c_ptr!(ciphertexts_json_p, ErrorCode::CommonInvalidParam2);
+
+    trace!("indy_crypto_cl_proof_get_verifiable_ciphertexts: entity >>> proof: {:?}", proof);
+
+    let res = match serde_json::to_string(&proof.get_verifiable_ciphertexts()) {
+        Ok(ciphertexts_json) => {
+            trace!("indy_crypto_cl_proof_get_verifiable_ciphertexts: ciphertexts_json: {:?}", ciphertexts_json);
+            unsafe {
+                *ciphertexts_json_p = CTypesUtils::string_to_cstring(ciphertexts_json).into_raw();
+                trace!("indy_crypto_cl_proof_get_verifiable_ciphertexts: *ciphertexts_json_p: {:?}", *ciphertexts_json_p);
+            }
+            ErrorCode::Success
+        }
+        Err(_) => ErrorCode::CommonInvalidState
+    };
+
+    trace!("indy_crypto_cl_proof_get_verifiable_ciphertexts: <<< res: {:?}", res);
+    res
+}
+
+/// Decrypts verifiably encrypted attribute value.
+///
+/// # Arguments
+/// * `auditor_pub_key_json` - Auditor public key json.
+/// * `auditor_priv_key_json` - Auditor private key json.
+/// * `ciphertext_json` - Ciphertext json taken from verified proof.
+/// * `dec_value_p` - Reference that will contain attribute value as decimal BigNum representation.
+#[no_mangle]
+pub extern fn indy_crypto_cl_auditor_decrypt(auditor_pub_key_json: *const c_char,
+                                             auditor_priv_key_json: *const c_char,
+                                             ciphertext_json: *const c_char,
+                                             dec_value_p: *mut *const c_char) -> ErrorCode {
+    trace!("indy_crypto_cl_auditor_decrypt: >>> auditor_pub_key_json: {:?}, auditor_priv_key_json: {:?}, ciphertext_json: {:?}, dec_value_p: {:?}",
+           auditor_pub_key_json, auditor_priv_key_json, ciphertext_json, dec_value_p);
+
+    check_useful_c_str!(auditor_pub_key_json, ErrorCode::CommonInvalidParam1);
+    check_useful_c_str!(auditor_priv_key_json, ErrorCode::CommonInvalidParam2);
+    check_useful_c_str!(ciphertext_json, ErrorCode::CommonInvalidParam3);
+    check_useful_c_ptr!(dec_value_p, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_crypto_cl_auditor_decrypt: entities: auditor_pub_key_json: {:?}, ciphertext_json: {:?}", auditor_pub_key_json, ciphertext_json);
+
+    let (auditor_pub_key, auditor_priv_key, ciphertext) =
+        match (serde_json::from_str::<AuditorPublicKey>(&auditor_pub_key_json),
+               serde_json::from_str::<AuditorPrivateKey>(&auditor_priv_key_json),
+               serde_json::from_str::<VerifiableCiphertext>(&ciphertext_json)) {
+            (Ok(auditor_pub_key), Ok(auditor_priv_key), Ok(ciphertext)) => (auditor_pub_key, auditor_priv_key, ciphertext),
+            _ => return ErrorCode::CommonInvalidStructure
+        };
+
+    let res = match Auditor::decrypt(&auditor_pub_key, &auditor_priv_key, &ciphertext).and_then(|value| value.to_dec()) {
+        Ok(dec_value) => {
+            trace!("indy_crypto_cl_auditor_decrypt: dec_value: {:?}", secret!(&dec_value));
+            unsafe {
+                *dec_value_p = CTypesUtils::string_to_cstring(dec_value).into_raw();
+                trace!("indy_crypto_cl_auditor_decrypt: *dec_value_p: {:?}", *dec_value_p);
+            }
+            ErrorCode::Success
+        }
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_auditor_decrypt: <<< res: {:?}", res);
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ffi::CString;
+    use std::ptr;
+
+    #[test]
+    fn indy_crypto_cl_auditor_new_auditor_keys_works() {
+        let mut auditor_pub_key_json_p: *const c_char = ptr::null();
+        let mut auditor_priv_key_json_p: *const c_char = ptr::null();
+        let err_code = indy_crypto_cl_auditor_new_auditor_keys(&mut auditor_pub_key_json_p, &mut auditor_priv_key_json_p);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let auditor_pub_key_json = unsafe { CString::from_raw(auditor_pub_key_json_p as *mut c_char) };
+        let auditor_priv_key_json = unsafe { CString::from_raw(auditor_priv_key_json_p as *mut c_char) };
+        assert!(serde_json::from_str::<AuditorPublicKey>(auditor_pub_key_json.to_str().unwrap()).is_ok());
+        assert!(serde_json::from_str::<AuditorPrivateKey>(auditor_priv_key_json.to_str().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn indy_crypto_cl_auditor_decrypt_works_for_invalid_ciphertext() {
+        let auditor_pub_key_json = CString::new(r#"{"n":"15","g":"4","y1":"4","y2":"4","y3":"4"}"#).unwrap();
+        let auditor_priv_key_json = CString::new(r#"{"x1":"2","x2":"2","x3":"2"}"#).unwrap();
+        let mut dec_value_p: *const c_char = ptr::null();
+
+        let ciphertext_json = CString::new(r#"{"u":"4","e":"4"}"#).unwrap();
+        let err_code = indy_crypto_cl_auditor_decrypt(auditor_pub_key_json.as_ptr(),
+                                                      auditor_priv_key_json.as_ptr(),
+                                                      ciphertext_json.as_ptr(),
+                                                      &mut dec_value_p);
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+
+        // `v` is greater than `n^2 / 2`
+        let ciphertext_json = CString::new(r#"{"u":"4","e":"4","v":"200","label":"label"}"#).unwrap();
+        let err_code = indy_crypto_cl_auditor_decrypt(auditor_pub_key_json.as_ptr(),
+                                                      auditor_priv_key_json.as_ptr(),
+                                                      ciphertext_json.as_ptr(),
+                                                      &mut dec_value_p);
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+    }
+}
@@ -10,7 +10,7 @@ use serde_json;
 use std::os::raw::c_void;
 use std::ptr::null;
 use std::slice;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::iter::FromIterator;
 
 
@@ -403,6 +403,71 @@ pub extern fn indy_crypto_cl_issuer_new_revocation_registry_def(credential_pub_k
     res
 }
 
+/// Creates and returns revocation registry definition entities (see indy_crypto_cl_issuer_new_revocation_registry_def)
+/// calculating accumulator of issuance by default registry in `threads` threads.
+///
+/// Note that keys registries deallocation must be performed by
+/// calling indy_crypto_cl_revocation_key_public_free and
+/// indy_crypto_cl_revocation_key_private_free.
+///
+/// Note that accumulator deallocation must be performed by
+/// calling indy_crypto_cl_revocation_registry_free.
+///
+/// Note that tails generator deallocation must be performed by
+/// calling indy_crypto_cl_revocation_tails_generator_free.
+///
+/// # Arguments
+/// * `credential_pub_key` - Reference that contains credential pub key instance pointer.
+/// * `max_cred_num` - Max credential number in generated registry.
+/// * `issuance_by_default` - Type of issuance.
+/// * `threads` - Number of threads.
+/// * `rev_key_pub_p` - Reference that will contain revocation key public instance pointer.
+/// * `rev_key_priv_p` - Reference that will contain revocation key private instance pointer.
+/// * `rev_reg_p` - Reference that will contain revocation registry instance pointer.
+/// * `rev_tails_generator_p` - Reference that will contain revocation tails generator instance pointer.
+#[no_mangle]
+pub extern fn indy_crypto_cl_issuer_new_revocation_registry_def_parallel(credential_pub_key: *const c_void,
+                                                                         max_cred_num: u32,
+                                                                         issuance_by_default: bool,
+                                                                         threads: u32,
+                                                                         rev_key_pub_p: *mut *const c_void,
+                                                                         rev_key_priv_p: *mut *const c_void,
+                                                                         rev_reg_p: *mut *const c_void,
+                                                                         rev_tails_generator_p: *mut *const c_void) -> ErrorCode {
+    trace!("indy_crypto_cl_issuer_new_revocation_registry_def_parallel: >>> credential_pub_key: {:?}, max_cred_num: {:?}, threads: {:?}, rev_key_pub_p: {:?}, rev_key_priv_p: {:?}, \
+    rev_reg_p: {:?}, rev_tails_generator_p: {:?}",
+           credential_pub_key, max_cred_num, threads, rev_key_pub_p, rev_key_priv_p, rev_reg_p, rev_tails_generator_p);
+
+    check_useful_c_reference!(credential_pub_key, CredentialPublicKey, ErrorCode::CommonInvalidParam1);
+    check_useful_c_ptr!(rev_key_pub_p, ErrorCode::CommonInvalidParam5);
+    check_useful_c_ptr!(rev_key_priv_p, ErrorCode::CommonInvalidParam6);
+    check_useful_c_ptr!(rev_reg_p, ErrorCode::CommonInvalidParam7);
+    check_useful_c_ptr!(rev_tails_generator_p, ErrorCode::CommonInvalidParam8);
+
+    trace!("indy_crypto_cl_issuer_new_revocation_registry_def_parallel: entities: credential_pub_key: {:?}, max_cred_num: {:?}, threads: {:?}",
+           credential_pub_key, max_cred_num, threads);
+
+    let res = match Issuer::new_revocation_registry_def_parallel(credential_pub_key, max_cred_num, issuance_by_default, threads as usize) {
+        Ok((rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator)) => {
+            trace!("indy_crypto_cl_issuer_new_revocation_registry_def_parallel: rev_key_pub_p: {:?}, rev_key_priv: {:?}, rev_reg: {:?}, rev_tails_generator: {:?}",
+                   rev_key_pub_p, secret!(&rev_key_priv), rev_reg, rev_tails_generator);
+            unsafe {
+                *rev_key_pub_p = Box::into_raw(Box::new(rev_key_pub)) as *const c_void;
+                *rev_key_priv_p = Box::into_raw(Box::new(rev_key_priv)) as *const c_void;
+                *rev_reg_p = Box::into_raw(Box::new(rev_reg)) as *const c_void;
+                *rev_tails_generator_p = Box::into_raw(Box::new(rev_tails_generator)) as *const c_void;
+                trace!("indy_crypto_cl_issuer_new_revocation_registry_def_parallel: *rev_key_pub_p: {:?}, *rev_key_priv_p: {:?}, *rev_reg_p: {:?}, *rev_tails_generator_p: {:?}",
+                       *rev_key_pub_p, *rev_key_priv_p, *rev_reg_p, *rev_tails_generator_p);
+            }
+            ErrorCode::Success
+        }
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_issuer_new_revocation_registry_def_parallel: <<< res: {:?}", res);
+    res
+}
+
 /// Returns json representation of revocation key public.
 ///
 /// # Arguments
@@ -1304,6 +1369,59 @@ pub extern fn indy_crypto_cl_issuer_recovery_credential(rev_reg: *const c_void,
     ErrorCode::Success
 }
 
+/// Recovers and revokes credentials by rev_idx's in a given revocation registry
+/// with single accumulator update and returns one revocation registry delta for all changes.
+///
+/// Note that revocation registry delta deallocation must be performed by
+/// calling indy_crypto_cl_revocation_registry_delta_free.
+///
+/// # Arguments
+/// * `rev_reg` - Reference that contain revocation registry instance pointer.
+/// * `max_cred_num` - Max credential number in revocation registry.
+/// * `issued` - Indexes of credentials to recover.
+/// * `issued_len` - Number of indexes of credentials to recover.
+/// * `revoked` - Indexes of credentials to revoke.
+/// * `revoked_len` - Number of indexes of credentials to revoke.
+/// * `ctx_tails` - Reference that contain tails context.
+/// * `take_tail` - Callback that takes tail by index.
+/// * `put_tail` - Callback that returns taken tail.
+/// * `rev_reg_delta_p` - Reference that will contain revocation registry delta instance pointer.
+#[no_mangle]
+pub extern fn indy_crypto_cl_issuer_update_revocation_registry(rev_reg: *const c_void,
+                                                               max_cred_num: u32,
+                                                               issued: *const u32, issued_len: usize,
+                                                               revoked: *const u32, revoked_len: usize,
+                                                               ctx_tails: *const c_void,
+                                                               take_tail: FFITailTake,
+                                                               put_tail: FFITailPut,
+                                                               rev_reg_delta_p: *mut *const c_void) -> ErrorCode {
+    trace!("indy_crypto_cl_issuer_update_revocation_registry: >>> rev_reg: {:?}, max_cred_num: {:?}, issued: {:?}, issued_len: {:?}, revoked: {:?}, \
+    revoked_len: {:?}, ctx_tails {:?}, take_tail {:?}, put_tail {:?}, rev_reg_delta_p {:?}",
+           rev_reg, max_cred_num, issued, issued_len, revoked, revoked_len, ctx_tails, take_tail, put_tail, rev_reg_delta_p);
+
+    check_useful_mut_c_reference!(rev_reg, RevocationRegistry, ErrorCode::CommonInvalidParam1);
+    check_useful_btreeset!(issued, issued_len, ErrorCode::CommonInvalidParam3, ErrorCode::CommonInvalidParam4);
+    check_useful_btreeset!(revoked, revoked_len, ErrorCode::CommonInvalidParam5, ErrorCode::CommonInvalidParam6);
+    check_useful_c_ptr!(rev_reg_delta_p, ErrorCode::CommonInvalidParam10);
+
+    trace!("indy_crypto_cl_issuer_update_revocation_registry: entities: rev_reg: {:?}, issued: {:?}, revoked: {:?}", rev_reg, issued, revoked);
+
+    let rta = FFITailsAccessor::new(ctx_tails, take_tail, put_tail);
+    let res = match Issuer::update_revocation_registry(rev_reg, max_cred_num, &issued, &revoked, &rta) {
+        Ok(rev_reg_delta) => {
+            unsafe {
+                *rev_reg_delta_p = Box::into_raw(Box::new(rev_reg_delta)) as *const c_void;
+                trace!("indy_crypto_cl_issuer_update_revocation_registry: *rev_reg_delta_p: {:?}", *rev_reg_delta_p);
+            }
+            ErrorCode::Success
+        }
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_issuer_update_revocation_registry: <<< res: {:?}", res);
+    res
+}
+
 #[no_mangle]
 pub extern fn indy_crypto_cl_issuer_merge_revocation_registry_deltas(revoc_reg_delta: *const c_void,
                                                                      other_revoc_reg_delta: *const c_void,
@@ -1485,6 +1603,32 @@ mod tests {
         _free_revocation_registry_def(rev_key_pub_p, rev_key_priv_p, rev_reg_p, rev_tails_generator_p);
     }
 
+    #[test]
+    fn indy_crypto_cl_issuer_new_revocation_registry_def_parallel_works() {
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
+        let mut rev_key_pub_p: *const c_void = ptr::null();
+        let mut rev_key_priv_p: *const c_void = ptr::null();
+        let mut rev_reg_p: *const c_void = ptr::null();
+        let mut rev_tails_generator_p: *const c_void = ptr::null();
+
+        let err_code = indy_crypto_cl_issuer_new_revocation_registry_def_parallel(credential_pub_key,
+                                                                                  100,
+                                                                                  true,
+                                                                                  2,
+                                                                                  &mut rev_key_pub_p,
+                                                                                  &mut rev_key_priv_p,
+                                                                                  &mut rev_reg_p,
+                                                                                  &mut rev_tails_generator_p);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!rev_key_pub_p.is_null());
+        assert!(!rev_key_priv_p.is_null());
+        assert!(!rev_reg_p.is_null());
+        assert!(!rev_tails_generator_p.is_null());
+
+        _free_credential_def(credential_pub_key, credential_priv_key, credential_key_correctness_proof);
+        _free_revocation_registry_def(rev_key_pub_p, rev_key_priv_p, rev_reg_p, rev_tails_generator_p);
+    }
+
     #[test]
     fn indy_crypto_cl_revocation_key_public_to_json_works() {
         let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
@@ -1958,6 +2102,47 @@ mod tests {
         _free_revocation_registry_def(rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator);
     }
 
+    #[test]
+    fn indy_crypto_cl_issuer_update_revocation_registry_works() {
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
+        let (rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator) = _revocation_registry_def(credential_pub_key);
+        let tail_storage = FFISimpleTailStorage::new(rev_tails_generator);
+
+        let issued_h = vec![1, 2];
+        let revoked_h = vec![3];
+
+        let mut revocation_registry_delta_p: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_issuer_update_revocation_registry(rev_reg,
+                                                                        5,
+                                                                        issued_h.as_ptr(), issued_h.len(),
+                                                                        revoked_h.as_ptr(), revoked_h.len(),
+                                                                        tail_storage.get_ctx(),
+                                                                        FFISimpleTailStorage::tail_take,
+                                                                        FFISimpleTailStorage::tail_put,
+                                                                        &mut revocation_registry_delta_p);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!revocation_registry_delta_p.is_null());
+
+        let err_code = indy_crypto_cl_revocation_registry_delta_free(revocation_registry_delta_p);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let revoked_h = vec![6];
+
+        let mut revocation_registry_delta_p: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_issuer_update_revocation_registry(rev_reg,
+                                                                        5,
+                                                                        issued_h.as_ptr(), issued_h.len(),
+                                                                        revoked_h.as_ptr(), revoked_h.len(),
+                                                                        tail_storage.get_ctx(),
+                                                                        FFISimpleTailStorage::tail_take,
+                                                                        FFISimpleTailStorage::tail_put,
+                                                                        &mut revocation_registry_delta_p);
+        assert_eq!(err_code, ErrorCode::AnoncredsInvalidRevocationAccumulatorIndex);
+
+        _free_credential_def(credential_pub_key, credential_priv_key, credential_key_correctness_proof);
+        _free_revocation_registry_def(rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator);
+    }
+
     #[test]
     fn indy_crypto_cl_issuer_merge_revoc_deltas_works() {
         let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
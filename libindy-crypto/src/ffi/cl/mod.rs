@@ -1,5 +1,7 @@
 use cl::*;
+use cl::encoding::AttributeValue;
 use cl::issuer::Issuer;
+use cl::tails::FileTailsAccessor;
 use cl::verifier::Verifier;
 use errors::{IndyCryptoError, ToErrorCode};
 use errors::ErrorCode;
@@ -10,8 +12,10 @@ use std::ptr;
 use std::os::raw::c_void;
 use libc::c_char;
 
+pub mod auditor;
 pub mod issuer;
 pub mod prover;
+pub mod tails;
 pub mod verifier;
 
 type FFITailTake = extern fn(ctx: *const c_void, idx: u32, tail_p: *mut *const c_void) -> ErrorCode;
@@ -111,6 +115,47 @@ pub extern fn indy_crypto_cl_witness_new(rev_idx: u32,
     res
 }
 
+/// Creates witness reading tails with file tails accessor, so block prefix sums of tails
+/// loaded by `indy_crypto_cl_file_tails_accessor_open_with_endpoint_checks` are used for issuance by default registries.
+///
+/// Note that witness deallocation must be performed by calling indy_crypto_cl_witness_free.
+///
+/// # Arguments
+/// * `rev_idx` - Index of the credential in the revocation registry.
+/// * `max_cred_num` - Max credential number in the revocation registry.
+/// * `issuance_by_default` - Type of issuance.
+/// * `rev_reg_delta` - Reference that contains revocation registry delta instance pointer.
+/// * `file_tails_accessor` - Reference that contains file tails accessor instance pointer.
+/// * `witness_p` - Reference that will contain witness instance pointer.
+#[no_mangle]
+pub extern fn indy_crypto_cl_witness_new_with_file_tails_accessor(rev_idx: u32,
+                                                                  max_cred_num: u32,
+                                                                  issuance_by_default: bool,
+                                                                  rev_reg_delta: *const c_void,
+                                                                  file_tails_accessor: *const c_void,
+                                                                  witness_p: *mut *const c_void) -> ErrorCode {
+    trace!("indy_crypto_cl_witness_new_with_file_tails_accessor: >>> rev_idx: {:?}, max_cred_num {}, issuance_by_default {}, rev_reg_delta {:?}, \
+    file_tails_accessor {:?}, witness_p {:?}", rev_idx, max_cred_num, issuance_by_default, rev_reg_delta, file_tails_accessor, witness_p);
+
+    check_useful_c_reference!(rev_reg_delta, RevocationRegistryDelta, ErrorCode::CommonInvalidParam4);
+    check_useful_c_reference!(file_tails_accessor, FileTailsAccessor, ErrorCode::CommonInvalidParam5);
+    check_useful_c_ptr!(witness_p, ErrorCode::CommonInvalidParam6);
+
+    let res = match Witness::new(rev_idx, max_cred_num, issuance_by_default, rev_reg_delta, file_tails_accessor) {
+        Ok(witness) => {
+            unsafe {
+                *witness_p = Box::into_raw(Box::new(witness)) as *const c_void;
+                trace!("indy_crypto_cl_witness_new_with_file_tails_accessor: *witness_p: {:?}", *witness_p);
+            }
+            ErrorCode::Success
+        }
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_witness_new_with_file_tails_accessor: <<< res: {:?}", res);
+    res
+}
+
 #[no_mangle]
 pub extern fn indy_crypto_cl_witness_update(rev_idx: u32,
                                             max_cred_num: u32,
@@ -491,6 +536,108 @@ pub extern fn indy_crypto_cl_credential_values_builder_add_dec_commitment(creden
     res
 }
 
+/// Adds new known attribute given as raw typed value to credential values map.
+///
+/// # Arguments
+/// * `credential_values_builder` - Reference that contains credential values builder instance pointer.
+/// * `attr` - Credential attr to add as null terminated string.
+/// * `attr_value_json` - Raw attribute value json, e.g. `{"type": "string", "value": "Alex"}`.
+#[no_mangle]
+pub extern fn indy_crypto_cl_credential_values_builder_add_typed_known(credential_values_builder: *const c_void,
+                                                                      attr: *const c_char,
+                                                                      attr_value_json: *const c_char) -> ErrorCode {
+    trace!("indy_crypto_cl_credential_values_builder_add_typed_known: >>> credential_values_builder: {:?}, attr: {:?}, attr_value_json: {:?}",
+           credential_values_builder, attr, attr_value_json);
+
+    check_useful_mut_c_reference!(credential_values_builder, CredentialValuesBuilder, ErrorCode::CommonInvalidParam1);
+    check_useful_c_str!(attr, ErrorCode::CommonInvalidParam2);
+    check_useful_c_str!(attr_value_json, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_crypto_cl_credential_values_builder_add_typed_known: entities: credential_values_builder: {:?}, attr: {:?}, attr_value_json: {:?}", credential_values_builder, attr, attr_value_json);
+
+    let attr_value = match serde_json::from_str::<AttributeValue>(&attr_value_json) {
+        Ok(attr_value) => attr_value,
+        Err(_) => return ErrorCode::CommonInvalidStructure
+    };
+
+    let res = match credential_values_builder.add_typed_known(&attr, &attr_value) {
+        Ok(_) => ErrorCode::Success,
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_credential_values_builder_add_typed_known: <<< res: {:?}", res);
+    res
+}
+
+/// Adds new hidden attribute given as raw typed value to credential values map.
+///
+/// # Arguments
+/// * `credential_values_builder` - Reference that contains credential values builder instance pointer.
+/// * `attr` - Credential attr to add as null terminated string.
+/// * `attr_value_json` - Raw attribute value json, e.g. `{"type": "date", "value": "1990-05-17"}`.
+#[no_mangle]
+pub extern fn indy_crypto_cl_credential_values_builder_add_typed_hidden(credential_values_builder: *const c_void,
+                                                                       attr: *const c_char,
+                                                                       attr_value_json: *const c_char) -> ErrorCode {
+    trace!("indy_crypto_cl_credential_values_builder_add_typed_hidden: >>> credential_values_builder: {:?}, attr: {:?}, attr_value_json: {:?}",
+           credential_values_builder, attr, attr_value_json);
+
+    check_useful_mut_c_reference!(credential_values_builder, CredentialValuesBuilder, ErrorCode::CommonInvalidParam1);
+    check_useful_c_str!(attr, ErrorCode::CommonInvalidParam2);
+    check_useful_c_str!(attr_value_json, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_crypto_cl_credential_values_builder_add_typed_hidden: entities: credential_values_builder: {:?}, attr: {:?}, attr_value_json: {:?}", credential_values_builder, attr, attr_value_json);
+
+    let attr_value = match serde_json::from_str::<AttributeValue>(&attr_value_json) {
+        Ok(attr_value) => attr_value,
+        Err(_) => return ErrorCode::CommonInvalidStructure
+    };
+
+    let res = match credential_values_builder.add_typed_hidden(&attr, &attr_value) {
+        Ok(_) => ErrorCode::Success,
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_credential_values_builder_add_typed_hidden: <<< res: {:?}", res);
+    res
+}
+
+/// Encodes raw typed attribute value to value that is signed by issuer and revealed in proofs.
+///
+/// # Arguments
+/// * `attr_value_json` - Raw attribute value json, e.g. `{"type": "int", "value": "28"}`.
+/// * `encoded_dec_value_p` - Reference that will contain encoded value as decimal BigNum representation.
+#[no_mangle]
+pub extern fn indy_crypto_cl_attribute_value_encode(attr_value_json: *const c_char,
+                                                    encoded_dec_value_p: *mut *const c_char) -> ErrorCode {
+    trace!("indy_crypto_cl_attribute_value_encode: >>> attr_value_json: {:?}, encoded_dec_value_p: {:?}", attr_value_json, encoded_dec_value_p);
+
+    check_useful_c_str!(attr_value_json, ErrorCode::CommonInvalidParam1);
+    check_useful_c_ptr!(encoded_dec_value_p, ErrorCode::CommonInvalidParam2);
+
+    trace!("indy_crypto_cl_attribute_value_encode: entities: attr_value_json: {:?}", attr_value_json);
+
+    let attr_value = match serde_json::from_str::<AttributeValue>(&attr_value_json) {
+        Ok(attr_value) => attr_value,
+        Err(_) => return ErrorCode::CommonInvalidStructure
+    };
+
+    let res = match attr_value.encode().and_then(|encoded| encoded.to_dec()) {
+        Ok(encoded_dec_value) => {
+            trace!("indy_crypto_cl_attribute_value_encode: encoded_dec_value: {:?}", encoded_dec_value);
+            unsafe {
+                *encoded_dec_value_p = CTypesUtils::string_to_cstring(encoded_dec_value).into_raw();
+                trace!("indy_crypto_cl_attribute_value_encode: *encoded_dec_value_p: {:?}", *encoded_dec_value_p);
+            }
+            ErrorCode::Success
+        }
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_attribute_value_encode: <<< res: {:?}", res);
+    res
+}
+
 /// Deallocates credential values builder and returns credential values entity instead.
 ///
 /// Note: Credentials values instance deallocation must be performed by
@@ -609,7 +756,7 @@ pub extern fn indy_crypto_cl_sub_proof_request_builder_add_revealed_attr(sub_pro
 /// # Arguments
 /// * `sub_proof_request_builder` - Reference that contains sub proof request builder instance pointer.
 /// * `attr_name` - Related attribute
-/// * `p_type` - Predicate type (Currently `GE` only).
+/// * `p_type` - Predicate type (`GE`, `LE`, `GT`, `LT` or `NE`).
 /// * `value` - Requested value.
 #[no_mangle]
 pub extern fn indy_crypto_cl_sub_proof_request_builder_add_predicate(sub_proof_request_builder: *const c_void,
@@ -635,6 +782,171 @@ pub extern fn indy_crypto_cl_sub_proof_request_builder_add_predicate(sub_proof_r
     res
 }
 
+/// Adds range predicate to sub proof request.
+///
+/// Requested attribute value must satisfy `from <= value < to`.
+///
+/// # Arguments
+/// * `sub_proof_request_builder` - Reference that contains sub proof request builder instance pointer.
+/// * `attr_name` - Related attribute
+/// * `from` - Lower bound (inclusive).
+/// * `to` - Upper bound (exclusive).
+#[no_mangle]
+pub extern fn indy_crypto_cl_sub_proof_request_builder_add_range_predicate(sub_proof_request_builder: *const c_void,
+                                                                           attr_name: *const c_char,
+                                                                           from: i32,
+                                                                           to: i32) -> ErrorCode {
+    trace!("indy_crypto_cl_sub_proof_request_builder_add_range_predicate: >>> sub_proof_request_builder: {:?}, attr_name: {:?}, from: {:?}, to: {:?}",
+           sub_proof_request_builder, attr_name, from, to);
+
+    check_useful_mut_c_reference!(sub_proof_request_builder, SubProofRequestBuilder, ErrorCode::CommonInvalidParam1);
+    check_useful_c_str!(attr_name, ErrorCode::CommonInvalidParam2);
+
+    trace!("indy_crypto_cl_sub_proof_request_builder_add_range_predicate: entities: >>> sub_proof_request_builder: {:?}, attr_name: {:?}, from: {:?}, to: {:?}",
+           sub_proof_request_builder, attr_name, from, to);
+
+    let res = match sub_proof_request_builder.add_range_predicate(&attr_name, from, to) {
+        Ok(_) => ErrorCode::Success,
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_sub_proof_request_builder_add_range_predicate: <<< res: {:?}", res);
+    res
+}
+
+/// Adds predicate with value of arbitrary precision to sub proof request.
+///
+/// # Arguments
+/// * `sub_proof_request_builder` - Reference that contains sub proof request builder instance pointer.
+/// * `attr_name` - Related attribute
+/// * `p_type` - Predicate type (`GE`, `LE`, `GT`, `LT` or `NE`).
+/// * `value` - Requested value as decimal string, may be negative.
+#[no_mangle]
+pub extern fn indy_crypto_cl_sub_proof_request_builder_add_dec_predicate(sub_proof_request_builder: *const c_void,
+                                                                         attr_name: *const c_char,
+                                                                         p_type: *const c_char,
+                                                                         value: *const c_char) -> ErrorCode {
+    trace!("indy_crypto_cl_sub_proof_request_builder_add_dec_predicate: >>> sub_proof_request_builder: {:?}, attr_name: {:?}, p_type: {:?}, value: {:?}",
+           sub_proof_request_builder, attr_name, p_type, value);
+
+    check_useful_mut_c_reference!(sub_proof_request_builder, SubProofRequestBuilder, ErrorCode::CommonInvalidParam1);
+    check_useful_c_str!(attr_name, ErrorCode::CommonInvalidParam2);
+    check_useful_c_str!(p_type, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(value, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_crypto_cl_sub_proof_request_builder_add_dec_predicate: entities: >>> sub_proof_request_builder: {:?}, attr_name: {:?}, p_type: {:?}, value: {:?}",
+           sub_proof_request_builder, attr_name, p_type, value);
+
+    let res = match sub_proof_request_builder.add_dec_predicate(&attr_name, &p_type, &value) {
+        Ok(_) => ErrorCode::Success,
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_sub_proof_request_builder_add_dec_predicate: <<< res: {:?}", res);
+    res
+}
+
+/// Adds range predicate with bounds of arbitrary precision to sub proof request.
+///
+/// Requested attribute value must satisfy `from <= value < to`.
+///
+/// # Arguments
+/// * `sub_proof_request_builder` - Reference that contains sub proof request builder instance pointer.
+/// * `attr_name` - Related attribute
+/// * `from` - Lower bound (inclusive) as decimal string, may be negative.
+/// * `to` - Upper bound (exclusive) as decimal string, may be negative.
+#[no_mangle]
+pub extern fn indy_crypto_cl_sub_proof_request_builder_add_dec_range_predicate(sub_proof_request_builder: *const c_void,
+                                                                               attr_name: *const c_char,
+                                                                               from: *const c_char,
+                                                                               to: *const c_char) -> ErrorCode {
+    trace!("indy_crypto_cl_sub_proof_request_builder_add_dec_range_predicate: >>> sub_proof_request_builder: {:?}, attr_name: {:?}, from: {:?}, to: {:?}",
+           sub_proof_request_builder, attr_name, from, to);
+
+    check_useful_mut_c_reference!(sub_proof_request_builder, SubProofRequestBuilder, ErrorCode::CommonInvalidParam1);
+    check_useful_c_str!(attr_name, ErrorCode::CommonInvalidParam2);
+    check_useful_c_str!(from, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(to, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_crypto_cl_sub_proof_request_builder_add_dec_range_predicate: entities: >>> sub_proof_request_builder: {:?}, attr_name: {:?}, from: {:?}, to: {:?}",
+           sub_proof_request_builder, attr_name, from, to);
+
+    let res = match sub_proof_request_builder.add_dec_range_predicate(&attr_name, &from, &to) {
+        Ok(_) => ErrorCode::Success,
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_sub_proof_request_builder_add_dec_range_predicate: <<< res: {:?}", res);
+    res
+}
+
+/// Adds set membership predicate to sub proof request.
+///
+/// Requested attribute value must be one of given values, verifier doesn't learn which one.
+///
+/// # Arguments
+/// * `sub_proof_request_builder` - Reference that contains sub proof request builder instance pointer.
+/// * `attr_name` - Related attribute
+/// * `values_json` - JSON array of allowed encoded attribute values as decimal strings.
+#[no_mangle]
+pub extern fn indy_crypto_cl_sub_proof_request_builder_add_set_membership_predicate(sub_proof_request_builder: *const c_void,
+                                                                                    attr_name: *const c_char,
+                                                                                    values_json: *const c_char) -> ErrorCode {
+    trace!("indy_crypto_cl_sub_proof_request_builder_add_set_membership_predicate: >>> sub_proof_request_builder: {:?}, attr_name: {:?}, values_json: {:?}",
+           sub_proof_request_builder, attr_name, values_json);
+
+    check_useful_mut_c_reference!(sub_proof_request_builder, SubProofRequestBuilder, ErrorCode::CommonInvalidParam1);
+    check_useful_c_str!(attr_name, ErrorCode::CommonInvalidParam2);
+    check_useful_c_str!(values_json, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_crypto_cl_sub_proof_request_builder_add_set_membership_predicate: entities: >>> sub_proof_request_builder: {:?}, attr_name: {:?}, values_json: {:?}",
+           sub_proof_request_builder, attr_name, values_json);
+
+    let values = match serde_json::from_str::<Vec<String>>(&values_json) {
+        Ok(values) => values,
+        Err(_) => return ErrorCode::CommonInvalidStructure
+    };
+
+    let values = values.iter().map(String::as_str).collect::<Vec<&str>>();
+
+    let res = match sub_proof_request_builder.add_set_membership_predicate(&attr_name, &values) {
+        Ok(_) => ErrorCode::Success,
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_sub_proof_request_builder_add_set_membership_predicate: <<< res: {:?}", res);
+    res
+}
+
+/// Requests scope-exclusive pseudonym `H(scope)^secret` of hidden attribute in sub proof request.
+///
+/// # Arguments
+/// * `sub_proof_request_builder` - Reference that contains sub proof request builder instance pointer.
+/// * `attr_name` - Hidden attribute pseudonym is derived from (usually master secret).
+/// * `scope` - Scope of pseudonym as null terminated string.
+#[no_mangle]
+pub extern fn indy_crypto_cl_sub_proof_request_builder_add_pseudonym(sub_proof_request_builder: *const c_void,
+                                                                     attr_name: *const c_char,
+                                                                     scope: *const c_char) -> ErrorCode {
+    trace!("indy_crypto_cl_sub_proof_request_builder_add_pseudonym: >>> sub_proof_request_builder: {:?}, attr_name: {:?}, scope: {:?}",
+           sub_proof_request_builder, attr_name, scope);
+
+    check_useful_mut_c_reference!(sub_proof_request_builder, SubProofRequestBuilder, ErrorCode::CommonInvalidParam1);
+    check_useful_c_str!(attr_name, ErrorCode::CommonInvalidParam2);
+    check_useful_c_str!(scope, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_crypto_cl_sub_proof_request_builder_add_pseudonym: entities: >>> sub_proof_request_builder: {:?}, attr_name: {:?}, scope: {:?}",
+           sub_proof_request_builder, attr_name, scope);
+
+    let res = match sub_proof_request_builder.add_pseudonym(&attr_name, &scope) {
+        Ok(_) => ErrorCode::Success,
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_sub_proof_request_builder_add_pseudonym: <<< res: {:?}", res);
+    res
+}
+
 /// Deallocates sub proof request builder and returns sub proof request entity instead.
 ///
 /// Note: Sub proof request instance deallocation must be performed by
@@ -1012,6 +1324,42 @@ mod tests {
         _free_credential_values_builder(credential_values_builder);
     }
 
+    #[test]
+    fn indy_crypto_cl_credential_values_builder_add_typed_known_and_hidden_works() {
+        let credential_values_builder = _credential_values_builder();
+
+        let attr = CString::new("name").unwrap();
+        let attr_value_json = CString::new(r#"{"type":"string","value":"Alex"}"#).unwrap();
+        let err_code = indy_crypto_cl_credential_values_builder_add_typed_known(credential_values_builder, attr.as_ptr(), attr_value_json.as_ptr());
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let attr = CString::new("birth_date").unwrap();
+        let attr_value_json = CString::new(r#"{"type":"date","value":"1990-05-17"}"#).unwrap();
+        let err_code = indy_crypto_cl_credential_values_builder_add_typed_hidden(credential_values_builder, attr.as_ptr(), attr_value_json.as_ptr());
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let attr_value_json = CString::new(r#"{"type":"date","value":"1990-02-30"}"#).unwrap();
+        let err_code = indy_crypto_cl_credential_values_builder_add_typed_hidden(credential_values_builder, attr.as_ptr(), attr_value_json.as_ptr());
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+
+        let attr_value_json = CString::new(r#"{"type":"float","value":"1.5"}"#).unwrap();
+        let err_code = indy_crypto_cl_credential_values_builder_add_typed_known(credential_values_builder, attr.as_ptr(), attr_value_json.as_ptr());
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+
+        _free_credential_values_builder(credential_values_builder);
+    }
+
+    #[test]
+    fn indy_crypto_cl_attribute_value_encode_works() {
+        let attr_value_json = CString::new(r#"{"type":"int","value":"28"}"#).unwrap();
+        let mut encoded_dec_value_p: *const c_char = ptr::null();
+        let err_code = indy_crypto_cl_attribute_value_encode(attr_value_json.as_ptr(), &mut encoded_dec_value_p);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let encoded_dec_value = unsafe { CString::from_raw(encoded_dec_value_p as *mut c_char) };
+        assert_eq!("28", encoded_dec_value.to_str().unwrap());
+    }
+
     #[test]
     fn indy_crypto_cl_credential_values_builder_add_dec_commitment_works() {
         let credential_values_builder = _credential_values_builder();
@@ -1077,6 +1425,105 @@ mod tests {
         _free_sub_proof_request_builder(sub_proof_request_builder);
     }
 
+    #[test]
+    fn indy_crypto_cl_sub_proof_request_builder_add_predicate_works_for_ne() {
+        let sub_proof_request_builder = _sub_proof_request_builder();
+
+        let attr_name = CString::new("age").unwrap();
+        let p_type = CString::new("NE").unwrap();
+        let value = 18;
+
+        let err_code = indy_crypto_cl_sub_proof_request_builder_add_predicate(sub_proof_request_builder, attr_name.as_ptr(), p_type.as_ptr(), value);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        _free_sub_proof_request_builder(sub_proof_request_builder);
+    }
+
+    #[test]
+    fn indy_crypto_cl_sub_proof_request_builder_add_range_predicate_works() {
+        let sub_proof_request_builder = _sub_proof_request_builder();
+
+        let attr_name = CString::new("age").unwrap();
+
+        let err_code = indy_crypto_cl_sub_proof_request_builder_add_range_predicate(sub_proof_request_builder, attr_name.as_ptr(), 18, 65);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_cl_sub_proof_request_builder_add_range_predicate(sub_proof_request_builder, attr_name.as_ptr(), 65, 18);
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+
+        _free_sub_proof_request_builder(sub_proof_request_builder);
+    }
+
+    #[test]
+    fn indy_crypto_cl_sub_proof_request_builder_add_dec_predicate_works() {
+        let sub_proof_request_builder = _sub_proof_request_builder();
+
+        let attr_name = CString::new("balance").unwrap();
+        let p_type = CString::new("GE").unwrap();
+        let value = CString::new("-12345678901234567890").unwrap();
+
+        let err_code = indy_crypto_cl_sub_proof_request_builder_add_dec_predicate(sub_proof_request_builder, attr_name.as_ptr(), p_type.as_ptr(), value.as_ptr());
+        assert_eq!(err_code, ErrorCode::Success);
+
+        _free_sub_proof_request_builder(sub_proof_request_builder);
+    }
+
+    #[test]
+    fn indy_crypto_cl_sub_proof_request_builder_add_dec_range_predicate_works() {
+        let sub_proof_request_builder = _sub_proof_request_builder();
+
+        let attr_name = CString::new("timestamp").unwrap();
+        let from = CString::new("-1").unwrap();
+        let to = CString::new("1700000000000").unwrap();
+
+        let err_code = indy_crypto_cl_sub_proof_request_builder_add_dec_range_predicate(sub_proof_request_builder, attr_name.as_ptr(), from.as_ptr(), to.as_ptr());
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_cl_sub_proof_request_builder_add_dec_range_predicate(sub_proof_request_builder, attr_name.as_ptr(), to.as_ptr(), from.as_ptr());
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+
+        _free_sub_proof_request_builder(sub_proof_request_builder);
+    }
+
+    #[test]
+    fn indy_crypto_cl_sub_proof_request_builder_add_set_membership_predicate_works() {
+        let sub_proof_request_builder = _sub_proof_request_builder();
+
+        let attr_name = CString::new("age").unwrap();
+        let values_json = CString::new(r#"["18", "28", "35"]"#).unwrap();
+
+        let err_code = indy_crypto_cl_sub_proof_request_builder_add_set_membership_predicate(sub_proof_request_builder, attr_name.as_ptr(), values_json.as_ptr());
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let values_json = CString::new("[]").unwrap();
+
+        let err_code = indy_crypto_cl_sub_proof_request_builder_add_set_membership_predicate(sub_proof_request_builder, attr_name.as_ptr(), values_json.as_ptr());
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+
+        let values_json = CString::new(r#"[18, 28]"#).unwrap();
+
+        let err_code = indy_crypto_cl_sub_proof_request_builder_add_set_membership_predicate(sub_proof_request_builder, attr_name.as_ptr(), values_json.as_ptr());
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+
+        _free_sub_proof_request_builder(sub_proof_request_builder);
+    }
+
+    #[test]
+    fn indy_crypto_cl_sub_proof_request_builder_add_pseudonym_works() {
+        let sub_proof_request_builder = _sub_proof_request_builder();
+
+        let attr_name = CString::new("master_secret").unwrap();
+        let scope = CString::new("example.com").unwrap();
+
+        let err_code = indy_crypto_cl_sub_proof_request_builder_add_pseudonym(sub_proof_request_builder, attr_name.as_ptr(), scope.as_ptr());
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_cl_sub_proof_request_builder_add_pseudonym(sub_proof_request_builder, attr_name.as_ptr(), scope.as_ptr());
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+
+        _free_sub_proof_request_builder(sub_proof_request_builder);
+    }
+
     #[test]
     fn indy_crypto_cl_sub_proof_request_builder_finalize_works() {
         let sub_proof_request_builder = _sub_proof_request_builder();
@@ -1362,6 +1809,26 @@ pub mod mocks {
         sub_proof_request
     }
 
+    pub fn _sub_proof_request_with_pseudonym() -> *const c_void {
+        let sub_proof_request_builder = _sub_proof_request_builder();
+
+        let revealed_attr = CString::new("name").unwrap();
+        let err_code = indy_crypto_cl_sub_proof_request_builder_add_revealed_attr(sub_proof_request_builder, revealed_attr.as_ptr());
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let attr_name = CString::new("master_secret").unwrap();
+        let scope = CString::new("example.com").unwrap();
+        let err_code = indy_crypto_cl_sub_proof_request_builder_add_pseudonym(sub_proof_request_builder, attr_name.as_ptr(), scope.as_ptr());
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sub_proof_request: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_sub_proof_request_builder_finalize(sub_proof_request_builder, &mut sub_proof_request);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!sub_proof_request.is_null());
+
+        sub_proof_request
+    }
+
     pub fn _free_sub_proof_request(sub_proof_request: *const c_void) {
         let err_code = indy_crypto_cl_sub_proof_request_free(sub_proof_request);
         assert_eq!(err_code, ErrorCode::Success);
@@ -6,6 +6,7 @@ use ffi::ctypes::CTypesUtils;
 
 use serde_json;
 use std::os::raw::c_void;
+use std::slice;
 use libc::c_char;
 
 /// Creates a master secret.
@@ -616,6 +617,111 @@ pub extern fn indy_crypto_cl_prover_new_proof_builder(proof_builder_p: *mut *con
     res
 }
 
+/// Requires the prover to use equal hidden values for two attributes of (possibly) different sub proofs.
+///
+/// Must be called before the referenced sub proofs are added to the proof builder.
+///
+/// # Arguments
+/// * `proof_builder` - Reference that contain proof builder instance pointer.
+/// * `attr_equality_json` - Attribute equality in json format:
+///     {"first": {"sub_proof_index": 0, "attr_name": "name"}, "second": {"sub_proof_index": 1, "attr_name": "name"}}
+#[no_mangle]
+pub extern fn indy_crypto_cl_proof_builder_add_attribute_equality(proof_builder: *const c_void,
+                                                                  attr_equality_json: *const c_char) -> ErrorCode {
+    trace!("indy_crypto_cl_proof_builder_add_attribute_equality: >>> proof_builder: {:?}, attr_equality_json: {:?}",
+           proof_builder, attr_equality_json);
+
+    check_useful_mut_c_reference!(proof_builder, ProofBuilder, ErrorCode::CommonInvalidParam1);
+    check_useful_c_str!(attr_equality_json, ErrorCode::CommonInvalidParam2);
+
+    trace!("indy_crypto_cl_proof_builder_add_attribute_equality: entities: proof_builder: {:?}, attr_equality_json: {:?}",
+           proof_builder, attr_equality_json);
+
+    let attr_equality = match serde_json::from_str::<AttributeEquality>(&attr_equality_json) {
+        Ok(attr_equality) => attr_equality,
+        Err(_) => return ErrorCode::CommonInvalidStructure
+    };
+
+    let res = match proof_builder.add_attribute_equality(&attr_equality) {
+        Ok(()) => ErrorCode::Success,
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_proof_builder_add_attribute_equality: <<< res: {:?}", res);
+    res
+}
+
+/// Adds linear predicate over hidden attributes of sub proofs to the proof builder.
+///
+/// Linear predicates are proven on finalization, the order must be the same as for proof verifier.
+///
+/// # Arguments
+/// * `proof_builder` - Reference that contain proof builder instance pointer.
+/// * `linear_predicate_json` - Linear predicate in json format:
+///     {"terms": [{"attr": {"sub_proof_index": 0, "attr_name": "income"}, "coefficient": 1},
+///                {"attr": {"sub_proof_index": 1, "attr_name": "rent"}, "coefficient": -3}],
+///      "p_type": "GT", "value": 0}
+#[no_mangle]
+pub extern fn indy_crypto_cl_proof_builder_add_linear_predicate(proof_builder: *const c_void,
+                                                                linear_predicate_json: *const c_char) -> ErrorCode {
+    trace!("indy_crypto_cl_proof_builder_add_linear_predicate: >>> proof_builder: {:?}, linear_predicate_json: {:?}",
+           proof_builder, linear_predicate_json);
+
+    check_useful_mut_c_reference!(proof_builder, ProofBuilder, ErrorCode::CommonInvalidParam1);
+    check_useful_c_str!(linear_predicate_json, ErrorCode::CommonInvalidParam2);
+
+    trace!("indy_crypto_cl_proof_builder_add_linear_predicate: entities: proof_builder: {:?}, linear_predicate_json: {:?}",
+           proof_builder, linear_predicate_json);
+
+    let linear_predicate = match serde_json::from_str::<LinearPredicate>(&linear_predicate_json) {
+        Ok(linear_predicate) => linear_predicate,
+        Err(_) => return ErrorCode::CommonInvalidStructure
+    };
+
+    let res = match proof_builder.add_linear_predicate(&linear_predicate) {
+        Ok(()) => ErrorCode::Success,
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_proof_builder_add_linear_predicate: <<< res: {:?}", res);
+    res
+}
+
+/// Adds requirement to verifiably encrypt hidden attribute of sub proof to auditor to the proof builder.
+///
+/// Encryptions are proven on finalization, the order must be the same as for proof verifier.
+///
+/// # Arguments
+/// * `proof_builder` - Reference that contain proof builder instance pointer.
+/// * `verifiable_encryption_json` - Verifiable encryption in json format:
+///     {"attr": {"sub_proof_index": 0, "attr_name": "ssn"}, "auditor_pub_key": {"n": "...", "g": "...", "y1": "...", "y2": "...", "y3": "..."},
+///      "label": "fraud investigation"}
+#[no_mangle]
+pub extern fn indy_crypto_cl_proof_builder_add_verifiable_encryption(proof_builder: *const c_void,
+                                                                     verifiable_encryption_json: *const c_char) -> ErrorCode {
+    trace!("indy_crypto_cl_proof_builder_add_verifiable_encryption: >>> proof_builder: {:?}, verifiable_encryption_json: {:?}",
+           proof_builder, verifiable_encryption_json);
+
+    check_useful_mut_c_reference!(proof_builder, ProofBuilder, ErrorCode::CommonInvalidParam1);
+    check_useful_c_str!(verifiable_encryption_json, ErrorCode::CommonInvalidParam2);
+
+    trace!("indy_crypto_cl_proof_builder_add_verifiable_encryption: entities: proof_builder: {:?}, verifiable_encryption_json: {:?}",
+           proof_builder, verifiable_encryption_json);
+
+    let verifiable_encryption = match serde_json::from_str::<VerifiableEncryption>(&verifiable_encryption_json) {
+        Ok(verifiable_encryption) => verifiable_encryption,
+        Err(_) => return ErrorCode::CommonInvalidStructure
+    };
+
+    let res = match proof_builder.add_verifiable_encryption(&verifiable_encryption) {
+        Ok(()) => ErrorCode::Success,
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_proof_builder_add_verifiable_encryption: <<< res: {:?}", res);
+    res
+}
+
 /// Add a sub proof request to the proof builder
 ///
 /// # Arguments
@@ -694,7 +800,7 @@ pub extern fn indy_crypto_cl_proof_builder_add_sub_proof_request(proof_builder:
                                                         credential_pub_key,
                                                         rev_reg,
                                                         witness) {
-        Ok(()) => ErrorCode::Success,
+        Ok(_) => ErrorCode::Success,
         Err(err) => err.to_error_code()
     };
 
@@ -702,6 +808,114 @@ pub extern fn indy_crypto_cl_proof_builder_add_sub_proof_request(proof_builder:
     ErrorCode::Success
 }
 
+/// Add a sub proof request to proof builder and returns pseudonym requested by it
+/// (null pointer is returned if sub proof request doesn't request pseudonym).
+///
+/// # Arguments
+/// * `proof_builder` - Reference that contain proof builder instance pointer.
+/// * `sub_proof_request` - Reference that contain requested attributes and predicates instance pointer.
+/// * `credential_schema` - Reference that contains credential schema instance pointer.
+/// * `non_credential_schema` - Reference that contains non credential schema instance pointer.
+/// * `credential_signature` - Reference that contains the credential signature pointer.
+/// * `credential_values` - Reference that contains credential values instance pointer.
+/// * `credential_pub_key` - Reference that contains credential public key instance pointer.
+/// * `rev_reg` - (Optional) Reference that will contain revocation registry public instance pointer.
+/// * `witness` - (Optional) Reference that will contain witness instance pointer.
+/// * `pseudonym_json_p` - Reference that will contain pseudonym json or null.
+#[no_mangle]
+pub extern fn indy_crypto_cl_proof_builder_add_sub_proof_request_with_pseudonym(proof_builder: *const c_void,
+                                                                                sub_proof_request: *const c_void,
+                                                                                credential_schema: *const c_void,
+                                                                                non_credential_schema: *const c_void,
+                                                                                credential_signature: *const c_void,
+                                                                                credential_values: *const c_void,
+                                                                                credential_pub_key: *const c_void,
+                                                                                rev_reg: *const c_void,
+                                                                                witness: *const c_void,
+                                                                                pseudonym_json_p: *mut *const c_char) -> ErrorCode {
+    trace!("indy_crypto_cl_proof_builder_add_sub_proof_request_with_pseudonym: >>> proof_builder: {:?}, \
+                                                                                   sub_proof_request: {:?}, \
+                                                                                   credential_schema: {:?}, \
+                                                                                   non_credential_schema: {:?}, \
+                                                                                   credential_signature: {:?}, \
+                                                                                   credential_values: {:?}, \
+                                                                                   credential_pub_key: {:?}, \
+                                                                                   rev_reg: {:?}, \
+                                                                                   witness: {:?}, \
+                                                                                   pseudonym_json_p: {:?}",
+           proof_builder,
+           sub_proof_request,
+           credential_schema,
+           non_credential_schema,
+           credential_signature,
+           credential_values,
+           credential_pub_key,
+           rev_reg,
+           witness,
+           pseudonym_json_p);
+
+    check_useful_mut_c_reference!(proof_builder, ProofBuilder, ErrorCode::CommonInvalidParam1);
+    check_useful_c_reference!(sub_proof_request, SubProofRequest, ErrorCode::CommonInvalidParam2);
+    check_useful_c_reference!(credential_schema, CredentialSchema, ErrorCode::CommonInvalidParam3);
+    check_useful_c_reference!(non_credential_schema, NonCredentialSchema, ErrorCode::CommonInvalidParam4);
+    check_useful_c_reference!(credential_signature, CredentialSignature, ErrorCode::CommonInvalidParam5);
+    check_useful_c_reference!(credential_values, CredentialValues, ErrorCode::CommonInvalidParam6);
+    check_useful_c_reference!(credential_pub_key, CredentialPublicKey, ErrorCode::CommonInvalidParam7);
+    check_useful_opt_c_reference!(rev_reg, RevocationRegistry);
+    check_useful_opt_c_reference!(witness, Witness);
+    check_useful_c_ptr!(pseudonym_json_p, ErrorCode::CommonInvalidParam10);
+
+    trace!("indy_crypto_cl_proof_builder_add_sub_proof_request_with_pseudonym: entities: proof_builder: {:?}, \
+                                                                                         sub_proof_request: {:?}, \
+                                                                                         credential_schema: {:?}, \
+                                                                                         non_credential_schema: {:?}, \
+                                                                                         credential_signature: {:?}, \
+                                                                                         credential_values: {:?}, \
+                                                                                         credential_pub_key: {:?}, \
+                                                                                         rev_reg: {:?}, \
+                                                                                         witness: {:?}",
+           proof_builder,
+           sub_proof_request,
+           credential_schema,
+           non_credential_schema,
+           credential_signature,
+           credential_values,
+           credential_pub_key,
+           rev_reg,
+           witness);
+
+    let res = match proof_builder.add_sub_proof_request(sub_proof_request,
+                                                        credential_schema,
+                                                        non_credential_schema,
+                                                        credential_signature,
+                                                        credential_values,
+                                                        credential_pub_key,
+                                                        rev_reg,
+                                                        witness) {
+        Ok(Some(pseudonym)) => {
+            trace!("indy_crypto_cl_proof_builder_add_sub_proof_request_with_pseudonym: pseudonym: {:?}", pseudonym);
+            match serde_json::to_string(&pseudonym) {
+                Ok(pseudonym_json) => {
+                    unsafe {
+                        *pseudonym_json_p = CTypesUtils::string_to_cstring(pseudonym_json).into_raw();
+                        trace!("indy_crypto_cl_proof_builder_add_sub_proof_request_with_pseudonym: *pseudonym_json_p: {:?}", *pseudonym_json_p);
+                    }
+                    ErrorCode::Success
+                }
+                Err(_) => ErrorCode::CommonInvalidState
+            }
+        }
+        Ok(None) => {
+            unsafe { *pseudonym_json_p = ::std::ptr::null(); }
+            ErrorCode::Success
+        }
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_proof_builder_add_sub_proof_request_with_pseudonym: <<< res: {:?}", res);
+    res
+}
+
 
 /// Finalize proof.
 ///
@@ -744,6 +958,52 @@ pub extern fn indy_crypto_cl_proof_builder_finalize(proof_builder: *const c_void
     res
 }
 
+/// Finalize proof bound to the message (signature of knowledge over the message).
+///
+/// Note that proof deallocation must be performed by
+/// calling indy_crypto_cl_proof_free.
+///
+/// # Arguments
+/// * `proof_builder` - Reference that contain proof builder instance pointer.
+/// * `nonce` - Reference that contain nonce instance pointer.
+/// * `message` - Message buffer pointer.
+/// * `message_len` - Message buffer len.
+/// * `proof_p` - Reference that will contain proof instance pointer.
+#[no_mangle]
+pub extern fn indy_crypto_cl_proof_builder_finalize_with_message(proof_builder: *const c_void,
+                                                                 nonce: *const c_void,
+                                                                 message: *const u8,
+                                                                 message_len: usize,
+                                                                 proof_p: *mut *const c_void) -> ErrorCode {
+    trace!("indy_crypto_cl_proof_builder_finalize_with_message: >>> proof_builder: {:?}, nonce: {:?}, message: {:?}, message_len: {:?}, proof_p: {:?}",
+           proof_builder, nonce, message, message_len, proof_p);
+
+    check_useful_c_ptr!(proof_builder, ErrorCode::CommonInvalidParam1);
+    check_useful_c_reference!(nonce, Nonce, ErrorCode::CommonInvalidParam2);
+    check_useful_c_byte_array!(message, message_len, ErrorCode::CommonInvalidParam3, ErrorCode::CommonInvalidParam4);
+    check_useful_c_ptr!(proof_p, ErrorCode::CommonInvalidParam5);
+
+    let proof_builder = unsafe { Box::from_raw(proof_builder as *mut ProofBuilder) };
+
+    trace!("indy_crypto_cl_proof_builder_finalize_with_message: entities: proof_builder: {:?}, nonce: {:?}, message: {:?}",
+           proof_builder, nonce, message);
+
+    let res = match proof_builder.finalize_with_message(nonce, message) {
+        Ok(proof) => {
+            trace!("indy_crypto_cl_proof_builder_finalize_with_message: proof: {:?}", proof);
+            unsafe {
+                *proof_p = Box::into_raw(Box::new(proof)) as *const c_void;
+                trace!("indy_crypto_cl_proof_builder_finalize_with_message: *proof_p: {:?}", *proof_p);
+            }
+            ErrorCode::Success
+        }
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_proof_builder_finalize_with_message: <<< res: {:?}", res);
+    res
+}
+
 /// Returns json representation of proof.
 ///
 /// # Arguments
@@ -833,6 +1093,7 @@ pub extern fn indy_crypto_cl_proof_free(proof: *const c_void) -> ErrorCode {
 mod tests {
     use super::*;
 
+    use std::ffi::CString;
     use std::ptr;
     use ffi::cl::mocks::*;
     use ffi::cl::issuer::mocks::*;
@@ -1014,6 +1275,72 @@ mod tests {
         _free_non_credential_schema(non_credential_schema);
     }
 
+    #[test]
+    fn indy_crypto_cl_proof_builder_add_sub_proof_request_with_pseudonym_works() {
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
+        let credential_values = _credential_values();
+        let credential_nonce = _nonce();
+        let (blinded_credential_secrets,
+            credential_secrets_blinding_factors,
+            blinded_credential_secrets_correctness_proof) = _blinded_credential_secrets(credential_pub_key,
+                                                                                        credential_key_correctness_proof,
+                                                                                        credential_values,
+                                                                                        credential_nonce);
+        let sub_proof_request = _sub_proof_request();
+        let credential_schema = _credential_schema();
+        let non_credential_schema = _non_credential_schema();
+        let credential_issuance_nonce = _nonce();
+        let (credential_signature, signature_correctness_proof) = _credential_signature(blinded_credential_secrets,
+                                                                                        blinded_credential_secrets_correctness_proof,
+                                                                                        credential_nonce,
+                                                                                        credential_issuance_nonce,
+                                                                                        credential_values,
+                                                                                        credential_pub_key,
+                                                                                        credential_pub_key);
+        _process_credential_signature(credential_signature,
+                                      signature_correctness_proof,
+                                      credential_secrets_blinding_factors,
+                                      credential_values,
+                                      credential_pub_key,
+                                      credential_issuance_nonce,
+                                      ptr::null(),
+                                      ptr::null(),
+                                      ptr::null());
+
+        let nonce = _nonce();
+        let (proof, pseudonym_json) = _proof_with_pseudonym(credential_pub_key, credential_signature, nonce, credential_values);
+        assert!(pseudonym_json.contains("example.com"));
+
+        // Pseudonym is not requested
+        let proof_builder = _proof_builder();
+        let mut pseudonym_json_p: *const c_char = ptr::null();
+        let err_code = indy_crypto_cl_proof_builder_add_sub_proof_request_with_pseudonym(proof_builder,
+                                                                                         sub_proof_request,
+                                                                                         credential_schema,
+                                                                                         non_credential_schema,
+                                                                                         credential_signature,
+                                                                                         credential_values,
+                                                                                         credential_pub_key,
+                                                                                         ptr::null(),
+                                                                                         ptr::null(),
+                                                                                         &mut pseudonym_json_p);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(pseudonym_json_p.is_null());
+
+        _free_proof(proof);
+        _free_proof_builder(proof_builder, nonce);
+        _free_nonce(nonce);
+        _free_credential_def(credential_pub_key, credential_priv_key, credential_key_correctness_proof);
+        _free_blinded_credential_secrets(blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof);
+        _free_nonce(credential_nonce);
+        _free_nonce(credential_issuance_nonce);
+        _free_credential_values(credential_values);
+        _free_sub_proof_request(sub_proof_request);
+        _free_credential_signature(credential_signature, signature_correctness_proof);
+        _free_credential_schema(credential_schema);
+        _free_non_credential_schema(non_credential_schema);
+    }
+
     #[test]
     fn indy_crypto_cl_prover_blinded_credential_secrets_from_json_works() {
         let credential_values = _credential_values();
@@ -1193,6 +1520,111 @@ mod tests {
         _free_proof_builder(proof_builder, nonce);
     }
 
+    #[test]
+    fn indy_crypto_cl_proof_builder_add_attribute_equality_works() {
+        let proof_builder = _proof_builder();
+        let attr_equality_json = CString::new(r#"{"first":{"sub_proof_index":0,"attr_name":"name"},"second":{"sub_proof_index":1,"attr_name":"name"}}"#).unwrap();
+
+        let err_code = indy_crypto_cl_proof_builder_add_attribute_equality(proof_builder, attr_equality_json.as_ptr());
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let nonce = _nonce();
+
+        _free_proof_builder(proof_builder, nonce);
+        _free_nonce(nonce);
+    }
+
+    #[test]
+    fn indy_crypto_cl_proof_builder_add_attribute_equality_works_for_invalid_json() {
+        let proof_builder = _proof_builder();
+        let attr_equality_json = CString::new(r#"{"first":{"sub_proof_index":0,"attr_name":"name"}}"#).unwrap();
+
+        let err_code = indy_crypto_cl_proof_builder_add_attribute_equality(proof_builder, attr_equality_json.as_ptr());
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+
+        let nonce = _nonce();
+
+        _free_proof_builder(proof_builder, nonce);
+        _free_nonce(nonce);
+    }
+
+    #[test]
+    fn indy_crypto_cl_proof_builder_add_linear_predicate_works() {
+        let proof_builder = _proof_builder();
+        let linear_predicate_json = CString::new(r#"{"terms":[{"attr":{"sub_proof_index":0,"attr_name":"age"},"coefficient":1}],"p_type":"GE","value":18}"#).unwrap();
+
+        let err_code = indy_crypto_cl_proof_builder_add_linear_predicate(proof_builder, linear_predicate_json.as_ptr());
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let nonce = _nonce();
+
+        // Linear predicate refers to sub proof that wasn't added
+        let mut proof: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_proof_builder_finalize(proof_builder, nonce, &mut proof);
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+
+        _free_nonce(nonce);
+    }
+
+    #[test]
+    fn indy_crypto_cl_proof_builder_add_linear_predicate_works_for_unsupported_predicate_type() {
+        let proof_builder = _proof_builder();
+        let linear_predicate_json = CString::new(r#"{"terms":[{"attr":{"sub_proof_index":0,"attr_name":"age"},"coefficient":1}],"p_type":"NE","value":18}"#).unwrap();
+
+        let err_code = indy_crypto_cl_proof_builder_add_linear_predicate(proof_builder, linear_predicate_json.as_ptr());
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+
+        let nonce = _nonce();
+
+        _free_proof_builder(proof_builder, nonce);
+        _free_nonce(nonce);
+    }
+
+    #[test]
+    fn indy_crypto_cl_proof_builder_finalize_with_message_works_for_empty_message() {
+        let proof_builder = _proof_builder();
+        let nonce = _nonce();
+
+        let mut proof: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_proof_builder_finalize_with_message(proof_builder, nonce, ptr::null(), 0, &mut proof);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam3);
+
+        _free_proof_builder(proof_builder, nonce);
+        _free_nonce(nonce);
+    }
+
+    #[test]
+    fn indy_crypto_cl_proof_builder_add_verifiable_encryption_works() {
+        let proof_builder = _proof_builder();
+        let verifiable_encryption_json = CString::new(r#"{"attr":{"sub_proof_index":0,"attr_name":"age"},"auditor_pub_key":{"n":"15","g":"4","y1":"4","y2":"4","y3":"4"},"label":"label"}"#).unwrap();
+
+        let err_code = indy_crypto_cl_proof_builder_add_verifiable_encryption(proof_builder, verifiable_encryption_json.as_ptr());
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let nonce = _nonce();
+
+        // Verifiable encryption refers to sub proof that wasn't added
+        let mut proof: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_proof_builder_finalize(proof_builder, nonce, &mut proof);
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+
+        _free_nonce(nonce);
+    }
+
+    #[test]
+    fn indy_crypto_cl_proof_builder_add_verifiable_encryption_works_for_invalid_json() {
+        let proof_builder = _proof_builder();
+        let verifiable_encryption_json = CString::new(r#"{"attr":{"sub_proof_index":0,"attr_name":"age"},"label":"label"}"#).unwrap();
+
+        let err_code = indy_crypto_cl_proof_builder_add_verifiable_encryption(proof_builder, verifiable_encryption_json.as_ptr());
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+
+        let nonce = _nonce();
+
+        _free_proof_builder(proof_builder, nonce);
+        _free_nonce(nonce);
+    }
+
     #[test]
     fn indy_crypto_cl_prover_proof_builder_finalize_works() {
         let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
@@ -1452,6 +1884,7 @@ mod tests {
 pub mod mocks {
     use super::*;
 
+    use std::ffi::CStr;
     use std::ptr;
     use ffi::cl::mocks::*;
 
@@ -1546,6 +1979,74 @@ pub mod mocks {
     pub fn _proof(credential_pub_key: *const c_void, credential_signature: *const c_void,
                   nonce: *const c_void, credential_values: *const c_void,
                   rev_reg: *const c_void, witness: *const c_void) -> *const c_void {
+        let proof_builder = _proof_builder_with_sub_proof(credential_pub_key, credential_signature, credential_values, rev_reg, witness);
+
+        let mut proof: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_proof_builder_finalize(proof_builder,
+                                                             nonce,
+                                                             &mut proof);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!proof.is_null());
+
+        proof
+    }
+
+    pub fn _proof_with_message(credential_pub_key: *const c_void, credential_signature: *const c_void,
+                               nonce: *const c_void, credential_values: *const c_void, message: &[u8]) -> *const c_void {
+        let proof_builder = _proof_builder_with_sub_proof(credential_pub_key, credential_signature, credential_values, ptr::null(), ptr::null());
+
+        let mut proof: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_proof_builder_finalize_with_message(proof_builder,
+                                                                          nonce,
+                                                                          message.as_ptr(),
+                                                                          message.len(),
+                                                                          &mut proof);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!proof.is_null());
+
+        proof
+    }
+
+    // Returns proof with pseudonym for "example.com" scope and json of the pseudonym
+    pub fn _proof_with_pseudonym(credential_pub_key: *const c_void, credential_signature: *const c_void,
+                                 nonce: *const c_void, credential_values: *const c_void) -> (*const c_void, String) {
+        let proof_builder = _proof_builder();
+        let credential_schema = _credential_schema();
+        let non_credential_schema = _non_credential_schema();
+        let sub_proof_request = _sub_proof_request_with_pseudonym();
+
+        let mut pseudonym_json_p: *const c_char = ptr::null();
+        let err_code = indy_crypto_cl_proof_builder_add_sub_proof_request_with_pseudonym(proof_builder,
+                                                                                         sub_proof_request,
+                                                                                         credential_schema,
+                                                                                         non_credential_schema,
+                                                                                         credential_signature,
+                                                                                         credential_values,
+                                                                                         credential_pub_key,
+                                                                                         ptr::null(),
+                                                                                         ptr::null(),
+                                                                                         &mut pseudonym_json_p);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!pseudonym_json_p.is_null());
+        let pseudonym_json = unsafe { CStr::from_ptr(pseudonym_json_p).to_str().unwrap().to_owned() };
+
+        _free_credential_schema(credential_schema);
+        _free_non_credential_schema(non_credential_schema);
+        _free_sub_proof_request(sub_proof_request);
+
+        let mut proof: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_proof_builder_finalize(proof_builder,
+                                                             nonce,
+                                                             &mut proof);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!proof.is_null());
+
+        (proof, pseudonym_json)
+    }
+
+    fn _proof_builder_with_sub_proof(credential_pub_key: *const c_void, credential_signature: *const c_void,
+                                     credential_values: *const c_void,
+                                     rev_reg: *const c_void, witness: *const c_void) -> *const c_void {
         let proof_builder = _proof_builder();
         let credential_schema = _credential_schema();
         let non_credential_schema = _non_credential_schema();
@@ -1561,19 +2062,12 @@ pub mod mocks {
                                                            rev_reg,
                                                            witness);
 
-        let mut proof: *const c_void = ptr::null();
-        let err_code = indy_crypto_cl_proof_builder_finalize(proof_builder,
-                                                             nonce,
-                                                             &mut proof);
-        assert_eq!(err_code, ErrorCode::Success);
-        assert!(!proof.is_null());
-
         _free_credential_schema(credential_schema);
         _free_non_credential_schema(non_credential_schema);
         _free_credential_values(credential_values);
         _free_sub_proof_request(sub_proof_request);
 
-        proof
+        proof_builder
     }
 
     pub fn _free_proof(proof: *const c_void) {
@@ -0,0 +1,454 @@
+use cl::*;
+use cl::tails::*;
+use errors::ToErrorCode;
+use errors::ErrorCode;
+use ffi::ctypes::CTypesUtils;
+
+use std::os::raw::c_void;
+use libc::c_char;
+
+/// Writes all tails of unused revocation tails generator to the binary tails file
+/// and returns canonical tails hash that must be published with the registry definition.
+///
+/// # Arguments
+/// * `rev_tails_generator` - Reference that contains revocation tails generator instance pointer.
+/// * `tails_file_path` - Path of the tails file to create.
+/// * `tails_hash_p` - Reference that will contain hex encoded tails hash.
+#[no_mangle]
+pub extern fn indy_crypto_cl_tails_file_write(rev_tails_generator: *const c_void,
+                                              tails_file_path: *const c_char,
+                                              tails_hash_p: *mut *const c_char) -> ErrorCode {
+    trace!("indy_crypto_cl_tails_file_write: >>> rev_tails_generator: {:?}, tails_file_path: {:?}, tails_hash_p: {:?}",
+           rev_tails_generator, tails_file_path, tails_hash_p);
+
+    check_useful_mut_c_reference!(rev_tails_generator, RevocationTailsGenerator, ErrorCode::CommonInvalidParam1);
+    check_useful_c_str!(tails_file_path, ErrorCode::CommonInvalidParam2);
+    check_useful_c_ptr!(tails_hash_p, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_crypto_cl_tails_file_write: entities: rev_tails_generator: {:?}, tails_file_path: {:?}", rev_tails_generator, tails_file_path);
+
+    let res = match TailsFileWriter::write(rev_tails_generator, &tails_file_path) {
+        Ok(tails_hash) => {
+            trace!("indy_crypto_cl_tails_file_write: tails_hash: {:?}", tails_hash);
+            unsafe {
+                *tails_hash_p = CTypesUtils::string_to_cstring(tails_hash).into_raw();
+                trace!("indy_crypto_cl_tails_file_write: *tails_hash_p: {:?}", *tails_hash_p);
+            }
+            ErrorCode::Success
+        }
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_tails_file_write: <<< res: {:?}", res);
+    res
+}
+
+/// Writes all tails of unused revocation tails generator to the binary tails file generating them
+/// in several threads and returns canonical tails hash that must be published with the registry definition.
+///
+/// # Arguments
+/// * `rev_tails_generator` - Reference that contains revocation tails generator instance pointer.
+/// * `tails_file_path` - Path of the tails file to create.
+/// * `threads` - Number of threads.
+/// * `tails_hash_p` - Reference that will contain hex encoded tails hash.
+#[no_mangle]
+pub extern fn indy_crypto_cl_tails_file_write_parallel(rev_tails_generator: *const c_void,
+                                                       tails_file_path: *const c_char,
+                                                       threads: u32,
+                                                       tails_hash_p: *mut *const c_char) -> ErrorCode {
+    trace!("indy_crypto_cl_tails_file_write_parallel: >>> rev_tails_generator: {:?}, tails_file_path: {:?}, threads: {:?}, tails_hash_p: {:?}",
+           rev_tails_generator, tails_file_path, threads, tails_hash_p);
+
+    check_useful_mut_c_reference!(rev_tails_generator, RevocationTailsGenerator, ErrorCode::CommonInvalidParam1);
+    check_useful_c_str!(tails_file_path, ErrorCode::CommonInvalidParam2);
+    check_useful_c_ptr!(tails_hash_p, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_crypto_cl_tails_file_write_parallel: entities: rev_tails_generator: {:?}, tails_file_path: {:?}, threads: {:?}",
+           rev_tails_generator, tails_file_path, threads);
+
+    let res = match TailsFileWriter::write_parallel(rev_tails_generator, &tails_file_path, threads as usize) {
+        Ok(tails_hash) => {
+            trace!("indy_crypto_cl_tails_file_write_parallel: tails_hash: {:?}", tails_hash);
+            unsafe {
+                *tails_hash_p = CTypesUtils::string_to_cstring(tails_hash).into_raw();
+                trace!("indy_crypto_cl_tails_file_write_parallel: *tails_hash_p: {:?}", *tails_hash_p);
+            }
+            ErrorCode::Success
+        }
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_tails_file_write_parallel: <<< res: {:?}", res);
+    res
+}
+
+/// Writes all tails of unused revocation tails generator to the binary tails file and block prefix sums
+/// of tails to the file next to it and returns canonical tails hash that must be published with the registry definition.
+///
+/// Prefix sums are loaded by `indy_crypto_cl_file_tails_accessor_open_with_endpoint_checks` and make `indy_crypto_cl_witness_new_with_file_tails_accessor`
+/// for issuance by default registries fast.
+///
+/// # Arguments
+/// * `rev_tails_generator` - Reference that contains revocation tails generator instance pointer.
+/// * `tails_file_path` - Path of the tails file to create.
+/// * `threads` - Number of threads.
+/// * `block_size` - Number of tails in block of prefix sums.
+/// * `tails_hash_p` - Reference that will contain hex encoded tails hash.
+#[no_mangle]
+pub extern fn indy_crypto_cl_tails_file_write_with_prefix_sums(rev_tails_generator: *const c_void,
+                                                               tails_file_path: *const c_char,
+                                                               threads: u32,
+                                                               block_size: u32,
+                                                               tails_hash_p: *mut *const c_char) -> ErrorCode {
+    trace!("indy_crypto_cl_tails_file_write_with_prefix_sums: >>> rev_tails_generator: {:?}, tails_file_path: {:?}, threads: {:?}, block_size: {:?}, \
+            tails_hash_p: {:?}", rev_tails_generator, tails_file_path, threads, block_size, tails_hash_p);
+
+    check_useful_mut_c_reference!(rev_tails_generator, RevocationTailsGenerator, ErrorCode::CommonInvalidParam1);
+    check_useful_c_str!(tails_file_path, ErrorCode::CommonInvalidParam2);
+    check_useful_c_ptr!(tails_hash_p, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_crypto_cl_tails_file_write_with_prefix_sums: entities: rev_tails_generator: {:?}, tails_file_path: {:?}, threads: {:?}, block_size: {:?}",
+           rev_tails_generator, tails_file_path, threads, block_size);
+
+    let res = match TailsFileWriter::write_with_prefix_sums(rev_tails_generator, &tails_file_path, threads as usize, block_size) {
+        Ok(tails_hash) => {
+            trace!("indy_crypto_cl_tails_file_write_with_prefix_sums: tails_hash: {:?}", tails_hash);
+            unsafe {
+                *tails_hash_p = CTypesUtils::string_to_cstring(tails_hash).into_raw();
+                trace!("indy_crypto_cl_tails_file_write_with_prefix_sums: *tails_hash_p: {:?}", *tails_hash_p);
+            }
+            ErrorCode::Success
+        }
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_tails_file_write_with_prefix_sums: <<< res: {:?}", res);
+    res
+}
+
+/// Opens binary tails file, checks it against published tails hash, checks only the first tail
+/// and the tail `L+1` against revocation registry and returns file tails accessor instance that reads tails on demand.
+/// Block prefix sums are loaded from the file next to the tails file if it exists.
+///
+/// File tails accessor instance pointer can be passed as `ctx_tails` together with
+/// `indy_crypto_cl_file_tails_accessor_take_tail` and `indy_crypto_cl_file_tails_accessor_put_tail`
+/// callbacks to functions that access tails.
+///
+/// Note that file tails accessor deallocation must be performed by
+/// calling indy_crypto_cl_file_tails_accessor_free.
+///
+/// # Arguments
+/// * `tails_file_path` - Path of the tails file.
+/// * `tails_hash` - Hex encoded tails hash published with the registry definition.
+/// * `credential_pub_key` - Reference that contains credential public key instance pointer.
+/// * `rev_key_pub` - Reference that contains revocation key public instance pointer.
+/// * `max_cred_num` - Max credential number in the revocation registry.
+/// * `file_tails_accessor_p` - Reference that will contain file tails accessor instance pointer.
+#[no_mangle]
+pub extern fn indy_crypto_cl_file_tails_accessor_open_with_endpoint_checks(tails_file_path: *const c_char,
+                                                                           tails_hash: *const c_char,
+                                                                           credential_pub_key: *const c_void,
+                                                                           rev_key_pub: *const c_void,
+                                                                           max_cred_num: u32,
+                                                                           file_tails_accessor_p: *mut *const c_void) -> ErrorCode {
+    trace!("indy_crypto_cl_file_tails_accessor_open_with_endpoint_checks: >>> tails_file_path: {:?}, tails_hash: {:?}, credential_pub_key: {:?}, rev_key_pub: {:?}, \
+            max_cred_num: {:?}, file_tails_accessor_p: {:?}", tails_file_path, tails_hash, credential_pub_key, rev_key_pub, max_cred_num, file_tails_accessor_p);
+
+    check_useful_c_str!(tails_file_path, ErrorCode::CommonInvalidParam1);
+    check_useful_c_str!(tails_hash, ErrorCode::CommonInvalidParam2);
+    check_useful_c_reference!(credential_pub_key, CredentialPublicKey, ErrorCode::CommonInvalidParam3);
+    check_useful_c_reference!(rev_key_pub, RevocationKeyPublic, ErrorCode::CommonInvalidParam4);
+    check_useful_c_ptr!(file_tails_accessor_p, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_crypto_cl_file_tails_accessor_open_with_endpoint_checks: entities: tails_file_path: {:?}, tails_hash: {:?}, credential_pub_key: {:?}, rev_key_pub: {:?}, \
+            max_cred_num: {:?}", tails_file_path, tails_hash, credential_pub_key, rev_key_pub, max_cred_num);
+
+    let res = match FileTailsAccessor::open_with_endpoint_checks(&tails_file_path, &tails_hash, credential_pub_key, rev_key_pub, max_cred_num) {
+        Ok(file_tails_accessor) => {
+            trace!("indy_crypto_cl_file_tails_accessor_open_with_endpoint_checks: file_tails_accessor: {:?}", file_tails_accessor);
+            unsafe {
+                *file_tails_accessor_p = Box::into_raw(Box::new(file_tails_accessor)) as *const c_void;
+                trace!("indy_crypto_cl_file_tails_accessor_open_with_endpoint_checks: *file_tails_accessor_p: {:?}", *file_tails_accessor_p);
+            }
+            ErrorCode::Success
+        }
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_file_tails_accessor_open_with_endpoint_checks: <<< res: {:?}", res);
+    res
+}
+
+/// `take_tail` callback that reads tail from the tails file.
+/// Tail must be returned by `indy_crypto_cl_file_tails_accessor_put_tail`.
+///
+/// # Arguments
+/// * `ctx` - Reference that contains file tails accessor instance pointer.
+/// * `idx` - Index of the tail.
+/// * `tail_p` - Reference that will contain tail instance pointer.
+#[no_mangle]
+pub extern fn indy_crypto_cl_file_tails_accessor_take_tail(ctx: *const c_void,
+                                                           idx: u32,
+                                                           tail_p: *mut *const c_void) -> ErrorCode {
+    trace!("indy_crypto_cl_file_tails_accessor_take_tail: >>> ctx: {:?}, idx: {:?}, tail_p: {:?}", ctx, idx, tail_p);
+
+    check_useful_c_reference!(ctx, FileTailsAccessor, ErrorCode::CommonInvalidParam1);
+    check_useful_c_ptr!(tail_p, ErrorCode::CommonInvalidParam3);
+
+    let mut tail: Option<Tail> = None;
+
+    let res = match ctx.access_tail(idx, &mut |t| tail = Some(*t)) {
+        Ok(()) => {
+            unsafe {
+                *tail_p = tail.map(|tail| Box::into_raw(Box::new(tail)) as *const c_void).unwrap_or(::std::ptr::null());
+                trace!("indy_crypto_cl_file_tails_accessor_take_tail: *tail_p: {:?}", *tail_p);
+            }
+            ErrorCode::Success
+        }
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_file_tails_accessor_take_tail: <<< res: {:?}", res);
+    res
+}
+
+/// `put_tail` callback that deallocates tail returned by `indy_crypto_cl_file_tails_accessor_take_tail`.
+///
+/// # Arguments
+/// * `ctx` - Reference that contains file tails accessor instance pointer.
+/// * `tail` - Reference that contains tail instance pointer.
+#[no_mangle]
+pub extern fn indy_crypto_cl_file_tails_accessor_put_tail(ctx: *const c_void,
+                                                          tail: *const c_void) -> ErrorCode {
+    trace!("indy_crypto_cl_file_tails_accessor_put_tail: >>> ctx: {:?}, tail: {:?}", ctx, tail);
+
+    check_useful_c_ptr!(tail, ErrorCode::CommonInvalidParam2);
+
+    let tail = unsafe { Box::from_raw(tail as *mut Tail); };
+    trace!("indy_crypto_cl_file_tails_accessor_put_tail: entity: tail: {:?}", tail);
+
+    let res = ErrorCode::Success;
+
+    trace!("indy_crypto_cl_file_tails_accessor_put_tail: <<< res: {:?}", res);
+    res
+}
+
+/// Deallocates file tails accessor instance.
+///
+/// # Arguments
+/// * `file_tails_accessor` - Reference that contains file tails accessor instance pointer.
+#[no_mangle]
+pub extern fn indy_crypto_cl_file_tails_accessor_free(file_tails_accessor: *const c_void) -> ErrorCode {
+    trace!("indy_crypto_cl_file_tails_accessor_free: >>> file_tails_accessor: {:?}", file_tails_accessor);
+
+    check_useful_c_ptr!(file_tails_accessor, ErrorCode::CommonInvalidParam1);
+
+    let file_tails_accessor = unsafe { Box::from_raw(file_tails_accessor as *mut FileTailsAccessor); };
+    trace!("indy_crypto_cl_file_tails_accessor_free: entity: file_tails_accessor: {:?}", file_tails_accessor);
+
+    let res = ErrorCode::Success;
+
+    trace!("indy_crypto_cl_file_tails_accessor_free: <<< res: {:?}", res);
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashSet;
+    use std::env;
+    use std::ffi::CString;
+    use std::fs;
+    use std::ptr;
+    use ffi::cl::{indy_crypto_cl_witness_new, indy_crypto_cl_witness_new_with_file_tails_accessor, indy_crypto_cl_witness_free};
+    use ffi::cl::issuer::mocks::*;
+    use serde_json;
+
+    #[test]
+    fn indy_crypto_cl_file_tails_accessor_works() {
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
+        let (rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator) = _revocation_registry_def(credential_pub_key);
+
+        let path = env::temp_dir().join("indy_crypto_cl_file_tails_accessor_works.tails");
+        let tails_file_path = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut tails_hash: *const c_char = ptr::null();
+        let err_code = indy_crypto_cl_tails_file_write(rev_tails_generator, tails_file_path.as_ptr(), &mut tails_hash);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!tails_hash.is_null());
+
+        let mut file_tails_accessor: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_file_tails_accessor_open_with_endpoint_checks(tails_file_path.as_ptr(),
+                                                                                    tails_hash,
+                                                                                    credential_pub_key,
+                                                                                    rev_key_pub,
+                                                                                    5,
+                                                                                    &mut file_tails_accessor);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!file_tails_accessor.is_null());
+
+        let mut tail: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_file_tails_accessor_take_tail(file_tails_accessor, 1, &mut tail);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!tail.is_null());
+
+        let err_code = indy_crypto_cl_file_tails_accessor_put_tail(file_tails_accessor, tail);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        // Registry for 5 credentials has 11 tails
+        let mut tail: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_file_tails_accessor_take_tail(file_tails_accessor, 11, &mut tail);
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+
+        let err_code = indy_crypto_cl_file_tails_accessor_free(file_tails_accessor);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        _free_credential_def(credential_pub_key, credential_priv_key, credential_key_correctness_proof);
+        _free_revocation_registry_def(rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn indy_crypto_cl_tails_file_write_parallel_works() {
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
+        let (rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator) = _revocation_registry_def(credential_pub_key);
+
+        let path = env::temp_dir().join("indy_crypto_cl_tails_file_write_parallel_works.tails");
+        let tails_file_path = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut tails_hash: *const c_char = ptr::null();
+        let err_code = indy_crypto_cl_tails_file_write_parallel(rev_tails_generator, tails_file_path.as_ptr(), 4, &mut tails_hash);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!tails_hash.is_null());
+
+        _free_credential_def(credential_pub_key, credential_priv_key, credential_key_correctness_proof);
+        _free_revocation_registry_def(rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn indy_crypto_cl_tails_file_write_with_prefix_sums_works() {
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
+        let (rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator) = _revocation_registry_def(credential_pub_key);
+
+        let path = env::temp_dir().join("indy_crypto_cl_tails_file_write_with_prefix_sums_works.tails");
+        let tails_file_path = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut tails_hash: *const c_char = ptr::null();
+        let err_code = indy_crypto_cl_tails_file_write_with_prefix_sums(rev_tails_generator, tails_file_path.as_ptr(), 2, 4, &mut tails_hash);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!tails_hash.is_null());
+
+        let mut file_tails_accessor: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_file_tails_accessor_open_with_endpoint_checks(tails_file_path.as_ptr(),
+                                                                                    tails_hash,
+                                                                                    credential_pub_key,
+                                                                                    rev_key_pub,
+                                                                                    5,
+                                                                                    &mut file_tails_accessor);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(unsafe { &*(file_tails_accessor as *const FileTailsAccessor) }.prefix_sums().is_some());
+
+        let rev_reg_delta = RevocationRegistryDelta::from_parts(None,
+                                                                unsafe { &*(rev_reg as *const RevocationRegistry) },
+                                                                &HashSet::new(),
+                                                                &vec![2].into_iter().collect());
+        let rev_reg_delta_p = &rev_reg_delta as *const RevocationRegistryDelta as *const c_void;
+
+        let mut witness: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_witness_new(1,
+                                                  5,
+                                                  true,
+                                                  rev_reg_delta_p,
+                                                  file_tails_accessor,
+                                                  indy_crypto_cl_file_tails_accessor_take_tail,
+                                                  indy_crypto_cl_file_tails_accessor_put_tail,
+                                                  &mut witness);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut fast_witness: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_witness_new_with_file_tails_accessor(1, 5, true, rev_reg_delta_p, file_tails_accessor, &mut fast_witness);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!fast_witness.is_null());
+
+        assert_eq!(serde_json::to_string(unsafe { &*(witness as *const Witness) }).unwrap(),
+                   serde_json::to_string(unsafe { &*(fast_witness as *const Witness) }).unwrap());
+
+        let err_code = indy_crypto_cl_witness_free(witness);
+        assert_eq!(err_code, ErrorCode::Success);
+        let err_code = indy_crypto_cl_witness_free(fast_witness);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_cl_file_tails_accessor_free(file_tails_accessor);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        _free_credential_def(credential_pub_key, credential_priv_key, credential_key_correctness_proof);
+        _free_revocation_registry_def(rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator);
+        fs::remove_file(&path).unwrap();
+        fs::remove_file(&tails_prefix_sums_path(&path)).unwrap();
+    }
+
+    #[test]
+    fn indy_crypto_cl_file_tails_accessor_open_with_endpoint_checks_works() {
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
+        let (rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator) = _revocation_registry_def(credential_pub_key);
+
+        let path = env::temp_dir().join("indy_crypto_cl_file_tails_accessor_open_with_endpoint_checks_works.tails");
+        let tails_file_path = CString::new(path.to_str().unwrap()).unwrap();
+
+        let mut tails_hash: *const c_char = ptr::null();
+        let err_code = indy_crypto_cl_tails_file_write(rev_tails_generator, tails_file_path.as_ptr(), &mut tails_hash);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut file_tails_accessor: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_file_tails_accessor_open_with_endpoint_checks(tails_file_path.as_ptr(),
+                                                                                    tails_hash,
+                                                                                    credential_pub_key,
+                                                                                    rev_key_pub,
+                                                                                    5,
+                                                                                    &mut file_tails_accessor);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!file_tails_accessor.is_null());
+
+        let err_code = indy_crypto_cl_file_tails_accessor_free(file_tails_accessor);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let other_tails_hash = CString::new(vec![b'0'; 64]).unwrap();
+        let mut file_tails_accessor: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_file_tails_accessor_open_with_endpoint_checks(tails_file_path.as_ptr(),
+                                                                                    other_tails_hash.as_ptr(),
+                                                                                    credential_pub_key,
+                                                                                    rev_key_pub,
+                                                                                    5,
+                                                                                    &mut file_tails_accessor);
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+        assert!(file_tails_accessor.is_null());
+
+        _free_credential_def(credential_pub_key, credential_priv_key, credential_key_correctness_proof);
+        _free_revocation_registry_def(rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn indy_crypto_cl_file_tails_accessor_open_with_endpoint_checks_works_for_missed_file() {
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
+        let (rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator) = _revocation_registry_def(credential_pub_key);
+
+        let path = env::temp_dir().join("indy_crypto_cl_file_tails_accessor_open_with_endpoint_checks_works_for_missed_file.tails");
+        let tails_file_path = CString::new(path.to_str().unwrap()).unwrap();
+        let tails_hash = CString::new(vec![b'0'; 64]).unwrap();
+
+        let mut file_tails_accessor: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_file_tails_accessor_open_with_endpoint_checks(tails_file_path.as_ptr(),
+                                                                                    tails_hash.as_ptr(),
+                                                                                    credential_pub_key,
+                                                                                    rev_key_pub,
+                                                                                    5,
+                                                                                    &mut file_tails_accessor);
+        assert_eq!(err_code, ErrorCode::CommonIOError);
+
+        _free_credential_def(credential_pub_key, credential_priv_key, credential_key_correctness_proof);
+        _free_revocation_registry_def(rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator);
+    }
+}
@@ -1,9 +1,14 @@
 use cl::verifier::*;
 use cl::*;
+use cl::encoding::AttributeValue;
 use errors::ToErrorCode;
 use errors::ErrorCode;
+use ffi::ctypes::CTypesUtils;
 
+use serde_json;
 use std::os::raw::c_void;
+use std::slice;
+use libc::c_char;
 
 /// Creates and returns proof verifier.
 ///
@@ -77,6 +82,168 @@ pub extern fn indy_crypto_cl_proof_verifier_add_sub_proof_request(proof_verifier
     ErrorCode::Success
 }
 
+/// Requires two attributes of (possibly) different sub proofs to have equal hidden values.
+///
+/// # Arguments
+/// * `proof_verifier` - Reference that contain proof verifier instance pointer.
+/// * `attr_equality_json` - Attribute equality in json format:
+///     {"first": {"sub_proof_index": 0, "attr_name": "name"}, "second": {"sub_proof_index": 1, "attr_name": "name"}}
+#[no_mangle]
+pub extern fn indy_crypto_cl_proof_verifier_add_attribute_equality(proof_verifier: *const c_void,
+                                                                   attr_equality_json: *const c_char) -> ErrorCode {
+    trace!("indy_crypto_cl_proof_verifier_add_attribute_equality: >>> proof_verifier: {:?}, attr_equality_json: {:?}",
+           proof_verifier, attr_equality_json);
+
+    check_useful_mut_c_reference!(proof_verifier, ProofVerifier, ErrorCode::CommonInvalidParam1);
+    check_useful_c_str!(attr_equality_json, ErrorCode::CommonInvalidParam2);
+
+    trace!("indy_crypto_cl_proof_verifier_add_attribute_equality: entities: proof_verifier: {:?}, attr_equality_json: {:?}",
+           proof_verifier, attr_equality_json);
+
+    let attr_equality = match serde_json::from_str::<AttributeEquality>(&attr_equality_json) {
+        Ok(attr_equality) => attr_equality,
+        Err(_) => return ErrorCode::CommonInvalidStructure
+    };
+
+    let res = match proof_verifier.add_attribute_equality(&attr_equality) {
+        Ok(()) => ErrorCode::Success,
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_proof_verifier_add_attribute_equality: <<< res: {:?}", res);
+    res
+}
+
+/// Adds linear predicate over hidden attributes of sub proofs to the proof verifier.
+///
+/// # Arguments
+/// * `proof_verifier` - Reference that contain proof verifier instance pointer.
+/// * `linear_predicate_json` - Linear predicate in json format:
+///     {"terms": [{"attr": {"sub_proof_index": 0, "attr_name": "income"}, "coefficient": 1},
+///                {"attr": {"sub_proof_index": 1, "attr_name": "rent"}, "coefficient": -3}],
+///      "p_type": "GT", "value": 0}
+#[no_mangle]
+pub extern fn indy_crypto_cl_proof_verifier_add_linear_predicate(proof_verifier: *const c_void,
+                                                                 linear_predicate_json: *const c_char) -> ErrorCode {
+    trace!("indy_crypto_cl_proof_verifier_add_linear_predicate: >>> proof_verifier: {:?}, linear_predicate_json: {:?}",
+           proof_verifier, linear_predicate_json);
+
+    check_useful_mut_c_reference!(proof_verifier, ProofVerifier, ErrorCode::CommonInvalidParam1);
+    check_useful_c_str!(linear_predicate_json, ErrorCode::CommonInvalidParam2);
+
+    trace!("indy_crypto_cl_proof_verifier_add_linear_predicate: entities: proof_verifier: {:?}, linear_predicate_json: {:?}",
+           proof_verifier, linear_predicate_json);
+
+    let linear_predicate = match serde_json::from_str::<LinearPredicate>(&linear_predicate_json) {
+        Ok(linear_predicate) => linear_predicate,
+        Err(_) => return ErrorCode::CommonInvalidStructure
+    };
+
+    let res = match proof_verifier.add_linear_predicate(&linear_predicate) {
+        Ok(()) => ErrorCode::Success,
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_proof_verifier_add_linear_predicate: <<< res: {:?}", res);
+    res
+}
+
+/// Adds requirement that hidden attribute of sub proof is verifiably encrypted to auditor.
+///
+/// The order of encryptions must be the same as for proof builder.
+///
+/// # Arguments
+/// * `proof_verifier` - Reference that contain proof verifier instance pointer.
+/// * `verifiable_encryption_json` - Verifiable encryption in json format:
+///     {"attr": {"sub_proof_index": 0, "attr_name": "ssn"}, "auditor_pub_key": {"n": "...", "g": "...", "y1": "...", "y2": "...", "y3": "..."},
+///      "label": "fraud investigation"}
+#[no_mangle]
+pub extern fn indy_crypto_cl_proof_verifier_add_verifiable_encryption(proof_verifier: *const c_void,
+                                                                      verifiable_encryption_json: *const c_char) -> ErrorCode {
+    trace!("indy_crypto_cl_proof_verifier_add_verifiable_encryption: >>> proof_verifier: {:?}, verifiable_encryption_json: {:?}",
+           proof_verifier, verifiable_encryption_json);
+
+    check_useful_mut_c_reference!(proof_verifier, ProofVerifier, ErrorCode::CommonInvalidParam1);
+    check_useful_c_str!(verifiable_encryption_json, ErrorCode::CommonInvalidParam2);
+
+    trace!("indy_crypto_cl_proof_verifier_add_verifiable_encryption: entities: proof_verifier: {:?}, verifiable_encryption_json: {:?}",
+           proof_verifier, verifiable_encryption_json);
+
+    let verifiable_encryption = match serde_json::from_str::<VerifiableEncryption>(&verifiable_encryption_json) {
+        Ok(verifiable_encryption) => verifiable_encryption,
+        Err(_) => return ErrorCode::CommonInvalidStructure
+    };
+
+    let res = match proof_verifier.add_verifiable_encryption(&verifiable_encryption) {
+        Ok(()) => ErrorCode::Success,
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_proof_verifier_add_verifiable_encryption: <<< res: {:?}", res);
+    res
+}
+
+/// Adds raw value that revealed attribute of sub proof must be encoded from.
+///
+/// # Arguments
+/// * `proof_verifier` - Reference that contain proof verifier instance pointer.
+/// * `sub_proof_index` - Index of sub proof in the order sub proof requests were added in.
+/// * `attr_name` - Revealed attribute name as null terminated string.
+/// * `raw_value_json` - Raw attribute value json, e.g. `{"type": "string", "value": "Alex"}`.
+#[no_mangle]
+pub extern fn indy_crypto_cl_proof_verifier_add_raw_revealed_value(proof_verifier: *const c_void,
+                                                                   sub_proof_index: usize,
+                                                                   attr_name: *const c_char,
+                                                                   raw_value_json: *const c_char) -> ErrorCode {
+    trace!("indy_crypto_cl_proof_verifier_add_raw_revealed_value: >>> proof_verifier: {:?}, sub_proof_index: {:?}, attr_name: {:?}, raw_value_json: {:?}",
+           proof_verifier, sub_proof_index, attr_name, raw_value_json);
+
+    check_useful_mut_c_reference!(proof_verifier, ProofVerifier, ErrorCode::CommonInvalidParam1);
+    check_useful_c_str!(attr_name, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(raw_value_json, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_crypto_cl_proof_verifier_add_raw_revealed_value: entities: proof_verifier: {:?}, sub_proof_index: {:?}, attr_name: {:?}, raw_value_json: {:?}",
+           proof_verifier, sub_proof_index, attr_name, raw_value_json);
+
+    let raw_value = match serde_json::from_str::<AttributeValue>(&raw_value_json) {
+        Ok(raw_value) => raw_value,
+        Err(_) => return ErrorCode::CommonInvalidStructure
+    };
+
+    let res = match proof_verifier.add_raw_revealed_value(sub_proof_index, &attr_name, &raw_value) {
+        Ok(()) => ErrorCode::Success,
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_proof_verifier_add_raw_revealed_value: <<< res: {:?}", res);
+    res
+}
+
+/// Sets minimal version of proofs accepted by proof verifier (legacy proofs of version 1 are accepted by default).
+///
+/// # Arguments
+/// * `proof_verifier` - Reference that contain proof verifier instance pointer.
+/// * `min_proof_version` - Minimal accepted proof version.
+#[no_mangle]
+pub extern fn indy_crypto_cl_proof_verifier_set_min_proof_version(proof_verifier: *const c_void,
+                                                                  min_proof_version: u32) -> ErrorCode {
+    trace!("indy_crypto_cl_proof_verifier_set_min_proof_version: >>> proof_verifier: {:?}, min_proof_version: {:?}",
+           proof_verifier, min_proof_version);
+
+    check_useful_mut_c_reference!(proof_verifier, ProofVerifier, ErrorCode::CommonInvalidParam1);
+
+    trace!("indy_crypto_cl_proof_verifier_set_min_proof_version: entities: proof_verifier: {:?}, min_proof_version: {:?}",
+           proof_verifier, min_proof_version);
+
+    let res = match proof_verifier.set_min_proof_version(min_proof_version) {
+        Ok(()) => ErrorCode::Success,
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_proof_verifier_set_min_proof_version: <<< res: {:?}", res);
+    res
+}
+
 
 /// Verifies proof and deallocates proof verifier.
 ///
@@ -117,10 +284,121 @@ pub extern fn indy_crypto_cl_proof_verifier_verify(proof_verifier: *const c_void
     res
 }
 
+/// Verifies proof bound to the message and deallocates proof verifier.
+///
+/// # Arguments
+/// * `proof_verifier` - Reference that contain proof verifier instance pointer.
+/// * `proof` - Reference that contain proof instance pointer.
+/// * `nonce` - Reference that contain nonce instance pointer.
+/// * `message` - Message buffer pointer.
+/// * `message_len` - Message buffer len.
+/// * `valid_p` - Reference that will be filled with true - if proof valid or false otherwise.
+#[no_mangle]
+pub extern fn indy_crypto_cl_proof_verifier_verify_with_message(proof_verifier: *const c_void,
+                                                                proof: *const c_void,
+                                                                nonce: *const c_void,
+                                                                message: *const u8,
+                                                                message_len: usize,
+                                                                valid_p: *mut bool) -> ErrorCode {
+    trace!("indy_crypto_cl_proof_verifier_verify_with_message: >>> proof_verifier: {:?}, proof: {:?}, nonce: {:?}, message: {:?}, message_len: {:?}, valid_p: {:?}",
+           proof_verifier, proof, nonce, message, message_len, valid_p);
+
+    check_useful_c_ptr!(proof_verifier, ErrorCode::CommonInvalidParam1);
+    check_useful_c_reference!(proof, Proof, ErrorCode::CommonInvalidParam2);
+    check_useful_c_reference!(nonce, Nonce, ErrorCode::CommonInvalidParam3);
+    check_useful_c_byte_array!(message, message_len, ErrorCode::CommonInvalidParam4, ErrorCode::CommonInvalidParam5);
+    check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam6);
+
+    let proof_verifier = unsafe { Box::from_raw(proof_verifier as *mut ProofVerifier) };
+
+    trace!("indy_crypto_cl_proof_verifier_verify_with_message: entities: >>> proof_verifier: {:?}, proof: {:?}, nonce: {:?}, message: {:?}",
+           proof_verifier, proof, nonce, message);
+
+    let res = match proof_verifier.verify_with_message(proof, nonce, message) {
+        Ok(valid) => {
+            trace!("indy_crypto_cl_proof_verifier_verify_with_message: valid: {:?}", valid);
+            unsafe {
+                *valid_p = valid;
+                trace!("indy_crypto_cl_proof_verifier_verify_with_message: *valid_p: {:?}", *valid_p);
+            }
+            ErrorCode::Success
+        }
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_proof_verifier_verify_with_message: <<< res: {:?}", res);
+    res
+}
+
+/// Verifies proof (optionally bound to the message), returns pseudonyms of sub proofs
+/// and deallocates proof verifier.
+///
+/// # Arguments
+/// * `proof_verifier` - Reference that contain proof verifier instance pointer.
+/// * `proof` - Reference that contain proof instance pointer.
+/// * `nonce` - Reference that contain nonce instance pointer.
+/// * `message` - (Optional) Message buffer pointer.
+/// * `message_len` - Message buffer len.
+/// * `valid_p` - Reference that will be filled with true - if proof valid or false otherwise.
+/// * `pseudonyms_json_p` - Reference that will contain json array of pseudonyms (in the order of sub proofs)
+///   if proof is valid or null otherwise.
+#[no_mangle]
+pub extern fn indy_crypto_cl_proof_verifier_verify_with_pseudonyms(proof_verifier: *const c_void,
+                                                                   proof: *const c_void,
+                                                                   nonce: *const c_void,
+                                                                   message: *const u8,
+                                                                   message_len: usize,
+                                                                   valid_p: *mut bool,
+                                                                   pseudonyms_json_p: *mut *const c_char) -> ErrorCode {
+    trace!("indy_crypto_cl_proof_verifier_verify_with_pseudonyms: >>> proof_verifier: {:?}, proof: {:?}, nonce: {:?}, message: {:?}, message_len: {:?}, \
+            valid_p: {:?}, pseudonyms_json_p: {:?}", proof_verifier, proof, nonce, message, message_len, valid_p, pseudonyms_json_p);
+
+    check_useful_c_ptr!(proof_verifier, ErrorCode::CommonInvalidParam1);
+    check_useful_c_reference!(proof, Proof, ErrorCode::CommonInvalidParam2);
+    check_useful_c_reference!(nonce, Nonce, ErrorCode::CommonInvalidParam3);
+    check_useful_opt_c_byte_array!(message, message_len, ErrorCode::CommonInvalidParam4, ErrorCode::CommonInvalidParam5);
+    check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam6);
+    check_useful_c_ptr!(pseudonyms_json_p, ErrorCode::CommonInvalidParam7);
+
+    let proof_verifier = unsafe { Box::from_raw(proof_verifier as *mut ProofVerifier) };
+
+    trace!("indy_crypto_cl_proof_verifier_verify_with_pseudonyms: entities: >>> proof_verifier: {:?}, proof: {:?}, nonce: {:?}, message: {:?}",
+           proof_verifier, proof, nonce, message);
+
+    let res = match proof_verifier.verify_with_pseudonyms(proof, nonce, message) {
+        Ok(Some(pseudonyms)) => {
+            trace!("indy_crypto_cl_proof_verifier_verify_with_pseudonyms: pseudonyms: {:?}", pseudonyms);
+            match serde_json::to_string(&pseudonyms) {
+                Ok(pseudonyms_json) => {
+                    unsafe {
+                        *valid_p = true;
+                        *pseudonyms_json_p = CTypesUtils::string_to_cstring(pseudonyms_json).into_raw();
+                        trace!("indy_crypto_cl_proof_verifier_verify_with_pseudonyms: *pseudonyms_json_p: {:?}", *pseudonyms_json_p);
+                    }
+                    ErrorCode::Success
+                }
+                Err(_) => ErrorCode::CommonInvalidState
+            }
+        }
+        Ok(None) => {
+            unsafe {
+                *valid_p = false;
+                *pseudonyms_json_p = ::std::ptr::null();
+            }
+            ErrorCode::Success
+        }
+        Err(err) => err.to_error_code()
+    };
+
+    trace!("indy_crypto_cl_proof_verifier_verify_with_pseudonyms: <<< res: {:?}", res);
+    res
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use std::ffi::{CStr, CString};
     use std::ptr;
     use ffi::cl::mocks::*;
     use super::mocks::*;
@@ -244,6 +522,259 @@ mod tests {
         _free_credential_signature(credential_signature, signature_correctness_proof);
     }
 
+    #[test]
+    fn indy_crypto_cl_proof_verifier_add_attribute_equality_works() {
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
+        let credential_values = _credential_values();
+        let credential_nonce = _nonce();
+        let (blinded_credential_secrets, credential_secrets_blinding_factors,
+            blinded_credential_secrets_correctness_proof) = _blinded_credential_secrets(credential_pub_key,
+                                                                                   credential_key_correctness_proof,
+                                                                                   credential_values,
+                                                                                   credential_nonce);
+        let credential_issuance_nonce = _nonce();
+        let (credential_signature, signature_correctness_proof) = _credential_signature(blinded_credential_secrets,
+                                                                                        blinded_credential_secrets_correctness_proof,
+                                                                                        credential_nonce,
+                                                                                        credential_issuance_nonce,
+                                                                                        credential_values,
+                                                                                        credential_pub_key,
+                                                                                        credential_priv_key);
+        let credential_schema = _credential_schema();
+        let non_credential_schema = _non_credential_schema();
+        let sub_proof_request = _sub_proof_request();
+        _process_credential_signature(credential_signature,
+                                      signature_correctness_proof,
+                                      credential_secrets_blinding_factors,
+                                      credential_values,
+                                      credential_pub_key,
+                                      credential_issuance_nonce,
+                                      ptr::null(),
+                                      ptr::null(),
+                                      ptr::null());
+
+        let proof_building_nonce = _nonce();
+        let proof = _proof(credential_pub_key,
+                           credential_signature,
+                           proof_building_nonce,
+                           credential_values,
+                           ptr::null(),
+                           ptr::null());
+
+        let proof_verifier = _proof_verifier();
+        _add_sub_proof_request(proof_verifier, credential_schema, non_credential_schema, credential_pub_key, sub_proof_request, ptr::null(), ptr::null());
+
+        let attr_equality_json = CString::new(r#"{"first":{"sub_proof_index":0,"attr_name":"name"},"second":{"sub_proof_index":1,"attr_name":"name"}}"#).unwrap();
+        let err_code = indy_crypto_cl_proof_verifier_add_attribute_equality(proof_verifier, attr_equality_json.as_ptr());
+        assert_eq!(err_code, ErrorCode::Success);
+
+        // Proof contains single sub proof, so equality with the second one can't be checked
+        let mut valid = false;
+        let err_code = indy_crypto_cl_proof_verifier_verify(proof_verifier, proof, proof_building_nonce, &mut valid);
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+
+        _free_credential_def(credential_pub_key, credential_priv_key, credential_key_correctness_proof);
+        _free_blinded_credential_secrets(blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof);
+        _free_nonce(credential_nonce);
+        _free_nonce(credential_issuance_nonce);
+        _free_nonce(proof_building_nonce);
+        _free_credential_schema(credential_schema);
+        _free_sub_proof_request(sub_proof_request);
+        _free_credential_signature(credential_signature, signature_correctness_proof);
+    }
+
+    #[test]
+    fn indy_crypto_cl_proof_verifier_add_linear_predicate_works() {
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
+        let credential_values = _credential_values();
+        let credential_nonce = _nonce();
+        let (blinded_credential_secrets, credential_secrets_blinding_factors,
+            blinded_credential_secrets_correctness_proof) = _blinded_credential_secrets(credential_pub_key,
+                                                                                   credential_key_correctness_proof,
+                                                                                   credential_values,
+                                                                                   credential_nonce);
+        let credential_issuance_nonce = _nonce();
+        let (credential_signature, signature_correctness_proof) = _credential_signature(blinded_credential_secrets,
+                                                                                        blinded_credential_secrets_correctness_proof,
+                                                                                        credential_nonce,
+                                                                                        credential_issuance_nonce,
+                                                                                        credential_values,
+                                                                                        credential_pub_key,
+                                                                                        credential_priv_key);
+        let credential_schema = _credential_schema();
+        let non_credential_schema = _non_credential_schema();
+        let sub_proof_request = _sub_proof_request();
+        _process_credential_signature(credential_signature,
+                                      signature_correctness_proof,
+                                      credential_secrets_blinding_factors,
+                                      credential_values,
+                                      credential_pub_key,
+                                      credential_issuance_nonce,
+                                      ptr::null(),
+                                      ptr::null(),
+                                      ptr::null());
+
+        let proof_building_nonce = _nonce();
+        let proof = _proof(credential_pub_key,
+                           credential_signature,
+                           proof_building_nonce,
+                           credential_values,
+                           ptr::null(),
+                           ptr::null());
+
+        let proof_verifier = _proof_verifier();
+        _add_sub_proof_request(proof_verifier, credential_schema, non_credential_schema, credential_pub_key, sub_proof_request, ptr::null(), ptr::null());
+
+        let linear_predicate_json = CString::new(r#"{"terms":[{"attr":{"sub_proof_index":0,"attr_name":"age"},"coefficient":1}],"p_type":"GE","value":18}"#).unwrap();
+        let err_code = indy_crypto_cl_proof_verifier_add_linear_predicate(proof_verifier, linear_predicate_json.as_ptr());
+        assert_eq!(err_code, ErrorCode::Success);
+
+        // Proof was built without linear predicate
+        let mut valid = false;
+        let err_code = indy_crypto_cl_proof_verifier_verify(proof_verifier, proof, proof_building_nonce, &mut valid);
+        assert_eq!(err_code, ErrorCode::AnoncredsProofRejected);
+
+        _free_credential_def(credential_pub_key, credential_priv_key, credential_key_correctness_proof);
+        _free_blinded_credential_secrets(blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof);
+        _free_nonce(credential_nonce);
+        _free_nonce(credential_issuance_nonce);
+        _free_nonce(proof_building_nonce);
+        _free_credential_schema(credential_schema);
+        _free_sub_proof_request(sub_proof_request);
+        _free_credential_signature(credential_signature, signature_correctness_proof);
+    }
+
+    #[test]
+    fn indy_crypto_cl_proof_verifier_add_raw_revealed_value_works() {
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
+        let credential_values = _credential_values();
+        let credential_nonce = _nonce();
+        let (blinded_credential_secrets, credential_secrets_blinding_factors,
+            blinded_credential_secrets_correctness_proof) = _blinded_credential_secrets(credential_pub_key,
+                                                                                   credential_key_correctness_proof,
+                                                                                   credential_values,
+                                                                                   credential_nonce);
+        let credential_issuance_nonce = _nonce();
+        let (credential_signature, signature_correctness_proof) = _credential_signature(blinded_credential_secrets,
+                                                                                        blinded_credential_secrets_correctness_proof,
+                                                                                        credential_nonce,
+                                                                                        credential_issuance_nonce,
+                                                                                        credential_values,
+                                                                                        credential_pub_key,
+                                                                                        credential_priv_key);
+        let credential_schema = _credential_schema();
+        let non_credential_schema = _non_credential_schema();
+        let sub_proof_request = _sub_proof_request();
+        _process_credential_signature(credential_signature,
+                                      signature_correctness_proof,
+                                      credential_secrets_blinding_factors,
+                                      credential_values,
+                                      credential_pub_key,
+                                      credential_issuance_nonce,
+                                      ptr::null(),
+                                      ptr::null(),
+                                      ptr::null());
+
+        let proof_building_nonce = _nonce();
+        let proof = _proof(credential_pub_key,
+                           credential_signature,
+                           proof_building_nonce,
+                           credential_values,
+                           ptr::null(),
+                           ptr::null());
+
+        let proof_verifier = _proof_verifier();
+        _add_sub_proof_request(proof_verifier, credential_schema, non_credential_schema, credential_pub_key, sub_proof_request, ptr::null(), ptr::null());
+
+        let attr_name = CString::new("name").unwrap();
+        let raw_value_json = CString::new(r#"{"type":"string"}"#).unwrap();
+        let err_code = indy_crypto_cl_proof_verifier_add_raw_revealed_value(proof_verifier, 0, attr_name.as_ptr(), raw_value_json.as_ptr());
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+
+        let raw_value_json = CString::new(r#"{"type":"string","value":"Alex"}"#).unwrap();
+        let err_code = indy_crypto_cl_proof_verifier_add_raw_revealed_value(proof_verifier, 0, attr_name.as_ptr(), raw_value_json.as_ptr());
+        assert_eq!(err_code, ErrorCode::Success);
+
+        // Revealed name was issued as plain number, not as encoded string
+        let mut valid = false;
+        let err_code = indy_crypto_cl_proof_verifier_verify(proof_verifier, proof, proof_building_nonce, &mut valid);
+        assert_eq!(err_code, ErrorCode::AnoncredsProofRejected);
+
+        _free_credential_def(credential_pub_key, credential_priv_key, credential_key_correctness_proof);
+        _free_blinded_credential_secrets(blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof);
+        _free_nonce(credential_nonce);
+        _free_nonce(credential_issuance_nonce);
+        _free_nonce(proof_building_nonce);
+        _free_credential_schema(credential_schema);
+        _free_sub_proof_request(sub_proof_request);
+        _free_credential_signature(credential_signature, signature_correctness_proof);
+    }
+
+    #[test]
+    fn indy_crypto_cl_proof_verifier_add_verifiable_encryption_works() {
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
+        let credential_values = _credential_values();
+        let credential_nonce = _nonce();
+        let (blinded_credential_secrets, credential_secrets_blinding_factors,
+            blinded_credential_secrets_correctness_proof) = _blinded_credential_secrets(credential_pub_key,
+                                                                                   credential_key_correctness_proof,
+                                                                                   credential_values,
+                                                                                   credential_nonce);
+        let credential_issuance_nonce = _nonce();
+        let (credential_signature, signature_correctness_proof) = _credential_signature(blinded_credential_secrets,
+                                                                                        blinded_credential_secrets_correctness_proof,
+                                                                                        credential_nonce,
+                                                                                        credential_issuance_nonce,
+                                                                                        credential_values,
+                                                                                        credential_pub_key,
+                                                                                        credential_priv_key);
+        let credential_schema = _credential_schema();
+        let non_credential_schema = _non_credential_schema();
+        let sub_proof_request = _sub_proof_request();
+        _process_credential_signature(credential_signature,
+                                      signature_correctness_proof,
+                                      credential_secrets_blinding_factors,
+                                      credential_values,
+                                      credential_pub_key,
+                                      credential_issuance_nonce,
+                                      ptr::null(),
+                                      ptr::null(),
+                                      ptr::null());
+
+        let proof_building_nonce = _nonce();
+        let proof = _proof(credential_pub_key,
+                           credential_signature,
+                           proof_building_nonce,
+                           credential_values,
+                           ptr::null(),
+                           ptr::null());
+
+        let proof_verifier = _proof_verifier();
+        _add_sub_proof_request(proof_verifier, credential_schema, non_credential_schema, credential_pub_key, sub_proof_request, ptr::null(), ptr::null());
+
+        let verifiable_encryption_json = CString::new(r#"{"attr":{"sub_proof_index":0,"attr_name":"age"},"label":"label"}"#).unwrap();
+        let err_code = indy_crypto_cl_proof_verifier_add_verifiable_encryption(proof_verifier, verifiable_encryption_json.as_ptr());
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+
+        let verifiable_encryption_json = CString::new(r#"{"attr":{"sub_proof_index":0,"attr_name":"age"},"auditor_pub_key":{"n":"15","g":"4","y1":"4","y2":"4","y3":"4"},"label":"label"}"#).unwrap();
+        let err_code = indy_crypto_cl_proof_verifier_add_verifiable_encryption(proof_verifier, verifiable_encryption_json.as_ptr());
+        assert_eq!(err_code, ErrorCode::Success);
+
+        // Proof doesn't contain requested encryption
+        let mut valid = false;
+        let err_code = indy_crypto_cl_proof_verifier_verify(proof_verifier, proof, proof_building_nonce, &mut valid);
+        assert_eq!(err_code, ErrorCode::AnoncredsProofRejected);
+
+        _free_credential_def(credential_pub_key, credential_priv_key, credential_key_correctness_proof);
+        _free_blinded_credential_secrets(blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof);
+        _free_nonce(credential_nonce);
+        _free_nonce(credential_issuance_nonce);
+        _free_nonce(proof_building_nonce);
+        _free_credential_schema(credential_schema);
+        _free_sub_proof_request(sub_proof_request);
+        _free_credential_signature(credential_signature, signature_correctness_proof);
+    }
+
     #[test]
     fn indy_crypto_cl_proof_verifier_verify_works_for_primary_proof() {
         let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
@@ -286,6 +817,12 @@ mod tests {
         let proof_verifier = _proof_verifier();
         _add_sub_proof_request(proof_verifier, credential_schema, non_credential_schema, credential_pub_key, sub_proof_request, ptr::null(), ptr::null());
 
+        let err_code = indy_crypto_cl_proof_verifier_set_min_proof_version(proof_verifier, 0);
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+
+        let err_code = indy_crypto_cl_proof_verifier_set_min_proof_version(proof_verifier, CURRENT_PROOF_VERSION);
+        assert_eq!(err_code, ErrorCode::Success);
+
         let mut valid = false;
         let err_code = indy_crypto_cl_proof_verifier_verify(proof_verifier, proof, proof_building_nonce, &mut valid);
         assert_eq!(err_code, ErrorCode::Success);
@@ -301,6 +838,144 @@ mod tests {
         _free_credential_signature(credential_signature, signature_correctness_proof);
     }
 
+    #[test]
+    fn indy_crypto_cl_proof_verifier_verify_with_message_works() {
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
+        let credential_values = _credential_values();
+        let credential_nonce = _nonce();
+        let (blinded_credential_secrets, credential_secrets_blinding_factors,
+            blinded_credential_secrets_correctness_proof) = _blinded_credential_secrets(credential_pub_key,
+                                                                                   credential_key_correctness_proof,
+                                                                                   credential_values,
+                                                                                   credential_nonce);
+        let credential_issuance_nonce = _nonce();
+        let (credential_signature, signature_correctness_proof) = _credential_signature(blinded_credential_secrets,
+                                                                                        blinded_credential_secrets_correctness_proof,
+                                                                                        credential_nonce,
+                                                                                        credential_issuance_nonce,
+                                                                                        credential_values,
+                                                                                        credential_pub_key,
+                                                                                        credential_priv_key);
+        let credential_schema = _credential_schema();
+        let non_credential_schema = _non_credential_schema();
+        let sub_proof_request = _sub_proof_request();
+        _process_credential_signature(credential_signature,
+                                      signature_correctness_proof,
+                                      credential_secrets_blinding_factors,
+                                      credential_values,
+                                      credential_pub_key,
+                                      credential_issuance_nonce,
+                                      ptr::null(),
+                                      ptr::null(),
+                                      ptr::null());
+
+        let proof_building_nonce = _nonce();
+        let proof = _proof_with_message(credential_pub_key,
+                                        credential_signature,
+                                        proof_building_nonce,
+                                        credential_values,
+                                        b"message");
+
+        let message = b"message";
+        let proof_verifier = _proof_verifier();
+        _add_sub_proof_request(proof_verifier, credential_schema, non_credential_schema, credential_pub_key, sub_proof_request, ptr::null(), ptr::null());
+
+        let mut valid = false;
+        let err_code = indy_crypto_cl_proof_verifier_verify_with_message(proof_verifier, proof, proof_building_nonce,
+                                                                         message.as_ptr(), message.len(), &mut valid);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(valid);
+
+        let other_message = b"other message";
+        let proof_verifier = _proof_verifier();
+        _add_sub_proof_request(proof_verifier, credential_schema, non_credential_schema, credential_pub_key, sub_proof_request, ptr::null(), ptr::null());
+
+        let err_code = indy_crypto_cl_proof_verifier_verify_with_message(proof_verifier, proof, proof_building_nonce,
+                                                                         other_message.as_ptr(), other_message.len(), &mut valid);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!valid);
+
+        _free_credential_def(credential_pub_key, credential_priv_key, credential_key_correctness_proof);
+        _free_blinded_credential_secrets(blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof);
+        _free_nonce(credential_nonce);
+        _free_nonce(credential_issuance_nonce);
+        _free_nonce(proof_building_nonce);
+        _free_credential_schema(credential_schema);
+        _free_sub_proof_request(sub_proof_request);
+        _free_credential_signature(credential_signature, signature_correctness_proof);
+    }
+
+    #[test]
+    fn indy_crypto_cl_proof_verifier_verify_with_pseudonyms_works() {
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
+        let credential_values = _credential_values();
+        let credential_nonce = _nonce();
+        let (blinded_credential_secrets, credential_secrets_blinding_factors,
+            blinded_credential_secrets_correctness_proof) = _blinded_credential_secrets(credential_pub_key,
+                                                                                   credential_key_correctness_proof,
+                                                                                   credential_values,
+                                                                                   credential_nonce);
+        let credential_issuance_nonce = _nonce();
+        let (credential_signature, signature_correctness_proof) = _credential_signature(blinded_credential_secrets,
+                                                                                        blinded_credential_secrets_correctness_proof,
+                                                                                        credential_nonce,
+                                                                                        credential_issuance_nonce,
+                                                                                        credential_values,
+                                                                                        credential_pub_key,
+                                                                                        credential_priv_key);
+        let credential_schema = _credential_schema();
+        let non_credential_schema = _non_credential_schema();
+        let sub_proof_request = _sub_proof_request_with_pseudonym();
+        _process_credential_signature(credential_signature,
+                                      signature_correctness_proof,
+                                      credential_secrets_blinding_factors,
+                                      credential_values,
+                                      credential_pub_key,
+                                      credential_issuance_nonce,
+                                      ptr::null(),
+                                      ptr::null(),
+                                      ptr::null());
+
+        let proof_building_nonce = _nonce();
+        let (proof, pseudonym_json) = _proof_with_pseudonym(credential_pub_key,
+                                                            credential_signature,
+                                                            proof_building_nonce,
+                                                            credential_values);
+
+        let proof_verifier = _proof_verifier();
+        _add_sub_proof_request(proof_verifier, credential_schema, non_credential_schema, credential_pub_key, sub_proof_request, ptr::null(), ptr::null());
+
+        let mut valid = false;
+        let mut pseudonyms_json_p: *const c_char = ptr::null();
+        let err_code = indy_crypto_cl_proof_verifier_verify_with_pseudonyms(proof_verifier, proof, proof_building_nonce,
+                                                                            ptr::null(), 0, &mut valid, &mut pseudonyms_json_p);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(valid);
+        let pseudonyms_json = unsafe { CStr::from_ptr(pseudonyms_json_p).to_str().unwrap() };
+        assert_eq!(format!("[{}]", pseudonym_json), pseudonyms_json);
+
+        // Proof is not bound to the message
+        let message = b"message";
+        let proof_verifier = _proof_verifier();
+        _add_sub_proof_request(proof_verifier, credential_schema, non_credential_schema, credential_pub_key, sub_proof_request, ptr::null(), ptr::null());
+
+        let err_code = indy_crypto_cl_proof_verifier_verify_with_pseudonyms(proof_verifier, proof, proof_building_nonce,
+                                                                            message.as_ptr(), message.len(), &mut valid, &mut pseudonyms_json_p);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!valid);
+        assert!(pseudonyms_json_p.is_null());
+
+        _free_proof(proof);
+        _free_credential_def(credential_pub_key, credential_priv_key, credential_key_correctness_proof);
+        _free_blinded_credential_secrets(blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof);
+        _free_nonce(credential_nonce);
+        _free_nonce(credential_issuance_nonce);
+        _free_nonce(proof_building_nonce);
+        _free_credential_schema(credential_schema);
+        _free_sub_proof_request(sub_proof_request);
+        _free_credential_signature(credential_signature, signature_correctness_proof);
+    }
+
     #[test]
     fn indy_crypto_cl_proof_verifier_verify_works_for_revocation_proof() {
         let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = _credential_def();
@@ -111,6 +111,16 @@ macro_rules! check_useful_hashset {
     }
 }
 
+macro_rules! check_useful_btreeset {
+    ($ptr:ident, $len:expr, $err1:expr, $err2:expr) => {
+        if $ptr.is_null() {
+            return $err1
+        }
+
+        let $ptr = BTreeSet::from_iter( unsafe {  slice::from_raw_parts($ptr, $len) }.iter().cloned());
+    }
+}
+
 macro_rules! check_useful_c_ptr {
     ($ptr:ident, $err1:expr) => {
         if $ptr.is_null() {
@@ -3,7 +3,7 @@ extern crate wasm_bindgen;
 #[cfg(feature = "wasm")]
 extern crate console_error_panic_hook;
 
-#[cfg(feature = "pair_amcl")]
+#[cfg(any(feature = "pair_amcl", feature = "pair_bls12_381"))]
 extern crate amcl;
 extern crate env_logger;
 #[macro_use]
@@ -57,6 +57,13 @@ pub mod ffi;
 #[path = "pair/amcl.rs"]
 pub mod pair;
 
+#[cfg(all(feature = "pair_amcl", feature = "pair_bls12_381"))]
+compile_error!("features `pair_amcl` and `pair_bls12_381` are mutually exclusive, build with `--no-default-features` to select BLS12-381");
+
+#[cfg(all(feature = "pair_bls12_381", not(feature = "pair_amcl")))]
+#[path = "pair/bls12_381.rs"]
+pub mod pair;
+
 #[macro_use]
 extern crate lazy_static;
 
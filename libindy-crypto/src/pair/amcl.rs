@@ -10,16 +10,19 @@ use amcl::rom::{
     CURVE_PYA,
     CURVE_PXB,
     CURVE_PYB,
-    MODBYTES
+    MODBYTES,
+    MODULUS
 };
 
 use amcl::ecp::ECP;
 use amcl::ecp2::ECP2;
 use amcl::fp12::FP12;
 use amcl::fp2::FP2;
-use amcl::pair::{ate, g1mul, g2mul, gtpow, fexp};
+use amcl::pair::{ate, ate2, g1mul, g2mul, gtpow, fexp};
 use amcl::rand::RAND;
 
+use sha2::{Sha256, Digest};
+
 use rand::rngs::OsRng;
 use rand::RngCore;
 use std::fmt::{Debug, Formatter, Error};
@@ -91,6 +94,12 @@ pub struct PointG1 {
 
 impl PointG1 {
     pub const BYTES_REPR_SIZE: usize = MODBYTES * 4;
+    pub const BYTES_REPR_COMPRESSED_SIZE: usize = MODBYTES + 1;
+
+    /// Hash-to-curve suite implemented by `hash_to_curve`. RFC 9380 does not define a suite for
+    /// this curve, so the ID is our own and only follows the naming convention of the spec:
+    /// expand_message_xmd with SHA-256, Shallue-van de Woestijne map with Z = -1, random oracle.
+    pub const HASH_TO_CURVE_SUITE: &'static str = "BN254AMCLG1_XMD:SHA-256_SVDW_RO_";
 
     /// Creates new random PointG1
     pub fn new() -> Result<PointG1, IndyCryptoError> {
@@ -188,6 +197,67 @@ impl PointG1 {
         )
     }
 
+    /// Encodes PointG1 as x coordinate prefixed by 0x02 or 0x03 tag holding parity of y.
+    /// Infinity is encoded as all zeroes.
+    pub fn to_bytes_compressed(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        let mut vec = vec![0u8; Self::BYTES_REPR_COMPRESSED_SIZE];
+
+        if self.is_inf()? {
+            return Ok(vec);
+        }
+
+        let mut r = self.point;
+        r.affine();
+        let mut x = r.getx();
+        let y = r.gety();
+
+        x.tobytes(&mut vec[1..]);
+        vec[0] = if fp_sgn0(&y) == 1 { 0x03 } else { 0x02 };
+        Ok(vec)
+    }
+
+    pub fn from_bytes_compressed(b: &[u8]) -> Result<PointG1, IndyCryptoError> {
+        if b.len() != Self::BYTES_REPR_COMPRESSED_SIZE {
+            return Err(IndyCryptoError::InvalidStructure(
+                "Invalid len of compressed bytes representation".to_string()));
+        }
+
+        if b.iter().all(|byte| *byte == 0) {
+            return PointG1::new_inf();
+        }
+
+        let sign = match b[0] {
+            0x02 => 0,
+            0x03 => 1,
+            _ => return Err(IndyCryptoError::InvalidStructure(
+                "Invalid tag of compressed bytes representation".to_string()))
+        };
+
+        let x = BIG::frombytes(&b[1..]);
+
+        if BIG::comp(&x, &fp_modulus()) >= 0 {
+            return Err(IndyCryptoError::InvalidStructure(
+                "Invalid x coordinate of compressed bytes representation".to_string()));
+        }
+
+        let gx = fp_add(&fp_mul(&fp_mul(&x, &x), &x), &BIG::new_int(CURVE_B_I));
+
+        if !fp_is_square(&gx) {
+            return Err(IndyCryptoError::InvalidStructure(
+                "Invalid x coordinate of compressed bytes representation".to_string()));
+        }
+
+        let mut y = fp_sqrt(&gx);
+
+        if fp_sgn0(&y) != sign {
+            y = fp_neg(&y);
+        }
+
+        Ok(PointG1 {
+            point: ECP::new_bigs(&x, &y)
+        })
+    }
+
     pub fn from_hash(hash: &[u8]) -> Result<PointG1, IndyCryptoError> {
         let mut el = GroupOrderElement::from_bytes(hash)?;
         let mut point = ECP::new_big(&el.bn);
@@ -201,6 +271,19 @@ impl PointG1 {
             point: point
         })
     }
+
+    /// Hashes message to PointG1 as defined by `hash_to_curve` of the IETF hash-to-curve spec
+    /// for `HASH_TO_CURVE_SUITE`. `dst` is the domain separation tag.
+    pub fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Result<PointG1, IndyCryptoError> {
+        let u = hash_to_field(msg, dst, 2)?;
+        let mut q0 = map_to_curve(&u[0]);
+        let mut q1 = map_to_curve(&u[1]);
+        q0.add(&mut q1);
+
+        Ok(PointG1 {
+            point: clear_cofactor(&q0)
+        })
+    }
 }
 
 impl Debug for PointG1 {
@@ -239,6 +322,228 @@ impl<'a> Deserialize<'a> for PointG1 {
     }
 }
 
+// Hash to curve (IETF hash-to-curve spec)
+
+/// ceil((ceil(log2(p)) + 128) / 8)
+const HASH_TO_FIELD_LEN: usize = 48;
+
+const CURVE_B_I: isize = 2;
+const SVDW_Z: &'static str = "2523648240000001ba344d80000000086121000000000013a700000000000012";
+const SVDW_C1: &'static str = "0000000000000000000000000000000000000000000000000000000000000001";
+const SVDW_C2: &'static str = "1291b24120000000dd1a26c0000000043090800000000009d38000000000000a";
+const SVDW_C3: &'static str = "252364824000000126cd890000000003cf0f0000000000060c00000000000004";
+const SVDW_C4: &'static str = "0c612180c00000009366c48000000002cb0b0000000000068d00000000000005";
+const FP_P_MINUS_1_OVER_2: &'static str = "1291b24120000000dd1a26c0000000043090800000000009d380000000000009";
+const FP_P_PLUS_1_OVER_4: &'static str = "0948d920900000006e8d1360000000021848400000000004e9c0000000000005";
+
+/// Expands `msg` into `len_in_bytes` uniformly random bytes as defined by `expand_message_xmd`
+/// with SHA-256 in the IETF hash-to-curve spec.
+fn expand_message_xmd(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Result<Vec<u8>, IndyCryptoError> {
+    let ell = (len_in_bytes + 31) / 32;
+
+    if ell > 255 || len_in_bytes > 65535 {
+        return Err(IndyCryptoError::InvalidStructure(
+            format!("Invalid len of expanded message: {}", len_in_bytes)));
+    }
+
+    let mut dst_prime = if dst.len() > 255 {
+        let mut hasher = Sha256::default();
+        hasher.input(b"H2C-OVERSIZE-DST-");
+        hasher.input(dst);
+        hasher.result().to_vec()
+    } else {
+        dst.to_vec()
+    };
+    let dst_len = dst_prime.len() as u8;
+    dst_prime.push(dst_len);
+
+    let mut hasher = Sha256::default();
+    hasher.input(&[0u8; 64]);
+    hasher.input(msg);
+    hasher.input(&[(len_in_bytes >> 8) as u8, len_in_bytes as u8, 0u8]);
+    hasher.input(&dst_prime);
+    let b_0 = hasher.result();
+
+    let mut hasher = Sha256::default();
+    hasher.input(&b_0);
+    hasher.input(&[1u8]);
+    hasher.input(&dst_prime);
+    let mut b_i = hasher.result();
+
+    let mut uniform_bytes = b_i.to_vec();
+
+    for i in 2..ell + 1 {
+        let xored: Vec<u8> = b_0.iter().zip(b_i.iter()).map(|(a, b)| a ^ b).collect();
+
+        let mut hasher = Sha256::default();
+        hasher.input(&xored);
+        hasher.input(&[i as u8]);
+        hasher.input(&dst_prime);
+        b_i = hasher.result();
+
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+
+    uniform_bytes.truncate(len_in_bytes);
+    Ok(uniform_bytes)
+}
+
+/// Hashes `msg` to `count` elements of the base field.
+fn hash_to_field(msg: &[u8], dst: &[u8], count: usize) -> Result<Vec<BIG>, IndyCryptoError> {
+    let uniform_bytes = expand_message_xmd(msg, dst, count * HASH_TO_FIELD_LEN)?;
+    Ok(uniform_bytes.chunks(HASH_TO_FIELD_LEN).map(fp_reduce).collect())
+}
+
+fn fp_modulus() -> BIG {
+    BIG::new_ints(&MODULUS)
+}
+
+fn fp_from_hex(hex: &str) -> BIG {
+    let bytes: Vec<u8> = (0..hex.len() / 2)
+        .map(|i| u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap())
+        .collect();
+    let mut r = BIG::frombytes(&bytes);
+    r.norm();
+    r
+}
+
+/// Reduces a big-endian byte string of any length modulo p.
+fn fp_reduce(bytes: &[u8]) -> BIG {
+    bytes_mod(bytes, &fp_modulus())
+}
+
+/// Reduces a big-endian byte string of any length modulo m, 16 bytes at a time.
+fn bytes_mod(bytes: &[u8], m: &BIG) -> BIG {
+    let mut shift_bytes = vec![0u8; MODBYTES];
+    shift_bytes[MODBYTES - 17] = 1;
+    let mut shift = BIG::frombytes(&shift_bytes);
+
+    let mut acc = BIG::new();
+
+    for limb in bytes.chunks(16) {
+        let mut limb_bytes = vec![0u8; MODBYTES];
+        limb_bytes[MODBYTES - limb.len()..].copy_from_slice(limb);
+
+        acc = BIG::modmul(&mut acc, &mut shift, m);
+        acc.add(&BIG::frombytes(&limb_bytes));
+        acc.rmod(m);
+    }
+
+    acc
+}
+
+fn fp_is_zero(a: &BIG) -> bool {
+    BIG::comp(a, &BIG::new()) == 0
+}
+
+fn fp_add(a: &BIG, b: &BIG) -> BIG {
+    let mut r = *a;
+    r.add(b);
+    r.rmod(&fp_modulus());
+    r
+}
+
+fn fp_neg(a: &BIG) -> BIG {
+    let p = fp_modulus();
+    let mut r = *a;
+    r = BIG::modneg(&mut r, &p);
+    r.rmod(&p);
+    r
+}
+
+fn fp_sub(a: &BIG, b: &BIG) -> BIG {
+    fp_add(a, &fp_neg(b))
+}
+
+fn fp_mul(a: &BIG, b: &BIG) -> BIG {
+    let mut x = *a;
+    let mut y = *b;
+    BIG::modmul(&mut x, &mut y, &fp_modulus())
+}
+
+fn fp_pow(a: &BIG, e: &str) -> BIG {
+    let mut x = *a;
+    let mut e = fp_from_hex(e);
+    x.powmod(&mut e, &fp_modulus())
+}
+
+/// 1 / a, with 1 / 0 == 0 as required by the spec (inv0).
+fn fp_inv0(a: &BIG) -> BIG {
+    if fp_is_zero(a) {
+        return BIG::new();
+    }
+    let mut r = *a;
+    r.invmodp(&fp_modulus());
+    r
+}
+
+fn fp_is_square(a: &BIG) -> bool {
+    fp_is_zero(a) || BIG::comp(&fp_pow(a, FP_P_MINUS_1_OVER_2), &BIG::new_int(1)) == 0
+}
+
+/// Square root for p = 3 mod 4. Only meaningful if `a` is a square.
+fn fp_sqrt(a: &BIG) -> BIG {
+    fp_pow(a, FP_P_PLUS_1_OVER_4)
+}
+
+fn fp_sgn0(a: &BIG) -> isize {
+    let mut r = *a;
+    r.norm();
+    r.parity()
+}
+
+
+/// Shallue-van de Woestijne map to E: y^2 = x^3 + B. BN254 has A = 0,
+/// so the simplified SWU map of the spec can not be applied directly.
+fn map_to_curve_svdw(u: &BIG) -> (BIG, BIG) {
+    let b = BIG::new_int(CURVE_B_I);
+    let one = BIG::new_int(1);
+    let c1 = fp_from_hex(SVDW_C1);
+    let c2 = fp_from_hex(SVDW_C2);
+    let c3 = fp_from_hex(SVDW_C3);
+    let c4 = fp_from_hex(SVDW_C4);
+    let z = fp_from_hex(SVDW_Z);
+
+    let g = |x: &BIG| fp_add(&fp_mul(&fp_mul(x, x), x), &b);
+
+    let mut tv1 = fp_mul(&fp_mul(u, u), &c1);
+    let tv2 = fp_add(&one, &tv1);
+    tv1 = fp_sub(&one, &tv1);
+    let tv3 = fp_inv0(&fp_mul(&tv1, &tv2));
+    let tv4 = fp_mul(&fp_mul(&fp_mul(u, &tv1), &tv3), &c3);
+
+    let x1 = fp_sub(&c2, &tv4);
+    let x2 = fp_add(&c2, &tv4);
+    let mut x3 = fp_mul(&fp_mul(&tv2, &tv2), &tv3);
+    x3 = fp_add(&fp_mul(&fp_mul(&x3, &x3), &c4), &z);
+
+    let x = if fp_is_square(&g(&x1)) {
+        x1
+    } else if fp_is_square(&g(&x2)) {
+        x2
+    } else {
+        x3
+    };
+
+    let mut y = fp_sqrt(&g(&x));
+
+    if fp_sgn0(u) != fp_sgn0(&y) {
+        y = fp_neg(&y);
+    }
+
+    (x, y)
+}
+
+fn map_to_curve(u: &BIG) -> ECP {
+    let (x, y) = map_to_curve_svdw(u);
+    ECP::new_bigs(&x, &y)
+}
+
+/// BN254 G1 has cofactor 1.
+fn clear_cofactor(point: &ECP) -> ECP {
+    *point
+}
+
 #[derive(Copy, Clone, PartialEq)]
 pub struct PointG2 {
     point: ECP2
@@ -307,6 +612,21 @@ impl PointG2 {
         })
     }
 
+    /// Maps hash to PointG2, cofactor is cleared
+    pub fn from_hash(hash: &[u8]) -> Result<PointG2, IndyCryptoError> {
+        if hash.len() > MODBYTES {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Invalid len of hash: expected at most {}, actual {}", MODBYTES, hash.len())));
+        }
+
+        let mut bytes = vec![0u8; MODBYTES - hash.len()];
+        bytes.extend_from_slice(hash);
+
+        Ok(PointG2 {
+            point: ECP2::mapit(&bytes)
+        })
+    }
+
     pub fn to_string(&self) -> Result<String, IndyCryptoError> {
         Ok(self.point.to_hex())
     }
@@ -403,6 +723,13 @@ impl GroupOrderElement {
         })
     }
 
+    /// Creates group order element from big-endian bytes of any length reduced modulo GroupOrder
+    pub fn from_bytes_mod_order(b: &[u8]) -> Result<GroupOrderElement, IndyCryptoError> {
+        Ok(GroupOrderElement {
+            bn: bytes_mod(b, &BIG::new_ints(&CURVE_ORDER))
+        })
+    }
+
     /// (GroupOrderElement ^ GroupOrderElement) mod GroupOrder
     pub fn pow_mod(&self, e: &GroupOrderElement) -> Result<GroupOrderElement, IndyCryptoError> {
         let mut base = self.bn;
@@ -568,6 +895,47 @@ impl Pair {
         })
     }
 
+    /// e(PointG1, PointG2) * ... * e(PointG1, PointG2)
+    ///
+    /// Miller loops of adjacent pairs are shared and the final exponentiation is done only once,
+    /// so it is much cheaper than multiplying results of separate `pair` calls.
+    pub fn multi_pair(pairs: &[(&PointG1, &PointG2)]) -> Result<Pair, IndyCryptoError> {
+        if pairs.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("List of pairs is empty")));
+        }
+
+        let mut result = FP12::new_int(1);
+
+        for chunk in pairs.chunks(2) {
+            let (p1, q1) = chunk[0];
+            let mut p1_new = *p1;
+            let mut q1_new = *q1;
+
+            let mut miller = if chunk.len() == 2 {
+                let (p2, q2) = chunk[1];
+                let mut p2_new = *p2;
+                let mut q2_new = *q2;
+                ate2(&mut q1_new.point, &mut p1_new.point, &mut q2_new.point, &mut p2_new.point)
+            } else {
+                ate(&mut q1_new.point, &mut p1_new.point)
+            };
+            result.mul(&mut miller);
+        }
+
+        let mut result = fexp(&result);
+        result.reduce();
+
+        Ok(Pair {
+            pair: result
+        })
+    }
+
+    /// Checks e() == 1
+    pub fn is_unity(&self) -> Result<bool, IndyCryptoError> {
+        let mut r = self.pair;
+        Ok(r.isunity())
+    }
+
     /// e() * e()
     pub fn mul(&self, b: &Pair) -> Result<Pair, IndyCryptoError> {
         let mut base = self.pair;
@@ -664,6 +1032,29 @@ mod tests {
         assert_eq!(err.to_error_code(), ErrorCode::CommonInvalidStructure);
     }
 
+    #[test]
+    fn group_order_element_from_bytes_mod_order_works() {
+        // group order + 5 padded to 48 bytes
+        let bytes = [
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0x25, 0x23, 0x64, 0x82, 0x40, 0x00, 0x00, 0x01, 0xba, 0x34, 0x4d, 0x80, 0x00, 0x00, 0x00, 0x07,
+            0xff, 0x9f, 0x80, 0x00, 0x00, 0x00, 0x00, 0x10, 0xa1, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x12
+        ];
+        let element = GroupOrderElement::from_bytes_mod_order(&bytes).unwrap();
+        assert_eq!(GroupOrderElement::from_bytes(&[5]).unwrap().to_bytes().unwrap(), element.to_bytes().unwrap());
+    }
+
+    #[test]
+    fn point_g2_from_hash_works() {
+        let point1 = PointG2::from_hash(&[1u8; 32]).unwrap();
+        let point2 = PointG2::from_hash(&[1u8; 32]).unwrap();
+        let point3 = PointG2::from_hash(&[2u8; 32]).unwrap();
+
+        assert_eq!(point1.to_bytes().unwrap(), point2.to_bytes().unwrap());
+        assert_ne!(point1.to_bytes().unwrap(), point3.to_bytes().unwrap());
+        assert!(PointG2::from_hash(&[1u8; PointG2::BYTES_REPR_SIZE]).is_err());
+    }
+
     #[test]
     fn pairing_definition_bilinearity() {
         let a = GroupOrderElement::new().unwrap();
@@ -692,6 +1083,38 @@ mod tests {
         assert_eq!(q, result);
     }
 
+    #[test]
+    fn point_g1_from_bytes_compressed_to_bytes_compressed_works() {
+        for _ in 0..10 {
+            let p = PointG1::new().unwrap();
+            let bytes = p.to_bytes_compressed().unwrap();
+            assert_eq!(bytes.len(), PointG1::BYTES_REPR_COMPRESSED_SIZE);
+            assert_eq!(p, PointG1::from_bytes_compressed(&bytes).unwrap());
+            assert_eq!(p.neg().unwrap(), PointG1::from_bytes_compressed(&p.neg().unwrap().to_bytes_compressed().unwrap()).unwrap());
+        }
+    }
+
+    #[test]
+    fn point_g1_from_bytes_compressed_to_bytes_compressed_works_for_infinity() {
+        let inf = PointG1::new_inf().unwrap();
+        let bytes = inf.to_bytes_compressed().unwrap();
+        assert!(bytes.iter().all(|b| *b == 0));
+        assert!(PointG1::from_bytes_compressed(&bytes).unwrap().is_inf().unwrap());
+    }
+
+    #[test]
+    fn point_g1_from_bytes_compressed_works_for_invalid_bytes() {
+        let mut bytes = PointG1::new().unwrap().to_bytes_compressed().unwrap();
+        assert!(PointG1::from_bytes_compressed(&bytes[1..]).is_err());
+
+        bytes[0] = 0x04;
+        assert!(PointG1::from_bytes_compressed(&bytes).is_err());
+
+        let mut bytes = vec![0xffu8; PointG1::BYTES_REPR_COMPRESSED_SIZE];
+        bytes[0] = 0x02;
+        assert!(PointG1::from_bytes_compressed(&bytes).is_err());
+    }
+
     #[test]
     fn point_g2_infinity_test() {
         let p = PointG2::new_inf().unwrap();
@@ -712,6 +1135,85 @@ mod tests {
         let pair3 = pair_result.mul(&pair1.inverse().unwrap()).unwrap();
         assert_eq!(pair2, pair3);
     }
+
+    #[test]
+    fn multi_pair_works() {
+        let p1 = PointG1::new().unwrap();
+        let q1 = PointG2::new().unwrap();
+        let p2 = PointG1::new().unwrap();
+        let q2 = PointG2::new().unwrap();
+        let expected = Pair::pair(&p1, &q1).unwrap().mul(&Pair::pair(&p2, &q2).unwrap()).unwrap();
+        let result = Pair::multi_pair(&[(&p1, &q1), (&p2, &q2)]).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn multi_pair_works_for_odd_number_of_pairs() {
+        let p1 = PointG1::new().unwrap();
+        let q1 = PointG2::new().unwrap();
+        let p2 = PointG1::new().unwrap();
+        let q2 = PointG2::new().unwrap();
+        let p3 = PointG1::new().unwrap();
+        let q3 = PointG2::new().unwrap();
+        let expected = Pair::pair(&p1, &q1).unwrap()
+            .mul(&Pair::pair(&p2, &q2).unwrap()).unwrap()
+            .mul(&Pair::pair(&p3, &q3).unwrap()).unwrap();
+        let result = Pair::multi_pair(&[(&p1, &q1), (&p2, &q2), (&p3, &q3)]).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn multi_pair_works_for_empty_list() {
+        assert!(Pair::multi_pair(&[]).is_err());
+    }
+
+    #[test]
+    fn is_unity_works() {
+        let p = PointG1::new().unwrap();
+        let q = PointG2::new().unwrap();
+        let pair = Pair::pair(&p, &q).unwrap();
+        assert!(!pair.is_unity().unwrap());
+        assert!(pair.mul(&pair.inverse().unwrap()).unwrap().is_unity().unwrap());
+    }
+
+    // RFC 9380, appendix K.1
+    #[test]
+    fn expand_message_xmd_works() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        let vectors: [(&[u8], &str); 3] = [
+            (&b""[..], "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"),
+            (&b"abc"[..], "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"),
+            (&b"abcdef0123456789"[..], "eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1")
+        ];
+
+        for &(msg, expected) in vectors.iter() {
+            let uniform_bytes = expand_message_xmd(msg, dst, 0x20).unwrap();
+            let hex: String = uniform_bytes.iter().map(|b| format!("{:02x}", b)).collect();
+            assert_eq!(expected, hex);
+        }
+    }
+
+    // There are no published test vectors for this suite. Expected points below are regression
+    // values, cross-checked against an independent implementation of the SvdW map of RFC 9380.
+    #[test]
+    fn hash_to_curve_works_for_empty_message() {
+        let point = PointG1::hash_to_curve(b"", b"QUUX-V01-CS02-with-BN254AMCLG1_XMD:SHA-256_SVDW_RO_").unwrap();
+        let expected = PointG1 {
+            point: ECP::new_bigs(&fp_from_hex("038690265e3361dc5c30d3e5000900292be3501c465735f600281fce637fdd1c"),
+                                 &fp_from_hex("1c7d96ccddb595ace3481bbd50ba3f97b15f46c9b2dce3aafce074f8dbc45659"))
+        };
+        assert_eq!(expected, point);
+    }
+
+    #[test]
+    fn hash_to_curve_works_for_abc() {
+        let point = PointG1::hash_to_curve(b"abc", b"QUUX-V01-CS02-with-BN254AMCLG1_XMD:SHA-256_SVDW_RO_").unwrap();
+        let expected = PointG1 {
+            point: ECP::new_bigs(&fp_from_hex("0240d5894bc5065e30f31893ee4d989cf93a6df741badb10dc092ca25b31af55"),
+                                 &fp_from_hex("014dc2dc5271008948d7c2aeadc23928ffac27593d1775fff7173d2e5db43bf0"))
+        };
+        assert_eq!(expected, point);
+    }
 }
 
 #[cfg(feature = "serialization")]
@@ -0,0 +1,1483 @@
+//! BLS12-381 pairing backend.
+//!
+//! Exposes the same API as the BN254 backend in `pair/amcl.rs` so `bls` and the CL revocation
+//! code can be built against either curve. BN254 no longer provides 128-bit security after the
+//! exTNFS improvements, BLS12-381 does.
+
+use errors::IndyCryptoError;
+
+use amcl::big::BIG;
+
+use amcl::rom::{
+    CURVE_GX,
+    CURVE_GY,
+    CURVE_ORDER,
+    CURVE_PXA,
+    CURVE_PYA,
+    CURVE_PXB,
+    CURVE_PYB,
+    MODBYTES,
+    MODULUS
+};
+
+use amcl::ecp::ECP;
+use amcl::ecp2::ECP2;
+use amcl::fp12::FP12;
+use amcl::fp2::FP2;
+use amcl::pair::{ate, ate2, g1mul, g2mul, gtpow, fexp};
+use amcl::rand::RAND;
+
+use sha2::{Sha256, Digest};
+
+use rand::rngs::OsRng;
+use rand::RngCore;
+use std::fmt::{Debug, Formatter, Error};
+
+#[cfg(feature = "serialization")]
+use serde::ser::{Serialize, Serializer, Error as SError};
+#[cfg(feature = "serialization")]
+use serde::de::{Deserialize, Deserializer, Visitor, Error as DError};
+#[cfg(feature = "serialization")]
+use std::fmt;
+
+#[cfg(test)]
+use std::cell::RefCell;
+
+#[cfg(test)]
+thread_local! {
+  pub static PAIR_USE_MOCKS: RefCell<bool> = RefCell::new(false);
+}
+
+#[cfg(test)]
+pub struct PairMocksHelper {}
+
+#[cfg(test)]
+impl PairMocksHelper {
+    pub fn inject() {
+        PAIR_USE_MOCKS.with(|use_mocks| {
+            *use_mocks.borrow_mut() = true;
+        });
+    }
+
+    pub fn is_injected() -> bool {
+        PAIR_USE_MOCKS.with(|use_mocks| {
+            return *use_mocks.borrow();
+        })
+    }
+}
+
+#[cfg(not(test))]
+fn random_mod_order() -> Result<BIG, IndyCryptoError> {
+    _random_mod_order()
+}
+
+#[cfg(test)]
+fn random_mod_order() -> Result<BIG, IndyCryptoError> {
+    if PairMocksHelper::is_injected() {
+        Ok(BIG::from_hex("22EB5716FB01F2122DE924466542B923D8C96F16C9B5FE2C00B7D7DC1499EA50".to_string()))
+    }
+    else {
+        _random_mod_order()
+    }
+}
+
+fn _random_mod_order() -> Result<BIG, IndyCryptoError> {
+    let entropy_bytes = 128;
+    let mut seed = vec![0; entropy_bytes];
+    let mut os_rng = OsRng::new().unwrap();
+    os_rng.fill_bytes(&mut seed.as_mut_slice());
+    let mut rng = RAND::new();
+    rng.clean();
+    // AMCL recommends to initialise from at least 128 bytes, check doc for `RAND.seed`
+    rng.seed(entropy_bytes, &seed);
+    Ok(BIG::randomnum(&BIG::new_ints(&CURVE_ORDER), &mut rng))
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub struct PointG1 {
+    point: ECP
+}
+
+impl PointG1 {
+    pub const BYTES_REPR_SIZE: usize = MODBYTES * 4;
+    pub const BYTES_REPR_COMPRESSED_SIZE: usize = MODBYTES + 1;
+
+    /// Hash-to-curve suite implemented by `hash_to_curve`.
+    pub const HASH_TO_CURVE_SUITE: &'static str = "BLS12381G1_XMD:SHA-256_SSWU_RO_";
+
+    /// Creates new random PointG1
+    pub fn new() -> Result<PointG1, IndyCryptoError> {
+        // generate random point from the group G1
+        let point_x = BIG::new_ints(&CURVE_GX);
+        let point_y = BIG::new_ints(&CURVE_GY);
+        let mut gen_g1 = ECP::new_bigs(&point_x, &point_y);
+
+        let point = g1mul(&mut gen_g1, &mut random_mod_order()?);
+
+        Ok(PointG1 {
+            point: point
+        })
+    }
+
+    /// Creates new infinity PointG1
+    pub fn new_inf() -> Result<PointG1, IndyCryptoError> {
+        let mut r = ECP::new();
+        r.inf();
+        Ok(PointG1 {
+            point: r
+        })
+    }
+
+    /// Checks infinity
+    pub fn is_inf(&self) -> Result<bool, IndyCryptoError> {
+        let mut r = self.point;
+        Ok(r.is_infinity())
+    }
+
+    /// PointG1 ^ GroupOrderElement
+    pub fn mul(&self, e: &GroupOrderElement) -> Result<PointG1, IndyCryptoError> {
+        let mut r = self.point;
+        let mut bn = e.bn;
+        Ok(PointG1 {
+            point: g1mul(&mut r, &mut bn)
+        })
+    }
+
+    /// PointG1 * PointG1
+    pub fn add(&self, q: &PointG1) -> Result<PointG1, IndyCryptoError> {
+        let mut r = self.point;
+        let mut point = q.point;
+        r.add(&mut point);
+        Ok(PointG1 {
+            point: r
+        })
+    }
+
+    /// PointG1 / PointG1
+    pub fn sub(&self, q: &PointG1) -> Result<PointG1, IndyCryptoError> {
+        let mut r = self.point;
+        let mut point = q.point;
+        r.sub(&mut point);
+        Ok(PointG1 {
+            point: r
+        })
+    }
+
+    /// 1 / PointG1
+    pub fn neg(&self) -> Result<PointG1, IndyCryptoError> {
+        let mut r = self.point;
+        r.neg();
+        Ok(PointG1 {
+            point: r
+        })
+    }
+
+    pub fn to_string(&self) -> Result<String, IndyCryptoError> {
+        Ok(self.point.to_hex())
+    }
+
+    pub fn from_string(str: &str) -> Result<PointG1, IndyCryptoError> {
+        PointG1::_checked(ECP::from_hex(str.to_string()))
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        let mut r = self.point;
+        let mut vec = vec![0u8; Self::BYTES_REPR_SIZE];
+        r.tobytes(&mut vec);
+        Ok(vec)
+    }
+
+    pub fn from_bytes(b: &[u8]) -> Result<PointG1, IndyCryptoError> {
+        if b.len() != Self::BYTES_REPR_SIZE {
+            return Err(IndyCryptoError::InvalidStructure(
+                "Invalid len of bytes representation".to_string()));
+        }
+        PointG1::_checked(ECP::frombytes(b))
+    }
+
+    /// Encodes PointG1 as x coordinate prefixed by 0x02 or 0x03 tag holding parity of y.
+    /// Infinity is encoded as all zeroes.
+    pub fn to_bytes_compressed(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        let mut vec = vec![0u8; Self::BYTES_REPR_COMPRESSED_SIZE];
+
+        if self.is_inf()? {
+            return Ok(vec);
+        }
+
+        let mut r = self.point;
+        r.affine();
+        let mut x = r.getx();
+        let y = r.gety();
+
+        x.tobytes(&mut vec[1..]);
+        vec[0] = if fp_sgn0(&y) == 1 { 0x03 } else { 0x02 };
+        Ok(vec)
+    }
+
+    pub fn from_bytes_compressed(b: &[u8]) -> Result<PointG1, IndyCryptoError> {
+        if b.len() != Self::BYTES_REPR_COMPRESSED_SIZE {
+            return Err(IndyCryptoError::InvalidStructure(
+                "Invalid len of compressed bytes representation".to_string()));
+        }
+
+        if b.iter().all(|byte| *byte == 0) {
+            return PointG1::new_inf();
+        }
+
+        let sign = match b[0] {
+            0x02 => 0,
+            0x03 => 1,
+            _ => return Err(IndyCryptoError::InvalidStructure(
+                "Invalid tag of compressed bytes representation".to_string()))
+        };
+
+        let x = BIG::frombytes(&b[1..]);
+
+        if BIG::comp(&x, &fp_modulus()) >= 0 {
+            return Err(IndyCryptoError::InvalidStructure(
+                "Invalid x coordinate of compressed bytes representation".to_string()));
+        }
+
+        let gx = fp_add(&fp_mul(&fp_mul(&x, &x), &x), &BIG::new_int(CURVE_B_I));
+
+        if !fp_is_square(&gx) {
+            return Err(IndyCryptoError::InvalidStructure(
+                "Invalid x coordinate of compressed bytes representation".to_string()));
+        }
+
+        let mut y = fp_sqrt(&gx);
+
+        if fp_sgn0(&y) != sign {
+            y = fp_neg(&y);
+        }
+
+        PointG1::_checked(ECP::new_bigs(&x, &y))
+    }
+
+    /// Maps hash to PointG1, cofactor is cleared
+    pub fn from_hash(hash: &[u8]) -> Result<PointG1, IndyCryptoError> {
+        let mut el = GroupOrderElement::from_bytes(hash)?;
+        let mut point = clear_cofactor(&ECP::new_big(&el.bn));
+
+        while point.is_infinity() {
+            el.bn.inc(1);
+            point = clear_cofactor(&ECP::new_big(&el.bn));
+        }
+
+        Ok(PointG1 {
+            point: point
+        })
+    }
+
+    /// BLS12-381 G1 has cofactor, so points read from outside must be checked
+    /// to be in the prime order subgroup.
+    fn _checked(point: ECP) -> Result<PointG1, IndyCryptoError> {
+        let mut r = point;
+
+        if !r.mul(&BIG::new_ints(&CURVE_ORDER)).is_infinity() {
+            return Err(IndyCryptoError::InvalidStructure(
+                "PointG1 is not in the prime order subgroup".to_string()));
+        }
+
+        Ok(PointG1 {
+            point: point
+        })
+    }
+
+    /// Hashes message to PointG1 as defined by `hash_to_curve` of the IETF hash-to-curve spec
+    /// for `HASH_TO_CURVE_SUITE`. `dst` is the domain separation tag.
+    pub fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Result<PointG1, IndyCryptoError> {
+        let u = hash_to_field(msg, dst, 2)?;
+        let mut q0 = map_to_curve(&u[0]);
+        let mut q1 = map_to_curve(&u[1]);
+        q0.add(&mut q1);
+
+        Ok(PointG1 {
+            point: clear_cofactor(&q0)
+        })
+    }
+}
+
+impl Debug for PointG1 {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "PointG1 {{ point: {} }}", self.point.to_hex())
+    }
+}
+
+#[cfg(feature = "serialization")]
+impl Serialize for PointG1 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        serializer.serialize_newtype_struct("PointG1", &self.to_string().map_err(SError::custom)?)
+    }
+}
+
+#[cfg(feature = "serialization")]
+impl<'a> Deserialize<'a> for PointG1 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'a> {
+        struct PointG1Visitor;
+
+        impl<'a> Visitor<'a> for PointG1Visitor {
+            type Value = PointG1;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("expected PointG1")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<PointG1, E>
+                where E: DError
+            {
+                Ok(PointG1::from_string(value).map_err(DError::custom)?)
+            }
+        }
+
+        deserializer.deserialize_str(PointG1Visitor)
+    }
+}
+
+// Hash to curve (IETF hash-to-curve spec)
+
+/// ceil((ceil(log2(p)) + 128) / 8)
+const HASH_TO_FIELD_LEN: usize = 64;
+
+const CURVE_B_I: isize = 4;
+
+const SSWU_A: &'static str = "00144698a3b8e9433d693a02c96d4982b0ea985383ee66a8d8e8981aefd881ac98936f8da0e0f97f5cf428082d584c1d";
+const SSWU_B: &'static str = "12e2908d11688030018b12e8753eee3b2016c1f0f24f4070a0b9c14fcef35ef55a23215a316ceaa5d1cc48e98e172be0";
+const SSWU_Z: &'static str = "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000b";
+const SSWU_MINUS_B_OVER_A: &'static str = "0793154fd85631d966ef2470460c78f6a928ad9f5bdbfac21df39753aa278ba751bdfcf95a84188e29d670675e4c9c7c";
+const SSWU_B_OVER_ZA: &'static str = "123939a31626a32de772bc7a591ea140683bca0c62efb105310d5ce1d27d1aadf79a5d5cbe8e2c4ff7d4816af76d2814";
+const FP_P_MINUS_1_OVER_2: &'static str = "0d0088f51cbff34d258dd3db21a5d66bb23ba5c279c2895fb39869507b587b120f55ffff58a9ffffdcff7fffffffd555";
+const FP_P_PLUS_1_OVER_4: &'static str = "0680447a8e5ff9a692c6e9ed90d2eb35d91dd2e13ce144afd9cc34a83dac3d8907aaffffac54ffffee7fbfffffffeaab";
+const H_EFF: &'static str = "00000000000000000000000000000000000000000000000000000000000000000000000000000000d201000000010001";
+const G2_COFACTOR_HI: &'static str = "0000000000000000000000000000000005d543a95414e7f1091d50792876a202cd91de4547085abaa68a205b2e5a7ddf";
+const G2_COFACTOR_LO: &'static str = "00000000000000000000000000000000a628f1cb4d9e82ef21537e293a6691ae1616ec6e786f0c70cf1c38e31c7238e5";
+
+const ISO_X_NUM: [&'static str; 12] = [
+    "11a05f2b1e833340b809101dd99815856b303e88a2d7005ff2627b56cdb4e2c85610c2d5f2e62d6eaeac1662734649b7",
+    "17294ed3e943ab2f0588bab22147a81c7c17e75b2f6a8417f565e33c70d1e86b4838f2a6f318c356e834eef1b3cb83bb",
+    "0d54005db97678ec1d1048c5d10a9a1bce032473295983e56878e501ec68e25c958c3e3d2a09729fe0179f9dac9edcb0",
+    "1778e7166fcc6db74e0609d307e55412d7f5e4656a8dbf25f1b33289f1b330835336e25ce3107193c5b388641d9b6861",
+    "0e99726a3199f4436642b4b3e4118e5499db995a1257fb3f086eeb65982fac18985a286f301e77c451154ce9ac8895d9",
+    "1630c3250d7313ff01d1201bf7a74ab5db3cb17dd952799b9ed3ab9097e68f90a0870d2dcae73d19cd13c1c66f652983",
+    "0d6ed6553fe44d296a3726c38ae652bfb11586264f0f8ce19008e218f9c86b2a8da25128c1052ecaddd7f225a139ed84",
+    "17b81e7701abdbe2e8743884d1117e53356de5ab275b4db1a682c62ef0f2753339b7c8f8c8f475af9ccb5618e3f0c88e",
+    "080d3cf1f9a78fc47b90b33563be990dc43b756ce79f5574a2c596c928c5d1de4fa295f296b74e956d71986a8497e317",
+    "169b1f8e1bcfa7c42e0c37515d138f22dd2ecb803a0c5c99676314baf4bb1b7fa3190b2edc0327797f241067be390c9e",
+    "10321da079ce07e272d8ec09d2565b0dfa7dccdde6787f96d50af36003b14866f69b771f8c285decca67df3f1605fb7b",
+    "06e08c248e260e70bd1e962381edee3d31d79d7e22c837bc23c0bf1bc24c6b68c24b1b80b64d391fa9c8ba2e8ba2d229",
+];
+
+const ISO_X_DEN: [&'static str; 11] = [
+    "08ca8d548cff19ae18b2e62f4bd3fa6f01d5ef4ba35b48ba9c9588617fc8ac62b558d681be343df8993cf9fa40d21b1c",
+    "12561a5deb559c4348b4711298e536367041e8ca0cf0800c0126c2588c48bf5713daa8846cb026e9e5c8276ec82b3bff",
+    "0b2962fe57a3225e8137e629bff2991f6f89416f5a718cd1fca64e00b11aceacd6a3d0967c94fedcfcc239ba5cb83e19",
+    "03425581a58ae2fec83aafef7c40eb545b08243f16b1655154cca8abc28d6fd04976d5243eecf5c4130de8938dc62cd8",
+    "13a8e162022914a80a6f1d5f43e7a07dffdfc759a12062bb8d6b44e833b306da9bd29ba81f35781d539d395b3532a21e",
+    "0e7355f8e4e667b955390f7f0506c6e9395735e9ce9cad4d0a43bcef24b8982f7400d24bc4228f11c02df9a29f6304a5",
+    "0772caacf16936190f3e0c63e0596721570f5799af53a1894e2e073062aede9cea73b3538f0de06cec2574496ee84a3a",
+    "14a7ac2a9d64a8b230b3f5b074cf01996e7f63c21bca68a81996e1cdf9822c580fa5b9489d11e2d311f7d99bbdcc5a5e",
+    "0a10ecf6ada54f825e920b3dafc7a3cce07f8d1d7161366b74100da67f39883503826692abba43704776ec3a79a1d641",
+    "095fc13ab9e92ad4476d6e3eb3a56680f682b4ee96f7d03776df533978f31c1593174e4b4b7865002d6384d168ecdd0a",
+    "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001",
+];
+
+const ISO_Y_NUM: [&'static str; 16] = [
+    "090d97c81ba24ee0259d1f094980dcfa11ad138e48a869522b52af6c956543d3cd0c7aee9b3ba3c2be9845719707bb33",
+    "134996a104ee5811d51036d776fb46831223e96c254f383d0f906343eb67ad34d6c56711962fa8bfe097e75a2e41c696",
+    "00cc786baa966e66f4a384c86a3b49942552e2d658a31ce2c344be4b91400da7d26d521628b00523b8dfe240c72de1f6",
+    "01f86376e8981c217898751ad8746757d42aa7b90eeb791c09e4a3ec03251cf9de405aba9ec61deca6355c77b0e5f4cb",
+    "08cc03fdefe0ff135caf4fe2a21529c4195536fbe3ce50b879833fd221351adc2ee7f8dc099040a841b6daecf2e8fedb",
+    "16603fca40634b6a2211e11db8f0a6a074a7d0d4afadb7bd76505c3d3ad5544e203f6326c95a807299b23ab13633a5f0",
+    "04ab0b9bcfac1bbcb2c977d027796b3ce75bb8ca2be184cb5231413c4d634f3747a87ac2460f415ec961f8855fe9d6f2",
+    "0987c8d5333ab86fde9926bd2ca6c674170a05bfe3bdd81ffd038da6c26c842642f64550fedfe935a15e4ca31870fb29",
+    "09fc4018bd96684be88c9e221e4da1bb8f3abd16679dc26c1e8b6e6a1f20cabe69d65201c78607a360370e577bdba587",
+    "0e1bba7a1186bdb5223abde7ada14a23c42a0ca7915af6fe06985e7ed1e4d43b9b3f7055dd4eba6f2bafaaebca731c30",
+    "19713e47937cd1be0dfd0b8f1d43fb93cd2fcbcb6caf493fd1183e416389e61031bf3a5cce3fbafce813711ad011c132",
+    "18b46a908f36f6deb918c143fed2edcc523559b8aaf0c2462e6bfe7f911f643249d9cdf41b44d606ce07c8a4d0074d8e",
+    "0b182cac101b9399d155096004f53f447aa7b12a3426b08ec02710e807b4633f06c851c1919211f20d4c04f00b971ef8",
+    "0245a394ad1eca9b72fc00ae7be315dc757b3b080d4c158013e6632d3c40659cc6cf90ad1c232a6442d9d3f5db980133",
+    "05c129645e44cf1102a159f748c4a3fc5e673d81d7e86568d9ab0f5d396a7ce46ba1049b6579afb7866b1e715475224b",
+    "15e6be4e990f03ce4ea50b3b42df2eb5cb181d8f84965a3957add4fa95af01b2b665027efec01c7704b456be69c8b604",
+];
+
+const ISO_Y_DEN: [&'static str; 16] = [
+    "16112c4c3a9c98b252181140fad0eae9601a6de578980be6eec3232b5be72e7a07f3688ef60c206d01479253b03663c1",
+    "1962d75c2381201e1a0cbd6c43c348b885c84ff731c4d59ca4a10356f453e01f78a4260763529e3532f6102c2e49a03d",
+    "058df3306640da276faaae7d6e8eb15778c4855551ae7f310c35a5dd279cd2eca6757cd636f96f891e2538b53dbf67f2",
+    "16b7d288798e5395f20d23bf89edb4d1d115c5dbddbcd30e123da489e726af41727364f2c28297ada8d26d98445f5416",
+    "0be0e079545f43e4b00cc912f8228ddcc6d19c9f0f69bbb0542eda0fc9dec916a20b15dc0fd2ededda39142311a5001d",
+    "08d9e5297186db2d9fb266eaac783182b70152c65550d881c5ecd87b6f0f5a6449f38db9dfa9cce202c6477faaf9b7ac",
+    "166007c08a99db2fc3ba8734ace9824b5eecfdfa8d0cf8ef5dd365bc400a0051d5fa9c01a58b1fb93d1a1399126a775c",
+    "16a3ef08be3ea7ea03bcddfabba6ff6ee5a4375efa1f4fd7feb34fd206357132b920f5b00801dee460ee415a15812ed9",
+    "1866c8ed336c61231a1be54fd1d74cc4f9fb0ce4c6af5920abc5750c4bf39b4852cfe2f7bb9248836b233d9d55535d4a",
+    "167a55cda70a6e1cea820597d94a84903216f763e13d87bb5308592e7ea7d4fbc7385ea3d529b35e346ef48bb8913f55",
+    "04d2f259eea405bd48f010a01ad2911d9c6dd039bb61a6290e591b36e636a5c871a5c29f4f83060400f8b49cba8f6aa8",
+    "0accbb67481d033ff5852c1e48c50c477f94ff8aefce42d28c0f9a88cea7913516f968986f7ebbea9684b529e2561092",
+    "0ad6b9514c767fe3c3613144b45f1496543346d98adf02267d5ceef9a00d9b8693000763e3b90ac11e99b138573345cc",
+    "02660400eb2e4f3b628bdd0d53cd76f2bf565b94e72927c1cb748df27942480e420517bd8714cc80d1fadc1326ed06f7",
+    "0e0fa1d816ddc03e6b24255e0d7819c171c40f65e273b853324efcd6356caa205ca2f570f13497804415473a1d634b8f",
+    "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001",
+];
+
+/// Expands `msg` into `len_in_bytes` uniformly random bytes as defined by `expand_message_xmd`
+/// with SHA-256 in the IETF hash-to-curve spec.
+fn expand_message_xmd(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Result<Vec<u8>, IndyCryptoError> {
+    let ell = (len_in_bytes + 31) / 32;
+
+    if ell > 255 || len_in_bytes > 65535 {
+        return Err(IndyCryptoError::InvalidStructure(
+            format!("Invalid len of expanded message: {}", len_in_bytes)));
+    }
+
+    let mut dst_prime = if dst.len() > 255 {
+        let mut hasher = Sha256::default();
+        hasher.input(b"H2C-OVERSIZE-DST-");
+        hasher.input(dst);
+        hasher.result().to_vec()
+    } else {
+        dst.to_vec()
+    };
+    let dst_len = dst_prime.len() as u8;
+    dst_prime.push(dst_len);
+
+    let mut hasher = Sha256::default();
+    hasher.input(&[0u8; 64]);
+    hasher.input(msg);
+    hasher.input(&[(len_in_bytes >> 8) as u8, len_in_bytes as u8, 0u8]);
+    hasher.input(&dst_prime);
+    let b_0 = hasher.result();
+
+    let mut hasher = Sha256::default();
+    hasher.input(&b_0);
+    hasher.input(&[1u8]);
+    hasher.input(&dst_prime);
+    let mut b_i = hasher.result();
+
+    let mut uniform_bytes = b_i.to_vec();
+
+    for i in 2..ell + 1 {
+        let xored: Vec<u8> = b_0.iter().zip(b_i.iter()).map(|(a, b)| a ^ b).collect();
+
+        let mut hasher = Sha256::default();
+        hasher.input(&xored);
+        hasher.input(&[i as u8]);
+        hasher.input(&dst_prime);
+        b_i = hasher.result();
+
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+
+    uniform_bytes.truncate(len_in_bytes);
+    Ok(uniform_bytes)
+}
+
+/// Hashes `msg` to `count` elements of the base field.
+fn hash_to_field(msg: &[u8], dst: &[u8], count: usize) -> Result<Vec<BIG>, IndyCryptoError> {
+    let uniform_bytes = expand_message_xmd(msg, dst, count * HASH_TO_FIELD_LEN)?;
+    Ok(uniform_bytes.chunks(HASH_TO_FIELD_LEN).map(fp_reduce).collect())
+}
+
+fn fp_modulus() -> BIG {
+    BIG::new_ints(&MODULUS)
+}
+
+fn fp_from_hex(hex: &str) -> BIG {
+    let bytes: Vec<u8> = (0..hex.len() / 2)
+        .map(|i| u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap())
+        .collect();
+    let mut r = BIG::frombytes(&bytes);
+    r.norm();
+    r
+}
+
+/// Reduces a big-endian byte string of any length modulo p.
+fn fp_reduce(bytes: &[u8]) -> BIG {
+    bytes_mod(bytes, &fp_modulus())
+}
+
+/// Reduces a big-endian byte string of any length modulo m, 16 bytes at a time.
+fn bytes_mod(bytes: &[u8], m: &BIG) -> BIG {
+    let mut shift_bytes = vec![0u8; MODBYTES];
+    shift_bytes[MODBYTES - 17] = 1;
+    let mut shift = BIG::frombytes(&shift_bytes);
+
+    let mut acc = BIG::new();
+
+    for limb in bytes.chunks(16) {
+        let mut limb_bytes = vec![0u8; MODBYTES];
+        limb_bytes[MODBYTES - limb.len()..].copy_from_slice(limb);
+
+        acc = BIG::modmul(&mut acc, &mut shift, m);
+        acc.add(&BIG::frombytes(&limb_bytes));
+        acc.rmod(m);
+    }
+
+    acc
+}
+
+fn fp_is_zero(a: &BIG) -> bool {
+    BIG::comp(a, &BIG::new()) == 0
+}
+
+fn fp_add(a: &BIG, b: &BIG) -> BIG {
+    let mut r = *a;
+    r.add(b);
+    r.rmod(&fp_modulus());
+    r
+}
+
+fn fp_neg(a: &BIG) -> BIG {
+    let p = fp_modulus();
+    let mut r = *a;
+    r = BIG::modneg(&mut r, &p);
+    r.rmod(&p);
+    r
+}
+
+fn fp_sub(a: &BIG, b: &BIG) -> BIG {
+    fp_add(a, &fp_neg(b))
+}
+
+fn fp_mul(a: &BIG, b: &BIG) -> BIG {
+    let mut x = *a;
+    let mut y = *b;
+    BIG::modmul(&mut x, &mut y, &fp_modulus())
+}
+
+fn fp_pow(a: &BIG, e: &str) -> BIG {
+    let mut x = *a;
+    let mut e = fp_from_hex(e);
+    x.powmod(&mut e, &fp_modulus())
+}
+
+/// 1 / a, with 1 / 0 == 0 as required by the spec (inv0).
+fn fp_inv0(a: &BIG) -> BIG {
+    if fp_is_zero(a) {
+        return BIG::new();
+    }
+    let mut r = *a;
+    r.invmodp(&fp_modulus());
+    r
+}
+
+fn fp_is_square(a: &BIG) -> bool {
+    fp_is_zero(a) || BIG::comp(&fp_pow(a, FP_P_MINUS_1_OVER_2), &BIG::new_int(1)) == 0
+}
+
+/// Square root for p = 3 mod 4. Only meaningful if `a` is a square.
+fn fp_sqrt(a: &BIG) -> BIG {
+    fp_pow(a, FP_P_PLUS_1_OVER_4)
+}
+
+fn fp_sgn0(a: &BIG) -> isize {
+    let mut r = *a;
+    r.norm();
+    r.parity()
+}
+
+
+/// Simplified SWU map to the 11-isogenous curve E': y^2 = x^3 + A' * x + B'.
+fn map_to_curve_simple_swu(u: &BIG) -> (BIG, BIG) {
+    let a = fp_from_hex(SSWU_A);
+    let b = fp_from_hex(SSWU_B);
+    let z = fp_from_hex(SSWU_Z);
+    let one = BIG::new_int(1);
+
+    let u2 = fp_mul(u, u);
+    let z_u2 = fp_mul(&z, &u2);
+    let tv1 = fp_inv0(&fp_add(&fp_mul(&z_u2, &z_u2), &z_u2));
+
+    let x1 = if fp_is_zero(&tv1) {
+        fp_from_hex(SSWU_B_OVER_ZA)
+    } else {
+        fp_mul(&fp_from_hex(SSWU_MINUS_B_OVER_A), &fp_add(&one, &tv1))
+    };
+    let gx1 = fp_add(&fp_mul(&fp_add(&fp_mul(&x1, &x1), &a), &x1), &b);
+
+    let x2 = fp_mul(&z_u2, &x1);
+    let gx2 = fp_add(&fp_mul(&fp_add(&fp_mul(&x2, &x2), &a), &x2), &b);
+
+    let (x, mut y) = if fp_is_square(&gx1) {
+        (x1, fp_sqrt(&gx1))
+    } else {
+        (x2, fp_sqrt(&gx2))
+    };
+
+    if fp_sgn0(u) != fp_sgn0(&y) {
+        y = fp_neg(&y);
+    }
+
+    (x, y)
+}
+
+/// Evaluates polynomial with coefficients given in ascending order of degree.
+fn fp_poly(coefficients: &[&str], x: &BIG) -> BIG {
+    coefficients.iter().rev()
+        .fold(BIG::new(), |acc, k| fp_add(&fp_mul(&acc, x), &fp_from_hex(k)))
+}
+
+/// 11-isogeny map from E' to E. Returns infinity for the exceptional points.
+fn iso_map(x: &BIG, y: &BIG) -> ECP {
+    let x_den = fp_poly(&ISO_X_DEN, x);
+    let y_den = fp_poly(&ISO_Y_DEN, x);
+
+    if fp_is_zero(&x_den) || fp_is_zero(&y_den) {
+        let mut r = ECP::new();
+        r.inf();
+        return r;
+    }
+
+    let x_mapped = fp_mul(&fp_poly(&ISO_X_NUM, x), &fp_inv0(&x_den));
+    let y_mapped = fp_mul(y, &fp_mul(&fp_poly(&ISO_Y_NUM, x), &fp_inv0(&y_den)));
+
+    ECP::new_bigs(&x_mapped, &y_mapped)
+}
+
+fn map_to_curve(u: &BIG) -> ECP {
+    let (x, y) = map_to_curve_simple_swu(u);
+    iso_map(&x, &y)
+}
+
+fn clear_cofactor(point: &ECP) -> ECP {
+    let mut r = *point;
+    r.mul(&fp_from_hex(H_EFF))
+}
+
+/// Multiplies by G2 cofactor h2 = h2_hi * 2^256 + h2_lo, h2 does not fit BIG as a whole.
+fn clear_cofactor_g2(point: &ECP2) -> ECP2 {
+    let mut r = *point;
+    let mut hi = r.mul(&fp_from_hex(G2_COFACTOR_HI));
+
+    for _ in 0..256 {
+        hi.dbl();
+    }
+
+    let mut lo = r.mul(&fp_from_hex(G2_COFACTOR_LO));
+    hi.add(&mut lo);
+    hi
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub struct PointG2 {
+    point: ECP2
+}
+
+impl PointG2 {
+    pub const BYTES_REPR_SIZE: usize = MODBYTES * 4;
+
+    /// Creates new random PointG2
+    pub fn new() -> Result<PointG2, IndyCryptoError> {
+        let point_xa = BIG::new_ints(&CURVE_PXA);
+        let point_xb = BIG::new_ints(&CURVE_PXB);
+        let point_ya = BIG::new_ints(&CURVE_PYA);
+        let point_yb = BIG::new_ints(&CURVE_PYB);
+
+        let point_x = FP2::new_bigs(&point_xa, &point_xb);
+        let point_y = FP2::new_bigs(&point_ya, &point_yb);
+
+        let mut gen_g2 = ECP2::new_fp2s(&point_x, &point_y);
+
+        let point = g2mul(&mut gen_g2, &mut random_mod_order()?);
+
+        Ok(PointG2 {
+            point: point
+        })
+    }
+
+    /// Creates new infinity PointG2
+    pub fn new_inf() -> Result<PointG2, IndyCryptoError> {
+        let mut point = ECP2::new();
+        point.inf();
+
+        Ok(PointG2 {
+            point: point
+        })
+    }
+
+    /// PointG2 * PointG2
+    pub fn add(&self, q: &PointG2) -> Result<PointG2, IndyCryptoError> {
+        let mut r = self.point;
+        let mut point = q.point;
+        r.add(&mut point);
+
+        Ok(PointG2 {
+            point: r
+        })
+    }
+
+    /// PointG2 / PointG2
+    pub fn sub(&self, q: &PointG2) -> Result<PointG2, IndyCryptoError> {
+        let mut r = self.point;
+        let mut point = q.point;
+        r.sub(&mut point);
+
+        Ok(PointG2 {
+            point: r
+        })
+    }
+
+    /// PointG2 ^ GroupOrderElement
+    pub fn mul(&self, e: &GroupOrderElement) -> Result<PointG2, IndyCryptoError> {
+        let mut r = self.point;
+        let mut bn = e.bn;
+        Ok(PointG2 {
+            point: g2mul(&mut r, &mut bn)
+        })
+    }
+
+    /// Maps hash to PointG2, cofactor is cleared
+    pub fn from_hash(hash: &[u8]) -> Result<PointG2, IndyCryptoError> {
+        if hash.len() > MODBYTES {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Invalid len of hash: expected at most {}, actual {}", MODBYTES, hash.len())));
+        }
+
+        let mut bytes = vec![0u8; MODBYTES - hash.len()];
+        bytes.extend_from_slice(hash);
+
+        let mut point = clear_cofactor_g2(&ECP2::mapit(&bytes));
+
+        if point.is_infinity() {
+            return Err(IndyCryptoError::InvalidStructure(
+                "Hash is mapped to infinity PointG2".to_string()));
+        }
+
+        Ok(PointG2 {
+            point: point
+        })
+    }
+
+    pub fn to_string(&self) -> Result<String, IndyCryptoError> {
+        Ok(self.point.to_hex())
+    }
+
+    pub fn from_string(str: &str) -> Result<PointG2, IndyCryptoError> {
+        PointG2::_checked(ECP2::from_hex(str.to_string()))
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        let mut point = self.point;
+        let mut vec = vec![0u8; Self::BYTES_REPR_SIZE];
+        point.tobytes(&mut vec);
+        Ok(vec)
+    }
+
+    pub fn from_bytes(b: &[u8]) -> Result<PointG2, IndyCryptoError> {
+        if b.len() != Self::BYTES_REPR_SIZE {
+            return Err(IndyCryptoError::InvalidStructure(
+                "Invalid len of bytes representation".to_string()));
+        }
+        PointG2::_checked(ECP2::frombytes(b))
+    }
+
+    /// BLS12-381 G2 has cofactor, so points read from outside must be checked
+    /// to be in the prime order subgroup.
+    fn _checked(point: ECP2) -> Result<PointG2, IndyCryptoError> {
+        let mut r = point;
+
+        if !r.mul(&BIG::new_ints(&CURVE_ORDER)).is_infinity() {
+            return Err(IndyCryptoError::InvalidStructure(
+                "PointG2 is not in the prime order subgroup".to_string()));
+        }
+
+        Ok(PointG2 {
+            point: point
+        })
+    }
+}
+
+impl Debug for PointG2 {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "PointG2 {{ point: {} }}", self.point.to_hex())
+    }
+}
+
+#[cfg(feature = "serialization")]
+impl Serialize for PointG2 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        serializer.serialize_newtype_struct("PointG2", &self.to_string().map_err(SError::custom)?)
+    }
+}
+
+#[cfg(feature = "serialization")]
+impl<'a> Deserialize<'a> for PointG2 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'a> {
+        struct PointG2Visitor;
+
+        impl<'a> Visitor<'a> for PointG2Visitor {
+            type Value = PointG2;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("expected PointG2")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<PointG2, E>
+                where E: DError
+            {
+                Ok(PointG2::from_string(value).map_err(DError::custom)?)
+            }
+        }
+
+        deserializer.deserialize_str(PointG2Visitor)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub struct GroupOrderElement {
+    bn: BIG
+}
+
+impl GroupOrderElement {
+    pub const BYTES_REPR_SIZE: usize = MODBYTES;
+
+    /// Seed length accepted by `new_from_seed`. Kept equal to the BN254 backend seed length
+    /// so seeds produced for one backend are valid for the other.
+    pub const SEED_SIZE: usize = 32;
+
+    pub fn new() -> Result<GroupOrderElement, IndyCryptoError> {
+        // returns random element in 0, ..., GroupOrder-1
+        Ok(GroupOrderElement {
+            bn: random_mod_order()?
+        })
+    }
+
+    pub fn new_from_seed(seed: &[u8]) -> Result<GroupOrderElement, IndyCryptoError> {
+        // returns random element in 0, ..., GroupOrder-1
+        if seed.len() != Self::SEED_SIZE {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Invalid len of seed: expected {}, actual {}", Self::SEED_SIZE, seed.len())));
+        }
+        let mut rng = RAND::new();
+        rng.clean();
+        rng.seed(seed.len(), seed);
+
+        Ok(GroupOrderElement {
+            bn: BIG::randomnum(&BIG::new_ints(&CURVE_ORDER), &mut rng)
+        })
+    }
+
+    /// Creates group order element from big-endian bytes of any length reduced modulo GroupOrder
+    pub fn from_bytes_mod_order(b: &[u8]) -> Result<GroupOrderElement, IndyCryptoError> {
+        Ok(GroupOrderElement {
+            bn: bytes_mod(b, &BIG::new_ints(&CURVE_ORDER))
+        })
+    }
+
+    /// (GroupOrderElement ^ GroupOrderElement) mod GroupOrder
+    pub fn pow_mod(&self, e: &GroupOrderElement) -> Result<GroupOrderElement, IndyCryptoError> {
+        let mut base = self.bn;
+        let mut pow = e.bn;
+        Ok(GroupOrderElement {
+            bn: base.powmod(&mut pow, &BIG::new_ints(&CURVE_ORDER))
+        })
+    }
+
+    /// (GroupOrderElement + GroupOrderElement) mod GroupOrder
+    pub fn add_mod(&self, r: &GroupOrderElement) -> Result<GroupOrderElement, IndyCryptoError> {
+        let mut sum = self.bn;
+        sum.add(&r.bn);
+        sum.rmod(&BIG::new_ints(&CURVE_ORDER));
+        Ok(GroupOrderElement {
+            bn: sum
+        })
+    }
+
+    /// (GroupOrderElement - GroupOrderElement) mod GroupOrder
+    pub fn sub_mod(&self, r: &GroupOrderElement) -> Result<GroupOrderElement, IndyCryptoError> {
+        //need to use modneg if sub is negative
+        let mut diff = self.bn;
+        diff.sub(&r.bn);
+        let mut zero = BIG::new();
+        zero.zero();
+
+        if diff < zero {
+            return Ok(GroupOrderElement {
+                bn: BIG::modneg(&mut diff, &BIG::new_ints(&CURVE_ORDER))
+            });
+        }
+
+        Ok(GroupOrderElement {
+            bn: diff
+        })
+    }
+
+    /// (GroupOrderElement * GroupOrderElement) mod GroupOrder
+    pub fn mul_mod(&self, r: &GroupOrderElement) -> Result<GroupOrderElement, IndyCryptoError> {
+        let mut base = self.bn;
+        let mut r = r.bn;
+        Ok(GroupOrderElement {
+            bn: BIG::modmul(&mut base, &mut r, &BIG::new_ints(&CURVE_ORDER))
+        })
+    }
+
+    /// 1 / GroupOrderElement
+    pub fn inverse(&self) -> Result<GroupOrderElement, IndyCryptoError> {
+        let mut bn = self.bn;
+        bn.invmodp(&BIG::new_ints(&CURVE_ORDER));
+
+        Ok(GroupOrderElement {
+            bn: bn
+        })
+    }
+
+    /// - GroupOrderElement mod GroupOrder
+    pub fn mod_neg(&self) -> Result<GroupOrderElement, IndyCryptoError> {
+        let mut r = self.bn;
+        r = BIG::modneg(&mut r, &BIG::new_ints(&CURVE_ORDER));
+        Ok(GroupOrderElement {
+            bn: r
+        })
+    }
+
+    pub fn to_string(&self) -> Result<String, IndyCryptoError> {
+        let mut bn = self.bn;
+        Ok(bn.to_hex())
+    }
+
+    pub fn from_string(str: &str) -> Result<GroupOrderElement, IndyCryptoError> {
+        Ok(GroupOrderElement {
+            bn: BIG::from_hex(str.to_string())
+        })
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        let mut bn = self.bn;
+        let mut vec = vec![0u8; Self::BYTES_REPR_SIZE];
+        bn.tobytes(&mut vec);
+        Ok(vec)
+    }
+
+    pub fn from_bytes(b: &[u8]) -> Result<GroupOrderElement, IndyCryptoError> {
+        if b.len() > Self::BYTES_REPR_SIZE {
+            return Err(IndyCryptoError::InvalidStructure(
+                "Invalid len of bytes representation".to_string()));
+        }
+        let mut vec = b.to_vec();
+        let len = vec.len();
+        if len < MODBYTES {
+            let diff = MODBYTES - len;
+            let mut result = vec![0; diff];
+            result.append(&mut vec);
+            return Ok(
+                GroupOrderElement {
+                    bn: BIG::frombytes(&result)
+                }
+            );
+        }
+        Ok(
+            GroupOrderElement {
+                bn: BIG::frombytes(b)
+            }
+        )
+    }
+}
+
+impl Debug for GroupOrderElement {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let mut bn = self.bn;
+        write!(f, "GroupOrderElement {{ bn: {} }}", bn.to_hex())
+    }
+}
+
+#[cfg(feature = "serialization")]
+impl Serialize for GroupOrderElement {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        serializer.serialize_newtype_struct("GroupOrderElement", &self.to_string().map_err(SError::custom)?)
+    }
+}
+
+#[cfg(feature = "serialization")]
+impl<'a> Deserialize<'a> for GroupOrderElement {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'a> {
+        struct GroupOrderElementVisitor;
+
+        impl<'a> Visitor<'a> for GroupOrderElementVisitor {
+            type Value = GroupOrderElement;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("expected GroupOrderElement")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<GroupOrderElement, E>
+                where E: DError
+            {
+                Ok(GroupOrderElement::from_string(value).map_err(DError::custom)?)
+            }
+        }
+
+        deserializer.deserialize_str(GroupOrderElementVisitor)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub struct Pair {
+    pair: FP12
+}
+
+impl Pair {
+    pub const BYTES_REPR_SIZE: usize = MODBYTES * 16;
+    /// e(PointG1, PointG2)
+    pub fn pair(p: &PointG1, q: &PointG2) -> Result<Pair, IndyCryptoError> {
+        let mut p_new = *p;
+        let mut q_new = *q;
+        let mut result = fexp(&ate(&mut q_new.point, &mut p_new.point));
+        result.reduce();
+
+        Ok(Pair {
+            pair: result
+        })
+    }
+
+    /// e(PointG1, PointG2) * ... * e(PointG1, PointG2)
+    ///
+    /// Miller loops of adjacent pairs are shared and the final exponentiation is done only once,
+    /// so it is much cheaper than multiplying results of separate `pair` calls.
+    pub fn multi_pair(pairs: &[(&PointG1, &PointG2)]) -> Result<Pair, IndyCryptoError> {
+        if pairs.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("List of pairs is empty")));
+        }
+
+        let mut result = FP12::new_int(1);
+
+        for chunk in pairs.chunks(2) {
+            let (p1, q1) = chunk[0];
+            let mut p1_new = *p1;
+            let mut q1_new = *q1;
+
+            let mut miller = if chunk.len() == 2 {
+                let (p2, q2) = chunk[1];
+                let mut p2_new = *p2;
+                let mut q2_new = *q2;
+                ate2(&mut q1_new.point, &mut p1_new.point, &mut q2_new.point, &mut p2_new.point)
+            } else {
+                ate(&mut q1_new.point, &mut p1_new.point)
+            };
+            result.mul(&mut miller);
+        }
+
+        let mut result = fexp(&result);
+        result.reduce();
+
+        Ok(Pair {
+            pair: result
+        })
+    }
+
+    /// Checks e() == 1
+    pub fn is_unity(&self) -> Result<bool, IndyCryptoError> {
+        let mut r = self.pair;
+        Ok(r.isunity())
+    }
+
+    /// e() * e()
+    pub fn mul(&self, b: &Pair) -> Result<Pair, IndyCryptoError> {
+        let mut base = self.pair;
+        let mut b = b.pair;
+        base.mul(&mut b);
+        base.reduce();
+        Ok(Pair {
+            pair: base
+        })
+    }
+
+    /// e() ^ GroupOrderElement
+    pub fn pow(&self, b: &GroupOrderElement) -> Result<Pair, IndyCryptoError> {
+        let mut base = self.pair;
+        let mut b = b.bn;
+
+        Ok(Pair {
+            pair: gtpow(&mut base, &mut b)
+        })
+    }
+
+    /// 1 / e()
+    pub fn inverse(&self) -> Result<Pair, IndyCryptoError> {
+        let mut r = self.pair;
+        r.conj();
+        Ok(Pair {
+            pair: r
+        })
+    }
+
+    pub fn to_string(&self) -> Result<String, IndyCryptoError> {
+        Ok(self.pair.to_hex())
+    }
+
+    pub fn from_string(str: &str) -> Result<Pair, IndyCryptoError> {
+        Ok(Pair {
+            pair: FP12::from_hex(str.to_string())
+        })
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        let mut r = self.pair;
+        let mut vec = vec![0u8; Self::BYTES_REPR_SIZE];
+        r.tobytes(&mut vec);
+        Ok(vec)
+    }
+}
+
+impl Debug for Pair {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "Pair {{ pair: {} }}", self.pair.to_hex())
+    }
+}
+
+#[cfg(feature = "serialization")]
+impl Serialize for Pair {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        serializer.serialize_newtype_struct("Pair", &self.to_string().map_err(SError::custom)?)
+    }
+}
+
+#[cfg(feature = "serialization")]
+impl<'a> Deserialize<'a> for Pair {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'a> {
+        struct PairVisitor;
+
+        impl<'a> Visitor<'a> for PairVisitor {
+            type Value = Pair;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("expected Pair")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Pair, E>
+                where E: DError
+            {
+                Ok(Pair::from_string(value).map_err(DError::custom)?)
+            }
+        }
+
+        deserializer.deserialize_str(PairVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use errors::ToErrorCode;
+    use errors::ErrorCode;
+
+    #[test]
+    fn group_order_element_new_from_seed_works_for_invalid_seed_len() {
+        let err = GroupOrderElement::new_from_seed(&[0, 1, 2]).unwrap_err();
+        assert_eq!(err.to_error_code(), ErrorCode::CommonInvalidStructure);
+    }
+
+    #[test]
+    fn group_order_element_from_bytes_mod_order_works() {
+        // group order + 5 padded to 48 bytes
+        let bytes = [
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0x73, 0xed, 0xa7, 0x53, 0x29, 0x9d, 0x7d, 0x48, 0x33, 0x39, 0xd8, 0x08, 0x09, 0xa1, 0xd8, 0x05,
+            0x53, 0xbd, 0xa4, 0x02, 0xff, 0xfe, 0x5b, 0xfe, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x06
+        ];
+        let element = GroupOrderElement::from_bytes_mod_order(&bytes).unwrap();
+        assert_eq!(GroupOrderElement::from_bytes(&[5]).unwrap().to_bytes().unwrap(), element.to_bytes().unwrap());
+    }
+
+    #[test]
+    fn point_g2_from_hash_works() {
+        let point1 = PointG2::from_hash(&[1u8; 32]).unwrap();
+        let point2 = PointG2::from_hash(&[1u8; 32]).unwrap();
+        let point3 = PointG2::from_hash(&[2u8; 32]).unwrap();
+
+        assert_eq!(point1.to_bytes().unwrap(), point2.to_bytes().unwrap());
+        assert_ne!(point1.to_bytes().unwrap(), point3.to_bytes().unwrap());
+        assert!(PointG2::from_hash(&[1u8; PointG2::BYTES_REPR_SIZE]).is_err());
+    }
+
+    #[test]
+    fn pairing_definition_bilinearity() {
+        let a = GroupOrderElement::new().unwrap();
+        let b = GroupOrderElement::new().unwrap();
+        let p = PointG1::new().unwrap();
+        let q = PointG2::new().unwrap();
+        let left = Pair::pair(&p.mul(&a).unwrap(), &q.mul(&b).unwrap()).unwrap();
+        let right = Pair::pair(&p, &q).unwrap().pow(&a.mul_mod(&b).unwrap()).unwrap();
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn point_g1_infinity_test() {
+        let p = PointG1::new_inf().unwrap();
+        let q = PointG1::new().unwrap();
+        let result = p.add(&q).unwrap();
+        assert_eq!(q, result);
+    }
+
+    #[test]
+    fn point_g1_infinity_test2() {
+        let p = PointG1::new().unwrap();
+        let inf = p.sub(&p).unwrap();
+        let q = PointG1::new().unwrap();
+        let result = inf.add(&q).unwrap();
+        assert_eq!(q, result);
+    }
+
+    #[test]
+    fn point_g1_from_bytes_compressed_to_bytes_compressed_works() {
+        for _ in 0..10 {
+            let p = PointG1::new().unwrap();
+            let bytes = p.to_bytes_compressed().unwrap();
+            assert_eq!(bytes.len(), PointG1::BYTES_REPR_COMPRESSED_SIZE);
+            assert_eq!(p, PointG1::from_bytes_compressed(&bytes).unwrap());
+            assert_eq!(p.neg().unwrap(), PointG1::from_bytes_compressed(&p.neg().unwrap().to_bytes_compressed().unwrap()).unwrap());
+        }
+    }
+
+    #[test]
+    fn point_g1_from_bytes_compressed_to_bytes_compressed_works_for_infinity() {
+        let inf = PointG1::new_inf().unwrap();
+        let bytes = inf.to_bytes_compressed().unwrap();
+        assert!(bytes.iter().all(|b| *b == 0));
+        assert!(PointG1::from_bytes_compressed(&bytes).unwrap().is_inf().unwrap());
+    }
+
+    fn _in_subgroup_g1(p: &PointG1) -> bool {
+        let mut r = p.point;
+        r.mul(&BIG::new_ints(&CURVE_ORDER)).is_infinity()
+    }
+
+    fn _not_in_subgroup_g1() -> PointG1 {
+        let mut x = BIG::new_int(1);
+        loop {
+            let point = PointG1 { point: ECP::new_big(&x) };
+            if !point.is_inf().unwrap() && !_in_subgroup_g1(&point) {
+                return point;
+            }
+            x.inc(1);
+        }
+    }
+
+    #[test]
+    fn point_g1_from_hash_works() {
+        for i in 0..10u8 {
+            assert!(_in_subgroup_g1(&PointG1::from_hash(&[i; 32]).unwrap()));
+        }
+    }
+
+    #[test]
+    fn point_g1_from_bytes_works_for_point_out_of_subgroup() {
+        let point = _not_in_subgroup_g1();
+        assert!(PointG1::from_bytes(&point.to_bytes().unwrap()).is_err());
+        assert!(PointG1::from_bytes_compressed(&point.to_bytes_compressed().unwrap()).is_err());
+        assert!(PointG1::from_string(&point.to_string().unwrap()).is_err());
+    }
+
+    #[test]
+    fn point_g2_from_hash_works_for_subgroup() {
+        for i in 0..3u8 {
+            let mut point = PointG2::from_hash(&[i; 32]).unwrap().point;
+            assert!(point.mul(&BIG::new_ints(&CURVE_ORDER)).is_infinity());
+        }
+    }
+
+    #[test]
+    fn point_g1_from_bytes_compressed_works_for_invalid_bytes() {
+        let mut bytes = PointG1::new().unwrap().to_bytes_compressed().unwrap();
+        assert!(PointG1::from_bytes_compressed(&bytes[1..]).is_err());
+
+        bytes[0] = 0x04;
+        assert!(PointG1::from_bytes_compressed(&bytes).is_err());
+
+        let mut bytes = vec![0xffu8; PointG1::BYTES_REPR_COMPRESSED_SIZE];
+        bytes[0] = 0x02;
+        assert!(PointG1::from_bytes_compressed(&bytes).is_err());
+    }
+
+    #[test]
+    fn point_g2_infinity_test() {
+        let p = PointG2::new_inf().unwrap();
+        let q = PointG2::new().unwrap();
+        let result = p.add(&q).unwrap();
+        assert_eq!(q, result);
+    }
+
+    #[test]
+    fn inverse_for_pairing() {
+        let p1 = PointG1::new().unwrap();
+        let q1 = PointG2::new().unwrap();
+        let p2 = PointG1::new().unwrap();
+        let q2 = PointG2::new().unwrap();
+        let pair1 = Pair::pair(&p1, &q1).unwrap();
+        let pair2 = Pair::pair(&p2, &q2).unwrap();
+        let pair_result = pair1.mul(&pair2).unwrap();
+        let pair3 = pair_result.mul(&pair1.inverse().unwrap()).unwrap();
+        assert_eq!(pair2, pair3);
+    }
+
+    #[test]
+    fn multi_pair_works() {
+        let p1 = PointG1::new().unwrap();
+        let q1 = PointG2::new().unwrap();
+        let p2 = PointG1::new().unwrap();
+        let q2 = PointG2::new().unwrap();
+        let expected = Pair::pair(&p1, &q1).unwrap().mul(&Pair::pair(&p2, &q2).unwrap()).unwrap();
+        let result = Pair::multi_pair(&[(&p1, &q1), (&p2, &q2)]).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn multi_pair_works_for_odd_number_of_pairs() {
+        let p1 = PointG1::new().unwrap();
+        let q1 = PointG2::new().unwrap();
+        let p2 = PointG1::new().unwrap();
+        let q2 = PointG2::new().unwrap();
+        let p3 = PointG1::new().unwrap();
+        let q3 = PointG2::new().unwrap();
+        let expected = Pair::pair(&p1, &q1).unwrap()
+            .mul(&Pair::pair(&p2, &q2).unwrap()).unwrap()
+            .mul(&Pair::pair(&p3, &q3).unwrap()).unwrap();
+        let result = Pair::multi_pair(&[(&p1, &q1), (&p2, &q2), (&p3, &q3)]).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn multi_pair_works_for_empty_list() {
+        assert!(Pair::multi_pair(&[]).is_err());
+    }
+
+    #[test]
+    fn is_unity_works() {
+        let p = PointG1::new().unwrap();
+        let q = PointG2::new().unwrap();
+        let pair = Pair::pair(&p, &q).unwrap();
+        assert!(!pair.is_unity().unwrap());
+        assert!(pair.mul(&pair.inverse().unwrap()).unwrap().is_unity().unwrap());
+    }
+
+    // RFC 9380, appendix K.1
+    #[test]
+    fn expand_message_xmd_works() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        let vectors: [(&[u8], &str); 3] = [
+            (&b""[..], "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"),
+            (&b"abc"[..], "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"),
+            (&b"abcdef0123456789"[..], "eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1")
+        ];
+
+        for &(msg, expected) in vectors.iter() {
+            let uniform_bytes = expand_message_xmd(msg, dst, 0x20).unwrap();
+            let hex: String = uniform_bytes.iter().map(|b| format!("{:02x}", b)).collect();
+            assert_eq!(expected, hex);
+        }
+    }
+
+    // RFC 9380, appendix J.9.1
+    #[test]
+    fn hash_to_curve_works_for_empty_message() {
+        let point = PointG1::hash_to_curve(b"", b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_").unwrap();
+        let expected = PointG1 {
+            point: ECP::new_bigs(&fp_from_hex("052926add2207b76ca4fa57a8734416c8dc95e24501772c814278700eed6d1e4e8cf62d9c09db0fac349612b759e79a1"),
+                                 &fp_from_hex("08ba738453bfed09cb546dbb0783dbb3a5f1f566ed67bb6be0e8c67e2e81a4cc68ee29813bb7994998f3eae0c9c6a265"))
+        };
+        assert_eq!(expected, point);
+    }
+
+    #[test]
+    fn hash_to_curve_works_for_abc() {
+        let point = PointG1::hash_to_curve(b"abc", b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_").unwrap();
+        let expected = PointG1 {
+            point: ECP::new_bigs(&fp_from_hex("03567bc5ef9c690c2ab2ecdf6a96ef1c139cc0b2f284dca0a9a7943388a49a3aee664ba5379a7655d3c68900be2f6903"),
+                                 &fp_from_hex("0b9c15f3fe6e5cf4211f346271d7b01c8f3b28be689c8429c85b67af215533311f0b8dfaaa154fa6b88176c229f2885d"))
+        };
+        assert_eq!(expected, point);
+    }
+
+    #[test]
+    fn hash_to_curve_works_for_abcdef0123456789() {
+        let point = PointG1::hash_to_curve(b"abcdef0123456789", b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_").unwrap();
+        let expected = PointG1 {
+            point: ECP::new_bigs(&fp_from_hex("11e0b079dea29a68f0383ee94fed1b940995272407e3bb916bbf268c263ddd57a6a27200a784cbc248e84f357ce82d98"),
+                                 &fp_from_hex("03a87ae2caf14e8ee52e51fa2ed8eefe80f02457004ba4d486d6aa1f517c0889501dc7413753f9599b099ebcbbd2d709"))
+        };
+        assert_eq!(expected, point);
+    }
+}
+
+#[cfg(feature = "serialization")]
+#[cfg(test)]
+mod serialization_tests {
+    use super::*;
+
+    extern crate serde_json;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct TestGroupOrderElementStructure {
+        field: GroupOrderElement
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct TestPointG1Structure {
+        field: PointG1
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct TestPointG2Structure {
+        field: PointG2
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct TestPairStructure {
+        field: Pair
+    }
+
+    #[test]
+    fn from_bytes_to_bytes_works_for_group_order_element() {
+        let element = GroupOrderElement::new().unwrap();
+        let bytes = element.to_bytes().unwrap();
+        assert_eq!(bytes.len(), GroupOrderElement::BYTES_REPR_SIZE);
+        assert_eq!(element, GroupOrderElement::from_bytes(&bytes).unwrap());
+    }
+
+    #[test]
+    fn from_bytes_to_bytes_works_for_points() {
+        let p = PointG1::new().unwrap();
+        assert_eq!(p, PointG1::from_bytes(&p.to_bytes().unwrap()).unwrap());
+
+        let q = PointG2::new().unwrap();
+        assert_eq!(q, PointG2::from_bytes(&q.to_bytes().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn serialize_deserialize_works_for_group_order_element() {
+        let structure = TestGroupOrderElementStructure {
+            field: GroupOrderElement::new().unwrap()
+        };
+        let deserialized: TestGroupOrderElementStructure = serde_json::from_str(&serde_json::to_string(&structure).unwrap()).unwrap();
+
+        assert_eq!(structure, deserialized);
+    }
+
+    #[test]
+    fn serialize_deserialize_works_for_point_g1() {
+        let structure = TestPointG1Structure {
+            field: PointG1::new().unwrap()
+        };
+        let deserialized: TestPointG1Structure = serde_json::from_str(&serde_json::to_string(&structure).unwrap()).unwrap();
+
+        assert_eq!(structure, deserialized);
+    }
+
+    #[test]
+    fn serialize_deserialize_works_for_point_g2() {
+        let structure = TestPointG2Structure {
+            field: PointG2::new().unwrap()
+        };
+        let deserialized: TestPointG2Structure = serde_json::from_str(&serde_json::to_string(&structure).unwrap()).unwrap();
+
+        assert_eq!(structure, deserialized);
+    }
+
+    #[test]
+    fn serialize_deserialize_works_for_pair() {
+        let pair = TestPairStructure {
+            field: Pair::pair(&PointG1::new().unwrap(), &PointG2::new().unwrap()).unwrap()
+        };
+        let deserialized: TestPairStructure = serde_json::from_str(&serde_json::to_string(&pair).unwrap()).unwrap();
+
+        assert_eq!(pair, deserialized);
+    }
+}
@@ -129,6 +129,18 @@ pub fn blsMultiSignature(signatures: Vec<JsValue>) -> Result<JsValue, JsValue> {
     Ok(JsValue::from_serde(&ms).unwrap())
 }
 
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsMultiSignatureBdn(signatures: Vec<JsValue>, verKeys: Vec<JsValue>) -> Result<JsValue, JsValue> {
+    let sigs: Vec<bls::Signature> = signatures.iter().map(|x| convert_from_js(x)).collect::<Result<Vec<_>, _>>()?;
+    let vks: Vec<bls::VerKey> = verKeys.iter().map(|x| convert_from_js(x)).collect::<Result<Vec<_>, _>>()?;
+    let ms = bls::MultiSignature::new_bdn(
+        sigs.iter().collect::<Vec<_>>().as_slice(),
+        vks.iter().collect::<Vec<_>>().as_slice(),
+    )?;
+    Ok(JsValue::from_serde(&ms).unwrap())
+}
+
 #[wasm_bindgen]
 #[allow(non_snake_case)]
 pub fn blsMultiSignatureAsBytes(multiSignature: &JsValue) -> Result<Vec<u8>, JsValue> {
@@ -194,6 +206,59 @@ pub fn blsVerifyMultiSig(
     )?)
 }
 
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsVerifyMultiSigBdn(
+    multiSig: &JsValue,
+    message: &[u8],
+    verKeys: Vec<JsValue>,
+    generator: &JsValue,
+) -> Result<bool, JsValue> {
+    let ms: bls::MultiSignature = convert_from_js(multiSig)?;
+    let vks: Vec<bls::VerKey> = verKeys
+        .iter()
+        .map(|x| convert_from_js(x))
+        .collect::<Result<Vec<_>, _>>()?;
+    let gen: bls::Generator = convert_from_js(generator)?;
+    Ok(bls::Bls::verify_multi_sig_bdn(
+        &ms,
+        message,
+        vks.iter().collect::<Vec<_>>().as_slice(),
+        &gen,
+    )?)
+}
+
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsVerifyAggregate(
+    multiSig: &JsValue,
+    messages: Vec<JsValue>,
+    verKeys: Vec<JsValue>,
+    generator: &JsValue,
+) -> Result<bool, JsValue> {
+    let ms: bls::MultiSignature = convert_from_js(multiSig)?;
+    let msgs: Vec<Vec<u8>> = messages
+        .iter()
+        .map(|x| convert_from_js(x))
+        .collect::<Result<Vec<_>, _>>()?;
+    let vks: Vec<bls::VerKey> = verKeys
+        .iter()
+        .map(|x| convert_from_js(x))
+        .collect::<Result<Vec<_>, _>>()?;
+    if msgs.len() != vks.len() {
+        return Err(IndyCryptoError::InvalidStructure(
+            "Number of messages and verification keys must be equal".to_string(),
+        ).into());
+    }
+    let gen: bls::Generator = convert_from_js(generator)?;
+    let msgs_vks: Vec<(&[u8], &bls::VerKey)> = msgs
+        .iter()
+        .map(|msg| msg.as_slice())
+        .zip(vks.iter())
+        .collect();
+    Ok(bls::Bls::verify_aggregate(&ms, &msgs_vks, &gen)?)
+}
+
 #[wasm_bindgen]
 #[allow(non_snake_case)]
 pub fn blsSignatureAsBytes(signature: &JsValue) -> Result<Vec<u8>, JsValue> {
@@ -2,8 +2,12 @@
 extern crate serde_derive;
 extern crate serde_json;
 extern crate indy_crypto;
+#[macro_use]
+extern crate lazy_static;
 
-use indy_crypto::cl::{new_nonce, Witness, RevocationRegistry, RevocationRegistryDelta, SimpleTailsAccessor};
+use indy_crypto::cl::auditor::Auditor;
+use indy_crypto::cl::encoding::AttributeValue;
+use indy_crypto::cl::{new_nonce, AttributeEquality, Proof, Witness, RevocationRegistry, RevocationRegistryDelta, SimpleTailsAccessor, VerifiableEncryption};
 use indy_crypto::cl::issuer::Issuer;
 use indy_crypto::cl::prover::Prover;
 use indy_crypto::cl::verifier::Verifier;
@@ -2840,198 +2844,1516 @@ mod test {
     }
 
     #[test]
-    fn proof_verifier_add_sub_proof_request_works_for_credential_schema_not_satisfied_to_sub_proof_request() {
+    fn anoncreds_works_for_ne_predicate() {
         IndyCryptoDefaultLogger::init(None).ok();
 
-        // 1. Issuer creates credential schema
         let credential_schema = helpers::gvt_credential_schema();
         let non_credential_schema = helpers::non_credential_schema();
+        let credential_values = helpers::gvt_credential_values(&Prover::new_master_secret().unwrap());
 
-        // 2. Issuer creates credential definition
-        let (credential_pub_key, _, _) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
+        let (credential_pub_key, credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &credential_values).unwrap();
 
-        // 3. Verifier build proof verifier
-        let sub_proof_request = helpers::gvt_sub_proof_request();
-        let xyz_credential_schema = helpers::xyz_credential_schema();
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        sub_proof_request_builder.add_predicate("age", "NE", 30).unwrap();
+        sub_proof_request_builder.add_predicate("height", "NE", -2000000000).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
 
-        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        let nonce = new_nonce().unwrap();
 
-        let res = proof_verifier.add_sub_proof_request(&sub_proof_request,
-                                                       &xyz_credential_schema,
-                                                       &non_credential_schema,
-                                                       &credential_pub_key,
-                                                       None, None);
-        assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err().to_error_code());
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
     }
 
     #[test]
-    fn prover_blind_credential_secrets_works_for_key_correctness_proof_not_correspond_to_public_key() {
+    fn anoncreds_works_for_between_predicate() {
         IndyCryptoDefaultLogger::init(None).ok();
 
-        // 1. Prover creates master secret
-        let master_secret = Prover::new_master_secret().unwrap();
-        let credential_values = helpers::gvt_credential_values(&master_secret);
-
-        // 2. Issuer creates GVT credential definition
-        let gvt_credential_schema = helpers::gvt_credential_schema();
+        let credential_schema = helpers::gvt_credential_schema();
         let non_credential_schema = helpers::non_credential_schema();
-        let (gvt_credential_pub_key, _, _) =
-            Issuer::new_credential_def(&gvt_credential_schema, &non_credential_schema, false).unwrap();
+        let credential_values = helpers::gvt_credential_values(&Prover::new_master_secret().unwrap());
 
-        // 3. Issuer creates XYZ credential definition
-        let xyz_credential_schema = helpers::xyz_credential_schema();
-        let (_, _, xyz_credential_key_correctness_proof) =
-            Issuer::new_credential_def(&xyz_credential_schema, &non_credential_schema, false).unwrap();
+        let (credential_pub_key, credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &credential_values).unwrap();
 
-        // 4. Issuer creates nonce used Prover to blind master secret
-        let gvt_credential_nonce = new_nonce().unwrap();
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        sub_proof_request_builder.add_range_predicate("age", 18, 65).unwrap();
+        sub_proof_request_builder.add_range_predicate("height", 175, 176).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
 
-        // 5. Prover blind master secret by gvt_public_key and xyz_key_correctness_proof
-        let res =
-            Prover::blind_credential_secrets(&gvt_credential_pub_key,
-                                             &xyz_credential_key_correctness_proof,
-                                             &credential_values,
-                                             &gvt_credential_nonce);
-        assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err().to_error_code());
+        let nonce = new_nonce().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
     }
 
     #[test]
-    fn issuer_sign_credential_works_for_prover_used_different_nonce_to_blind_credential_secrets() {
+    fn anoncreds_works_for_big_and_negative_predicate_values() {
         IndyCryptoDefaultLogger::init(None).ok();
 
-        // 1. Issuer creates credential schema
-        let credential_schema = helpers::gvt_credential_schema();
+        let credential_schema = helpers::balance_credential_schema();
         let non_credential_schema = helpers::non_credential_schema();
+        let credential_values = helpers::balance_credential_values(&Prover::new_master_secret().unwrap());
 
-        // 2. Issuer creates credential definition
-        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
+        let (credential_pub_key, credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &credential_values).unwrap();
 
-        // 3. Prover creates master secret
-        let master_secret = Prover::new_master_secret().unwrap();
-        let credential_values = helpers::gvt_credential_values(&master_secret);
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_dec_predicate("balance", "GE", "-123456789012345678901234567890").unwrap();
+        sub_proof_request_builder.add_predicate("balance", "LT", 0).unwrap();
+        sub_proof_request_builder.add_dec_range_predicate("timestamp", "1600000000000", "1800000000000").unwrap();
+        sub_proof_request_builder.add_dec_predicate("timestamp", "NE", "1700000000001").unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
 
-        // 4. Issuer creates nonce used Prover to blind master secret
-        let credential_nonce = new_nonce().unwrap();
+        let nonce = new_nonce().unwrap();
 
-        let other_nonce = new_nonce().unwrap();
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
 
-        // 5. Prover blinds master secret
-        let (blinded_credential_secrets, _, blinded_credential_secrets_correctness_proof) =
-            Prover::blind_credential_secrets(&credential_pub_key, &credential_key_correctness_proof, &credential_values, &other_nonce).unwrap();
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+    }
 
-        // 6. Prover creates nonce used Issuer to credential issue
-        let credential_issuance_nonce = new_nonce().unwrap();
+    #[test]
+    fn proof_builder_add_sub_proof_works_for_credential_not_satisfied_negative_predicate() {
+        IndyCryptoDefaultLogger::init(None).ok();
 
-        // 7. Issuer creates credential values
+        let credential_schema = helpers::balance_credential_schema();
+        let non_credential_schema = helpers::non_credential_schema();
+        let credential_values = helpers::balance_credential_values(&Prover::new_master_secret().unwrap());
 
-        // 8. Issuer signs credential values
-        let res = Issuer::sign_credential(PROVER_ID,
-                                          &blinded_credential_secrets,
-                                          &blinded_credential_secrets_correctness_proof,
-                                          &credential_nonce,
-                                          &credential_issuance_nonce,
-                                          &credential_values,
-                                          &credential_pub_key,
-                                          &credential_priv_key);
+        let (credential_pub_key, credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &credential_values).unwrap();
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_predicate("balance", "GE", -249).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        let res = proof_builder.add_sub_proof_request(&sub_proof_request,
+                                                      &credential_schema,
+                                                      &non_credential_schema,
+                                                      &credential_signature,
+                                                      &credential_values,
+                                                      &credential_pub_key,
+                                                      None,
+                                                      None);
         assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err().to_error_code());
     }
 
     #[test]
-    fn issuer_sign_credential_works_for_keys_not_correspond_to_blinded_credential_secrets_correctness_proof() {
+    fn anoncreds_works_for_set_membership_predicate() {
         IndyCryptoDefaultLogger::init(None).ok();
 
-        // 1. Issuer creates GVT credential definition
         let credential_schema = helpers::gvt_credential_schema();
         let non_credential_schema = helpers::non_credential_schema();
-        let (gvt_credential_pub_key, _, gvt_credential_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
-
-        // 2. Issuer creates XYZ credential definition
-        let credential_schema = helpers::xyz_credential_schema();
-        let (xyz_credential_pub_key, xyz_credential_priv_key, _) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
-
-        // 3. Prover creates master secret
-        let master_secret = Prover::new_master_secret().unwrap();
-        let gvt_credential_values = helpers::gvt_credential_values(&master_secret);
+        let credential_values = helpers::gvt_credential_values(&Prover::new_master_secret().unwrap());
 
-        // 4. Issuer creates nonce used Prover to blind master secret
-        let credential_nonce = new_nonce().unwrap();
+        let (credential_pub_key, credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &credential_values).unwrap();
 
-        // 5. Prover blinds master secret by GVT key
-        let (blinded_credential_secrets, _, blinded_credential_secrets_correctness_proof) =
-            Prover::blind_credential_secrets(&gvt_credential_pub_key, &gvt_credential_key_correctness_proof, &gvt_credential_values, &credential_nonce).unwrap();
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        sub_proof_request_builder.add_set_membership_predicate("age", &["18", "28", "65"]).unwrap();
+        sub_proof_request_builder.add_set_membership_predicate("height", &["175"]).unwrap();
+        sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
 
-        // 6. Prover creates nonce used Issuer to credential issue
-        let credential_issuance_nonce = new_nonce().unwrap();
+        let nonce = new_nonce().unwrap();
 
-        // 7. Issuer creates credential values
-        let xyz_credential_values = helpers::xyz_credential_values(&master_secret);
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
 
-        // 8. Issuer signs XYZ credential values for Prover
-        let res = Issuer::sign_credential(PROVER_ID,
-                                          &blinded_credential_secrets,
-                                          &blinded_credential_secrets_correctness_proof,
-                                          &credential_nonce,
-                                          &credential_issuance_nonce,
-                                          &xyz_credential_values,
-                                          &xyz_credential_pub_key,
-                                          &xyz_credential_priv_key);
-        assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err().to_error_code());
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
     }
 
     #[test]
-    fn issuer_sign_credential_works_for_blinded_credential_secrets_not_correspond_to_blinded_credential_secrets_correctness_proof() {
+    fn proof_builder_add_sub_proof_works_for_credential_not_satisfied_set_membership_predicate() {
         IndyCryptoDefaultLogger::init(None).ok();
 
-        // 1. Issuer creates GVT credential definition
         let credential_schema = helpers::gvt_credential_schema();
         let non_credential_schema = helpers::non_credential_schema();
-        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
-
-        // 2. Prover creates master secret
-        let master_secret = Prover::new_master_secret().unwrap();
-        let credential_values = helpers::gvt_credential_values(&master_secret);
-
-        // 3. Issuer creates nonce used Prover to blind master secret
-        let credential_nonce = new_nonce().unwrap();
-
-        // 4. Prover blinds master secret
-        let (_, _, blinded_credential_secrets_correctness_proof) =
-            Prover::blind_credential_secrets(&credential_pub_key, &credential_key_correctness_proof, &credential_values, &credential_nonce).unwrap();
-
-        // 5. Prover blinds master secret second time
-        let (blinded_credential_secrets, _, _) =
-            Prover::blind_credential_secrets(&credential_pub_key, &credential_key_correctness_proof, &credential_values, &credential_nonce).unwrap();
+        let credential_values = helpers::gvt_credential_values(&Prover::new_master_secret().unwrap());
 
-        // 6. Prover creates nonce used Issuer to credential issue
-        let credential_issuance_nonce = new_nonce().unwrap();
+        let (credential_pub_key, credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &credential_values).unwrap();
 
-        // 7. Issuer creates credential values
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_set_membership_predicate("age", &["18", "27", "29"]).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
 
-        // 8. Issuer signs credential values for Prover
-        let res = Issuer::sign_credential(PROVER_ID,
-                                          &blinded_credential_secrets,
-                                          &blinded_credential_secrets_correctness_proof,
-                                          &credential_nonce,
-                                          &credential_issuance_nonce,
-                                          &credential_values,
-                                          &credential_pub_key,
-                                          &credential_priv_key);
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        let res = proof_builder.add_sub_proof_request(&sub_proof_request,
+                                                      &credential_schema,
+                                                      &non_credential_schema,
+                                                      &credential_signature,
+                                                      &credential_values,
+                                                      &credential_pub_key,
+                                                      None,
+                                                      None);
         assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err().to_error_code());
     }
 
     #[test]
-    fn prover_process_credential_signature_works_for_issuer_used_different_nonce() {
+    fn anoncreds_works_for_attribute_equality_across_credentials() {
         IndyCryptoDefaultLogger::init(None).ok();
 
-        // 1. Issuer creates credential schema
         let credential_schema = helpers::gvt_credential_schema();
         let non_credential_schema = helpers::non_credential_schema();
+        let master_secret = Prover::new_master_secret().unwrap();
 
-        // 2. Issuer creates credential definition
-        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
+        // Credentials are issued by different issuers
+        let first_credential_values = helpers::gvt_credential_values(&master_secret);
+        let (first_credential_pub_key, first_credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &first_credential_values).unwrap();
 
-        // 3. Prover creates master secret
-        let master_secret = Prover::new_master_secret().unwrap();
+        let second_credential_values = helpers::gvt_credential_values(&master_secret);
+        let (second_credential_pub_key, second_credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &second_credential_values).unwrap();
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+        let attr_equality = AttributeEquality::new(0, "name", 1, "name").unwrap();
+
+        let nonce = new_nonce().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_attribute_equality(&attr_equality).unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &first_credential_signature,
+                                            &first_credential_values,
+                                            &first_credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &second_credential_signature,
+                                            &second_credential_values,
+                                            &second_credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &first_credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &second_credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        proof_verifier.add_attribute_equality(&attr_equality).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+    }
+
+    #[test]
+    fn anoncreds_works_for_attribute_equality_across_credentials_with_different_values() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        let credential_schema = helpers::gvt_credential_schema();
+        let non_credential_schema = helpers::non_credential_schema();
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        // Credentials are issued by different issuers
+        let first_credential_values = helpers::gvt_credential_values(&master_secret);
+        let (first_credential_pub_key, first_credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &first_credential_values).unwrap();
+
+        let second_credential_values = helpers::gvt_other_name_credential_values(&master_secret);
+        let (second_credential_pub_key, second_credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &second_credential_values).unwrap();
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+        let attr_equality = AttributeEquality::new(0, "name", 1, "name").unwrap();
+
+        let nonce = new_nonce().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_attribute_equality(&attr_equality).unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &first_credential_signature,
+                                            &first_credential_values,
+                                            &first_credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &second_credential_signature,
+                                            &second_credential_values,
+                                            &second_credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &first_credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &second_credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        proof_verifier.add_attribute_equality(&attr_equality).unwrap();
+
+        let res = proof_verifier.verify(&proof, &nonce);
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn anoncreds_works_for_attribute_equality_not_added_by_prover() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        let credential_schema = helpers::gvt_credential_schema();
+        let non_credential_schema = helpers::non_credential_schema();
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        // Credentials are issued by different issuers
+        let first_credential_values = helpers::gvt_credential_values(&master_secret);
+        let (first_credential_pub_key, first_credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &first_credential_values).unwrap();
+
+        let second_credential_values = helpers::gvt_credential_values(&master_secret);
+        let (second_credential_pub_key, second_credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &second_credential_values).unwrap();
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+        let attr_equality = AttributeEquality::new(0, "name", 1, "name").unwrap();
+
+        let nonce = new_nonce().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &first_credential_signature,
+                                            &first_credential_values,
+                                            &first_credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &second_credential_signature,
+                                            &second_credential_values,
+                                            &second_credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &first_credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &second_credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        proof_verifier.add_attribute_equality(&attr_equality).unwrap();
+
+        let res = proof_verifier.verify(&proof, &nonce);
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn anoncreds_works_for_linear_predicates() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        let non_credential_schema = helpers::non_credential_schema();
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        let gvt_credential_schema = helpers::gvt_credential_schema();
+        let gvt_credential_values = helpers::gvt_credential_values(&master_secret);
+        let (gvt_credential_pub_key, gvt_credential_signature) =
+            helpers::issue_credential(&gvt_credential_schema, &non_credential_schema, &gvt_credential_values).unwrap();
+        let gvt_sub_proof_request = helpers::gvt_sub_proof_request();
+
+        let xyz_credential_schema = helpers::xyz_credential_schema();
+        let xyz_credential_values = helpers::xyz_credential_values(&master_secret);
+        let (xyz_credential_pub_key, xyz_credential_signature) =
+            helpers::issue_credential(&xyz_credential_schema, &non_credential_schema, &xyz_credential_values).unwrap();
+        let xyz_sub_proof_request = helpers::xyz_sub_proof_request();
+
+        // GVT.height > 6 * GVT.age
+        let height_linear_predicate = helpers::linear_predicate(&[(0, "height", 1), (0, "age", -6)], "GT", 0);
+        // GVT.age - 3 * XYZ.period >= 4
+        let age_linear_predicate = helpers::linear_predicate(&[(0, "age", 1), (1, "period", -3)], "GE", 4);
+        // XYZ.period < GVT.age
+        let period_linear_predicate = helpers::linear_predicate(&[(1, "period", 1), (0, "age", -1)], "LT", 0);
+
+        let nonce = new_nonce().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_linear_predicate(&height_linear_predicate).unwrap();
+        proof_builder.add_linear_predicate(&age_linear_predicate).unwrap();
+        proof_builder.add_linear_predicate(&period_linear_predicate).unwrap();
+        proof_builder.add_sub_proof_request(&gvt_sub_proof_request,
+                                            &gvt_credential_schema,
+                                            &non_credential_schema,
+                                            &gvt_credential_signature,
+                                            &gvt_credential_values,
+                                            &gvt_credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        proof_builder.add_sub_proof_request(&xyz_sub_proof_request,
+                                            &xyz_credential_schema,
+                                            &non_credential_schema,
+                                            &xyz_credential_signature,
+                                            &xyz_credential_values,
+                                            &xyz_credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&gvt_sub_proof_request,
+                                             &gvt_credential_schema,
+                                             &non_credential_schema,
+                                             &gvt_credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        proof_verifier.add_sub_proof_request(&xyz_sub_proof_request,
+                                             &xyz_credential_schema,
+                                             &non_credential_schema,
+                                             &xyz_credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        proof_verifier.add_linear_predicate(&height_linear_predicate).unwrap();
+        proof_verifier.add_linear_predicate(&age_linear_predicate).unwrap();
+        proof_verifier.add_linear_predicate(&period_linear_predicate).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+    }
+
+    #[test]
+    fn proof_builder_finalize_works_for_credentials_not_satisfied_linear_predicate() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        let non_credential_schema = helpers::non_credential_schema();
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        let gvt_credential_schema = helpers::gvt_credential_schema();
+        let gvt_credential_values = helpers::gvt_credential_values(&master_secret);
+        let (gvt_credential_pub_key, gvt_credential_signature) =
+            helpers::issue_credential(&gvt_credential_schema, &non_credential_schema, &gvt_credential_values).unwrap();
+        let gvt_sub_proof_request = helpers::gvt_sub_proof_request();
+
+        let xyz_credential_schema = helpers::xyz_credential_schema();
+        let xyz_credential_values = helpers::xyz_credential_values(&master_secret);
+        let (xyz_credential_pub_key, xyz_credential_signature) =
+            helpers::issue_credential(&xyz_credential_schema, &non_credential_schema, &xyz_credential_values).unwrap();
+        let xyz_sub_proof_request = helpers::xyz_sub_proof_request();
+
+        let linear_predicate = helpers::linear_predicate(&[(0, "age", 1), (1, "period", -3)], "GT", 4);
+
+        let nonce = new_nonce().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_linear_predicate(&linear_predicate).unwrap();
+        proof_builder.add_sub_proof_request(&gvt_sub_proof_request,
+                                            &gvt_credential_schema,
+                                            &non_credential_schema,
+                                            &gvt_credential_signature,
+                                            &gvt_credential_values,
+                                            &gvt_credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        proof_builder.add_sub_proof_request(&xyz_sub_proof_request,
+                                            &xyz_credential_schema,
+                                            &non_credential_schema,
+                                            &xyz_credential_signature,
+                                            &xyz_credential_values,
+                                            &xyz_credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let res = proof_builder.finalize(&nonce);
+        assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn proof_builder_finalize_works_for_linear_predicate_over_revealed_attribute() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        let non_credential_schema = helpers::non_credential_schema();
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        let gvt_credential_schema = helpers::gvt_credential_schema();
+        let gvt_credential_values = helpers::gvt_credential_values(&master_secret);
+        let (gvt_credential_pub_key, gvt_credential_signature) =
+            helpers::issue_credential(&gvt_credential_schema, &non_credential_schema, &gvt_credential_values).unwrap();
+        let gvt_sub_proof_request = helpers::gvt_sub_proof_request();
+
+        let xyz_credential_schema = helpers::xyz_credential_schema();
+        let xyz_credential_values = helpers::xyz_credential_values(&master_secret);
+        let (xyz_credential_pub_key, xyz_credential_signature) =
+            helpers::issue_credential(&xyz_credential_schema, &non_credential_schema, &xyz_credential_values).unwrap();
+        let xyz_sub_proof_request = helpers::xyz_sub_proof_request();
+
+        let linear_predicate = helpers::linear_predicate(&[(1, "status", 1), (0, "age", -1)], "GE", 0);
+
+        let nonce = new_nonce().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_linear_predicate(&linear_predicate).unwrap();
+        proof_builder.add_sub_proof_request(&gvt_sub_proof_request,
+                                            &gvt_credential_schema,
+                                            &non_credential_schema,
+                                            &gvt_credential_signature,
+                                            &gvt_credential_values,
+                                            &gvt_credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        proof_builder.add_sub_proof_request(&xyz_sub_proof_request,
+                                            &xyz_credential_schema,
+                                            &non_credential_schema,
+                                            &xyz_credential_signature,
+                                            &xyz_credential_values,
+                                            &xyz_credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let res = proof_builder.finalize(&nonce);
+        assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn anoncreds_works_for_linear_predicate_not_proven_by_prover() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        let non_credential_schema = helpers::non_credential_schema();
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        let gvt_credential_schema = helpers::gvt_credential_schema();
+        let gvt_credential_values = helpers::gvt_credential_values(&master_secret);
+        let (gvt_credential_pub_key, gvt_credential_signature) =
+            helpers::issue_credential(&gvt_credential_schema, &non_credential_schema, &gvt_credential_values).unwrap();
+        let gvt_sub_proof_request = helpers::gvt_sub_proof_request();
+
+        let xyz_credential_schema = helpers::xyz_credential_schema();
+        let xyz_credential_values = helpers::xyz_credential_values(&master_secret);
+        let (xyz_credential_pub_key, xyz_credential_signature) =
+            helpers::issue_credential(&xyz_credential_schema, &non_credential_schema, &xyz_credential_values).unwrap();
+        let xyz_sub_proof_request = helpers::xyz_sub_proof_request();
+
+        let proven_linear_predicate = helpers::linear_predicate(&[(0, "age", 1), (1, "period", -3)], "GE", 0);
+        let requested_linear_predicate = helpers::linear_predicate(&[(0, "age", 1), (1, "period", -3)], "GE", 4);
+
+        let nonce = new_nonce().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_linear_predicate(&proven_linear_predicate).unwrap();
+        proof_builder.add_sub_proof_request(&gvt_sub_proof_request,
+                                            &gvt_credential_schema,
+                                            &non_credential_schema,
+                                            &gvt_credential_signature,
+                                            &gvt_credential_values,
+                                            &gvt_credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        proof_builder.add_sub_proof_request(&xyz_sub_proof_request,
+                                            &xyz_credential_schema,
+                                            &non_credential_schema,
+                                            &xyz_credential_signature,
+                                            &xyz_credential_values,
+                                            &xyz_credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&gvt_sub_proof_request,
+                                             &gvt_credential_schema,
+                                             &non_credential_schema,
+                                             &gvt_credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        proof_verifier.add_sub_proof_request(&xyz_sub_proof_request,
+                                             &xyz_credential_schema,
+                                             &non_credential_schema,
+                                             &xyz_credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        proof_verifier.add_linear_predicate(&requested_linear_predicate).unwrap();
+        let res = proof_verifier.verify(&proof, &nonce);
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+
+        // Linear predicate is not proven at all
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_sub_proof_request(&gvt_sub_proof_request,
+                                            &gvt_credential_schema,
+                                            &non_credential_schema,
+                                            &gvt_credential_signature,
+                                            &gvt_credential_values,
+                                            &gvt_credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        proof_builder.add_sub_proof_request(&xyz_sub_proof_request,
+                                            &xyz_credential_schema,
+                                            &non_credential_schema,
+                                            &xyz_credential_signature,
+                                            &xyz_credential_values,
+                                            &xyz_credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&gvt_sub_proof_request,
+                                             &gvt_credential_schema,
+                                             &non_credential_schema,
+                                             &gvt_credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        proof_verifier.add_sub_proof_request(&xyz_sub_proof_request,
+                                             &xyz_credential_schema,
+                                             &non_credential_schema,
+                                             &xyz_credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        proof_verifier.add_linear_predicate(&requested_linear_predicate).unwrap();
+        let res = proof_verifier.verify(&proof, &nonce);
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn anoncreds_works_for_typed_attribute_values() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        let credential_schema = helpers::passport_credential_schema();
+        let non_credential_schema = helpers::non_credential_schema();
+        let credential_values = helpers::passport_credential_values(&Prover::new_master_secret().unwrap());
+
+        let (credential_pub_key, credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &credential_values).unwrap();
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        sub_proof_request_builder.add_revealed_attr("verified").unwrap();
+        sub_proof_request_builder.add_typed_predicate("birth_date", "LT", &AttributeValue::Date("2000-01-01".to_owned())).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let nonce = new_nonce().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        proof_verifier.add_raw_revealed_value(0, "name", &AttributeValue::String("Alex".to_owned())).unwrap();
+        proof_verifier.add_raw_revealed_value(0, "verified", &AttributeValue::Bool(true)).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+    }
+
+    #[test]
+    fn anoncreds_works_for_raw_revealed_value_not_correspond_to_proof() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        let credential_schema = helpers::passport_credential_schema();
+        let non_credential_schema = helpers::non_credential_schema();
+        let credential_values = helpers::passport_credential_values(&Prover::new_master_secret().unwrap());
+
+        let (credential_pub_key, credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &credential_values).unwrap();
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        sub_proof_request_builder.add_revealed_attr("verified").unwrap();
+        sub_proof_request_builder.add_typed_predicate("birth_date", "LT", &AttributeValue::Date("2000-01-01".to_owned())).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let nonce = new_nonce().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        proof_verifier.add_raw_revealed_value(0, "name", &AttributeValue::String("Alexander".to_owned())).unwrap();
+        let res = proof_verifier.verify(&proof, &nonce);
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+
+        // Encoding of boolean never collides with encoding of integer
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        proof_verifier.add_raw_revealed_value(0, "verified", &AttributeValue::from_i64(1).unwrap()).unwrap();
+        let res = proof_verifier.verify(&proof, &nonce);
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+
+        // Birth date is not revealed
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        proof_verifier.add_raw_revealed_value(0, "birth_date", &AttributeValue::Date("1990-05-17".to_owned())).unwrap();
+        let res = proof_verifier.verify(&proof, &nonce);
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn sub_proof_request_builder_add_typed_predicate_works_for_string_value() {
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        let res = sub_proof_request_builder.add_typed_predicate("name", "GE", &AttributeValue::String("Alex".to_owned()));
+        assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn anoncreds_works_for_pseudonym() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        let credential_schema = helpers::gvt_credential_schema();
+        let non_credential_schema = helpers::non_credential_schema();
+        let master_secret = Prover::new_master_secret().unwrap();
+        let other_master_secret = Prover::new_master_secret().unwrap();
+
+        // Prover has two credentials of the same master secret issued by different issuers
+        let credential_values = helpers::gvt_credential_values(&master_secret);
+        let (credential_pub_key, credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &credential_values).unwrap();
+
+        let second_credential_values = helpers::gvt_credential_values(&master_secret);
+        let (second_credential_pub_key, second_credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &second_credential_values).unwrap();
+
+        let other_prover_credential_values = helpers::gvt_credential_values(&other_master_secret);
+        let (other_prover_credential_pub_key, other_prover_credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &other_prover_credential_values).unwrap();
+
+        let sub_proof_request = helpers::gvt_sub_proof_request_with_pseudonym(Some("example.com"));
+        let nonce = new_nonce().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        let pseudonym = proof_builder.add_sub_proof_request(&sub_proof_request,
+                                                            &credential_schema,
+                                                            &non_credential_schema,
+                                                            &credential_signature,
+                                                            &credential_values,
+                                                            &credential_pub_key,
+                                                            None,
+                                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        let pseudonyms = proof_verifier.verify_with_pseudonyms(&proof, &nonce, None).unwrap().unwrap();
+        assert_eq!(1, pseudonyms.len());
+        assert_eq!("example.com", pseudonyms[0].scope());
+        assert_eq!(pseudonym.as_ref(), pseudonyms.first());
+
+        // Pseudonym is the same in proofs of other credentials for the same scope
+        let nonce = new_nonce().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &second_credential_signature,
+                                            &second_credential_values,
+                                            &second_credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &second_credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        let same_scope_pseudonyms = proof_verifier.verify_with_pseudonyms(&proof, &nonce, None).unwrap().unwrap();
+        assert_eq!(pseudonyms, same_scope_pseudonyms);
+
+        // Pseudonyms for different scopes are unlinkable
+        let sub_proof_request = helpers::gvt_sub_proof_request_with_pseudonym(Some("example.org"));
+        let nonce = new_nonce().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        let other_scope_pseudonym = proof_builder.add_sub_proof_request(&sub_proof_request,
+                                                                        &credential_schema,
+                                                                        &non_credential_schema,
+                                                                        &credential_signature,
+                                                                        &credential_values,
+                                                                        &credential_pub_key,
+                                                                        None,
+                                                                        None).unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        let other_scope_pseudonyms = proof_verifier.verify_with_pseudonyms(&proof, &nonce, None).unwrap().unwrap();
+        assert_eq!("example.org", other_scope_pseudonyms[0].scope());
+        assert_eq!(other_scope_pseudonym.as_ref(), other_scope_pseudonyms.first());
+        assert_ne!(pseudonyms[0].nym().unwrap(), other_scope_pseudonyms[0].nym().unwrap());
+
+        // Pseudonyms of different provers for the same scope are different
+        let sub_proof_request = helpers::gvt_sub_proof_request_with_pseudonym(Some("example.com"));
+        let nonce = new_nonce().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &other_prover_credential_signature,
+                                            &other_prover_credential_values,
+                                            &other_prover_credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &other_prover_credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        let other_prover_pseudonyms = proof_verifier.verify_with_pseudonyms(&proof, &nonce, None).unwrap().unwrap();
+        assert_ne!(pseudonyms[0].nym().unwrap(), other_prover_pseudonyms[0].nym().unwrap());
+    }
+
+    #[test]
+    fn anoncreds_works_for_pseudonym_not_correspond_to_verifier_scope() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        let credential_schema = helpers::gvt_credential_schema();
+        let non_credential_schema = helpers::non_credential_schema();
+        let credential_values = helpers::gvt_credential_values(&Prover::new_master_secret().unwrap());
+
+        let (credential_pub_key, credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &credential_values).unwrap();
+
+        let prover_sub_proof_request = helpers::gvt_sub_proof_request_with_pseudonym(Some("example.org"));
+        let verifier_sub_proof_request = helpers::gvt_sub_proof_request_with_pseudonym(Some("example.com"));
+        let nonce = new_nonce().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        let pseudonym = proof_builder.add_sub_proof_request(&prover_sub_proof_request,
+                                                            &credential_schema,
+                                                            &non_credential_schema,
+                                                            &credential_signature,
+                                                            &credential_values,
+                                                            &credential_pub_key,
+                                                            None,
+                                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
+
+        assert_eq!("example.org", pseudonym.unwrap().scope());
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&verifier_sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        let res = proof_verifier.verify_with_pseudonyms(&proof, &nonce, None);
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+
+        // Pseudonym is not requested by prover
+        let prover_sub_proof_request = helpers::gvt_sub_proof_request_with_pseudonym(None);
+        let verifier_sub_proof_request = helpers::gvt_sub_proof_request_with_pseudonym(Some("example.com"));
+        let nonce = new_nonce().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        let pseudonym = proof_builder.add_sub_proof_request(&prover_sub_proof_request,
+                                                            &credential_schema,
+                                                            &non_credential_schema,
+                                                            &credential_signature,
+                                                            &credential_values,
+                                                            &credential_pub_key,
+                                                            None,
+                                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
+
+        assert!(pseudonym.is_none());
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&verifier_sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        let res = proof_verifier.verify_with_pseudonyms(&proof, &nonce, None);
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn anoncreds_works_for_pseudonym_replaced_in_proof() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        let credential_schema = helpers::gvt_credential_schema();
+        let non_credential_schema = helpers::non_credential_schema();
+        let credential_values = helpers::gvt_credential_values(&Prover::new_master_secret().unwrap());
+
+        let (credential_pub_key, credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &credential_values).unwrap();
+
+        let sub_proof_request = helpers::gvt_sub_proof_request_with_pseudonym(Some("example.com"));
+        let nonce = new_nonce().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize_with_message(&nonce, b"document hash").unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+
+        let pseudonyms = proof_verifier.verify_with_pseudonyms(&proof, &nonce, Some(b"document hash")).unwrap().unwrap();
+        assert_eq!(1, pseudonyms.len());
+        assert!(proof_verifier.verify_with_pseudonyms(&proof, &nonce, None).unwrap().is_none());
+
+        // Pseudonym of other prover for the same scope
+        let other_credential_values = helpers::gvt_credential_values(&Prover::new_master_secret().unwrap());
+        let (other_credential_pub_key, other_credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &other_credential_values).unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        let other_pseudonym = proof_builder.add_sub_proof_request(&sub_proof_request,
+                                                                  &credential_schema,
+                                                                  &non_credential_schema,
+                                                                  &other_credential_signature,
+                                                                  &other_credential_values,
+                                                                  &other_credential_pub_key,
+                                                                  None,
+                                                                  None).unwrap().unwrap();
+        assert_ne!(pseudonyms[0].nym().unwrap(), other_pseudonym.nym().unwrap());
+
+        let mut proof_json = serde_json::to_value(&proof).unwrap();
+        proof_json["proofs"][0]["pseudonym"] = serde_json::to_value(&other_pseudonym).unwrap();
+        let forged_proof: Proof = serde_json::from_value(proof_json).unwrap();
+
+        assert!(proof_verifier.verify_with_pseudonyms(&forged_proof, &nonce, Some(b"document hash")).unwrap().is_none());
+    }
+
+    #[test]
+    fn anoncreds_works_for_proof_bound_to_message() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        let credential_schema = helpers::gvt_credential_schema();
+        let non_credential_schema = helpers::non_credential_schema();
+        let credential_values = helpers::gvt_credential_values(&Prover::new_master_secret().unwrap());
+
+        let (credential_pub_key, credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &credential_values).unwrap();
+
+        let sub_proof_request = helpers::gvt_sub_proof_request();
+        let nonce = new_nonce().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize_with_message(&nonce, b"document hash").unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+
+        assert!(proof_verifier.verify_with_message(&proof, &nonce, b"document hash").unwrap());
+        assert!(!proof_verifier.verify_with_message(&proof, &nonce, b"other document hash").unwrap());
+        assert!(!proof_verifier.verify(&proof, &nonce).unwrap());
+    }
+
+    #[test]
+    fn anoncreds_works_for_proof_with_modified_c_list() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        let credential_schema = helpers::gvt_credential_schema();
+        let non_credential_schema = helpers::non_credential_schema();
+        let credential_values = helpers::gvt_credential_values(&Prover::new_master_secret().unwrap());
+
+        let (credential_pub_key, credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &credential_values).unwrap();
+
+        let sub_proof_request = helpers::gvt_sub_proof_request();
+        let nonce = new_nonce().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        // C list values are bound to the proof parts they belong to, so they can't be reordered
+        let mut proof_json = serde_json::to_value(&proof).unwrap();
+        proof_json["aggregated_proof"]["c_list"].as_array_mut().unwrap().reverse();
+        let reordered_proof: Proof = serde_json::from_value(proof_json).unwrap();
+        assert!(!proof_verifier.verify(&reordered_proof, &nonce).unwrap());
+
+        let mut proof_json = serde_json::to_value(&proof).unwrap();
+        proof_json["aggregated_proof"]["c_list"].as_array_mut().unwrap().pop();
+        let truncated_proof: Proof = serde_json::from_value(proof_json).unwrap();
+        assert!(!proof_verifier.verify(&truncated_proof, &nonce).unwrap());
+    }
+
+    #[test]
+    fn anoncreds_works_for_verifiable_encryption() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        let (ref auditor_pub_key, ref auditor_priv_key) = *helpers::AUDITOR_KEYS;
+
+        let credential_schema = helpers::gvt_credential_schema();
+        let non_credential_schema = helpers::non_credential_schema();
+        let credential_values = helpers::gvt_credential_values(&Prover::new_master_secret().unwrap());
+
+        let (credential_pub_key, credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &credential_values).unwrap();
+
+        let sub_proof_request = helpers::gvt_sub_proof_request();
+        let nonce = new_nonce().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_verifiable_encryption(&VerifiableEncryption::new(0, "sex", auditor_pub_key, "fraud investigation").unwrap()).unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        proof_verifier.add_verifiable_encryption(&VerifiableEncryption::new(0, "sex", auditor_pub_key, "fraud investigation").unwrap()).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        let ciphertexts = proof.get_verifiable_ciphertexts();
+        assert_eq!(1, ciphertexts.len());
+        assert_eq!("fraud investigation", ciphertexts[0].label());
+
+        let sex = Auditor::decrypt(auditor_pub_key, auditor_priv_key, ciphertexts[0]).unwrap();
+        assert_eq!("5944657099558967239210949258394887428692050081607692519917050011144233115103", sex.to_dec().unwrap());
+    }
+
+    #[test]
+    fn anoncreds_works_for_verifiable_encryption_not_correspond_to_verifier_label() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        let (ref auditor_pub_key, _) = *helpers::AUDITOR_KEYS;
+
+        let credential_schema = helpers::gvt_credential_schema();
+        let non_credential_schema = helpers::non_credential_schema();
+        let credential_values = helpers::gvt_credential_values(&Prover::new_master_secret().unwrap());
+
+        let (credential_pub_key, credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &credential_values).unwrap();
+
+        let sub_proof_request = helpers::gvt_sub_proof_request();
+        let nonce = new_nonce().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_verifiable_encryption(&VerifiableEncryption::new(0, "sex", auditor_pub_key, "any reason").unwrap()).unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &credential_pub_key,
+                                             None,
+                                             None).unwrap();
+        proof_verifier.add_verifiable_encryption(&VerifiableEncryption::new(0, "sex", auditor_pub_key, "fraud investigation").unwrap()).unwrap();
+        let res = proof_verifier.verify(&proof, &nonce);
+        assert_eq!(ErrorCode::AnoncredsProofRejected, res.unwrap_err().to_error_code());
+
+        // Revealed attribute can't be verifiably encrypted
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_verifiable_encryption(&VerifiableEncryption::new(0, "name", auditor_pub_key, "fraud investigation").unwrap()).unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &credential_signature,
+                                            &credential_values,
+                                            &credential_pub_key,
+                                            None,
+                                            None).unwrap();
+        let res = proof_builder.finalize(&nonce);
+        assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn attribute_equality_new_works_for_same_attribute() {
+        let res = AttributeEquality::new(0, "name", 0, "name");
+        assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn proof_builder_add_sub_proof_works_for_credential_not_satisfied_ne_predicate() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        let credential_schema = helpers::gvt_credential_schema();
+        let non_credential_schema = helpers::non_credential_schema();
+        let credential_values = helpers::gvt_credential_values(&Prover::new_master_secret().unwrap());
+
+        let (credential_pub_key, credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &credential_values).unwrap();
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_predicate("age", "NE", 28).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        let res = proof_builder.add_sub_proof_request(&sub_proof_request,
+                                                      &credential_schema,
+                                                      &non_credential_schema,
+                                                      &credential_signature,
+                                                      &credential_values,
+                                                      &credential_pub_key,
+                                                      None,
+                                                      None);
+        assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn proof_builder_add_sub_proof_works_for_credential_not_satisfied_between_predicate() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        let credential_schema = helpers::gvt_credential_schema();
+        let non_credential_schema = helpers::non_credential_schema();
+        let credential_values = helpers::gvt_credential_values(&Prover::new_master_secret().unwrap());
+
+        let (credential_pub_key, credential_signature) =
+            helpers::issue_credential(&credential_schema, &non_credential_schema, &credential_values).unwrap();
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_range_predicate("age", 18, 28).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        let res = proof_builder.add_sub_proof_request(&sub_proof_request,
+                                                      &credential_schema,
+                                                      &non_credential_schema,
+                                                      &credential_signature,
+                                                      &credential_values,
+                                                      &credential_pub_key,
+                                                      None,
+                                                      None);
+        assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err().to_error_code());
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_range_predicate("age", 29, 65).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        let res = proof_builder.add_sub_proof_request(&sub_proof_request,
+                                                      &credential_schema,
+                                                      &non_credential_schema,
+                                                      &credential_signature,
+                                                      &credential_values,
+                                                      &credential_pub_key,
+                                                      None,
+                                                      None);
+        assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn proof_verifier_add_sub_proof_request_works_for_credential_schema_not_satisfied_to_sub_proof_request() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        // 1. Issuer creates credential schema
+        let credential_schema = helpers::gvt_credential_schema();
+        let non_credential_schema = helpers::non_credential_schema();
+
+        // 2. Issuer creates credential definition
+        let (credential_pub_key, _, _) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
+
+        // 3. Verifier build proof verifier
+        let sub_proof_request = helpers::gvt_sub_proof_request();
+        let xyz_credential_schema = helpers::xyz_credential_schema();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+
+        let res = proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                                       &xyz_credential_schema,
+                                                       &non_credential_schema,
+                                                       &credential_pub_key,
+                                                       None, None);
+        assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn prover_blind_credential_secrets_works_for_key_correctness_proof_not_correspond_to_public_key() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        // 1. Prover creates master secret
+        let master_secret = Prover::new_master_secret().unwrap();
+        let credential_values = helpers::gvt_credential_values(&master_secret);
+
+        // 2. Issuer creates GVT credential definition
+        let gvt_credential_schema = helpers::gvt_credential_schema();
+        let non_credential_schema = helpers::non_credential_schema();
+        let (gvt_credential_pub_key, _, _) =
+            Issuer::new_credential_def(&gvt_credential_schema, &non_credential_schema, false).unwrap();
+
+        // 3. Issuer creates XYZ credential definition
+        let xyz_credential_schema = helpers::xyz_credential_schema();
+        let (_, _, xyz_credential_key_correctness_proof) =
+            Issuer::new_credential_def(&xyz_credential_schema, &non_credential_schema, false).unwrap();
+
+        // 4. Issuer creates nonce used Prover to blind master secret
+        let gvt_credential_nonce = new_nonce().unwrap();
+
+        // 5. Prover blind master secret by gvt_public_key and xyz_key_correctness_proof
+        let res =
+            Prover::blind_credential_secrets(&gvt_credential_pub_key,
+                                             &xyz_credential_key_correctness_proof,
+                                             &credential_values,
+                                             &gvt_credential_nonce);
+        assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn issuer_sign_credential_works_for_prover_used_different_nonce_to_blind_credential_secrets() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        // 1. Issuer creates credential schema
+        let credential_schema = helpers::gvt_credential_schema();
+        let non_credential_schema = helpers::non_credential_schema();
+
+        // 2. Issuer creates credential definition
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
+
+        // 3. Prover creates master secret
+        let master_secret = Prover::new_master_secret().unwrap();
+        let credential_values = helpers::gvt_credential_values(&master_secret);
+
+        // 4. Issuer creates nonce used Prover to blind master secret
+        let credential_nonce = new_nonce().unwrap();
+
+        let other_nonce = new_nonce().unwrap();
+
+        // 5. Prover blinds master secret
+        let (blinded_credential_secrets, _, blinded_credential_secrets_correctness_proof) =
+            Prover::blind_credential_secrets(&credential_pub_key, &credential_key_correctness_proof, &credential_values, &other_nonce).unwrap();
+
+        // 6. Prover creates nonce used Issuer to credential issue
+        let credential_issuance_nonce = new_nonce().unwrap();
+
+        // 7. Issuer creates credential values
+
+        // 8. Issuer signs credential values
+        let res = Issuer::sign_credential(PROVER_ID,
+                                          &blinded_credential_secrets,
+                                          &blinded_credential_secrets_correctness_proof,
+                                          &credential_nonce,
+                                          &credential_issuance_nonce,
+                                          &credential_values,
+                                          &credential_pub_key,
+                                          &credential_priv_key);
+        assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn issuer_sign_credential_works_for_keys_not_correspond_to_blinded_credential_secrets_correctness_proof() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        // 1. Issuer creates GVT credential definition
+        let credential_schema = helpers::gvt_credential_schema();
+        let non_credential_schema = helpers::non_credential_schema();
+        let (gvt_credential_pub_key, _, gvt_credential_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
+
+        // 2. Issuer creates XYZ credential definition
+        let credential_schema = helpers::xyz_credential_schema();
+        let (xyz_credential_pub_key, xyz_credential_priv_key, _) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
+
+        // 3. Prover creates master secret
+        let master_secret = Prover::new_master_secret().unwrap();
+        let gvt_credential_values = helpers::gvt_credential_values(&master_secret);
+
+        // 4. Issuer creates nonce used Prover to blind master secret
+        let credential_nonce = new_nonce().unwrap();
+
+        // 5. Prover blinds master secret by GVT key
+        let (blinded_credential_secrets, _, blinded_credential_secrets_correctness_proof) =
+            Prover::blind_credential_secrets(&gvt_credential_pub_key, &gvt_credential_key_correctness_proof, &gvt_credential_values, &credential_nonce).unwrap();
+
+        // 6. Prover creates nonce used Issuer to credential issue
+        let credential_issuance_nonce = new_nonce().unwrap();
+
+        // 7. Issuer creates credential values
+        let xyz_credential_values = helpers::xyz_credential_values(&master_secret);
+
+        // 8. Issuer signs XYZ credential values for Prover
+        let res = Issuer::sign_credential(PROVER_ID,
+                                          &blinded_credential_secrets,
+                                          &blinded_credential_secrets_correctness_proof,
+                                          &credential_nonce,
+                                          &credential_issuance_nonce,
+                                          &xyz_credential_values,
+                                          &xyz_credential_pub_key,
+                                          &xyz_credential_priv_key);
+        assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn issuer_sign_credential_works_for_blinded_credential_secrets_not_correspond_to_blinded_credential_secrets_correctness_proof() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        // 1. Issuer creates GVT credential definition
+        let credential_schema = helpers::gvt_credential_schema();
+        let non_credential_schema = helpers::non_credential_schema();
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
+
+        // 2. Prover creates master secret
+        let master_secret = Prover::new_master_secret().unwrap();
+        let credential_values = helpers::gvt_credential_values(&master_secret);
+
+        // 3. Issuer creates nonce used Prover to blind master secret
+        let credential_nonce = new_nonce().unwrap();
+
+        // 4. Prover blinds master secret
+        let (_, _, blinded_credential_secrets_correctness_proof) =
+            Prover::blind_credential_secrets(&credential_pub_key, &credential_key_correctness_proof, &credential_values, &credential_nonce).unwrap();
+
+        // 5. Prover blinds master secret second time
+        let (blinded_credential_secrets, _, _) =
+            Prover::blind_credential_secrets(&credential_pub_key, &credential_key_correctness_proof, &credential_values, &credential_nonce).unwrap();
+
+        // 6. Prover creates nonce used Issuer to credential issue
+        let credential_issuance_nonce = new_nonce().unwrap();
+
+        // 7. Issuer creates credential values
+
+        // 8. Issuer signs credential values for Prover
+        let res = Issuer::sign_credential(PROVER_ID,
+                                          &blinded_credential_secrets,
+                                          &blinded_credential_secrets_correctness_proof,
+                                          &credential_nonce,
+                                          &credential_issuance_nonce,
+                                          &credential_values,
+                                          &credential_pub_key,
+                                          &credential_priv_key);
+        assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn prover_process_credential_signature_works_for_issuer_used_different_nonce() {
+        IndyCryptoDefaultLogger::init(None).ok();
+
+        // 1. Issuer creates credential schema
+        let credential_schema = helpers::gvt_credential_schema();
+        let non_credential_schema = helpers::non_credential_schema();
+
+        // 2. Issuer creates credential definition
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
+
+        // 3. Prover creates master secret
+        let master_secret = Prover::new_master_secret().unwrap();
         let credential_values = helpers::gvt_credential_values(&master_secret);
 
         // 4. Issuer creates nonce used Prover to blind master secret
@@ -3235,6 +4557,12 @@ mod test {
 mod helpers {
     use super::*;
     use indy_crypto::cl::*;
+    use indy_crypto::errors::IndyCryptoError;
+
+    // Generation of safe primes is slow, so all tests share the same auditor keys
+    lazy_static! {
+        pub static ref AUDITOR_KEYS: (AuditorPublicKey, AuditorPrivateKey) = Auditor::new_auditor_keys().unwrap();
+    }
 
     pub fn gvt_credential_schema() -> CredentialSchema {
         let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
@@ -3268,6 +4596,31 @@ mod helpers {
         credential_values_builder.finalize().unwrap()
     }
 
+    pub fn gvt_other_name_credential_values(master_secret: &MasterSecret) -> CredentialValues {
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value_known("master_secret", &master_secret.value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("name", "1139481716457488690172217916278103336").unwrap();
+        credential_values_builder.add_dec_known("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
+        credential_values_builder.add_dec_known("age", "28").unwrap();
+        credential_values_builder.add_dec_known("height", "175").unwrap();
+        credential_values_builder.finalize().unwrap()
+    }
+
+    pub fn balance_credential_schema() -> CredentialSchema {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("balance").unwrap();
+        credential_schema_builder.add_attr("timestamp").unwrap();
+        credential_schema_builder.finalize().unwrap()
+    }
+
+    pub fn balance_credential_values(master_secret: &MasterSecret) -> CredentialValues {
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value_known("master_secret", &master_secret.value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("balance", "-250").unwrap();
+        credential_values_builder.add_dec_known("timestamp", "1700000000000").unwrap();
+        credential_values_builder.finalize().unwrap()
+    }
+
     pub fn xyz_credential_values(master_secret: &MasterSecret) -> CredentialValues {
         let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
         credential_values_builder.add_value_known("master_secret", &master_secret.value().unwrap()).unwrap();
@@ -3283,6 +4636,78 @@ mod helpers {
         gvt_sub_proof_request_builder.finalize().unwrap()
     }
 
+    pub fn passport_credential_schema() -> CredentialSchema {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("birth_date").unwrap();
+        credential_schema_builder.add_attr("verified").unwrap();
+        credential_schema_builder.finalize().unwrap()
+    }
+
+    pub fn passport_credential_values(master_secret: &MasterSecret) -> CredentialValues {
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value_known("master_secret", &master_secret.value().unwrap()).unwrap();
+        credential_values_builder.add_typed_known("name", &AttributeValue::String("Alex".to_owned())).unwrap();
+        credential_values_builder.add_typed_known("birth_date", &AttributeValue::Date("1990-05-17".to_owned())).unwrap();
+        credential_values_builder.add_typed_known("verified", &AttributeValue::Bool(true)).unwrap();
+        credential_values_builder.finalize().unwrap()
+    }
+
+    pub fn gvt_sub_proof_request_with_pseudonym(scope: Option<&str>) -> SubProofRequest {
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        if let Some(scope) = scope {
+            sub_proof_request_builder.add_pseudonym("master_secret", scope).unwrap();
+        }
+        sub_proof_request_builder.finalize().unwrap()
+    }
+
+    // Issues credential and processes its signature by prover
+    pub fn issue_credential(credential_schema: &CredentialSchema,
+                            non_credential_schema: &NonCredentialSchema,
+                            credential_values: &CredentialValues) -> Result<(CredentialPublicKey, CredentialSignature), IndyCryptoError> {
+        let (credential_pub_key, credential_priv_key, credential_key_correctness_proof) =
+            Issuer::new_credential_def(credential_schema, non_credential_schema, false)?;
+
+        let credential_nonce = new_nonce()?;
+
+        let (blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof) =
+            Prover::blind_credential_secrets(&credential_pub_key,
+                                             &credential_key_correctness_proof,
+                                             credential_values,
+                                             &credential_nonce)?;
+
+        let credential_issuance_nonce = new_nonce()?;
+
+        let (mut credential_signature, signature_correctness_proof) = Issuer::sign_credential(PROVER_ID,
+                                                                                              &blinded_credential_secrets,
+                                                                                              &blinded_credential_secrets_correctness_proof,
+                                                                                              &credential_nonce,
+                                                                                              &credential_issuance_nonce,
+                                                                                              credential_values,
+                                                                                              &credential_pub_key,
+                                                                                              &credential_priv_key)?;
+
+        Prover::process_credential_signature(&mut credential_signature,
+                                             credential_values,
+                                             &signature_correctness_proof,
+                                             &credential_secrets_blinding_factors,
+                                             &credential_pub_key,
+                                             &credential_issuance_nonce,
+                                             None, None, None)?;
+
+        Ok((credential_pub_key, credential_signature))
+    }
+
+    pub fn linear_predicate(terms: &[(usize, &str, i32)], p_type: &str, value: i32) -> LinearPredicate {
+        let mut linear_predicate_builder = Verifier::new_linear_predicate_builder().unwrap();
+        for &(sub_proof_index, attr_name, coefficient) in terms {
+            linear_predicate_builder.add_term(sub_proof_index, attr_name, coefficient).unwrap();
+        }
+        linear_predicate_builder.set_predicate(p_type, value).unwrap();
+        linear_predicate_builder.finalize().unwrap()
+    }
+
     pub fn xyz_sub_proof_request() -> SubProofRequest {
         let mut xyz_sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
         xyz_sub_proof_request_builder.add_revealed_attr("status").unwrap();